@@ -0,0 +1,115 @@
+//! Alert channels and their `config`:
+//!
+//! - `email`: `{"to": ["ops@example.com"]}`
+//! - `slack`: `{"webhook_url": "https://hooks.slack.com/services/..."}`
+//! - `discord`: `{"webhook_url": "https://discord.com/api/webhooks/..."}`
+//! - `sms`: `{"to": ["+48123456789"]}`
+//!
+//! Chat webhook URLs must start with the configured Slack or Discord base
+//! URL, so alerts cannot be pointed at arbitrary hosts.
+
+use crate::features::status_pages::subscriptions::normalize_email;
+use crate::shared::config::Config;
+
+use super::{ChatConfig, EmailConfig, SmsConfig};
+
+pub const CHANNELS: [&str; 4] = ["email", "slack", "discord", "sms"];
+
+pub const MAX_EMAIL_RECIPIENTS: usize = 10;
+
+pub const MAX_SMS_RECIPIENTS: usize = 5;
+
+pub const MAX_NAME_LEN: usize = 100;
+
+pub fn validate_channel(channel: &str) -> Result<(), String> {
+    if !CHANNELS.contains(&channel) {
+        return Err(format!("Invalid channel: {}. Allowed: {}", channel, CHANNELS.join(", ")));
+    }
+    Ok(())
+}
+
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Alert name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("Alert name cannot be longer than {} characters", MAX_NAME_LEN));
+    }
+    Ok(name.to_string())
+}
+
+fn parse<T: serde::de::DeserializeOwned>(channel: &str, config: &serde_json::Value) -> Result<T, String> {
+    serde_json::from_value(config.clone()).map_err(|e| format!("Invalid {} alert config: {}", channel, e))
+}
+
+/// `+` and 8 to 15 digits once spaces, dashes and parentheses are dropped.
+pub fn normalize_phone(phone: &str) -> Result<String, String> {
+    let normalized: String = phone.chars().filter(|c| !matches!(c, ' ' | '-' | '(' | ')')).collect();
+    let digits = normalized.strip_prefix('+').unwrap_or_default();
+    if !(8..=15).contains(&digits.len()) || !digits.chars().all(|c| c.is_ascii_digit()) || digits.starts_with('0') {
+        return Err(format!("Invalid phone number: {}. Use the international format, e.g. +48123456789", phone.trim()));
+    }
+    Ok(normalized)
+}
+
+fn normalize_recipients(
+    recipients: &[String],
+    max: usize,
+    normalize: fn(&str) -> Result<String, String>,
+) -> Result<Vec<String>, String> {
+    if recipients.is_empty() {
+        return Err("An alert needs at least one recipient".to_string());
+    }
+    if recipients.len() > max {
+        return Err(format!("An alert cannot have more than {} recipients", max));
+    }
+    let mut normalized: Vec<String> = Vec::with_capacity(recipients.len());
+    for recipient in recipients {
+        let recipient = normalize(recipient)?;
+        if !normalized.contains(&recipient) {
+            normalized.push(recipient);
+        }
+    }
+    Ok(normalized)
+}
+
+fn validate_chat_url(url: &str, base_url: &str, service: &str) -> Result<String, String> {
+    let url = url.trim();
+    let parsed = reqwest::Url::parse(url).map_err(|_| format!("Invalid {} webhook URL: {}", service, url))?;
+    if !url.starts_with(base_url) || url.len() == base_url.len() || parsed.host_str().is_none() {
+        return Err(format!("{} webhook URL must start with {}", service, base_url));
+    }
+    Ok(url.to_string())
+}
+
+/// Checks `config` for `channel` and returns it normalized.
+pub fn validate_config(config: &Config, channel: &str, value: &serde_json::Value) -> Result<serde_json::Value, String> {
+    validate_channel(channel)?;
+    let normalized = match channel {
+        "email" => {
+            let email: EmailConfig = parse(channel, value)?;
+            serde_json::json!(EmailConfig {
+                to: normalize_recipients(&email.to, MAX_EMAIL_RECIPIENTS, normalize_email)?,
+            })
+        }
+        "slack" | "discord" => {
+            let chat: ChatConfig = parse(channel, value)?;
+            let (base_url, service) = if channel == "slack" {
+                (&config.slack_webhook_base_url, "Slack")
+            } else {
+                (&config.discord_webhook_base_url, "Discord")
+            };
+            serde_json::json!(ChatConfig {
+                webhook_url: validate_chat_url(&chat.webhook_url, base_url, service)?,
+            })
+        }
+        _ => {
+            let sms: SmsConfig = parse(channel, value)?;
+            serde_json::json!(SmsConfig {
+                to: normalize_recipients(&sms.to, MAX_SMS_RECIPIENTS, normalize_phone)?,
+            })
+        }
+    };
+    Ok(normalized)
+}
@@ -0,0 +1,125 @@
+//! What alerts say. Every event becomes one `Message` (a title and a few
+//! lines of text) that each `Notifier` lays out for its channel.
+
+use chrono::{DateTime, Utc};
+
+use crate::features::incidents::Incident;
+use crate::features::ingest::Log;
+use crate::features::monitors::Monitor;
+use crate::features::status_pages::render::display_time;
+
+/// Log messages are cut to this many characters.
+const MAX_LOG_MESSAGE_CHARS: usize = 500;
+
+#[derive(Debug, Clone)]
+pub enum AlertEvent {
+    Downtime { monitor: Monitor, incident: Incident },
+    Recovery { monitor: Monitor, incident: Incident },
+    ErrorSpike { latest: Log, errors: usize, window_minutes: i64 },
+    /// Sent on request to check an alert.
+    Test { alert_name: String, channel: String },
+}
+
+impl AlertEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertEvent::Downtime { .. } => "downtime",
+            AlertEvent::Recovery { .. } => "recovery",
+            AlertEvent::ErrorSpike { .. } => "error_spike",
+            AlertEvent::Test { .. } => "test",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Critical,
+    Warning,
+    Resolved,
+    Info,
+}
+
+impl Severity {
+    /// RGB color of chat messages.
+    pub fn color(self) -> u32 {
+        match self {
+            Severity::Critical => 0xD92D20,
+            Severity::Warning => 0xF79009,
+            Severity::Resolved => 0x12B76A,
+            Severity::Info => 0x2E90FA,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub event: &'static str,
+    pub severity: Severity,
+    pub title: String,
+    pub text: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// `1h 5m`, `3m 20s` or `45s`.
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (days, hours, minutes, rest) = (seconds / 86400, seconds % 86400 / 3600, seconds % 3600 / 60, seconds % 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", rest),
+        (0, 0, _) => format!("{}m {}s", minutes, rest),
+        (0, _, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+/// At most `max_chars` characters, ending in `…` when cut.
+pub fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let end = text.char_indices().nth(max_chars.saturating_sub(1)).map_or(text.len(), |(end, _)| end);
+    format!("{}…", &text[..end])
+}
+
+pub fn format(event: &AlertEvent, project_name: &str, now: DateTime<Utc>) -> Message {
+    let (severity, title, text) = match event {
+        AlertEvent::Downtime { monitor, incident } => {
+            let mut text = format!("{} ({}) is down since {}.", monitor.name, monitor.url, display_time(&incident.started_at));
+            if let Some(ref error) = incident.first_error {
+                text.push_str(&format!("\nError: {}", error));
+            }
+            if !incident.affected_regions.is_empty() {
+                text.push_str(&format!("\nRegions: {}", incident.affected_regions.join(", ")));
+            }
+            (Severity::Critical, format!("[{}] {} is down", project_name, monitor.name), text)
+        }
+        AlertEvent::Recovery { monitor, incident } => {
+            let text = match incident.duration_seconds {
+                Some(seconds) => format!("{} ({}) is back up after {} of downtime.", monitor.name, monitor.url, format_duration(seconds)),
+                None => format!("{} ({}) is back up.", monitor.name, monitor.url),
+            };
+            (Severity::Resolved, format!("[{}] {} is back up", project_name, monitor.name), text)
+        }
+        AlertEvent::ErrorSpike { latest, errors, window_minutes } => {
+            let mut text = format!(
+                "{} errors were logged in the last {} minutes.\nLatest ({}): {}",
+                errors,
+                window_minutes,
+                latest.level,
+                truncate(&latest.message, MAX_LOG_MESSAGE_CHARS)
+            );
+            let origin: Vec<&str> = [latest.source.as_deref(), latest.environment.as_deref()].into_iter().flatten().collect();
+            if !origin.is_empty() {
+                text.push_str(&format!("\nSource: {}", origin.join(" / ")));
+            }
+            (Severity::Warning, format!("[{}] Error spike: {} errors in {} minutes", project_name, errors, window_minutes), text)
+        }
+        AlertEvent::Test { alert_name, channel } => (
+            Severity::Info,
+            format!("[{}] Test alert: {}", project_name, alert_name),
+            format!("This is a test notification from StatusForge. The {} alert \"{}\" works.", channel, alert_name),
+        ),
+    };
+
+    Message { event: event.name(), severity, title, text, occurred_at: now }
+}
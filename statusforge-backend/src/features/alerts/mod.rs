@@ -0,0 +1,11 @@
+pub mod channels;
+pub mod message;
+mod model;
+pub mod notifier;
+pub mod repository;
+mod route;
+pub mod service;
+
+pub use model::*;
+pub use route::*;
+pub use service::*;
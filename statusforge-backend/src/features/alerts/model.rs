@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+/// A project's notification channel for downtime, recovery and error spikes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    /// One of `channels::CHANNELS`.
+    pub channel: String,
+    /// Channel settings, see `channels`.
+    pub config: serde_json::Value,
+    pub enabled: bool,
+    /// Monitor tag selector such as `env=prod,team`. When set, the alert only
+    /// fires for downtime and recovery of matching monitors.
+    #[serde(default)]
+    pub tag_selector: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateAlert {
+    pub name: String,
+    pub channel: String,
+    pub config: serde_json::Value,
+    pub enabled: Option<bool>,
+    pub tag_selector: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateAlert {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    /// An empty selector removes the scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag_selector: Option<Option<String>>,
+}
+
+/// `config` of `email` alerts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmailConfig {
+    pub to: Vec<String>,
+}
+
+/// `config` of `slack` and `discord` alerts: the incoming webhook URL.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChatConfig {
+    pub webhook_url: String,
+}
+
+/// `config` of `sms` alerts: E.164 phone numbers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SmsConfig {
+    pub to: Vec<String>,
+}
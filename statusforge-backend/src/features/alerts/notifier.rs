@@ -0,0 +1,210 @@
+//! Sending alert messages. Each channel has a `Notifier`: email goes through
+//! the configured `Mailer`, Slack and Discord messages are POSTed to the
+//! alert's incoming webhook, and SMS through a Twilio-compatible API.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::shared::config::Config;
+use crate::shared::mailer::{self, Email, Mailer};
+
+use super::message::{truncate, Message};
+use super::{Alert, ChatConfig, EmailConfig, SmsConfig};
+
+/// SMS text is cut to this many characters (two segments).
+pub const MAX_SMS_CHARS: usize = 306;
+
+/// Discord's embed description limit.
+const MAX_DISCORD_DESCRIPTION: usize = 4096;
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, alert: &Alert, message: &Message) -> Result<(), String>;
+}
+
+fn config<T: serde::de::DeserializeOwned>(alert: &Alert) -> Result<T, String> {
+    serde_json::from_value(alert.config.clone()).map_err(|e| format!("Invalid config of alert {}: {}", alert.id, e))
+}
+
+async fn post_json(client: &reqwest::Client, url: &str, body: &serde_json::Value) -> Result<(), String> {
+    let response = client.post(url).json(body).send().await.map_err(|e| format!("Request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Responded with {}", response.status()));
+    }
+    Ok(())
+}
+
+pub fn email(alert: &Alert, message: &Message, to: &str) -> Email {
+    Email {
+        to: to.to_string(),
+        subject: message.title.clone(),
+        body: format!("{}\n\n--\nSent by the StatusForge alert \"{}\".\n", message.text, alert.name),
+        unsubscribe_url: None,
+    }
+}
+
+pub struct EmailNotifier {
+    pub mailer: Box<dyn Mailer>,
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn send(&self, alert: &Alert, message: &Message) -> Result<(), String> {
+        let EmailConfig { to } = config(alert)?;
+        let mut failed = Vec::new();
+        for recipient in &to {
+            if let Err(e) = self.mailer.send(&email(alert, message, recipient)).await {
+                failed.push(format!("{}: {}", recipient, e));
+            }
+        }
+        if !failed.is_empty() {
+            return Err(format!("Email failed for {}", failed.join("; ")));
+        }
+        Ok(())
+    }
+}
+
+/// Incoming webhook message with a colored attachment.
+pub fn slack_payload(message: &Message) -> serde_json::Value {
+    serde_json::json!({
+        "text": message.title,
+        "attachments": [{
+            "color": format!("#{:06x}", message.severity.color()),
+            "title": message.title,
+            "text": message.text,
+            "footer": "StatusForge",
+            "ts": message.occurred_at.timestamp(),
+        }],
+    })
+}
+
+pub struct SlackNotifier {
+    pub client: reqwest::Client,
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    async fn send(&self, alert: &Alert, message: &Message) -> Result<(), String> {
+        let ChatConfig { webhook_url } = config(alert)?;
+        post_json(&self.client, &webhook_url, &slack_payload(message))
+            .await
+            .map_err(|e| format!("Slack: {}", e))
+    }
+}
+
+/// Webhook message with one embed.
+pub fn discord_payload(message: &Message) -> serde_json::Value {
+    serde_json::json!({
+        "username": "StatusForge",
+        "embeds": [{
+            "title": truncate(&message.title, 256),
+            "description": truncate(&message.text, MAX_DISCORD_DESCRIPTION),
+            "color": message.severity.color(),
+            "timestamp": message.occurred_at.to_rfc3339(),
+        }],
+    })
+}
+
+pub struct DiscordNotifier {
+    pub client: reqwest::Client,
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    async fn send(&self, alert: &Alert, message: &Message) -> Result<(), String> {
+        let ChatConfig { webhook_url } = config(alert)?;
+        post_json(&self.client, &webhook_url, &discord_payload(message))
+            .await
+            .map_err(|e| format!("Discord: {}", e))
+    }
+}
+
+/// The title and the first line of the text.
+pub fn sms_text(message: &Message) -> String {
+    let summary = message.text.lines().next().unwrap_or_default();
+    truncate(&format!("{}: {}", message.title, summary), MAX_SMS_CHARS)
+}
+
+pub struct SmsNotifier {
+    pub client: reqwest::Client,
+    pub api_url: String,
+    pub account_sid: Option<String>,
+    pub auth_token: Option<String>,
+    pub from: Option<String>,
+}
+
+#[async_trait]
+impl Notifier for SmsNotifier {
+    async fn send(&self, alert: &Alert, message: &Message) -> Result<(), String> {
+        let (Some(account_sid), Some(auth_token), Some(from)) = (&self.account_sid, &self.auth_token, &self.from) else {
+            return Err("SMS is not configured (SMS_ACCOUNT_SID, SMS_AUTH_TOKEN, SMS_FROM)".to_string());
+        };
+        let SmsConfig { to } = config(alert)?;
+        let url = format!("{}/2010-04-01/Accounts/{}/Messages.json", self.api_url, account_sid);
+        let body = sms_text(message);
+
+        let mut failed = Vec::new();
+        for recipient in &to {
+            let sent = self
+                .client
+                .post(&url)
+                .basic_auth(account_sid, Some(auth_token))
+                .form(&[("To", recipient.as_str()), ("From", from.as_str()), ("Body", body.as_str())])
+                .send()
+                .await;
+            match sent {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => failed.push(format!("{}: responded with {}", recipient, response.status())),
+                Err(e) => failed.push(format!("{}: {}", recipient, e)),
+            }
+        }
+        if !failed.is_empty() {
+            return Err(format!("SMS failed for {}", failed.join("; ")));
+        }
+        Ok(())
+    }
+}
+
+/// One notifier per channel.
+pub struct Notifiers {
+    pub email: Box<dyn Notifier>,
+    pub slack: Box<dyn Notifier>,
+    pub discord: Box<dyn Notifier>,
+    pub sms: Box<dyn Notifier>,
+}
+
+impl Notifiers {
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(SEND_TIMEOUT)
+            .user_agent("StatusForge-Alerts")
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| format!("HTTP client setup failed: {}", e))?;
+        Ok(Notifiers {
+            email: Box::new(EmailNotifier { mailer: mailer::from_config(config) }),
+            slack: Box::new(SlackNotifier { client: client.clone() }),
+            discord: Box::new(DiscordNotifier { client: client.clone() }),
+            sms: Box::new(SmsNotifier {
+                client,
+                api_url: config.sms_api_url.clone(),
+                account_sid: config.sms_account_sid.clone(),
+                auth_token: config.sms_auth_token.clone(),
+                from: config.sms_from.clone(),
+            }),
+        })
+    }
+
+    pub fn for_channel(&self, channel: &str) -> Option<&dyn Notifier> {
+        match channel {
+            "email" => Some(self.email.as_ref()),
+            "slack" => Some(self.slack.as_ref()),
+            "discord" => Some(self.discord.as_ref()),
+            "sms" => Some(self.sms.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use supabase::types::OrderDirection;
+
+use crate::AppState;
+
+use super::{Alert, CreateAlert, UpdateAlert};
+
+pub async fn list_by_project(state: &AppState, project_id: &str) -> Result<Vec<Alert>, crate::ApiError> {
+    let alerts = state
+        .supabase
+        .database()
+        .from("alerts")
+        .select("*")
+        .eq("project_id", project_id)
+        .order("created_at", OrderDirection::Ascending)
+        .execute::<Alert>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(alerts)
+}
+
+pub async fn list_enabled(state: &AppState, project_id: &str) -> Result<Vec<Alert>, crate::ApiError> {
+    let alerts = state
+        .supabase
+        .database()
+        .from("alerts")
+        .select("*")
+        .eq("project_id", project_id)
+        .eq("enabled", "true")
+        .execute::<Alert>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(alerts)
+}
+
+pub async fn get_by_id(state: &AppState, id: &str) -> Result<Alert, crate::ApiError> {
+    let mut alerts = state
+        .supabase
+        .database()
+        .from("alerts")
+        .select("*")
+        .eq("id", id)
+        .execute::<Alert>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    alerts.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create(state: &AppState, project_id: &str, alert: &CreateAlert) -> Result<Alert, crate::ApiError> {
+    let mut data = serde_json::to_value(alert).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    data["project_id"] = serde_json::Value::String(project_id.to_string());
+
+    let mut alerts = state
+        .supabase
+        .database()
+        .insert("alerts")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<Alert>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create alert: {:?}", e))
+        })?;
+
+    alerts.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update(state: &AppState, id: &str, alert: &UpdateAlert) -> Result<Alert, crate::ApiError> {
+    let mut alerts = state
+        .supabase
+        .database()
+        .update("alerts")
+        .set(alert)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Alert>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    alerts.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("alerts")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::Value;
+
+use crate::{AppState, ApiError};
+
+use super::{service, CreateAlert, UpdateAlert};
+
+async fn list_alerts(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let alerts = service::list_alerts(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(alerts).unwrap()))
+}
+
+async fn create_alert(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreateAlert>,
+) -> Result<Json<Value>, ApiError> {
+    let alert = service::create_alert(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(alert).unwrap()))
+}
+
+async fn get_alert(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let alert = service::get_alert(&state, &id).await?;
+    Ok(Json(serde_json::to_value(alert).unwrap()))
+}
+
+async fn update_alert(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<UpdateAlert>,
+) -> Result<Json<Value>, ApiError> {
+    let alert = service::update_alert(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(alert).unwrap()))
+}
+
+async fn delete_alert(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_alert(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Alert deleted" })))
+}
+
+async fn send_test(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::send_test(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Test alert sent" })))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/alerts", get(list_alerts).post(create_alert))
+        .route("/alerts/{id}", get(get_alert).put(update_alert).delete(delete_alert))
+        .route("/alerts/{id}/test", post(send_test))
+}
@@ -0,0 +1,163 @@
+use chrono::Utc;
+
+use crate::features::monitors::tags;
+use crate::features::projects;
+use crate::AppState;
+
+use super::channels;
+use super::message::{self, AlertEvent};
+use super::notifier::Notifiers;
+use super::{repository, Alert, CreateAlert, UpdateAlert};
+
+pub async fn list_alerts(state: &AppState, project_id: &str) -> Result<Vec<Alert>, crate::ApiError> {
+    repository::list_by_project(state, project_id).await
+}
+
+pub async fn get_alert(state: &AppState, id: &str) -> Result<Alert, crate::ApiError> {
+    repository::get_by_id(state, id).await
+}
+
+/// Trims a tag selector and checks it parses; an empty one means no scope.
+pub fn normalize_tag_selector(selector: &str) -> Result<Option<String>, crate::ApiError> {
+    let filters = tags::parse_selector(selector)?;
+    if filters.is_empty() {
+        return Ok(None);
+    }
+    let normalized: Vec<String> = filters
+        .into_iter()
+        .map(|(key, value)| match value {
+            Some(value) => format!("{}={}", key, value),
+            None => key,
+        })
+        .collect();
+    Ok(Some(normalized.join(",")))
+}
+
+/// Whether `alert` should be sent for `event`. Alerts scoped by a tag
+/// selector only cover downtime and recovery of matching monitors; tests
+/// always go through.
+pub fn alert_applies(alert: &Alert, event: &AlertEvent) -> bool {
+    let Some(ref selector) = alert.tag_selector else {
+        return true;
+    };
+    match event {
+        AlertEvent::Downtime { monitor, .. } | AlertEvent::Recovery { monitor, .. } => tags::parse_selector(selector)
+            .map(|filters| tags::matches_selector(&monitor.tags, &filters))
+            .unwrap_or(false),
+        AlertEvent::ErrorSpike { .. } => false,
+        AlertEvent::Test { .. } => true,
+    }
+}
+
+pub async fn create_alert(state: &AppState, project_id: &str, data: CreateAlert) -> Result<Alert, crate::ApiError> {
+    let name = channels::validate_name(&data.name).map_err(crate::ApiError::BadRequest)?;
+    let config = channels::validate_config(&state.config, &data.channel, &data.config).map_err(crate::ApiError::BadRequest)?;
+
+    let tag_selector = match data.tag_selector {
+        Some(ref selector) => normalize_tag_selector(selector)?,
+        None => None,
+    };
+
+    let normalized = CreateAlert {
+        name,
+        channel: data.channel,
+        config,
+        enabled: Some(data.enabled.unwrap_or(true)),
+        tag_selector,
+    };
+    repository::create(state, project_id, &normalized).await
+}
+
+/// A new channel needs a config that fits it; a config alone is checked
+/// against the current channel.
+pub async fn update_alert(state: &AppState, id: &str, data: UpdateAlert) -> Result<Alert, crate::ApiError> {
+    let current = repository::get_by_id(state, id).await?;
+    let name = data
+        .name
+        .as_deref()
+        .map(channels::validate_name)
+        .transpose()
+        .map_err(crate::ApiError::BadRequest)?;
+    let config = match (&data.channel, &data.config) {
+        (None, None) => None,
+        (Some(_), None) => return Err(crate::ApiError::BadRequest("Changing the channel requires a new config".to_string())),
+        (channel, Some(config)) => {
+            let channel = channel.as_deref().unwrap_or(&current.channel);
+            Some(channels::validate_config(&state.config, channel, config).map_err(crate::ApiError::BadRequest)?)
+        }
+    };
+
+    let tag_selector = match data.tag_selector {
+        Some(Some(ref selector)) => Some(normalize_tag_selector(selector)?),
+        other => other,
+    };
+
+    repository::update(state, id, &UpdateAlert { name, config, tag_selector, ..data }).await
+}
+
+pub async fn delete_alert(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete(state, id).await
+}
+
+fn notifiers(state: &AppState) -> Result<Notifiers, crate::ApiError> {
+    Notifiers::from_config(&state.config).map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })
+}
+
+/// Sends one alert, whether enabled or not.
+pub async fn send_alert(notifiers: &Notifiers, alert: &Alert, message: &message::Message) -> Result<(), String> {
+    let notifier = notifiers
+        .for_channel(&alert.channel)
+        .ok_or_else(|| format!("Unknown alert channel: {}", alert.channel))?;
+    notifier.send(alert, message).await
+}
+
+/// Sends `event` to every enabled alert of the project it applies to.
+/// Returns how many were sent.
+pub async fn notify(state: &AppState, notifiers: &Notifiers, project_id: &str, event: &AlertEvent) -> Result<usize, crate::ApiError> {
+    let mut alerts = repository::list_enabled(state, project_id).await?;
+    alerts.retain(|alert| alert_applies(alert, event));
+    if alerts.is_empty() {
+        return Ok(0);
+    }
+
+    let project = projects::repository::get_by_id(state, project_id).await?;
+    let message = message::format(event, &project.name, Utc::now());
+    let mut sent = 0;
+    for alert in &alerts {
+        match send_alert(notifiers, alert, &message).await {
+            Ok(()) => sent += 1,
+            Err(e) => eprintln!("Alert {} ({}) failed: {}", alert.id, alert.channel, e),
+        }
+    }
+    Ok(sent)
+}
+
+/// Sends `event` in the background, like webhook deliveries.
+pub fn dispatch(state: &AppState, project_id: &str, event: AlertEvent) {
+    let state = state.clone();
+    let project_id = project_id.to_string();
+    tokio::spawn(async move {
+        let result = match notifiers(&state) {
+            Ok(notifiers) => notify(&state, &notifiers, &project_id, &event).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to send {} alerts: {:?}", event.name(), e);
+        }
+    });
+}
+
+/// Sends a test message through the alert, so its settings can be checked.
+pub async fn send_test(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    let alert = repository::get_by_id(state, id).await?;
+    let project = projects::repository::get_by_id(state, &alert.project_id).await?;
+    let event = AlertEvent::Test { alert_name: alert.name.clone(), channel: alert.channel.clone() };
+    let message = message::format(&event, &project.name, Utc::now());
+
+    send_alert(&notifiers(state)?, &alert, &message)
+        .await
+        .map_err(|e| crate::ApiError::BadRequest(format!("Test alert could not be sent: {}", e)))
+}
@@ -5,3 +5,10 @@ pub struct Session {
     pub access_token: String,
     pub user_id: String,
 }
+
+/// The part of a Supabase Auth user the API needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthUser {
+    pub id: String,
+    pub email: Option<String>,
+}
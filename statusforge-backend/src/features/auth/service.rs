@@ -1,5 +1,88 @@
+use std::time::Duration;
+
 use crate::AppState;
 
-pub async fn validate_jwt(_state: &AppState, _token: &str) -> Result<String, crate::ApiError> {
-    Err(crate::ApiError::NotFound)
+use super::AuthUser;
+
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The id of the user a Supabase access token belongs to, as confirmed by
+/// the Supabase Auth API.
+pub async fn validate_jwt(state: &AppState, token: &str) -> Result<String, crate::ApiError> {
+    let client = reqwest::Client::builder().timeout(AUTH_TIMEOUT).build().map_err(|e| {
+        eprintln!("HTTP client error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+
+    let response = client
+        .get(format!("{}/auth/v1/user", state.config.supabase_url.trim_end_matches('/')))
+        .header("apikey", &state.config.supabase_publishable_key)
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+    if matches!(response.status().as_u16(), 401 | 403) {
+        return Err(crate::ApiError::Unauthorized("Invalid or expired access token".to_string()));
+    }
+
+    let user: AuthUser = response
+        .error_for_status()
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .json()
+        .await
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+    Ok(user.id)
+}
+
+/// The email address of a user, looked up with the Supabase Auth admin API.
+/// `None` without `SUPABASE_SECRET_KEY`, which that API requires.
+pub async fn user_email(state: &AppState, user_id: &str) -> Result<Option<String>, crate::ApiError> {
+    let Some(ref secret_key) = state.config.supabase_secret_key else {
+        return Ok(None);
+    };
+    let client = reqwest::Client::builder().timeout(AUTH_TIMEOUT).build().map_err(|e| {
+        eprintln!("HTTP client error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+
+    let response = client
+        .get(format!(
+            "{}/auth/v1/admin/users/{}",
+            state.config.supabase_url.trim_end_matches('/'),
+            user_id
+        ))
+        .header("apikey", secret_key)
+        .bearer_auth(secret_key)
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    let user: AuthUser = response
+        .error_for_status()
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .json()
+        .await
+        .map_err(|e| {
+            eprintln!("Auth error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+    Ok(user.email)
 }
@@ -0,0 +1,8 @@
+mod model;
+pub mod repository;
+mod route;
+pub mod service;
+
+pub use model::*;
+pub use route::*;
+pub use service::*;
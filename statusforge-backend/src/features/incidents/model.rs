@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Downtime of a single monitor, opened automatically when the monitor goes
+/// `down` and resolved when it recovers. Latency incidents are opened after a
+/// run of anomalous response times and resolved when latency is normal again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub monitor_id: String,
+    pub project_id: String,
+    pub status: String,
+    /// `down` or `latency`.
+    #[serde(default = "default_cause")]
+    pub cause: String,
+    pub started_at: String,
+    pub resolved_at: Option<String>,
+    pub duration_seconds: Option<i64>,
+    #[serde(default)]
+    pub affected_regions: Vec<String>,
+    pub first_error: Option<String>,
+    #[serde(default)]
+    pub result_ids: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn default_cause() -> String {
+    "down".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListIncidentsQuery {
+    pub status: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
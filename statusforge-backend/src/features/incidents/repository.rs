@@ -0,0 +1,142 @@
+use crate::AppState;
+use supabase::types::OrderDirection;
+
+use super::Incident;
+
+pub async fn list_by_project(
+    state: &AppState,
+    project_id: &str,
+    status: Option<&str>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<Incident>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("incidents")
+        .select("*")
+        .eq("project_id", project_id);
+
+    if let Some(status) = status {
+        query = query.eq("status", status);
+    }
+
+    query = query
+        .order("started_at", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(100));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let incidents = query
+        .execute::<Incident>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(incidents)
+}
+
+pub async fn get_by_id(state: &AppState, id: &str) -> Result<Incident, crate::ApiError> {
+    let mut incidents = state
+        .supabase
+        .database()
+        .from("incidents")
+        .select("*")
+        .eq("id", id)
+        .execute::<Incident>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    incidents.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn get_open_for_monitor(state: &AppState, monitor_id: &str) -> Result<Option<Incident>, crate::ApiError> {
+    let mut incidents = state
+        .supabase
+        .database()
+        .from("incidents")
+        .select("*")
+        .eq("monitor_id", monitor_id)
+        .eq("status", "open")
+        .order("started_at", OrderDirection::Descending)
+        .limit(1)
+        .execute::<Incident>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(incidents.pop())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create(
+    state: &AppState,
+    monitor_id: &str,
+    project_id: &str,
+    cause: &str,
+    started_at: &str,
+    affected_regions: &[String],
+    first_error: Option<&str>,
+    result_ids: &[String],
+) -> Result<Incident, crate::ApiError> {
+    let data = serde_json::json!({
+        "monitor_id": monitor_id,
+        "project_id": project_id,
+        "status": "open",
+        "cause": cause,
+        "started_at": started_at,
+        "affected_regions": affected_regions,
+        "first_error": first_error,
+        "result_ids": result_ids,
+    });
+
+    let mut incidents = state
+        .supabase
+        .database()
+        .insert("incidents")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<Incident>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create incident: {:?}", e))
+        })?;
+
+    incidents.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update(state: &AppState, id: &str, data: serde_json::Value) -> Result<Incident, crate::ApiError> {
+    let mut incidents = state
+        .supabase
+        .database()
+        .update("incidents")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Incident>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    incidents.pop().ok_or(crate::ApiError::NotFound)
+}
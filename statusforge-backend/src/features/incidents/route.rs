@@ -0,0 +1,29 @@
+use axum::{extract::Path, extract::Query, extract::State, routing::get, Json, Router};
+use serde_json::Value;
+
+use crate::{AppState, ApiError};
+
+use super::{service, ListIncidentsQuery};
+
+async fn list_incidents(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<ListIncidentsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let incidents = service::list_incidents(&state, &project_id, query).await?;
+    Ok(Json(serde_json::to_value(incidents).unwrap()))
+}
+
+async fn get_incident(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let incident = service::get_incident(&state, &id).await?;
+    Ok(Json(serde_json::to_value(incident).unwrap()))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/incidents", get(list_incidents))
+        .route("/incidents/{id}", get(get_incident))
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+
+use crate::features::monitors::{Monitor, MonitorResult};
+use crate::AppState;
+
+use super::{repository, Incident, ListIncidentsQuery};
+
+pub const INCIDENT_STATUSES: [&str; 2] = ["open", "resolved"];
+
+pub const INCIDENT_CAUSES: [&str; 2] = ["down", "latency"];
+
+/// Appends regions not yet listed, keeping the order they were first seen.
+pub fn merge_regions(existing: &[String], regions: &[&str]) -> Vec<String> {
+    let mut merged = existing.to_vec();
+    for region in regions {
+        if !merged.iter().any(|r| r == region) {
+            merged.push(region.to_string());
+        }
+    }
+    merged
+}
+
+pub fn duration_seconds(started_at: &str, resolved_at: DateTime<Utc>) -> Option<i64> {
+    crate::shared::utils::parse_timestamp(started_at).map(|started| (resolved_at - started).num_seconds().max(0))
+}
+
+fn failed(results: &[MonitorResult]) -> Vec<&MonitorResult> {
+    results.iter().filter(|r| r.status == "down").collect()
+}
+
+pub async fn list_incidents(
+    state: &AppState,
+    project_id: &str,
+    query: ListIncidentsQuery,
+) -> Result<Vec<Incident>, crate::ApiError> {
+    if let Some(ref status) = query.status
+        && !INCIDENT_STATUSES.contains(&status.as_str())
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid status: {}. Must be one of: {:?}", status, INCIDENT_STATUSES)
+        ));
+    }
+
+    repository::list_by_project(state, project_id, query.status.as_deref(), query.limit, query.offset).await
+}
+
+pub async fn get_incident(state: &AppState, id: &str) -> Result<Incident, crate::ApiError> {
+    repository::get_by_id(state, id).await
+}
+
+/// Opens an incident for a monitor that just went down. If a downtime
+/// incident is already open the results are attached to it instead; an open
+/// latency incident is resolved as of the outage start, which supersedes it.
+pub async fn open_incident(
+    state: &AppState,
+    monitor: &Monitor,
+    results: &[MonitorResult],
+) -> Result<Incident, crate::ApiError> {
+    let failed = failed(results);
+    let started_at = failed
+        .iter()
+        .map(|r| r.created_at.clone())
+        .min()
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+    match repository::get_open_for_monitor(state, &monitor.id).await? {
+        Some(open) if open.cause == "latency" => {
+            let resolved_at = crate::shared::utils::parse_timestamp(&started_at).unwrap_or_else(Utc::now);
+            resolve_incident(state, &monitor.id, resolved_at).await?;
+        }
+        Some(open) => return attach_results(state, open, results).await,
+        None => {}
+    }
+
+    let regions: Vec<&str> = failed.iter().map(|r| r.region.as_str()).collect();
+    let result_ids: Vec<String> = failed.iter().map(|r| r.id.clone()).collect();
+    let first_error = failed.iter().find_map(|r| r.error_message.as_deref());
+
+    repository::create(
+        state,
+        &monitor.id,
+        &monitor.project_id,
+        "down",
+        &started_at,
+        &merge_regions(&[], &regions),
+        first_error,
+        &result_ids,
+    ).await
+}
+
+/// Adds further failed results to the open downtime incident, opening one if
+/// the monitor was already down before incidents were tracked (or through a
+/// maintenance window) or only a latency incident is open. The flag is set
+/// when the incident was opened just now, so callers can announce it.
+pub async fn extend_incident(
+    state: &AppState,
+    monitor: &Monitor,
+    results: &[MonitorResult],
+) -> Result<(Incident, bool), crate::ApiError> {
+    match repository::get_open_for_monitor(state, &monitor.id).await? {
+        Some(open) if open.cause != "latency" => {
+            attach_results(state, open, results).await.map(|incident| (incident, false))
+        }
+        _ => open_incident(state, monitor, results).await.map(|incident| (incident, true)),
+    }
+}
+
+pub async fn resolve_incident(
+    state: &AppState,
+    monitor_id: &str,
+    resolved_at: DateTime<Utc>,
+) -> Result<Option<Incident>, crate::ApiError> {
+    let Some(open) = repository::get_open_for_monitor(state, monitor_id).await? else {
+        return Ok(None);
+    };
+
+    let data = serde_json::json!({
+        "status": "resolved",
+        "resolved_at": resolved_at.to_rfc3339(),
+        "duration_seconds": duration_seconds(&open.started_at, resolved_at),
+    });
+    repository::update(state, &open.id, data).await.map(Some)
+}
+
+/// Opens a latency incident after a run of anomalous response times, unless
+/// the monitor already has an open incident.
+pub async fn open_latency_incident(
+    state: &AppState,
+    monitor: &Monitor,
+    results: &[MonitorResult],
+    streak: u32,
+) -> Result<Option<Incident>, crate::ApiError> {
+    if repository::get_open_for_monitor(state, &monitor.id).await?.is_some() {
+        return Ok(None);
+    }
+
+    let anomalous: Vec<&MonitorResult> = results.iter().filter(|r| r.anomalous).collect();
+    let regions: Vec<&str> = anomalous.iter().map(|r| r.region.as_str()).collect();
+    let result_ids: Vec<String> = anomalous.iter().map(|r| r.id.clone()).collect();
+    let message = anomalous.first().map(|r| {
+        format!(
+            "Response time anomalous for {} consecutive checks ({} ms, baseline {:.0} ms)",
+            streak,
+            r.response_time_ms.unwrap_or_default(),
+            r.baseline_ms.unwrap_or_default()
+        )
+    });
+
+    repository::create(
+        state,
+        &monitor.id,
+        &monitor.project_id,
+        "latency",
+        &Utc::now().to_rfc3339(),
+        &merge_regions(&[], &regions),
+        message.as_deref(),
+        &result_ids,
+    ).await.map(Some)
+}
+
+/// Resolves the open incident only if it is a latency incident; downtime
+/// incidents are resolved by the monitor recovering.
+pub async fn resolve_latency_incident(
+    state: &AppState,
+    monitor_id: &str,
+    resolved_at: DateTime<Utc>,
+) -> Result<Option<Incident>, crate::ApiError> {
+    match repository::get_open_for_monitor(state, monitor_id).await? {
+        Some(open) if open.cause == "latency" => resolve_incident(state, monitor_id, resolved_at).await,
+        _ => Ok(None),
+    }
+}
+
+async fn attach_results(
+    state: &AppState,
+    open: Incident,
+    results: &[MonitorResult],
+) -> Result<Incident, crate::ApiError> {
+    let failed = failed(results);
+    if failed.is_empty() {
+        return Ok(open);
+    }
+
+    let regions: Vec<&str> = failed.iter().map(|r| r.region.as_str()).collect();
+    let mut result_ids = open.result_ids.clone();
+    result_ids.extend(failed.iter().map(|r| r.id.clone()));
+    let first_error = open
+        .first_error
+        .clone()
+        .or_else(|| failed.iter().find_map(|r| r.error_message.clone()));
+
+    let data = serde_json::json!({
+        "affected_regions": merge_regions(&open.affected_regions, &regions),
+        "result_ids": result_ids,
+        "first_error": first_error,
+    });
+    repository::update(state, &open.id, data).await
+}
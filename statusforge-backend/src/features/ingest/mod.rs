@@ -1,7 +1,7 @@
 mod model;
-mod repository;
+pub mod repository;
 mod route;
-mod service;
+pub mod service;
 
 pub use model::*;
 pub use route::*;
@@ -3,6 +3,7 @@ use supabase::types::OrderDirection;
 
 use super::Log;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create(
     state: &AppState,
     project_id: &str,
@@ -52,6 +53,7 @@ pub async fn create(
     logs.pop().ok_or(crate::ApiError::InternalServerError)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn list(
     state: &AppState,
     project_id: &str,
@@ -104,3 +106,32 @@ pub async fn list(
 
     Ok(logs)
 }
+
+/// Number of `error` and `fatal` logs of a project since `since`, counted
+/// up to `limit`.
+pub async fn count_errors_since(
+    state: &AppState,
+    project_id: &str,
+    since: &str,
+    limit: u32,
+) -> Result<usize, crate::ApiError> {
+    let logs = state
+        .supabase
+        .database()
+        .from("logs")
+        .select("id")
+        .eq("project_id", project_id)
+        .neq("level", "debug")
+        .neq("level", "info")
+        .neq("level", "warn")
+        .gte("created_at", since)
+        .limit(limit)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(logs.len())
+}
@@ -1,3 +1,4 @@
+use crate::features::webhooks;
 use crate::AppState;
 
 use super::{Log, repository, CreateLog, ListLogsQuery};
@@ -18,7 +19,7 @@ pub async fn ingest_log(
         return Err(crate::ApiError::BadRequest("Message cannot be empty".to_string()));
     }
 
-    repository::create(
+    let log = repository::create(
         state,
         project_id,
         &data.level,
@@ -27,7 +28,9 @@ pub async fn ingest_log(
         data.trace_id.as_deref(),
         data.source.as_deref(),
         data.environment.as_deref(),
-    ).await
+    ).await?;
+    webhooks::watch_log(state, &log);
+    Ok(log)
 }
 
 pub async fn list_logs(
@@ -0,0 +1,9 @@
+mod model;
+pub mod repository;
+mod route;
+pub mod schedule;
+pub mod service;
+
+pub use model::*;
+pub use route::*;
+pub use service::*;
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Planned maintenance for a whole project (`monitor_ids` empty) or for
+/// specific monitors. During an occurrence checks still run, but results are
+/// flagged `in_maintenance`, no incidents are opened and the results are left
+/// out of uptime reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub monitor_ids: Vec<String>,
+    /// First occurrence; its length is the length of every occurrence.
+    pub starts_at: String,
+    pub ends_at: String,
+    /// `once`, `rrule` or `cron`.
+    pub schedule: String,
+    pub rule: Option<String>,
+    pub until: Option<String>,
+    pub timezone: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateMaintenanceWindow {
+    pub title: String,
+    pub description: Option<String>,
+    pub monitor_ids: Option<Vec<String>>,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub schedule: Option<String>,
+    pub rule: Option<String>,
+    pub until: Option<String>,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateMaintenanceWindow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MaintenanceOccurrence {
+    pub starts_at: String,
+    pub ends_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListOccurrencesQuery {
+    pub limit: Option<usize>,
+}
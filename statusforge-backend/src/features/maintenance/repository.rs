@@ -0,0 +1,110 @@
+use crate::AppState;
+
+use super::{CreateMaintenanceWindow, MaintenanceWindow, UpdateMaintenanceWindow};
+
+pub async fn list_by_project(state: &AppState, project_id: &str) -> Result<Vec<MaintenanceWindow>, crate::ApiError> {
+    let windows = state
+        .supabase
+        .database()
+        .from("maintenance_windows")
+        .select("*")
+        .eq("project_id", project_id)
+        .execute::<MaintenanceWindow>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(windows)
+}
+
+pub async fn get_by_id(state: &AppState, id: &str) -> Result<MaintenanceWindow, crate::ApiError> {
+    let mut windows = state
+        .supabase
+        .database()
+        .from("maintenance_windows")
+        .select("*")
+        .eq("id", id)
+        .execute::<MaintenanceWindow>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    windows.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create(
+    state: &AppState,
+    project_id: &str,
+    window: &CreateMaintenanceWindow,
+) -> Result<MaintenanceWindow, crate::ApiError> {
+    let mut data = serde_json::to_value(window).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    data["project_id"] = serde_json::Value::String(project_id.to_string());
+
+    let mut windows = state
+        .supabase
+        .database()
+        .insert("maintenance_windows")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<MaintenanceWindow>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create maintenance window: {:?}", e))
+        })?;
+
+    windows.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update(
+    state: &AppState,
+    id: &str,
+    window: &UpdateMaintenanceWindow,
+) -> Result<MaintenanceWindow, crate::ApiError> {
+    let mut windows = state
+        .supabase
+        .database()
+        .update("maintenance_windows")
+        .set(window)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<MaintenanceWindow>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    windows.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("maintenance_windows")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use axum::{extract::Path, extract::Query, extract::State, routing::get, Json, Router};
+use serde_json::Value;
+
+use crate::{AppState, ApiError};
+
+use super::{service, CreateMaintenanceWindow, ListOccurrencesQuery, UpdateMaintenanceWindow};
+
+async fn list_windows(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let windows = service::list_windows(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(windows).unwrap()))
+}
+
+async fn create_window(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreateMaintenanceWindow>,
+) -> Result<Json<Value>, ApiError> {
+    let window = service::create_window(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(window).unwrap()))
+}
+
+async fn get_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let window = service::get_window(&state, &id).await?;
+    Ok(Json(serde_json::to_value(window).unwrap()))
+}
+
+async fn update_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<UpdateMaintenanceWindow>,
+) -> Result<Json<Value>, ApiError> {
+    let window = service::update_window(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(window).unwrap()))
+}
+
+async fn delete_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_window(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Maintenance window deleted" })))
+}
+
+async fn list_occurrences(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ListOccurrencesQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let occurrences = service::list_occurrences(&state, &id, query).await?;
+    Ok(Json(serde_json::to_value(occurrences).unwrap()))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/maintenance-windows", get(list_windows).post(create_window))
+        .route("/maintenance-windows/{id}", get(get_window).put(update_window).delete(delete_window))
+        .route("/maintenance-windows/{id}/occurrences", get(list_occurrences))
+}
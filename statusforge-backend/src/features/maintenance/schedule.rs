@@ -0,0 +1,293 @@
+//! Maintenance window schedules: one-off, a subset of RFC 5545 RRULE
+//! (`FREQ=DAILY|WEEKLY|MONTHLY`, `INTERVAL`, `BYDAY`, `COUNT`, `UNTIL`) or a
+//! 5-field cron expression. Recurrences are expanded in the window's IANA
+//! timezone so "every Tuesday 22:00 Europe/Warsaw" survives DST changes.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+pub const SCHEDULE_KINDS: [&str; 3] = ["once", "rrule", "cron"];
+
+/// Upper bound on generated occurrences when walking a recurrence.
+const MAX_OCCURRENCES: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub count: Option<u32>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Recurrence {
+    Once,
+    Rule(RecurrenceRule),
+    Cron(Box<cron::Schedule>),
+}
+
+/// Everything needed to tell whether a window is active at a given moment.
+#[derive(Debug, Clone)]
+pub struct WindowDefinition {
+    /// Start of the first occurrence (and DTSTART for RRULE).
+    pub starts_at: DateTime<Utc>,
+    pub duration: Duration,
+    /// Recurrence stops producing occurrences after this instant.
+    pub until: Option<DateTime<Utc>>,
+    pub timezone: Tz,
+    pub recurrence: Recurrence,
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    Tz::from_str(name.trim()).map_err(|_| format!("Unknown timezone: {}", name))
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(format!("Invalid BYDAY value: {}", other)),
+    }
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    let invalid = || format!("Invalid UNTIL value: {}", value);
+    if let Ok(date_time) = NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S") {
+        return Ok(date_time.and_utc());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+    Ok(date.and_hms_opt(23, 59, 59).ok_or_else(invalid)?.and_utc())
+}
+
+pub fn parse_rrule(rule: &str) -> Result<RecurrenceRule, String> {
+    let body = rule.trim();
+    let body = body.strip_prefix("RRULE:").unwrap_or(body);
+
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut by_day = Vec::new();
+    let mut count = None;
+    let mut until = None;
+
+    for part in body.split(';').filter(|p| !p.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid RRULE part: {}", part))?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = Some(match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    other => return Err(format!("Unsupported FREQ: {}. Must be DAILY, WEEKLY or MONTHLY", other)),
+                });
+            }
+            "INTERVAL" => {
+                interval = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|i| *i > 0)
+                    .ok_or_else(|| format!("Invalid INTERVAL: {}", value))?;
+            }
+            "BYDAY" => {
+                by_day = value
+                    .split(',')
+                    .map(|day| parse_weekday(&day.to_ascii_uppercase()))
+                    .collect::<Result<_, _>>()?;
+            }
+            "COUNT" => {
+                count = Some(
+                    value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|c| *c > 0)
+                        .ok_or_else(|| format!("Invalid COUNT: {}", value))?,
+                );
+            }
+            "UNTIL" => until = Some(parse_until(value)?),
+            other => return Err(format!("Unsupported RRULE part: {}", other)),
+        }
+    }
+
+    let frequency = frequency.ok_or_else(|| "RRULE requires FREQ".to_string())?;
+    if count.is_some() && until.is_some() {
+        return Err("RRULE cannot contain both COUNT and UNTIL".to_string());
+    }
+    if !by_day.is_empty() && frequency != Frequency::Weekly {
+        return Err("BYDAY is only supported with FREQ=WEEKLY".to_string());
+    }
+
+    Ok(RecurrenceRule {
+        frequency,
+        interval,
+        by_day,
+        count,
+        until,
+    })
+}
+
+/// Standard cron counts weekdays 0-7 from Sunday, the `cron` crate 1-7.
+fn convert_day_of_week(field: &str) -> String {
+    let shift = |n: &str| match n.parse::<u32>() {
+        Ok(0) | Ok(7) => "1".to_string(),
+        Ok(n) => (n + 1).to_string(),
+        Err(_) => n.to_string(),
+    };
+
+    field
+        .split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (part, None),
+            };
+            let range = match range.split_once('-') {
+                Some((start, "7")) => format!("{}-7,1", shift(start)),
+                Some((start, end)) => format!("{}-{}", shift(start), shift(end)),
+                None => shift(range),
+            };
+            match step {
+                Some(step) => format!("{}/{}", range, step),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parses a standard 5-field cron expression (minute hour day month weekday).
+pub fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err(format!("Cron expression must have 5 fields: {}", expression));
+    }
+    let converted = format!(
+        "0 {} {} {} {} {}",
+        fields[0],
+        fields[1],
+        fields[2],
+        fields[3],
+        convert_day_of_week(fields[4])
+    );
+    cron::Schedule::from_str(&converted).map_err(|e| format!("Invalid cron expression {}: {}", expression, e))
+}
+
+pub fn parse_recurrence(kind: &str, rule: Option<&str>) -> Result<Recurrence, String> {
+    match (kind, rule.map(str::trim).filter(|r| !r.is_empty())) {
+        ("once", _) => Ok(Recurrence::Once),
+        ("rrule", Some(rule)) => parse_rrule(rule).map(Recurrence::Rule),
+        ("cron", Some(rule)) => parse_cron(rule).map(|schedule| Recurrence::Cron(Box::new(schedule))),
+        ("rrule" | "cron", None) => Err(format!("A `rule` is required for {} schedules", kind)),
+        (other, _) => Err(format!("Invalid schedule kind: {}. Must be one of: {:?}", other, SCHEDULE_KINDS)),
+    }
+}
+
+fn resolve_local(timezone: &Tz, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+    // Times skipped by a DST jump have no instant; ambiguous ones take the first.
+    timezone.from_local_datetime(&local).earliest().map(|dt| dt.with_timezone(&Utc))
+}
+
+fn add_months(date: NaiveDate, months: u32) -> Option<NaiveDate> {
+    let total = date.year() * 12 + date.month0() as i32 + months as i32;
+    NaiveDate::from_ymd_opt(total.div_euclid(12), total.rem_euclid(12) as u32 + 1, date.day())
+}
+
+/// Occurrence starts of an RRULE in chronological order, starting at DTSTART.
+fn rule_occurrences<'a>(
+    rule: &'a RecurrenceRule,
+    starts_at: DateTime<Utc>,
+    timezone: &'a Tz,
+) -> impl Iterator<Item = DateTime<Utc>> + 'a {
+    let local_start = starts_at.with_timezone(timezone).naive_local();
+    let start_date = local_start.date();
+    let time = local_start.time();
+    let week_start = start_date - Duration::days(start_date.weekday().num_days_from_monday() as i64);
+    let mut days = rule.by_day.clone();
+    if days.is_empty() {
+        days.push(start_date.weekday());
+    }
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+
+    (0u32..)
+        .map_while(move |period| {
+            let step = period.checked_mul(rule.interval)?;
+            let dates: Vec<NaiveDate> = match rule.frequency {
+                Frequency::Daily => vec![start_date + Duration::days(step as i64)],
+                Frequency::Weekly => {
+                    let week = week_start + Duration::weeks(step as i64);
+                    days.iter()
+                        .map(|d| week + Duration::days(d.num_days_from_monday() as i64))
+                        .filter(|d| *d >= start_date)
+                        .collect()
+                }
+                // Months without the start day (e.g. the 31st) are skipped.
+                Frequency::Monthly => add_months(start_date, step).into_iter().collect(),
+            };
+            Some(dates)
+        })
+        .take(MAX_OCCURRENCES)
+        .flatten()
+        .filter_map(move |date| resolve_local(timezone, date.and_time(time)))
+        .take(rule.count.map(|c| c as usize).unwrap_or(usize::MAX))
+        .take_while(move |start| rule.until.is_none_or(|until| *start <= until))
+}
+
+/// Occurrence starts not earlier than `from`, in chronological order.
+pub fn occurrences_from(definition: &WindowDefinition, from: DateTime<Utc>) -> Box<dyn Iterator<Item = DateTime<Utc>> + '_> {
+    let until = definition.until;
+    let bounded = move |start: &DateTime<Utc>| until.is_none_or(|until| *start <= until);
+
+    match &definition.recurrence {
+        Recurrence::Once => Box::new(std::iter::once(definition.starts_at).filter(move |s| *s >= from)),
+        Recurrence::Rule(rule) => Box::new(
+            rule_occurrences(rule, definition.starts_at, &definition.timezone)
+                .skip_while(move |s| *s < from)
+                .take_while(bounded),
+        ),
+        Recurrence::Cron(schedule) => {
+            let after = from.max(definition.starts_at) - Duration::seconds(1);
+            Box::new(
+                schedule
+                    .after(&after.with_timezone(&definition.timezone))
+                    .map(|s| s.with_timezone(&Utc))
+                    .take_while(bounded),
+            )
+        }
+    }
+}
+
+/// The occurrence (`start`, `end`) covering `at`, if the window is active.
+pub fn active_occurrence(definition: &WindowDefinition, at: DateTime<Utc>) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    occurrences_from(definition, at - definition.duration + Duration::seconds(1))
+        .take_while(|start| *start <= at)
+        .map(|start| (start, start + definition.duration))
+        .find(|(start, end)| *start <= at && at < *end)
+}
+
+pub fn next_occurrences(
+    definition: &WindowDefinition,
+    after: DateTime<Utc>,
+    limit: usize,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    occurrences_from(definition, after - definition.duration + Duration::seconds(1))
+        .map(|start| (start, start + definition.duration))
+        .filter(|(_, end)| *end > after)
+        .take(limit)
+        .collect()
+}
@@ -0,0 +1,183 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::features::monitors::{self, Monitor};
+use crate::shared::utils::parse_timestamp;
+use crate::AppState;
+
+use super::schedule::{self, WindowDefinition};
+use super::{
+    repository, CreateMaintenanceWindow, ListOccurrencesQuery, MaintenanceOccurrence, MaintenanceWindow,
+    UpdateMaintenanceWindow,
+};
+
+pub const MAX_WINDOW_DAYS: i64 = 31;
+
+/// Fields shared by create and update, validated together after merging.
+struct WindowFields<'a> {
+    starts_at: &'a str,
+    ends_at: &'a str,
+    schedule: &'a str,
+    rule: Option<&'a str>,
+    until: Option<&'a str>,
+    timezone: &'a str,
+}
+
+fn parse_definition(fields: &WindowFields) -> Result<WindowDefinition, String> {
+    let starts_at = parse_timestamp(fields.starts_at)
+        .ok_or_else(|| "starts_at must be an RFC 3339 timestamp".to_string())?;
+    let ends_at = parse_timestamp(fields.ends_at)
+        .ok_or_else(|| "ends_at must be an RFC 3339 timestamp".to_string())?;
+    let until = fields
+        .until
+        .map(|until| parse_timestamp(until).ok_or_else(|| "until must be an RFC 3339 timestamp".to_string()))
+        .transpose()?;
+
+    if ends_at <= starts_at {
+        return Err("ends_at must be after starts_at".to_string());
+    }
+    if ends_at - starts_at > Duration::days(MAX_WINDOW_DAYS) {
+        return Err(format!("A maintenance window cannot be longer than {} days", MAX_WINDOW_DAYS));
+    }
+
+    Ok(WindowDefinition {
+        starts_at,
+        duration: ends_at - starts_at,
+        until,
+        timezone: schedule::parse_timezone(fields.timezone)?,
+        recurrence: schedule::parse_recurrence(fields.schedule, fields.rule)?,
+    })
+}
+
+pub fn definition(window: &MaintenanceWindow) -> Result<WindowDefinition, String> {
+    parse_definition(&WindowFields {
+        starts_at: &window.starts_at,
+        ends_at: &window.ends_at,
+        schedule: &window.schedule,
+        rule: window.rule.as_deref(),
+        until: window.until.as_deref(),
+        timezone: &window.timezone,
+    })
+}
+
+/// Whether `window` applies to `monitor_id` and has an occurrence covering `at`.
+pub fn covers(window: &MaintenanceWindow, monitor_id: &str, at: DateTime<Utc>) -> bool {
+    if !window.monitor_ids.is_empty() && !window.monitor_ids.iter().any(|id| id == monitor_id) {
+        return false;
+    }
+    match definition(window) {
+        Ok(definition) => schedule::active_occurrence(&definition, at).is_some(),
+        Err(e) => {
+            eprintln!("Invalid maintenance window {}: {}", window.id, e);
+            false
+        }
+    }
+}
+
+pub async fn is_in_maintenance(state: &AppState, monitor: &Monitor, at: DateTime<Utc>) -> Result<bool, crate::ApiError> {
+    let windows = repository::list_by_project(state, &monitor.project_id).await?;
+    Ok(windows.iter().any(|window| covers(window, &monitor.id, at)))
+}
+
+async fn validate_monitor_ids(state: &AppState, project_id: &str, monitor_ids: &[String]) -> Result<(), crate::ApiError> {
+    if monitor_ids.is_empty() {
+        return Ok(());
+    }
+
+    let monitors = monitors::repository::list_by_project(state, project_id).await?;
+    match monitor_ids.iter().find(|id| !monitors.iter().any(|m| &m.id == *id)) {
+        Some(unknown) => Err(crate::ApiError::BadRequest(format!("Monitor {} does not belong to this project", unknown))),
+        None => Ok(()),
+    }
+}
+
+pub async fn list_windows(state: &AppState, project_id: &str) -> Result<Vec<MaintenanceWindow>, crate::ApiError> {
+    repository::list_by_project(state, project_id).await
+}
+
+pub async fn get_window(state: &AppState, id: &str) -> Result<MaintenanceWindow, crate::ApiError> {
+    repository::get_by_id(state, id).await
+}
+
+pub async fn create_window(
+    state: &AppState,
+    project_id: &str,
+    data: CreateMaintenanceWindow,
+) -> Result<MaintenanceWindow, crate::ApiError> {
+    if data.title.trim().is_empty() {
+        return Err(crate::ApiError::BadRequest("Maintenance window title cannot be empty".to_string()));
+    }
+
+    let normalized = CreateMaintenanceWindow {
+        monitor_ids: Some(data.monitor_ids.unwrap_or_default()),
+        schedule: Some(data.schedule.unwrap_or_else(|| "once".to_string())),
+        timezone: Some(data.timezone.unwrap_or_else(|| "UTC".to_string())),
+        ..data
+    };
+
+    parse_definition(&WindowFields {
+        starts_at: &normalized.starts_at,
+        ends_at: &normalized.ends_at,
+        schedule: normalized.schedule.as_deref().unwrap_or_default(),
+        rule: normalized.rule.as_deref(),
+        until: normalized.until.as_deref(),
+        timezone: normalized.timezone.as_deref().unwrap_or_default(),
+    })
+    .map_err(crate::ApiError::BadRequest)?;
+
+    validate_monitor_ids(state, project_id, normalized.monitor_ids.as_deref().unwrap_or_default()).await?;
+
+    repository::create(state, project_id, &normalized).await
+}
+
+pub async fn update_window(
+    state: &AppState,
+    id: &str,
+    data: UpdateMaintenanceWindow,
+) -> Result<MaintenanceWindow, crate::ApiError> {
+    if let Some(ref title) = data.title
+        && title.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Maintenance window title cannot be empty".to_string()));
+    }
+
+    let existing = repository::get_by_id(state, id).await?;
+    parse_definition(&WindowFields {
+        starts_at: data.starts_at.as_deref().unwrap_or(&existing.starts_at),
+        ends_at: data.ends_at.as_deref().unwrap_or(&existing.ends_at),
+        schedule: data.schedule.as_deref().unwrap_or(&existing.schedule),
+        rule: data.rule.as_deref().or(existing.rule.as_deref()),
+        until: data.until.as_deref().or(existing.until.as_deref()),
+        timezone: data.timezone.as_deref().unwrap_or(&existing.timezone),
+    })
+    .map_err(crate::ApiError::BadRequest)?;
+
+    if let Some(ref monitor_ids) = data.monitor_ids {
+        validate_monitor_ids(state, &existing.project_id, monitor_ids).await?;
+    }
+
+    repository::update(state, id, &data).await
+}
+
+pub async fn delete_window(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete(state, id).await
+}
+
+/// Upcoming (or currently running) occurrences of a window.
+pub async fn list_occurrences(
+    state: &AppState,
+    id: &str,
+    query: ListOccurrencesQuery,
+) -> Result<Vec<MaintenanceOccurrence>, crate::ApiError> {
+    let window = repository::get_by_id(state, id).await?;
+    let definition = definition(&window).map_err(crate::ApiError::BadRequest)?;
+
+    let occurrences = schedule::next_occurrences(&definition, Utc::now(), query.limit.unwrap_or(10).min(100))
+        .into_iter()
+        .map(|(start, end)| MaintenanceOccurrence {
+            starts_at: start.to_rfc3339(),
+            ends_at: end.to_rfc3339(),
+        })
+        .collect();
+
+    Ok(occurrences)
+}
@@ -1,5 +1,10 @@
+pub mod alerts;
 pub mod auth;
+pub mod incidents;
+pub mod maintenance;
 pub mod ingest;
 pub mod monitors;
 pub mod organizations;
 pub mod projects;
+pub mod status_pages;
+pub mod webhooks;
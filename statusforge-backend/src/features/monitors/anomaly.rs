@@ -0,0 +1,114 @@
+//! Response-time anomaly detection against a rolling per-region baseline.
+//!
+//! The baseline is an EWMA of recent response times together with their
+//! median and median absolute deviation (MAD). A result is anomalous when its
+//! modified z-score `0.6745 * (x - median) / MAD` exceeds the threshold, it is
+//! slower than the EWMA and at least `min_delta_ms` above the median, so
+//! millisecond jitter on very stable endpoints is not flagged. Only slow
+//! outliers count; fast responses are never anomalous.
+
+use super::AnomalySettings;
+
+/// Scales MAD to be comparable with a standard deviation for normal data.
+const MAD_SCALE: f64 = 0.6745;
+
+/// Lower bound for MAD, relative to the median, so a perfectly flat history
+/// does not turn every small wobble into an infinite score.
+const MIN_MAD_RATIO: f64 = 0.01;
+
+pub const MAX_WINDOW: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub ewma: f64,
+    pub median: f64,
+    pub mad: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Verdict {
+    pub baseline: Baseline,
+    pub score: f64,
+    pub anomalous: bool,
+}
+
+pub fn validate_settings(settings: &AnomalySettings) -> Result<(), crate::ApiError> {
+    let invalid = |msg: &str| Err(crate::ApiError::BadRequest(msg.to_string()));
+
+    if !(settings.alpha > 0.0 && settings.alpha <= 1.0) {
+        return invalid("anomaly.alpha must be greater than 0 and at most 1");
+    }
+    if !(settings.threshold > 0.0 && settings.threshold <= 100.0) {
+        return invalid("anomaly.threshold must be greater than 0 and at most 100");
+    }
+    if !(5..=MAX_WINDOW).contains(&settings.window) {
+        return Err(crate::ApiError::BadRequest(format!("anomaly.window must be between 5 and {}", MAX_WINDOW)));
+    }
+    if settings.min_samples < 3 || settings.min_samples > settings.window {
+        return invalid("anomaly.min_samples must be at least 3 and at most anomaly.window");
+    }
+    if settings.min_delta_ms.is_nan() || settings.min_delta_ms < 0.0 {
+        return invalid("anomaly.min_delta_ms cannot be negative");
+    }
+    if settings.incident_after > 100 {
+        return invalid("anomaly.incident_after must be between 0 and 100");
+    }
+
+    Ok(())
+}
+
+pub fn ewma(values: &[f64], alpha: f64) -> Option<f64> {
+    let (first, rest) = values.split_first()?;
+    Some(rest.iter().fold(*first, |avg, x| alpha * x + (1.0 - alpha) * avg))
+}
+
+pub fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) { (sorted[mid - 1] + sorted[mid]) / 2.0 } else { sorted[mid] })
+}
+
+/// Baseline of the last `window` values of `history` (oldest first), or
+/// `None` with fewer than `min_samples` values.
+pub fn baseline(history: &[f64], settings: &AnomalySettings) -> Option<Baseline> {
+    let recent = &history[history.len().saturating_sub(settings.window)..];
+    if recent.len() < settings.min_samples.max(1) {
+        return None;
+    }
+
+    let center = median(recent)?;
+    let deviations: Vec<f64> = recent.iter().map(|x| (x - center).abs()).collect();
+    Some(Baseline {
+        ewma: ewma(recent, settings.alpha)?,
+        median: center,
+        mad: median(&deviations)?,
+    })
+}
+
+/// Scores `value` against `history` (oldest first). `None` while there is not
+/// enough history to judge.
+pub fn evaluate(history: &[f64], value: f64, settings: &AnomalySettings) -> Option<Verdict> {
+    let baseline = baseline(history, settings)?;
+    let mad = baseline.mad.max(baseline.median * MIN_MAD_RATIO).max(f64::EPSILON);
+    let score = MAD_SCALE * (value - baseline.median) / mad;
+
+    let anomalous = score > settings.threshold
+        && value > baseline.ewma
+        && value - baseline.median >= settings.min_delta_ms;
+
+    Some(Verdict { baseline, score, anomalous })
+}
+
+/// Number of consecutive `true` flags at the start of `flags` (newest first).
+pub fn streak(flags: impl IntoIterator<Item = bool>) -> u32 {
+    flags.into_iter().take_while(|anomalous| *anomalous).count() as u32
+}
+
+/// Whether a run of `streak` anomalous results should open an incident.
+pub fn opens_incident(streak: u32, settings: &AnomalySettings) -> bool {
+    settings.incident_after > 0 && streak >= settings.incident_after
+}
@@ -0,0 +1,181 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use publicsuffix::{IcannList, Psl};
+use serde_json::Value;
+
+use crate::features::monitors::{CreateMonitorResult, DomainConfig};
+use crate::shared::utils::parse_timestamp;
+
+use super::ssl::{crossed_threshold, days_remaining};
+
+/// EPP statuses (normalized) meaning the domain no longer resolves or is
+/// about to be released.
+const BLOCKING_STATUSES: [&str; 4] = ["clienthold", "serverhold", "redemptionperiod", "pendingdelete"];
+
+#[derive(Debug, Clone, Default)]
+pub struct DomainRegistration {
+    pub expires_at: Option<DateTime<Utc>>,
+    pub registrar: Option<String>,
+    pub statuses: Vec<String>,
+}
+
+/// ICANN section of the Public Suffix List, see `data/`. Private suffixes
+/// such as `github.io` are left out: their subdomains are not registered
+/// through a registry, so RDAP only knows the suffix itself.
+static PUBLIC_SUFFIXES: LazyLock<IcannList> = LazyLock::new(|| {
+    IcannList::from_bytes(include_bytes!("../../../../data/public_suffix_list.dat"))
+        .expect("Invalid public suffix list")
+});
+
+/// Registrable domain of a monitor target: scheme, port and path are
+/// dropped and the host is cut down to one label below its public suffix,
+/// so `https://api.example.co.uk` becomes `example.co.uk`.
+pub fn target_domain(url: &str) -> Option<String> {
+    let host = super::ping::target_host(url)?.trim_end_matches('.').to_ascii_lowercase();
+    if !host.contains('.') || host.parse::<std::net::IpAddr>().is_ok() {
+        return None;
+    }
+    let domain = PUBLIC_SUFFIXES.domain(host.as_bytes())?;
+    std::str::from_utf8(domain.as_bytes()).ok().map(str::to_string)
+}
+
+/// `clientTransferProhibited`, `client transfer prohibited` and
+/// `client_transfer_prohibited` all become `clienttransferprohibited`.
+pub fn normalize_status(status: &str) -> String {
+    status
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn registrar_name(entity: &Value) -> Option<String> {
+    // vcardArray: ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Name"], ...]]
+    entity
+        .get("vcardArray")?
+        .get(1)?
+        .as_array()?
+        .iter()
+        .find(|property| property.get(0).and_then(Value::as_str) == Some("fn"))
+        .and_then(|property| property.get(3))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+pub fn parse_rdap(body: &Value) -> DomainRegistration {
+    let expires_at = body
+        .get("events")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|event| event.get("eventAction").and_then(Value::as_str) == Some("expiration"))
+        .and_then(|event| event.get("eventDate").and_then(Value::as_str))
+        .and_then(parse_timestamp);
+
+    let registrar = body
+        .get("entities")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .find(|entity| {
+            entity
+                .get("roles")
+                .and_then(Value::as_array)
+                .is_some_and(|roles| roles.iter().any(|role| role.as_str() == Some("registrar")))
+        })
+        .and_then(registrar_name);
+
+    let statuses = body
+        .get("status")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect();
+
+    DomainRegistration {
+        expires_at,
+        registrar,
+        statuses,
+    }
+}
+
+pub fn to_result(region: &str, registration: &DomainRegistration, config: &DomainConfig, now: DateTime<Utc>) -> CreateMonitorResult {
+    let days = registration.expires_at.map(|expires_at| days_remaining(expires_at, now));
+    let blocking: Vec<&String> = registration
+        .statuses
+        .iter()
+        .filter(|status| BLOCKING_STATUSES.contains(&normalize_status(status).as_str()))
+        .collect();
+
+    let (status, error_message) = if let Some(days) = days.filter(|days| *days < 0) {
+        ("down", Some(format!("Domain registration expired {} days ago", -days)))
+    } else if !blocking.is_empty() {
+        let names: Vec<&str> = blocking.iter().map(|status| status.as_str()).collect();
+        ("down", Some(format!("Domain has blocking status: {}", names.join(", "))))
+    } else if let Some((days, threshold)) = days.and_then(|days| crossed_threshold(days, &config.warning_days).map(|t| (days, t))) {
+        ("degraded", Some(format!("Domain expires in {} days (warning threshold {} days)", days, threshold)))
+    } else if days.is_none() {
+        ("degraded", Some("RDAP response has no expiration date".to_string()))
+    } else {
+        ("up", None)
+    };
+
+    CreateMonitorResult {
+        region: region.to_string(),
+        status: status.to_string(),
+        domain_expires_at: registration.expires_at.map(|expires_at| expires_at.to_rfc3339()),
+        domain_days_remaining: days,
+        domain_registrar: registration.registrar.clone(),
+        domain_statuses: Some(registration.statuses.clone()),
+        error_message,
+        ..Default::default()
+    }
+}
+
+pub async fn lookup(base_url: &str, domain: &str, config: &DomainConfig) -> Result<DomainRegistration, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(config.timeout_ms))
+        .build()
+        .map_err(|e| format!("RDAP client setup failed: {}", e))?;
+
+    let response = client
+        .get(format!("{}/domain/{}", base_url, domain))
+        .header("Accept", "application/rdap+json")
+        .send()
+        .await
+        .map_err(|e| format!("RDAP lookup failed: {}", e))?;
+
+    match response.status().as_u16() {
+        200 => {}
+        404 => return Err(format!("Domain {} is not registered", domain)),
+        code => return Err(format!("RDAP lookup returned HTTP {}", code)),
+    }
+
+    let body: Value = response.json().await.map_err(|e| format!("Invalid RDAP response: {}", e))?;
+    Ok(parse_rdap(&body))
+}
+
+pub async fn run(base_url: &str, url: &str, config: &DomainConfig, region: &str) -> CreateMonitorResult {
+    let Some(domain) = target_domain(url) else {
+        return CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            error_message: Some(format!("Invalid domain target: {}", url)),
+            ..Default::default()
+        };
+    };
+
+    match lookup(base_url, &domain, config).await {
+        Ok(registration) => to_result(region, &registration, config, Utc::now()),
+        Err(error) => CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            error_message: Some(error),
+            ..Default::default()
+        },
+    }
+}
@@ -0,0 +1,10 @@
+//! Checks executed directly by the backend.
+//!
+//! HTTP based kinds are delegated to the `monitor-check` Edge Function, but
+//! probes that need raw network access (ICMP, TCP, TLS handshakes) cannot run
+//! there and live here instead. Domain registration lookups go through RDAP
+//! from here as well so the registry endpoint stays configurable.
+
+pub mod domain;
+pub mod ping;
+pub mod ssl;
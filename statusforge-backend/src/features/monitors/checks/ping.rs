@@ -0,0 +1,305 @@
+use std::io::{ErrorKind, Read};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+
+use crate::features::monitors::{CreateMonitorResult, PingConfig};
+
+const ICMP_ECHO_REQUEST: u8 = 8;
+const ICMP_ECHO_REPLY: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeMethod {
+    RawIcmp,
+    DatagramIcmp,
+    TcpConnect,
+}
+
+impl ProbeMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProbeMethod::RawIcmp => "icmp",
+            ProbeMethod::DatagramIcmp => "icmp-dgram",
+            ProbeMethod::TcpConnect => "tcp",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PingStats {
+    pub sent: u32,
+    pub received: u32,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub jitter_ms: Option<f64>,
+    pub loss_percent: f64,
+}
+
+/// Extracts the host to probe from a monitor URL. Accepts bare hosts
+/// (`example.com`, `10.0.0.1`), `host:port` and full URLs.
+pub fn target_host(url: &str) -> Option<String> {
+    let trimmed = url.trim();
+    let without_scheme = trimmed.split_once("://").map(|(_, rest)| rest).unwrap_or(trimmed);
+    let authority = without_scheme.split(['/', '?', '#']).next().unwrap_or("");
+    let authority = authority.rsplit_once('@').map(|(_, host)| host).unwrap_or(authority);
+
+    let host = if let Some(rest) = authority.strip_prefix('[') {
+        rest.split_once(']').map(|(host, _)| host)?
+    } else if authority.matches(':').count() == 1 {
+        authority.split(':').next().unwrap_or("")
+    } else {
+        authority
+    };
+
+    if host.is_empty() || host.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(host.to_string())
+}
+
+/// Aggregates per-probe round trip times (`None` = lost probe).
+pub fn summarize(samples: &[Option<f64>]) -> PingStats {
+    let sent = samples.len() as u32;
+    let replies: Vec<f64> = samples.iter().flatten().copied().collect();
+    let received = replies.len() as u32;
+
+    let loss_percent = if sent == 0 {
+        100.0
+    } else {
+        (sent - received) as f64 * 100.0 / sent as f64
+    };
+
+    if replies.is_empty() {
+        return PingStats {
+            sent,
+            received,
+            min_ms: None,
+            avg_ms: None,
+            max_ms: None,
+            jitter_ms: None,
+            loss_percent,
+        };
+    }
+
+    let min = replies.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = replies.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let avg = replies.iter().sum::<f64>() / replies.len() as f64;
+    let jitter = if replies.len() > 1 {
+        let diffs: f64 = replies.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+        Some(diffs / (replies.len() - 1) as f64)
+    } else {
+        None
+    };
+
+    PingStats {
+        sent,
+        received,
+        min_ms: Some(min),
+        avg_ms: Some(avg),
+        max_ms: Some(max),
+        jitter_ms: jitter,
+        loss_percent,
+    }
+}
+
+pub fn classify(stats: &PingStats, config: &PingConfig) -> &'static str {
+    if stats.received == 0 {
+        return "down";
+    }
+    let slow = stats.avg_ms.is_some_and(|avg| avg > config.degraded_latency_ms as f64);
+    if stats.loss_percent > config.degraded_loss_percent || slow {
+        "degraded"
+    } else {
+        "up"
+    }
+}
+
+pub fn to_result(region: &str, stats: &PingStats, config: &PingConfig, method: ProbeMethod) -> CreateMonitorResult {
+    let status = classify(stats, config);
+    let error_message = match status {
+        "down" => Some(format!(
+            "Host unreachable: {}/{} probes lost ({})",
+            stats.sent - stats.received,
+            stats.sent,
+            method.as_str()
+        )),
+        "degraded" => Some(format!(
+            "Packet loss {:.1}%, avg latency {:.1} ms ({})",
+            stats.loss_percent,
+            stats.avg_ms.unwrap_or_default(),
+            method.as_str()
+        )),
+        _ => None,
+    };
+
+    CreateMonitorResult {
+        region: region.to_string(),
+        status: status.to_string(),
+        response_time_ms: stats.avg_ms.map(|v| v.round() as i32),
+        min_response_time_ms: stats.min_ms.map(|v| v.round() as i32),
+        max_response_time_ms: stats.max_ms.map(|v| v.round() as i32),
+        packet_loss: Some(stats.loss_percent),
+        jitter_ms: stats.jitter_ms,
+        error_message,
+        ..Default::default()
+    }
+}
+
+pub async fn run(url: &str, config: &PingConfig, region: &str) -> CreateMonitorResult {
+    let Some(host) = target_host(url) else {
+        return CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            error_message: Some(format!("Invalid ping target: {}", url)),
+            ..Default::default()
+        };
+    };
+
+    let addrs: Vec<SocketAddr> = match tokio::net::lookup_host((host.as_str(), config.port)).await {
+        Ok(addrs) => addrs.collect(),
+        Err(e) => {
+            return CreateMonitorResult {
+                region: region.to_string(),
+                status: "down".to_string(),
+                error_message: Some(format!("DNS lookup failed for {}: {}", host, e)),
+                ..Default::default()
+            };
+        }
+    };
+
+    let Some(addr) = addrs.iter().find(|a| a.is_ipv4()).or(addrs.first()).copied() else {
+        return CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            error_message: Some(format!("No addresses found for {}", host)),
+            ..Default::default()
+        };
+    };
+
+    let (method, samples) = probe(addr, config).await;
+    to_result(region, &summarize(&samples), config, method)
+}
+
+/// Tries raw ICMP first, then unprivileged ICMP datagram sockets, and finally
+/// falls back to timing TCP connects when neither is permitted.
+async fn probe(addr: SocketAddr, config: &PingConfig) -> (ProbeMethod, Vec<Option<f64>>) {
+    if let IpAddr::V4(ip) = addr.ip() {
+        let icmp_config = config.clone();
+        let icmp = tokio::task::spawn_blocking(move || icmp_probes(ip, &icmp_config)).await;
+        if let Ok(Ok(result)) = icmp {
+            return result;
+        }
+    }
+
+    (ProbeMethod::TcpConnect, tcp_probes(addr, config).await)
+}
+
+async fn tcp_probes(addr: SocketAddr, config: &PingConfig) -> Vec<Option<f64>> {
+    let timeout = Duration::from_millis(config.timeout_ms);
+    let mut samples = Vec::with_capacity(config.count as usize);
+    for _ in 0..config.count {
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr)).await;
+        let rtt = start.elapsed().as_secs_f64() * 1000.0;
+        samples.push(match outcome {
+            Ok(Ok(_)) => Some(rtt),
+            // A refused connection still proves the host answered.
+            Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => Some(rtt),
+            _ => None,
+        });
+    }
+    samples
+}
+
+fn icmp_probes(ip: Ipv4Addr, config: &PingConfig) -> std::io::Result<(ProbeMethod, Vec<Option<f64>>)> {
+    let (socket, method) = match Socket::new(Domain::IPV4, Type::RAW, Some(Protocol::ICMPV4)) {
+        Ok(socket) => (socket, ProbeMethod::RawIcmp),
+        Err(_) => (
+            Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::ICMPV4))?,
+            ProbeMethod::DatagramIcmp,
+        ),
+    };
+
+    let target = SockAddr::from(SocketAddr::new(IpAddr::V4(ip), 0));
+    let timeout = Duration::from_millis(config.timeout_ms);
+    let ident = (std::process::id() & 0xffff) as u16;
+    let mut samples = Vec::with_capacity(config.count as usize);
+
+    for seq in 0..config.count {
+        let seq = seq as u16;
+        let start = Instant::now();
+        socket.send_to(&echo_request(ident, seq), &target)?;
+        samples.push(wait_for_reply(&socket, method, ident, seq, start, timeout)?);
+    }
+
+    Ok((method, samples))
+}
+
+fn wait_for_reply(
+    socket: &Socket,
+    method: ProbeMethod,
+    ident: u16,
+    seq: u16,
+    start: Instant,
+    timeout: Duration,
+) -> std::io::Result<Option<f64>> {
+    let mut buf = [0u8; 1500];
+    loop {
+        let Some(remaining) = timeout.checked_sub(start.elapsed()).filter(|d| !d.is_zero()) else {
+            return Ok(None);
+        };
+        socket.set_read_timeout(Some(remaining))?;
+
+        let len = match (&*socket).read(&mut buf) {
+            Ok(len) => len,
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        // Raw sockets deliver the IP header, datagram sockets do not and the
+        // kernel rewrites the identifier, so only the sequence is compared.
+        let packet = match method {
+            ProbeMethod::RawIcmp => {
+                let header_len = ((buf[0] & 0x0f) as usize) * 4;
+                buf.get(header_len..len).unwrap_or(&[])
+            }
+            _ => &buf[..len],
+        };
+        if is_echo_reply(packet, method, ident, seq) {
+            return Ok(Some(start.elapsed().as_secs_f64() * 1000.0));
+        }
+    }
+}
+
+fn is_echo_reply(packet: &[u8], method: ProbeMethod, ident: u16, seq: u16) -> bool {
+    if packet.len() < 8 || packet[0] != ICMP_ECHO_REPLY {
+        return false;
+    }
+    let reply_ident = u16::from_be_bytes([packet[4], packet[5]]);
+    let reply_seq = u16::from_be_bytes([packet[6], packet[7]]);
+    reply_seq == seq && (method != ProbeMethod::RawIcmp || reply_ident == ident)
+}
+
+pub fn echo_request(ident: u16, seq: u16) -> Vec<u8> {
+    let mut packet = vec![0u8; 16];
+    packet[0] = ICMP_ECHO_REQUEST;
+    packet[4..6].copy_from_slice(&ident.to_be_bytes());
+    packet[6..8].copy_from_slice(&seq.to_be_bytes());
+    packet[8..].copy_from_slice(b"sforge\0\0");
+    let sum = checksum(&packet);
+    packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    packet
+}
+
+pub fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]) as u32)
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
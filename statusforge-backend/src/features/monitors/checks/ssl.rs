@@ -0,0 +1,339 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{CertificateError, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use x509_parser::prelude::{FromDer, GeneralName, X509Certificate};
+use x509_parser::public_key::PublicKey;
+
+use crate::features::monitors::{CreateMonitorResult, SslConfig, SslDetails};
+
+/// Signature algorithms (by OID) considered too weak for a public certificate.
+const WEAK_SIGNATURES: [(&str, &str); 4] = [
+    ("1.2.840.113549.1.1.4", "md5WithRSAEncryption"),
+    ("1.2.840.113549.1.1.5", "sha1WithRSAEncryption"),
+    ("1.2.840.10045.4.1", "ecdsa-with-SHA1"),
+    ("1.2.840.10040.4.3", "dsa-with-sha1"),
+];
+
+const KNOWN_SIGNATURES: [(&str, &str); 7] = [
+    ("1.2.840.113549.1.1.11", "sha256WithRSAEncryption"),
+    ("1.2.840.113549.1.1.12", "sha384WithRSAEncryption"),
+    ("1.2.840.113549.1.1.13", "sha512WithRSAEncryption"),
+    ("1.2.840.113549.1.1.10", "rsassa-pss"),
+    ("1.2.840.10045.4.3.2", "ecdsa-with-SHA256"),
+    ("1.2.840.10045.4.3.3", "ecdsa-with-SHA384"),
+    ("1.3.101.112", "ed25519"),
+];
+
+#[derive(Debug, Clone)]
+pub struct CertificateInfo {
+    pub not_after: DateTime<Utc>,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    pub sans: Vec<String>,
+    pub signature_oid: String,
+    pub key_type: String,
+    pub key_bits: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SslInspection {
+    pub host: String,
+    pub certificate: CertificateInfo,
+    /// The negotiated version. Only TLS 1.2 and 1.3 are offered, so a server
+    /// limited to older versions fails the handshake instead.
+    pub protocol: Option<String>,
+    /// `None` when the chain verified against the bundled web PKI roots.
+    pub trust_error: Option<String>,
+    pub chain_complete: bool,
+}
+
+pub fn signature_name(oid: &str) -> String {
+    WEAK_SIGNATURES
+        .iter()
+        .chain(KNOWN_SIGNATURES.iter())
+        .find(|(known, _)| *known == oid)
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_else(|| oid.to_string())
+}
+
+pub fn is_weak_signature(oid: &str) -> bool {
+    WEAK_SIGNATURES.iter().any(|(weak, _)| *weak == oid)
+}
+
+pub fn is_weak_key(key_type: &str, bits: Option<u32>) -> bool {
+    match (key_type, bits) {
+        ("RSA", Some(bits)) | ("DSA", Some(bits)) => bits < 2048,
+        ("EC", Some(bits)) => bits < 256,
+        _ => false,
+    }
+}
+
+/// RFC 6125 style matching: exact, or a single left-most `*.` label.
+pub fn hostname_matches(host: &str, names: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    names.iter().any(|name| {
+        let name = name.trim_end_matches('.').to_ascii_lowercase();
+        match name.strip_prefix("*.") {
+            Some(suffix) => host
+                .split_once('.')
+                .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+            None => name == host,
+        }
+    })
+}
+
+pub fn days_remaining(not_after: DateTime<Utc>, now: DateTime<Utc>) -> i32 {
+    let seconds = (not_after - now).num_seconds();
+    // Round towards negative infinity so an expired certificate never shows 0.
+    seconds.div_euclid(86_400) as i32
+}
+
+/// The smallest configured threshold the certificate has crossed, if any.
+pub fn crossed_threshold(days: i32, warning_days: &[u32]) -> Option<u32> {
+    warning_days.iter().copied().filter(|&t| days <= t as i32).min()
+}
+
+pub fn to_result(region: &str, inspection: &SslInspection, config: &SslConfig, now: DateTime<Utc>) -> CreateMonitorResult {
+    let cert = &inspection.certificate;
+    let days = days_remaining(cert.not_after, now);
+    let hostname_match = hostname_matches(&inspection.host, &cert.sans)
+        || (cert.sans.is_empty() && cert.subject.as_deref().is_some_and(|cn| hostname_matches(&inspection.host, &[cn.to_string()])));
+
+    let mut weaknesses = Vec::new();
+    if is_weak_signature(&cert.signature_oid) {
+        weaknesses.push(format!("Weak signature algorithm: {}", signature_name(&cert.signature_oid)));
+    }
+    if is_weak_key(&cert.key_type, cert.key_bits) {
+        weaknesses.push(format!("Weak {} key: {} bits", cert.key_type, cert.key_bits.unwrap_or_default()));
+    }
+
+    let trusted = inspection.trust_error.is_none();
+    let mut problems = Vec::new();
+    if days < 0 {
+        problems.push(format!("Certificate expired {} days ago", -days));
+    }
+    if !hostname_match {
+        problems.push(format!("Certificate is not valid for {}", inspection.host));
+    }
+    if !inspection.chain_complete {
+        problems.push("Certificate chain is incomplete or issued by an unknown CA".to_string());
+    } else if let Some(error) = inspection.trust_error.as_deref().filter(|_| days >= 0 && hostname_match) {
+        problems.push(format!("Certificate is not trusted: {}", error));
+    }
+
+    let (status, error_message) = if !problems.is_empty() {
+        ("down", Some(problems.join("; ")))
+    } else if let Some(threshold) = crossed_threshold(days, &config.warning_days) {
+        ("degraded", Some(format!("Certificate expires in {} days (warning threshold {} days)", days, threshold)))
+    } else if !weaknesses.is_empty() {
+        ("degraded", Some(weaknesses.join("; ")))
+    } else {
+        ("up", None)
+    };
+
+    CreateMonitorResult {
+        region: region.to_string(),
+        status: status.to_string(),
+        ssl_valid: Some(trusted && hostname_match && days >= 0),
+        ssl_expires_at: Some(cert.not_after.to_rfc3339()),
+        ssl_days_remaining: Some(days),
+        ssl_issuer: cert.issuer.clone(),
+        ssl_details: Some(SslDetails {
+            subject: cert.subject.clone(),
+            sans: cert.sans.clone(),
+            hostname_match,
+            chain_complete: inspection.chain_complete,
+            trusted,
+            signature_algorithm: signature_name(&cert.signature_oid),
+            key_type: cert.key_type.clone(),
+            key_bits: cert.key_bits,
+            protocol: inspection.protocol.clone(),
+            weaknesses,
+        }),
+        error_message,
+        ..Default::default()
+    }
+}
+
+pub fn parse_certificate(der: &[u8]) -> Result<CertificateInfo, String> {
+    let (_, cert) = X509Certificate::from_der(der).map_err(|e| format!("Invalid certificate: {}", e))?;
+
+    let not_after = DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0)
+        .ok_or_else(|| "Invalid certificate expiry".to_string())?;
+    let subject = cert.subject().iter_common_name().next().and_then(|cn| cn.as_str().ok()).map(str::to_string);
+    let issuer = cert
+        .issuer()
+        .iter_organization()
+        .next()
+        .or_else(|| cert.issuer().iter_common_name().next())
+        .and_then(|attr| attr.as_str().ok())
+        .map(str::to_string);
+
+    let sans = match cert.subject_alternative_name() {
+        Ok(Some(ext)) => ext
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let (key_type, key_bits) = match cert.public_key().parsed() {
+        Ok(PublicKey::RSA(rsa)) => ("RSA", Some(rsa.key_size() as u32)),
+        Ok(PublicKey::EC(ec)) => ("EC", Some(ec.key_size() as u32)),
+        Ok(PublicKey::DSA(_)) => ("DSA", None),
+        _ => ("unknown", None),
+    };
+
+    Ok(CertificateInfo {
+        not_after,
+        subject,
+        issuer,
+        sans,
+        signature_oid: cert.signature_algorithm.algorithm.to_id_string(),
+        key_type: key_type.to_string(),
+        key_bits,
+    })
+}
+
+/// Delegates to the web PKI verifier but records its verdict instead of
+/// aborting the handshake, so invalid certificates can still be inspected.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    outcome: Mutex<Option<Result<(), rustls::Error>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let outcome = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .map(|_| ());
+        *self.outcome.lock().unwrap() = Some(outcome);
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+pub async fn inspect(host: &str, config: &SslConfig) -> Result<SslInspection, String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| format!("TLS setup failed: {}", e))?;
+    let verifier = Arc::new(RecordingVerifier {
+        inner,
+        outcome: Mutex::new(None),
+    });
+
+    let tls_config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS setup failed: {}", e))?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let server_name = ServerName::try_from(host.to_string()).map_err(|e| format!("Invalid host {}: {}", host, e))?;
+    let timeout = Duration::from_millis(config.timeout_ms);
+
+    let handshake = async {
+        let tcp = tokio::net::TcpStream::connect((host, config.port))
+            .await
+            .map_err(|e| format!("Connection to {}:{} failed: {}", host, config.port, e))?;
+        tokio_rustls::TlsConnector::from(Arc::new(tls_config))
+            .connect(server_name, tcp)
+            .await
+            .map_err(|e| format!("TLS handshake failed: {}", e))
+    };
+    let stream = tokio::time::timeout(timeout, handshake)
+        .await
+        .map_err(|_| format!("TLS handshake timed out after {} ms", config.timeout_ms))??;
+
+    let (_, connection) = stream.get_ref();
+    let leaf = connection
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .ok_or_else(|| "Server did not present a certificate".to_string())?;
+    let certificate = parse_certificate(leaf.as_ref())?;
+    let protocol = connection.protocol_version().map(|v| match v {
+        rustls::ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+        rustls::ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+        other => format!("{:?}", other),
+    });
+
+    let outcome = verifier.outcome.lock().unwrap().take().unwrap_or(Ok(()));
+    let chain_complete = !matches!(
+        outcome,
+        Err(rustls::Error::InvalidCertificate(CertificateError::UnknownIssuer))
+    );
+
+    Ok(SslInspection {
+        host: host.to_string(),
+        certificate,
+        protocol,
+        trust_error: outcome.err().map(|e| e.to_string()),
+        chain_complete,
+    })
+}
+
+pub async fn run(url: &str, config: &SslConfig, region: &str) -> CreateMonitorResult {
+    let Some(host) = super::ping::target_host(url) else {
+        return CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            error_message: Some(format!("Invalid SSL target: {}", url)),
+            ..Default::default()
+        };
+    };
+
+    match inspect(&host, config).await {
+        Ok(inspection) => to_result(region, &inspection, config, Utc::now()),
+        Err(error) => CreateMonitorResult {
+            region: region.to_string(),
+            status: "down".to_string(),
+            ssl_valid: Some(false),
+            error_message: Some(error),
+            ..Default::default()
+        },
+    }
+}
@@ -0,0 +1,27 @@
+//! Confirmation rules that turn raw per-region results into the status of a
+//! whole check run, so a single flaky probe does not count as downtime.
+
+use std::time::Duration;
+
+/// Pause between confirmation attempts in the same region.
+pub const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+pub const MAX_RETRIES: i32 = 5;
+
+/// Only `down` is re-checked; `degraded` is already a soft signal.
+pub fn needs_retry(status: &str, attempt: i32, retries: i32) -> bool {
+    status == "down" && attempt <= retries
+}
+
+/// `down` when at least `quorum` regions failed, `degraded` when some (but
+/// fewer than `quorum`) failed or any region is degraded, otherwise `up`.
+pub fn aggregate(statuses: &[&str], quorum: i32) -> &'static str {
+    let failed = statuses.iter().filter(|&&s| s == "down").count() as i32;
+    if statuses.is_empty() || failed >= quorum.max(1) {
+        "down"
+    } else if failed > 0 || statuses.contains(&"degraded") {
+        "degraded"
+    } else {
+        "up"
+    }
+}
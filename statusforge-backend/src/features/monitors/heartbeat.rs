@@ -0,0 +1,93 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::shared::utils::parse_timestamp;
+use crate::AppState;
+
+use super::{repository, CreateMonitorResult, HeartbeatConfig, HeartbeatSignal};
+
+/// Heartbeats are not probed from any location, results are stored under the
+/// default region.
+pub const HEARTBEAT_REGION: &str = "EU";
+
+/// Parses the optional path suffix of `POST /heartbeats/{token}/{suffix}`.
+pub fn parse_signal(suffix: Option<&str>) -> Result<HeartbeatSignal, crate::ApiError> {
+    match suffix {
+        None | Some("") => Ok(HeartbeatSignal::Success),
+        Some("start") => Ok(HeartbeatSignal::Start),
+        Some("fail") => Ok(HeartbeatSignal::Fail),
+        Some(code) => code
+            .parse::<i32>()
+            .map(HeartbeatSignal::ExitCode)
+            .map_err(|_| crate::ApiError::BadRequest(format!("Invalid heartbeat suffix: {}", code))),
+    }
+}
+
+pub fn is_overdue(last_seen: DateTime<Utc>, interval_seconds: i32, grace_seconds: u32, now: DateTime<Utc>) -> bool {
+    let deadline = last_seen + Duration::seconds(interval_seconds as i64 + grace_seconds as i64);
+    now > deadline
+}
+
+/// Result written for a ping, `None` for `/start` which only opens a run.
+pub fn result_for_signal(
+    signal: HeartbeatSignal,
+    started_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<CreateMonitorResult> {
+    let duration_ms = started_at
+        .map(|start| (now - start).num_milliseconds().clamp(0, i32::MAX as i64) as i32);
+
+    let (status, error_message) = match signal {
+        HeartbeatSignal::Start => return None,
+        HeartbeatSignal::Success | HeartbeatSignal::ExitCode(0) => ("up", None),
+        HeartbeatSignal::Fail => ("down", Some("Job reported failure".to_string())),
+        HeartbeatSignal::ExitCode(code) => ("down", Some(format!("Job exited with code {}", code))),
+    };
+
+    Some(CreateMonitorResult {
+        region: HEARTBEAT_REGION.to_string(),
+        status: status.to_string(),
+        response_time_ms: duration_ms,
+        error_message,
+        ..Default::default()
+    })
+}
+
+/// Writes a `down` result for every heartbeat monitor whose ping is overdue.
+/// A monitor is reported once per missed period: if the latest result is
+/// already a `down` newer than the last ping, nothing is written.
+pub async fn sweep(state: AppState) -> Result<(), crate::ApiError> {
+    let now = Utc::now();
+    let monitors = repository::list_enabled_by_kind(&state, "heartbeat").await?;
+
+    for monitor in monitors {
+        let last_seen_raw = monitor.last_heartbeat_at.as_deref().unwrap_or(&monitor.created_at);
+        let Some(last_seen) = parse_timestamp(last_seen_raw) else {
+            continue;
+        };
+        let config: HeartbeatConfig = serde_json::from_value(monitor.config.clone()).unwrap_or_default();
+        if !is_overdue(last_seen, monitor.interval_seconds, config.grace_seconds, now) {
+            continue;
+        }
+
+        let latest = repository::list_results(&state, &monitor.id, None, None, None, Some(1), None).await?;
+        let already_reported = latest.first().is_some_and(|r| {
+            r.status == "down" && parse_timestamp(&r.created_at).is_some_and(|at| at > last_seen)
+        });
+        if already_reported {
+            continue;
+        }
+
+        let result = CreateMonitorResult {
+            region: HEARTBEAT_REGION.to_string(),
+            status: "down".to_string(),
+            error_message: Some(format!(
+                "No heartbeat received within {}s (+{}s grace)",
+                monitor.interval_seconds, config.grace_seconds
+            )),
+            ..Default::default()
+        };
+        super::service::create_monitor_result(&state, &monitor.id, result).await?;
+    }
+
+    Ok(())
+}
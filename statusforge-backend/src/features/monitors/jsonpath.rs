@@ -0,0 +1,84 @@
+//! Minimal JSONPath subset shared by HTTP assertions and multi-step variable
+//! extraction: `$`, `.key`, `['key']` / `["key"]` and `[index]`. The
+//! `monitor-check` Edge Function evaluates exactly the same grammar.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+pub fn parse(path: &str) -> Result<Vec<Segment>, String> {
+    let mut chars = path.trim().chars().peekable();
+    if chars.next() != Some('$') {
+        return Err(format!("JSONPath must start with '$': {}", path));
+    }
+
+    let mut segments = Vec::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                let mut key = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() || next == '_' || next == '-' {
+                        key.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if key.is_empty() {
+                    return Err(format!("Empty key in JSONPath: {}", path));
+                }
+                segments.push(Segment::Key(key));
+            }
+            '[' => {
+                let segment = match chars.peek() {
+                    Some(&quote) if quote == '\'' || quote == '"' => {
+                        chars.next();
+                        let mut key = String::new();
+                        loop {
+                            match chars.next() {
+                                Some(c) if c == quote => break,
+                                Some(c) => key.push(c),
+                                None => return Err(format!("Unterminated string in JSONPath: {}", path)),
+                            }
+                        }
+                        Segment::Key(key)
+                    }
+                    _ => {
+                        let mut digits = String::new();
+                        while let Some(&next) = chars.peek() {
+                            if next.is_ascii_digit() {
+                                digits.push(next);
+                                chars.next();
+                            } else {
+                                break;
+                            }
+                        }
+                        let index = digits
+                            .parse::<usize>()
+                            .map_err(|_| format!("Invalid array index in JSONPath: {}", path))?;
+                        Segment::Index(index)
+                    }
+                };
+                if chars.next() != Some(']') {
+                    return Err(format!("Expected ']' in JSONPath: {}", path));
+                }
+                segments.push(segment);
+            }
+            other => return Err(format!("Unexpected '{}' in JSONPath: {}", other, path)),
+        }
+    }
+
+    Ok(segments)
+}
+
+pub fn select<'a>(value: &'a Value, segments: &[Segment]) -> Option<&'a Value> {
+    segments.iter().try_fold(value, |current, segment| match segment {
+        Segment::Key(key) => current.get(key.as_str()),
+        Segment::Index(index) => current.get(*index),
+    })
+}
@@ -0,0 +1,322 @@
+//! Monitors-as-code: a project's monitors exported as a YAML/JSON document,
+//! and the plan that turns an edited document back into creates, updates and
+//! deletes. Monitors are matched by a stable `key`; monitors created before
+//! keys existed get one derived from their name.
+//!
+//! Exports are meant to be committed, so credentials in the request config
+//! (auth passwords and tokens, sensitive headers) are written as `${NAME}`
+//! placeholders. A placeholder left in an applied document keeps the stored
+//! value; a real value replaces it.
+
+use super::{CreateMonitor, Monitor, MonitorGroup, MonitorSpec, MonitorsDocument, PlanItem};
+
+pub const DOCUMENT_VERSION: u32 = 1;
+
+pub const MAX_KEY_LEN: usize = 64;
+
+pub const EXPORT_FORMATS: [&str; 2] = ["yaml", "json"];
+
+/// Request headers whose values are exported as placeholders.
+pub const SENSITIVE_HEADERS: [&str; 5] = ["authorization", "proxy-authorization", "cookie", "x-api-key", "api-key"];
+
+pub fn validate_key(key: &str) -> Result<(), crate::ApiError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'));
+    if !valid {
+        return Err(crate::ApiError::BadRequest(format!(
+            "Invalid monitor key: {}. Use up to {} lowercase letters, digits, '-' or '_'",
+            key, MAX_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// `"API – Health check"` becomes `api-health-check`.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.trim_end_matches('-').chars().take(MAX_KEY_LEN).collect();
+    match slug.trim_end_matches('-') {
+        "" => "monitor".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+pub fn effective_key(monitor: &Monitor) -> String {
+    monitor.key.clone().unwrap_or_else(|| slugify(&monitor.name))
+}
+
+pub fn spec_from_monitor(monitor: &Monitor, groups: &[MonitorGroup]) -> MonitorSpec {
+    MonitorSpec {
+        key: effective_key(monitor),
+        name: monitor.name.clone(),
+        kind: monitor.kind.clone(),
+        url: if monitor.kind == "heartbeat" { String::new() } else { monitor.url.clone() },
+        keyword: monitor.keyword.clone(),
+        interval_seconds: monitor.interval_seconds,
+        enabled: monitor.enabled,
+        config: monitor.config.clone(),
+        confirmation: monitor.confirmation.clone(),
+        tags: monitor.tags.clone(),
+        anomaly: monitor.anomaly.clone(),
+        group: monitor
+            .group_id
+            .as_ref()
+            .and_then(|id| groups.iter().find(|g| &g.id == id))
+            .map(|g| g.name.clone()),
+    }
+}
+
+fn env_name(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect()
+}
+
+pub fn is_placeholder(value: &str) -> bool {
+    value.len() > 3 && value.starts_with("${") && value.ends_with('}')
+}
+
+/// JSON pointers to the requests of a config: the config itself, or each
+/// step of a multi-step monitor, with the prefix of their placeholder names.
+fn request_pointers(config: &serde_json::Value) -> Vec<(String, String)> {
+    match config.get("steps").and_then(|steps| steps.as_array()) {
+        Some(steps) => (0..steps.len()).map(|i| (format!("STEP{}_", i + 1), format!("/steps/{}", i))).collect(),
+        None => vec![(String::new(), String::new())],
+    }
+}
+
+/// Placeholder names and JSON pointers of the credentials in one request.
+fn secret_pointers(request: &serde_json::Value) -> Vec<(String, String)> {
+    let mut secrets = Vec::new();
+    for field in ["password", "token"] {
+        if request.pointer(&format!("/auth/{}", field)).is_some_and(|v| v.is_string()) {
+            secrets.push((format!("AUTH_{}", env_name(field)), format!("/auth/{}", field)));
+        }
+    }
+    if let Some(headers) = request.get("headers").and_then(|h| h.as_object()) {
+        for name in headers.keys() {
+            if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                secrets.push((format!("HEADER_{}", env_name(name)), format!("/headers/{}", name.replace('~', "~0"))));
+            }
+        }
+    }
+    secrets
+}
+
+/// Replaces the credentials in `config` with `${KEY_FIELD}` placeholders.
+pub fn redact_secrets(key: &str, config: &mut serde_json::Value) {
+    for (prefix, base) in request_pointers(config) {
+        let Some(request) = config.pointer(&base) else {
+            continue;
+        };
+        for (name, pointer) in secret_pointers(request) {
+            if let Some(value) = config.pointer_mut(&format!("{}{}", base, pointer)) {
+                *value = serde_json::Value::String(format!("${{{}_{}{}}}", env_name(key), prefix, name));
+            }
+        }
+    }
+}
+
+/// Puts the stored credentials of `current` (the config of the monitor with
+/// the same key) back where `config` has placeholders. Steps are matched by
+/// name. A placeholder without a stored value is an error.
+pub fn restore_secrets(
+    key: &str,
+    config: &mut serde_json::Value,
+    current: Option<&serde_json::Value>,
+) -> Result<(), crate::ApiError> {
+    for (_, base) in request_pointers(config) {
+        let Some(request) = config.pointer(&base).cloned() else {
+            continue;
+        };
+        let current_request = current.and_then(|current| {
+            if base.is_empty() {
+                return Some(current);
+            }
+            current
+                .get("steps")
+                .and_then(|steps| steps.as_array())
+                .and_then(|steps| steps.iter().find(|step| step.get("name") == request.get("name")))
+        });
+
+        for (_, pointer) in secret_pointers(&request) {
+            let Some(placeholder) = request.pointer(&pointer).and_then(|v| v.as_str()).filter(|v| is_placeholder(v)) else {
+                continue;
+            };
+            let stored = current_request
+                .and_then(|r| r.pointer(&pointer))
+                .and_then(|v| v.as_str())
+                .filter(|v| !is_placeholder(v));
+            let Some(stored) = stored else {
+                return Err(crate::ApiError::BadRequest(format!(
+                    "{}: {} has no stored value; set it before applying",
+                    key, placeholder
+                )));
+            };
+            if let Some(value) = config.pointer_mut(&format!("{}{}", base, pointer)) {
+                *value = serde_json::Value::String(stored.to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn create_from_spec(spec: &MonitorSpec) -> CreateMonitor {
+    CreateMonitor {
+        key: Some(spec.key.clone()),
+        name: spec.name.clone(),
+        kind: spec.kind.clone(),
+        url: spec.url.clone(),
+        keyword: spec.keyword.clone(),
+        interval_seconds: Some(spec.interval_seconds),
+        enabled: Some(spec.enabled),
+        config: (!spec.config.is_null()).then(|| spec.config.clone()),
+        confirmation_retries: Some(spec.confirmation.confirmation_retries),
+        regions: Some(spec.confirmation.regions.clone()),
+        down_quorum: Some(spec.confirmation.down_quorum),
+        tags: Some(spec.tags.clone()),
+        group_id: None,
+        anomaly: Some(spec.anomaly.clone()),
+    }
+}
+
+/// Parses a YAML or JSON document (JSON is valid YAML) and checks its keys.
+pub fn parse_document(body: &str) -> Result<MonitorsDocument, crate::ApiError> {
+    let document: MonitorsDocument = serde_yaml::from_str(body)
+        .map_err(|e| crate::ApiError::BadRequest(format!("Invalid monitors document: {}", e)))?;
+
+    if document.version != DOCUMENT_VERSION {
+        return Err(crate::ApiError::BadRequest(
+            format!("Unsupported document version: {}. Must be {}", document.version, DOCUMENT_VERSION)
+        ));
+    }
+
+    for (i, spec) in document.monitors.iter().enumerate() {
+        validate_key(&spec.key)?;
+        if document.monitors[..i].iter().any(|s| s.key == spec.key) {
+            return Err(crate::ApiError::BadRequest(format!("Duplicate monitor key: {}", spec.key)));
+        }
+    }
+
+    Ok(document)
+}
+
+pub fn render_document(document: &MonitorsDocument, format: &str) -> Result<String, crate::ApiError> {
+    let rendered = match format {
+        "yaml" => serde_yaml::to_string(document).map_err(|e| e.to_string()),
+        "json" => serde_json::to_string_pretty(document).map_err(|e| e.to_string()),
+        other => {
+            return Err(crate::ApiError::BadRequest(
+                format!("Invalid format: {}. Must be one of: {:?}", other, EXPORT_FORMATS)
+            ));
+        }
+    };
+
+    rendered.map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })
+}
+
+/// Names of the fields that differ between the current and desired spec.
+/// Heartbeat URLs are generated, and an omitted keyword keeps the current one.
+pub fn changed_fields(current: &MonitorSpec, desired: &MonitorSpec) -> Vec<String> {
+    let mut changes = Vec::new();
+    let mut check = |field: &str, changed: bool| {
+        if changed {
+            changes.push(field.to_string());
+        }
+    };
+
+    check("name", current.name != desired.name);
+    check("kind", current.kind != desired.kind);
+    check("url", desired.kind != "heartbeat" && current.url != desired.url);
+    check("keyword", desired.keyword.is_some() && current.keyword != desired.keyword);
+    check("interval_seconds", current.interval_seconds != desired.interval_seconds);
+    check("enabled", current.enabled != desired.enabled);
+    check("config", current.config != desired.config);
+    check("confirmation_retries", current.confirmation.confirmation_retries != desired.confirmation.confirmation_retries);
+    check("regions", current.confirmation.regions != desired.confirmation.regions);
+    check("down_quorum", current.confirmation.down_quorum != desired.confirmation.down_quorum);
+    check("tags", current.tags != desired.tags);
+    check("anomaly", current.anomaly != desired.anomaly);
+    check("group", current.group != desired.group);
+    changes
+}
+
+/// Plans how to make the project's monitors match `desired`, whose configs
+/// must already be normalized. Returns the plan items (creates, updates,
+/// unchanged, then deletes) and the group names that do not exist yet.
+pub fn build_plan(
+    existing: &[Monitor],
+    groups: &[MonitorGroup],
+    desired: &[MonitorSpec],
+) -> Result<(Vec<PlanItem>, Vec<String>), crate::ApiError> {
+    let keyed: Vec<(String, &Monitor)> = existing.iter().map(|m| (effective_key(m), m)).collect();
+    for (i, (key, _)) in keyed.iter().enumerate() {
+        if keyed[..i].iter().any(|(k, _)| k == key) {
+            return Err(crate::ApiError::BadRequest(format!(
+                "Several existing monitors share the key {}; give them distinct keys before applying",
+                key
+            )));
+        }
+    }
+
+    let mut items = Vec::new();
+    for spec in desired {
+        let Some((_, monitor)) = keyed.iter().find(|(key, _)| key == &spec.key) else {
+            items.push(PlanItem {
+                action: "create".to_string(),
+                key: spec.key.clone(),
+                monitor_id: None,
+                changes: Vec::new(),
+            });
+            continue;
+        };
+
+        if (monitor.kind == "heartbeat") != (spec.kind == "heartbeat") {
+            return Err(crate::ApiError::BadRequest(
+                format!("{}: Cannot convert between heartbeat and probed monitors", spec.key)
+            ));
+        }
+
+        let mut changes = changed_fields(&spec_from_monitor(monitor, groups), spec);
+        if monitor.key.is_none() {
+            changes.push("key".to_string());
+        }
+        items.push(PlanItem {
+            action: if changes.is_empty() { "unchanged" } else { "update" }.to_string(),
+            key: spec.key.clone(),
+            monitor_id: Some(monitor.id.clone()),
+            changes,
+        });
+    }
+
+    for (key, monitor) in &keyed {
+        if !desired.iter().any(|spec| &spec.key == key) {
+            items.push(PlanItem {
+                action: "delete".to_string(),
+                key: key.clone(),
+                monitor_id: Some(monitor.id.clone()),
+                changes: Vec::new(),
+            });
+        }
+    }
+
+    let mut groups_to_create: Vec<String> = Vec::new();
+    for name in desired.iter().filter_map(|spec| spec.group.as_ref()) {
+        if !groups.iter().any(|g| &g.name == name) && !groups_to_create.contains(name) {
+            groups_to_create.push(name.clone());
+        }
+    }
+
+    Ok((items, groups_to_create))
+}
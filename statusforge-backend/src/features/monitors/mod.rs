@@ -1,7 +1,19 @@
+pub mod anomaly;
+pub mod checks;
+pub mod confirmation;
+pub mod heartbeat;
+pub mod jsonpath;
+pub mod manifest;
 mod model;
+pub mod pause;
 pub mod repository;
+pub mod rollups;
+pub mod scheduler;
 mod route;
 pub mod service;
+pub mod state;
+pub mod tags;
+pub mod uptime;
 
 pub use model::*;
 pub use route::*;
@@ -1,19 +1,140 @@
 use serde::{Deserialize, Serialize};
 
+use super::tags::Tags;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Monitor {
     pub id: String,
     pub project_id: String,
+    /// Stable identifier used by monitors-as-code, see `manifest`.
+    #[serde(default)]
+    pub key: Option<String>,
     pub name: String,
     pub kind: String,
     pub url: String,
     pub keyword: Option<String>,
     pub interval_seconds: i32,
     pub enabled: bool,
+    #[serde(default)]
+    pub config: serde_json::Value,
+    #[serde(default)]
+    pub heartbeat_token: Option<String>,
+    #[serde(default)]
+    pub last_heartbeat_at: Option<String>,
+    #[serde(default)]
+    pub heartbeat_started_at: Option<String>,
+    /// Start of the latest check, the scheduler runs the next one
+    /// `interval_seconds` later.
+    #[serde(default)]
+    pub last_checked_at: Option<String>,
+    #[serde(flatten)]
+    pub confirmation: ConfirmationSettings,
+    #[serde(default)]
+    pub anomaly: AnomalySettings,
+    /// One of `state::MONITOR_STATES`; `unknown` until the first result.
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub state_since: Option<String>,
+    #[serde(default)]
+    pub tags: Tags,
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Set while paused through `pause`; cleared on resume.
+    #[serde(default)]
+    pub paused_at: Option<String>,
+    /// The pause job resumes the monitor once this has passed.
+    #[serde(default)]
+    pub paused_until: Option<String>,
+    #[serde(default)]
+    pub pause_reason: Option<String>,
+    #[serde(default)]
+    pub paused_by: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// When a failure counts as downtime: each region is re-checked
+/// `confirmation_retries` times before it is considered failed, and the
+/// monitor is `down` only if at least `down_quorum` regions failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmationSettings {
+    pub confirmation_retries: i32,
+    pub regions: Vec<String>,
+    pub down_quorum: i32,
+}
+
+impl Default for ConfirmationSettings {
+    fn default() -> Self {
+        ConfirmationSettings {
+            confirmation_retries: 0,
+            regions: vec!["EU".to_string()],
+            down_quorum: 1,
+        }
+    }
+}
+
+/// Response-time anomaly detection, see `anomaly`. Anomalous results can be
+/// downgraded to `degraded`, and `incident_after` consecutive anomalous
+/// results in a region open a latency incident (0 disables that).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnomalySettings {
+    pub enabled: bool,
+    /// EWMA smoothing factor.
+    pub alpha: f64,
+    /// Modified z-score above which a response time is anomalous.
+    pub threshold: f64,
+    /// Number of recent results per region forming the baseline.
+    pub window: usize,
+    pub min_samples: usize,
+    pub min_delta_ms: f64,
+    pub mark_degraded: bool,
+    pub incident_after: u32,
+}
+
+impl Default for AnomalySettings {
+    fn default() -> Self {
+        AnomalySettings {
+            enabled: false,
+            alpha: 0.3,
+            threshold: 5.0,
+            window: 30,
+            min_samples: 10,
+            min_delta_ms: 100.0,
+            mark_degraded: false,
+            incident_after: 0,
+        }
+    }
+}
+
+impl AnomalySettings {
+    pub fn is_default(&self) -> bool {
+        *self == AnomalySettings::default()
+    }
+}
+
+/// Aggregated outcome of one confirmed check across all configured regions.
+/// The raw per-region rows are in `monitor_results` (`result_ids`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorCheck {
+    pub id: String,
+    pub monitor_id: String,
+    pub status: String,
+    pub regions_total: i32,
+    pub regions_failed: i32,
+    pub attempts: i32,
+    pub result_ids: Vec<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckOutcome {
+    pub check: MonitorCheck,
+    pub results: Vec<MonitorResult>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitorResult {
     pub id: String,
@@ -25,38 +146,190 @@ pub struct MonitorResult {
     pub ssl_valid: Option<bool>,
     pub ssl_expires_at: Option<String>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub packet_loss: Option<f64>,
+    #[serde(default)]
+    pub jitter_ms: Option<f64>,
+    #[serde(default)]
+    pub min_response_time_ms: Option<i32>,
+    #[serde(default)]
+    pub max_response_time_ms: Option<i32>,
+    #[serde(default)]
+    pub failed_step: Option<String>,
+    #[serde(default)]
+    pub steps: Option<Vec<StepResult>>,
+    #[serde(default)]
+    pub ssl_days_remaining: Option<i32>,
+    #[serde(default)]
+    pub ssl_issuer: Option<String>,
+    #[serde(default)]
+    pub ssl_details: Option<SslDetails>,
+    #[serde(default)]
+    pub domain_expires_at: Option<String>,
+    #[serde(default)]
+    pub domain_days_remaining: Option<i32>,
+    #[serde(default)]
+    pub domain_registrar: Option<String>,
+    #[serde(default)]
+    pub domain_statuses: Option<Vec<String>>,
+    /// Recorded during a maintenance window; excluded from uptime.
+    #[serde(default)]
+    pub in_maintenance: bool,
+    #[serde(default)]
+    pub anomalous: bool,
+    #[serde(default)]
+    pub anomaly_score: Option<f64>,
+    /// EWMA of the region's recent response times when this result came in.
+    #[serde(default)]
+    pub baseline_ms: Option<f64>,
+    /// The check of `service::run_monitor_check` this result was part of.
+    #[serde(default)]
+    pub check_id: Option<String>,
     pub created_at: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CreateMonitor {
+    pub key: Option<String>,
     pub name: String,
     pub kind: String,
+    #[serde(default)]
     pub url: String,
     pub keyword: Option<String>,
     pub interval_seconds: Option<i32>,
     pub enabled: Option<bool>,
+    pub config: Option<serde_json::Value>,
+    pub confirmation_retries: Option<i32>,
+    pub regions: Option<Vec<String>>,
+    pub down_quorum: Option<i32>,
+    pub tags: Option<Tags>,
+    pub group_id: Option<String>,
+    pub anomaly: Option<AnomalySettings>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct UpdateMonitor {
+    pub key: Option<String>,
     pub name: Option<String>,
     pub kind: Option<String>,
     pub url: Option<String>,
     pub keyword: Option<String>,
     pub interval_seconds: Option<i32>,
     pub enabled: Option<bool>,
+    pub config: Option<serde_json::Value>,
+    pub confirmation_retries: Option<i32>,
+    pub regions: Option<Vec<String>>,
+    pub down_quorum: Option<i32>,
+    pub tags: Option<Tags>,
+    /// An empty string removes the monitor from its group.
+    pub group_id: Option<String>,
+    pub anomaly: Option<AnomalySettings>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Normalized settings of a new monitor, see `service::validate_new_monitor`.
+#[derive(Debug)]
+pub struct ValidatedMonitor {
+    pub config: serde_json::Value,
+    pub interval_seconds: i32,
+    pub confirmation: ConfirmationSettings,
+    pub tags: Tags,
+    pub anomaly: AnomalySettings,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PauseMonitor {
+    pub until: Option<String>,
+    pub reason: Option<String>,
+    /// Who paused the monitor, e.g. a user email or a deploy pipeline name.
+    pub actor: Option<String>,
+}
+
+pub const BULK_ACTIONS: [&str; 4] = ["pause", "resume", "delete", "retag"];
+
+/// Selects monitors of a project by explicit ids and/or a filter; all given
+/// criteria must match.
+#[derive(Debug, Default, Deserialize)]
+pub struct MonitorSelector {
+    pub monitor_ids: Option<Vec<String>>,
+    pub kind: Option<String>,
+    /// `key` or `key=value`, see `tags::parse_filter`.
+    pub tag: Option<String>,
+    pub group_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BulkMonitorRequest {
+    pub action: String,
+    #[serde(flatten)]
+    pub selector: MonitorSelector,
+    /// `pause` only.
+    #[serde(flatten)]
+    pub pause: PauseMonitor,
+    /// `retag` only: tags to add or overwrite.
+    #[serde(default)]
+    pub set_tags: Tags,
+    /// `retag` only: tag keys to remove.
+    #[serde(default)]
+    pub remove_tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkMonitorResponse {
+    pub action: String,
+    pub affected: usize,
+    pub monitor_ids: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CreateMonitorResult {
     pub region: String,
     pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub response_time_ms: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_status: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ssl_valid: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ssl_expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packet_loss: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_response_time_ms: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_time_ms: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<StepResult>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_days_remaining: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssl_details: Option<SslDetails>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_days_remaining: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_registrar: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain_statuses: Option<Vec<String>>,
+    /// Set by the backend from the project's maintenance windows.
+    #[serde(default)]
+    pub in_maintenance: bool,
+    /// Set by the backend, see `anomaly`.
+    #[serde(default)]
+    pub anomalous: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_ms: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,9 +338,431 @@ pub struct ListMonitorResultsQuery {
     pub status: Option<String>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// `auto` (default), `raw`, `hour` or `day`; see `rollups::resolution_for`.
+    pub resolution: Option<String>,
+}
+
+/// Aggregated results of one monitor and region over an hour or a day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultRollup {
+    pub monitor_id: String,
+    pub region: String,
+    pub resolution: String,
+    pub bucket_start: String,
+    pub total: i64,
+    pub up: i64,
+    pub degraded: i64,
+    pub down: i64,
+    pub latency_samples: i64,
+    pub min_ms: Option<f64>,
+    pub avg_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    pub p50_ms: Option<f64>,
+    pub p90_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+}
+
+/// Raw rows for short windows, rollups for long ones.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum MonitorResultsPage {
+    Raw(Vec<MonitorResult>),
+    Rollups(Vec<ResultRollup>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ListMonitorsQuery {
+    /// Comma-separated `key` / `key=value` filters, all of which must match.
+    pub tag: Option<String>,
+    pub group_id: Option<String>,
+    pub kind: Option<String>,
+    pub enabled: Option<bool>,
+    /// Current state, one of `state::MONITOR_STATES`.
+    pub status: Option<String>,
+    /// One of `MONITOR_SORT_FIELDS`, prefixed with `-` for descending order.
+    pub sort: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+pub const MONITOR_SORT_FIELDS: [&str; 6] = ["name", "kind", "state", "interval_seconds", "created_at", "updated_at"];
+
+/// Validated form of `ListMonitorsQuery` passed to the repository.
+#[derive(Debug, Default)]
+pub struct MonitorFilter {
+    pub tags: Vec<(String, Option<String>)>,
+    pub group_id: Option<String>,
+    pub kind: Option<String>,
+    pub enabled: Option<bool>,
+    pub state: Option<String>,
+    pub sort_field: String,
+    pub descending: bool,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Named group of monitors within a project, e.g. "API" or "Payments".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorGroup {
+    pub id: String,
+    pub project_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateMonitorGroup {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMonitorGroup {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListMonitorChecksQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UptimeQuery {
+    pub window: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Whether `degraded` results count as available (default `true`).
+    pub degraded_as_up: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeReport {
+    pub monitor_id: String,
+    #[serde(default)]
+    pub window: String,
+    /// `raw`, `hour` or `day`, depending on which data answered the report.
+    #[serde(default)]
+    pub resolution: String,
+    pub from: String,
+    pub to: String,
+    pub uptime_percent: Option<f64>,
+    pub total_checks: i64,
+    pub up_checks: i64,
+    pub degraded_checks: i64,
+    pub down_checks: i64,
+    pub incidents: i64,
+    pub downtime_seconds: f64,
+    pub mttr_seconds: Option<f64>,
+    pub mtbf_seconds: Option<f64>,
+    pub latency: LatencyPercentiles,
+    pub days: Vec<UptimeDay>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeDay {
+    pub date: String,
+    pub total_checks: i64,
+    pub up_checks: i64,
+    pub degraded_checks: i64,
+    pub down_checks: i64,
+    pub uptime_percent: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CheckMonitorRequest {
     pub region: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PingConfig {
+    pub count: u32,
+    pub timeout_ms: u64,
+    pub port: u16,
+    pub degraded_loss_percent: f64,
+    pub degraded_latency_ms: u32,
+}
+
+impl Default for PingConfig {
+    fn default() -> Self {
+        PingConfig {
+            count: 4,
+            timeout_ms: 1000,
+            port: 443,
+            degraded_loss_percent: 20.0,
+            degraded_latency_ms: 500,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeartbeatConfig {
+    pub grace_seconds: u32,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        HeartbeatConfig { grace_seconds: 60 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeartbeatSignal {
+    Success,
+    Start,
+    Fail,
+    ExitCode(i32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpConfig {
+    pub method: String,
+    pub headers: std::collections::BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<HttpAuth>,
+    pub timeout_ms: u64,
+    pub follow_redirects: bool,
+    pub expected_status: Vec<StatusSpec>,
+    pub assertions: Vec<HttpAssertion>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            method: "GET".to_string(),
+            headers: Default::default(),
+            body: None,
+            auth: None,
+            timeout_ms: 10_000,
+            follow_redirects: true,
+            expected_status: Vec::new(),
+            assertions: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HttpAuth {
+    Basic { username: String, password: String },
+    Bearer { token: String },
+}
+
+/// Expected status code: an exact code (`204`), a range (`"200-299"`) or a
+/// class (`"2xx"`). With no entries the default classification applies
+/// (5xx is `down`, 4xx is `degraded`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StatusSpec {
+    Code(u16),
+    Pattern(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HttpAssertion {
+    JsonPathEquals { path: String, value: serde_json::Value },
+    JsonPathExists { path: String },
+    HeaderMatches { name: String, pattern: String },
+    BodyRegex { pattern: String },
+    /// Slower responses are reported as `degraded` rather than `down`.
+    ResponseTime { max_ms: u32 },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MultistepConfig {
+    pub steps: Vec<MultistepStep>,
+}
+
+/// One request of a multi-step monitor. `url`, header values, `body` and auth
+/// credentials may reference variables extracted by earlier steps as
+/// `{{name}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultistepStep {
+    pub name: String,
+    pub url: String,
+    #[serde(flatten)]
+    pub request: HttpConfig,
+    #[serde(default)]
+    pub extract: Vec<VariableExtraction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableExtraction {
+    pub name: String,
+    #[serde(flatten)]
+    pub source: ExtractSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "from", rename_all = "snake_case")]
+pub enum ExtractSource {
+    JsonPath { path: String },
+    Header { header: String },
+    Cookie { cookie: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    pub name: String,
+    pub status: String,
+    pub response_time_ms: Option<i32>,
+    pub http_status: Option<i32>,
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SslConfig {
+    pub port: u16,
+    pub timeout_ms: u64,
+    /// Days before expiry at which results turn `degraded`, e.g. `[14, 7, 1]`.
+    pub warning_days: Vec<u32>,
+}
+
+impl Default for SslConfig {
+    fn default() -> Self {
+        SslConfig {
+            port: 443,
+            timeout_ms: 10_000,
+            warning_days: vec![14, 7, 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SslDetails {
+    pub subject: Option<String>,
+    pub sans: Vec<String>,
+    pub hostname_match: bool,
+    pub chain_complete: bool,
+    pub trusted: bool,
+    pub signature_algorithm: String,
+    pub key_type: String,
+    pub key_bits: Option<u32>,
+    /// The negotiated TLS version.
+    pub protocol: Option<String>,
+    /// Human readable reasons the certificate is considered weak.
+    pub weaknesses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateSummary {
+    pub monitor_id: String,
+    pub monitor_name: String,
+    pub url: String,
+    pub status: Option<String>,
+    pub expires_at: Option<String>,
+    pub days_remaining: Option<i32>,
+    pub issuer: Option<String>,
+    pub details: Option<SslDetails>,
+    pub checked_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DomainConfig {
+    pub timeout_ms: u64,
+    /// Days before registration expiry at which results turn `degraded`.
+    pub warning_days: Vec<u32>,
+}
+
+impl Default for DomainConfig {
+    fn default() -> Self {
+        DomainConfig {
+            timeout_ms: 10_000,
+            warning_days: vec![30, 14, 7],
+        }
+    }
+}
+
+/// One monitor in a monitors-as-code document, matched to existing monitors
+/// by `key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorSpec {
+    pub key: String,
+    pub name: String,
+    pub kind: String,
+    /// Omitted for heartbeat monitors, whose URL is generated.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub config: serde_json::Value,
+    #[serde(flatten)]
+    pub confirmation: ConfirmationSettings,
+    #[serde(default, skip_serializing_if = "Tags::is_empty")]
+    pub tags: Tags,
+    #[serde(default, skip_serializing_if = "AnomalySettings::is_default")]
+    pub anomaly: AnomalySettings,
+    /// Group name; missing groups are created on apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+fn default_interval_seconds() -> i32 {
+    300
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorsDocument {
+    pub version: u32,
+    pub monitors: Vec<MonitorSpec>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportMonitorsQuery {
+    /// `yaml` (default) or `json`.
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ApplyMonitorsQuery {
+    /// Only compute the plan; defaults to `true`.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanItem {
+    /// `create`, `update`, `delete` or `unchanged`.
+    pub action: String,
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitor_id: Option<String>,
+    /// Fields that differ, for `update`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonitorPlan {
+    pub dry_run: bool,
+    pub groups_to_create: Vec<String>,
+    pub items: Vec<PlanItem>,
+}
@@ -0,0 +1,46 @@
+//! Pausing with a recorded reason and actor, optionally until a timestamp
+//! after which a backend job resumes the monitor.
+
+use chrono::{DateTime, Utc};
+
+use crate::shared::utils::parse_timestamp;
+use crate::AppState;
+
+use super::{repository, PauseMonitor};
+
+pub const MAX_REASON_LEN: usize = 500;
+
+/// Checks a pause request and returns the parsed `until`, which must be in
+/// the future.
+pub fn validate_pause(data: &PauseMonitor, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, crate::ApiError> {
+    if let Some(ref reason) = data.reason
+        && reason.len() > MAX_REASON_LEN
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Pause reason cannot be longer than {} characters", MAX_REASON_LEN)
+        ));
+    }
+
+    let Some(ref until) = data.until else {
+        return Ok(None);
+    };
+    let until = parse_timestamp(until)
+        .ok_or_else(|| crate::ApiError::BadRequest("until must be an RFC 3339 timestamp".to_string()))?;
+    if until <= now {
+        return Err(crate::ApiError::BadRequest("until must be in the future".to_string()));
+    }
+    Ok(Some(until))
+}
+
+/// Resumes monitors whose `paused_until` has passed.
+pub async fn resume_expired(state: AppState) -> Result<(), crate::ApiError> {
+    let now = Utc::now();
+    let monitors = repository::list_pause_expired(&state, &now.to_rfc3339()).await?;
+
+    for monitor in monitors {
+        repository::set_paused(&state, &monitor.id, None).await?;
+        eprintln!("Resumed monitor {} after pause until {}", monitor.id, monitor.paused_until.unwrap_or_default());
+    }
+
+    Ok(())
+}
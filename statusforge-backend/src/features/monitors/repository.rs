@@ -1,7 +1,8 @@
 use crate::AppState;
 use supabase::types::OrderDirection;
 
-use super::{Monitor, MonitorResult};
+use super::tags::Tags;
+use super::{AnomalySettings, ConfirmationSettings, CreateMonitorGroup, CreateMonitorResult, Monitor, MonitorCheck, MonitorFilter, MonitorGroup, MonitorResult, PauseMonitor, UpdateMonitor, UpdateMonitorGroup, ResultRollup, UptimeReport};
 
 pub async fn list_by_project(state: &AppState, project_id: &str) -> Result<Vec<Monitor>, crate::ApiError> {
     let monitors = state
@@ -20,6 +21,59 @@ pub async fn list_by_project(state: &AppState, project_id: &str) -> Result<Vec<M
     Ok(monitors)
 }
 
+pub async fn list_filtered(
+    state: &AppState,
+    project_id: &str,
+    filter: &MonitorFilter,
+) -> Result<Vec<Monitor>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("monitors")
+        .select("*")
+        .eq("project_id", project_id);
+
+    for (key, value) in &filter.tags {
+        let path = format!("tags->>{}", key);
+        query = match value {
+            Some(value) => query.eq(&path, value),
+            None => query.like(&path, "*"),
+        };
+    }
+    if let Some(ref group_id) = filter.group_id {
+        query = query.eq("group_id", group_id);
+    }
+    if let Some(ref kind) = filter.kind {
+        query = query.eq("kind", kind);
+    }
+    if let Some(enabled) = filter.enabled {
+        query = query.eq("enabled", if enabled { "true" } else { "false" });
+    }
+    if let Some(ref monitor_state) = filter.state {
+        query = query.eq("state", monitor_state);
+    }
+
+    let direction = if filter.descending { OrderDirection::Descending } else { OrderDirection::Ascending };
+    query = query.order(&filter.sort_field, direction);
+
+    if let Some(limit) = filter.limit {
+        query = query.limit(limit.min(500));
+    }
+    if let Some(offset) = filter.offset {
+        query = query.offset(offset);
+    }
+
+    let monitors = query
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(monitors)
+}
+
 pub async fn get_by_id(state: &AppState, id: &str) -> Result<Monitor, crate::ApiError> {
     let mut monitors = state
         .supabase
@@ -37,6 +91,62 @@ pub async fn get_by_id(state: &AppState, id: &str) -> Result<Monitor, crate::Api
     monitors.pop().ok_or(crate::ApiError::NotFound)
 }
 
+pub async fn get_by_heartbeat_token(state: &AppState, token: &str) -> Result<Monitor, crate::ApiError> {
+    let mut monitors = state
+        .supabase
+        .database()
+        .from("monitors")
+        .select("*")
+        .eq("heartbeat_token", token)
+        .eq("kind", "heartbeat")
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    monitors.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn list_enabled_by_kind(state: &AppState, kind: &str) -> Result<Vec<Monitor>, crate::ApiError> {
+    let monitors = state
+        .supabase
+        .database()
+        .from("monitors")
+        .select("*")
+        .eq("kind", kind)
+        .eq("enabled", "true")
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(monitors)
+}
+
+/// Enabled monitors that are probed on a schedule, i.e. all but heartbeats.
+pub async fn list_scheduled(state: &AppState) -> Result<Vec<Monitor>, crate::ApiError> {
+    let monitors = state
+        .supabase
+        .database()
+        .from("monitors")
+        .select("*")
+        .eq("enabled", "true")
+        .neq("kind", "heartbeat")
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(monitors)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn create(
     state: &AppState,
     project_id: &str,
@@ -46,6 +156,13 @@ pub async fn create(
     keyword: Option<&str>,
     interval_seconds: i32,
     enabled: bool,
+    config: &serde_json::Value,
+    heartbeat_token: Option<&str>,
+    confirmation: &ConfirmationSettings,
+    tags: &Tags,
+    group_id: Option<&str>,
+    key: Option<&str>,
+    anomaly: &AnomalySettings,
 ) -> Result<Monitor, crate::ApiError> {
     let mut data = serde_json::json!({
         "project_id": project_id,
@@ -54,11 +171,22 @@ pub async fn create(
         "url": url,
         "interval_seconds": interval_seconds,
         "enabled": enabled,
+        "config": config,
+        "confirmation_retries": confirmation.confirmation_retries,
+        "regions": confirmation.regions,
+        "down_quorum": confirmation.down_quorum,
+        "tags": tags,
+        "group_id": group_id,
+        "key": key,
+        "anomaly": anomaly,
     });
 
     if let Some(kw) = keyword {
         data["keyword"] = serde_json::Value::String(kw.to_string());
     }
+    if let Some(token) = heartbeat_token {
+        data["heartbeat_token"] = serde_json::Value::String(token.to_string());
+    }
 
     let mut monitors = state
         .supabase
@@ -80,37 +208,52 @@ pub async fn create(
     monitors.pop().ok_or(crate::ApiError::InternalServerError)
 }
 
-pub async fn update(
-    state: &AppState,
-    id: &str,
-    name: Option<&str>,
-    kind: Option<&str>,
-    url: Option<&str>,
-    keyword: Option<&str>,
-    interval_seconds: Option<i32>,
-    enabled: Option<bool>,
-) -> Result<Monitor, crate::ApiError> {
+/// Writes the fields set in `changes`; the caller validates them first.
+pub async fn update(state: &AppState, id: &str, changes: &UpdateMonitor) -> Result<Monitor, crate::ApiError> {
     let mut data = serde_json::Map::new();
-    if let Some(name) = name {
-        data.insert("name".to_string(), serde_json::Value::String(name.to_string()));
+    if let Some(ref name) = changes.name {
+        data.insert("name".to_string(), serde_json::Value::String(name.clone()));
     }
-    if let Some(kind) = kind {
-        data.insert("kind".to_string(), serde_json::Value::String(kind.to_string()));
+    if let Some(ref kind) = changes.kind {
+        data.insert("kind".to_string(), serde_json::Value::String(kind.clone()));
     }
-    if let Some(url) = url {
-        data.insert("url".to_string(), serde_json::Value::String(url.to_string()));
+    if let Some(ref url) = changes.url {
+        data.insert("url".to_string(), serde_json::Value::String(url.clone()));
     }
-    if let Some(kw) = keyword {
-        data.insert("keyword".to_string(), serde_json::Value::String(kw.to_string()));
-    } else if keyword.is_some() {
-        data.insert("keyword".to_string(), serde_json::Value::Null);
+    if let Some(ref kw) = changes.keyword {
+        data.insert("keyword".to_string(), serde_json::Value::String(kw.clone()));
     }
-    if let Some(interval) = interval_seconds {
+    if let Some(interval) = changes.interval_seconds {
         data.insert("interval_seconds".to_string(), serde_json::Value::Number(interval.into()));
     }
-    if let Some(enabled) = enabled {
+    if let Some(enabled) = changes.enabled {
         data.insert("enabled".to_string(), serde_json::Value::Bool(enabled));
     }
+    if let Some(ref config) = changes.config {
+        data.insert("config".to_string(), config.clone());
+    }
+    if let Some(retries) = changes.confirmation_retries {
+        data.insert("confirmation_retries".to_string(), serde_json::Value::Number(retries.into()));
+    }
+    if let Some(ref regions) = changes.regions {
+        data.insert("regions".to_string(), serde_json::json!(regions));
+    }
+    if let Some(quorum) = changes.down_quorum {
+        data.insert("down_quorum".to_string(), serde_json::Value::Number(quorum.into()));
+    }
+    if let Some(ref tags) = changes.tags {
+        data.insert("tags".to_string(), serde_json::json!(tags));
+    }
+    if let Some(ref group_id) = changes.group_id {
+        let group_id = Some(group_id).filter(|id| !id.is_empty());
+        data.insert("group_id".to_string(), serde_json::json!(group_id));
+    }
+    if let Some(ref key) = changes.key {
+        data.insert("key".to_string(), serde_json::Value::String(key.clone()));
+    }
+    if let Some(ref anomaly) = changes.anomaly {
+        data.insert("anomaly".to_string(), serde_json::json!(anomaly));
+    }
 
     let mut monitors = state
         .supabase
@@ -133,6 +276,152 @@ pub async fn update(
     monitors.pop().ok_or(crate::ApiError::NotFound)
 }
 
+pub async fn update_heartbeat_state(
+    state: &AppState,
+    id: &str,
+    last_heartbeat_at: &str,
+    heartbeat_started_at: Option<&str>,
+) -> Result<(), crate::ApiError> {
+    let data = serde_json::json!({
+        "last_heartbeat_at": last_heartbeat_at,
+        "heartbeat_started_at": heartbeat_started_at,
+    });
+
+    state
+        .supabase
+        .database()
+        .update("monitors")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+pub async fn update_last_checked(state: &AppState, id: &str, last_checked_at: &str) -> Result<(), crate::ApiError> {
+    let data = serde_json::json!({ "last_checked_at": last_checked_at });
+
+    state
+        .supabase
+        .database()
+        .update("monitors")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+pub async fn update_state(state: &AppState, id: &str, monitor_state: &str) -> Result<Monitor, crate::ApiError> {
+    let data = serde_json::json!({
+        "state": monitor_state,
+        "state_since": chrono::Utc::now().to_rfc3339(),
+    });
+
+    let mut monitors = state
+        .supabase
+        .database()
+        .update("monitors")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    monitors.pop().ok_or(crate::ApiError::NotFound)
+}
+
+/// Pauses the monitor, or resumes it and clears the pause fields when
+/// `pause` is `None`.
+pub async fn set_paused(state: &AppState, id: &str, pause: Option<&PauseMonitor>) -> Result<Monitor, crate::ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let data = match pause {
+        Some(pause) => serde_json::json!({
+            "enabled": false,
+            "state": "paused",
+            "state_since": now,
+            "paused_at": now,
+            "paused_until": pause.until,
+            "pause_reason": pause.reason,
+            "paused_by": pause.actor,
+        }),
+        None => serde_json::json!({
+            "enabled": true,
+            "state": "unknown",
+            "state_since": now,
+            "paused_at": null,
+            "paused_until": null,
+            "pause_reason": null,
+            "paused_by": null,
+        }),
+    };
+
+    let mut monitors = state
+        .supabase
+        .database()
+        .update("monitors")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    monitors.pop().ok_or(crate::ApiError::NotFound)
+}
+
+/// Paused monitors whose `paused_until` is at or before `now`.
+pub async fn list_pause_expired(state: &AppState, now: &str) -> Result<Vec<Monitor>, crate::ApiError> {
+    let monitors = state
+        .supabase
+        .database()
+        .from("monitors")
+        .select("*")
+        .eq("enabled", "false")
+        .lte("paused_until", now)
+        .execute::<Monitor>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(monitors)
+}
+
 pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
     state
         .supabase
@@ -152,35 +441,13 @@ pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
 pub async fn create_result(
     state: &AppState,
     monitor_id: &str,
-    region: &str,
-    status: &str,
-    response_time_ms: Option<i32>,
-    http_status: Option<i32>,
-    ssl_valid: Option<bool>,
-    ssl_expires_at: Option<&str>,
-    error_message: Option<&str>,
+    result: &CreateMonitorResult,
 ) -> Result<MonitorResult, crate::ApiError> {
-    let mut data = serde_json::json!({
-        "monitor_id": monitor_id,
-        "region": region,
-        "status": status,
-    });
-
-    if let Some(rt) = response_time_ms {
-        data["response_time_ms"] = serde_json::Value::Number(rt.into());
-    }
-    if let Some(hs) = http_status {
-        data["http_status"] = serde_json::Value::Number(hs.into());
-    }
-    if let Some(sv) = ssl_valid {
-        data["ssl_valid"] = serde_json::Value::Bool(sv);
-    }
-    if let Some(se) = ssl_expires_at {
-        data["ssl_expires_at"] = serde_json::Value::String(se.to_string());
-    }
-    if let Some(em) = error_message {
-        data["error_message"] = serde_json::Value::String(em.to_string());
-    }
+    let mut data = serde_json::to_value(result).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    data["monitor_id"] = serde_json::Value::String(monitor_id.to_string());
 
     let mut results = state
         .supabase
@@ -207,6 +474,7 @@ pub async fn list_results(
     monitor_id: &str,
     region: Option<&str>,
     status: Option<&str>,
+    range: Option<(&str, &str)>,
     limit: Option<u32>,
     offset: Option<u32>,
 ) -> Result<Vec<MonitorResult>, crate::ApiError> {
@@ -223,6 +491,9 @@ pub async fn list_results(
     if let Some(status) = status {
         query = query.eq("status", status);
     }
+    if let Some((from, to)) = range {
+        query = query.gte("created_at", from).lt("created_at", to);
+    }
 
     query = query.order("created_at", OrderDirection::Descending);
 
@@ -246,3 +517,337 @@ pub async fn list_results(
 
     Ok(results)
 }
+
+pub async fn create_check(
+    state: &AppState,
+    monitor_id: &str,
+    status: &str,
+    regions_total: i32,
+    regions_failed: i32,
+    attempts: i32,
+    result_ids: &[String],
+) -> Result<MonitorCheck, crate::ApiError> {
+    let data = serde_json::json!({
+        "monitor_id": monitor_id,
+        "status": status,
+        "regions_total": regions_total,
+        "regions_failed": regions_failed,
+        "attempts": attempts,
+        "result_ids": result_ids,
+    });
+
+    let mut checks = state
+        .supabase
+        .database()
+        .insert("monitor_checks")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<MonitorCheck>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create monitor check: {:?}", e))
+        })?;
+
+    checks.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+/// Links a result to the check it was part of, see `monitor_check_samples`.
+pub async fn set_result_check(state: &AppState, result_id: &str, check_id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .update("monitor_results")
+        .set(serde_json::json!({ "check_id": check_id }))
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", result_id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+pub async fn list_checks(
+    state: &AppState,
+    monitor_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<MonitorCheck>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("monitor_checks")
+        .select("*")
+        .eq("monitor_id", monitor_id)
+        .order("created_at", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(100));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let checks = query
+        .execute::<MonitorCheck>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(checks)
+}
+
+pub async fn uptime_report(
+    state: &AppState,
+    monitor_id: &str,
+    from: &str,
+    to: &str,
+    degraded_as_up: bool,
+    resolution: &str,
+) -> Result<UptimeReport, crate::ApiError> {
+    let params = serde_json::json!({
+        "p_monitor_id": monitor_id,
+        "p_from": from,
+        "p_to": to,
+        "p_degraded_as_up": degraded_as_up,
+        "p_resolution": resolution,
+    });
+
+    let report = state
+        .supabase
+        .database()
+        .rpc("monitor_uptime_report", Some(params))
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    serde_json::from_value(report).map_err(|e| {
+        eprintln!("Deserialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })
+}
+
+/// The newest result of every SSL monitor of the project.
+pub async fn list_latest_ssl_results(state: &AppState, project_id: &str) -> Result<Vec<MonitorResult>, crate::ApiError> {
+    let results = state
+        .supabase
+        .database()
+        .rpc("latest_ssl_results", Some(serde_json::json!({ "p_project_id": project_id })))
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    serde_json::from_value(results).map_err(|e| {
+        eprintln!("Deserialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })
+}
+
+pub async fn list_rollups(
+    state: &AppState,
+    monitor_id: &str,
+    resolution: &str,
+    region: Option<&str>,
+    range: (&str, &str),
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<ResultRollup>, crate::ApiError> {
+    let (from, to) = range;
+    let mut query = state
+        .supabase
+        .database()
+        .from("monitor_result_rollups")
+        .select("*")
+        .eq("monitor_id", monitor_id)
+        .eq("resolution", resolution)
+        .gte("bucket_start", from)
+        .lt("bucket_start", to);
+
+    if let Some(region) = region {
+        query = query.eq("region", region);
+    }
+
+    query = query
+        .order("bucket_start", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(1000));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let rollups = query
+        .execute::<ResultRollup>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(rollups)
+}
+
+pub async fn refresh_rollups(state: &AppState, resolution: &str, from: &str, to: &str) -> Result<(), crate::ApiError> {
+    let params = serde_json::json!({
+        "p_resolution": resolution,
+        "p_from": from,
+        "p_to": to,
+    });
+
+    state
+        .supabase
+        .database()
+        .rpc("rollup_monitor_results", Some(params))
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+/// Deletes raw results older than `before`, returning how many were removed.
+pub async fn prune_results(state: &AppState, before: &str) -> Result<u64, crate::ApiError> {
+    let deleted = state
+        .supabase
+        .database()
+        .rpc("prune_monitor_results", Some(serde_json::json!({ "p_before": before })))
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(deleted.as_u64().unwrap_or_default())
+}
+
+pub async fn list_groups(state: &AppState, project_id: &str) -> Result<Vec<MonitorGroup>, crate::ApiError> {
+    let groups = state
+        .supabase
+        .database()
+        .from("monitor_groups")
+        .select("*")
+        .eq("project_id", project_id)
+        .order("name", OrderDirection::Ascending)
+        .execute::<MonitorGroup>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(groups)
+}
+
+pub async fn get_group(state: &AppState, id: &str) -> Result<MonitorGroup, crate::ApiError> {
+    let mut groups = state
+        .supabase
+        .database()
+        .from("monitor_groups")
+        .select("*")
+        .eq("id", id)
+        .execute::<MonitorGroup>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    groups.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create_group(
+    state: &AppState,
+    project_id: &str,
+    group: &CreateMonitorGroup,
+) -> Result<MonitorGroup, crate::ApiError> {
+    let data = serde_json::json!({
+        "project_id": project_id,
+        "name": group.name,
+        "description": group.description,
+    });
+
+    let mut groups = state
+        .supabase
+        .database()
+        .insert("monitor_groups")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<MonitorGroup>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create monitor group: {:?}", e))
+        })?;
+
+    groups.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update_group(
+    state: &AppState,
+    id: &str,
+    group: &UpdateMonitorGroup,
+) -> Result<MonitorGroup, crate::ApiError> {
+    let mut data = serde_json::Map::new();
+    if let Some(ref name) = group.name {
+        data.insert("name".to_string(), serde_json::Value::String(name.clone()));
+    }
+    if let Some(ref description) = group.description {
+        data.insert("description".to_string(), serde_json::Value::String(description.clone()));
+    }
+
+    let mut groups = state
+        .supabase
+        .database()
+        .update("monitor_groups")
+        .set(serde_json::Value::Object(data))
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<MonitorGroup>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    groups.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete_group(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("monitor_groups")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
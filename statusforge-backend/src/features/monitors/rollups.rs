@@ -0,0 +1,65 @@
+//! Hourly and daily rollups of `monitor_results` plus raw-row retention.
+//!
+//! A backend job keeps the most recent buckets up to date through the
+//! `rollup_monitor_results` database function and prunes raw rows older than
+//! `RESULT_RETENTION_DAYS`. Long windows in the results and uptime APIs are
+//! served from rollups instead of raw rows.
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+
+use crate::AppState;
+
+use super::repository;
+
+pub const RESOLUTIONS: [&str; 4] = ["auto", "raw", "hour", "day"];
+
+/// Longest window still answered from raw rows.
+pub const RAW_MAX_WINDOW_DAYS: i64 = 2;
+
+/// Longest window answered from hourly rollups; anything longer uses daily.
+pub const HOURLY_MAX_WINDOW_DAYS: i64 = 31;
+
+pub fn retention_cutoff(now: DateTime<Utc>, retention_days: u32) -> DateTime<Utc> {
+    now - Duration::days(retention_days as i64)
+}
+
+/// Resolution for `[from, to)`: raw for short recent windows, hourly up to a
+/// month, daily beyond that. Windows reaching past the retention cutoff never
+/// use raw rows since those have been pruned.
+pub fn resolution_for(from: DateTime<Utc>, to: DateTime<Utc>, cutoff: DateTime<Utc>) -> &'static str {
+    let span = to - from;
+    if span > Duration::days(HOURLY_MAX_WINDOW_DAYS) {
+        "day"
+    } else if span > Duration::days(RAW_MAX_WINDOW_DAYS) || from < cutoff {
+        "hour"
+    } else {
+        "raw"
+    }
+}
+
+/// Buckets refreshed on each run: the current and two previous hours, and
+/// today plus yesterday, so late results are still folded in.
+pub fn refresh_ranges(now: DateTime<Utc>) -> [(&'static str, DateTime<Utc>, DateTime<Utc>); 2] {
+    let hour = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+    let day = now.duration_trunc(Duration::days(1)).unwrap_or(now);
+    [
+        ("hour", hour - Duration::hours(2), now),
+        ("day", day - Duration::days(1), now),
+    ]
+}
+
+pub async fn run(state: AppState) -> Result<(), crate::ApiError> {
+    let now = Utc::now();
+
+    for (resolution, from, to) in refresh_ranges(now) {
+        repository::refresh_rollups(&state, resolution, &from.to_rfc3339(), &to.to_rfc3339()).await?;
+    }
+
+    let cutoff = retention_cutoff(now, state.config.result_retention_days);
+    let pruned = repository::prune_results(&state, &cutoff.to_rfc3339()).await?;
+    if pruned > 0 {
+        eprintln!("Pruned {} monitor results older than {}", pruned, cutoff.to_rfc3339());
+    }
+
+    Ok(())
+}
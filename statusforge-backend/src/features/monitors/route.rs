@@ -1,15 +1,22 @@
-use axum::{extract::Path, extract::Query, extract::State, routing::{delete, get, post, put}, Json, Router};
+use axum::{extract::Path, extract::Query, extract::State, routing::{get, post, put}, Json, Router};
+use axum::http::header;
+use axum::response::IntoResponse;
 use serde_json::Value;
 
 use crate::{AppState, ApiError};
 
-use super::{service, CreateMonitor, UpdateMonitor, CreateMonitorResult, ListMonitorResultsQuery, CheckMonitorRequest};
+use super::{heartbeat, service, CreateMonitor, UpdateMonitor, CreateMonitorResult, ListMonitorResultsQuery, ListMonitorChecksQuery, CheckMonitorRequest, UptimeQuery, PauseMonitor, BulkMonitorRequest, ListMonitorsQuery, CreateMonitorGroup, UpdateMonitorGroup, ExportMonitorsQuery, ApplyMonitorsQuery, manifest};
 
 async fn list_monitors(
     State(state): State<AppState>,
     Path(project_id): Path<String>,
+    Query(query): Query<ListMonitorsQuery>,
 ) -> Result<Json<Value>, ApiError> {
-    let monitors = service::list_monitors(&state, &project_id).await?;
+    let monitors: Vec<_> = service::list_monitors(&state, &project_id, query)
+        .await?
+        .into_iter()
+        .map(service::redacted)
+        .collect();
     Ok(Json(serde_json::to_value(monitors).unwrap()))
 }
 
@@ -17,7 +24,7 @@ async fn get_monitor(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> Result<Json<Value>, ApiError> {
-    let monitor = service::get_monitor(&state, &id).await?;
+    let monitor = service::redacted(service::get_monitor(&state, &id).await?);
     Ok(Json(serde_json::to_value(monitor).unwrap()))
 }
 
@@ -26,7 +33,7 @@ async fn create_monitor(
     Path(project_id): Path<String>,
     Json(data): Json<CreateMonitor>,
 ) -> Result<Json<Value>, ApiError> {
-    let monitor = service::create_monitor(&state, &project_id, data).await?;
+    let monitor = service::redacted(service::create_monitor(&state, &project_id, data).await?);
     Ok(Json(serde_json::to_value(monitor).unwrap()))
 }
 
@@ -35,7 +42,7 @@ async fn update_monitor(
     Path(id): Path<String>,
     Json(data): Json<UpdateMonitor>,
 ) -> Result<Json<Value>, ApiError> {
-    let monitor = service::update_monitor(&state, &id, data).await?;
+    let monitor = service::redacted(service::update_monitor(&state, &id, data).await?);
     Ok(Json(serde_json::to_value(monitor).unwrap()))
 }
 
@@ -47,6 +54,88 @@ async fn delete_monitor(
     Ok(Json(serde_json::json!({ "message": "Monitor deleted" })))
 }
 
+async fn pause_monitor(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<PauseMonitor>,
+) -> Result<Json<Value>, ApiError> {
+    let monitor = service::redacted(service::pause_monitor(&state, &id, data).await?);
+    Ok(Json(serde_json::to_value(monitor).unwrap()))
+}
+
+async fn resume_monitor(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let monitor = service::redacted(service::resume_monitor(&state, &id).await?);
+    Ok(Json(serde_json::to_value(monitor).unwrap()))
+}
+
+async fn bulk_monitors(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<BulkMonitorRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let response = service::bulk_monitors(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(response).unwrap()))
+}
+
+async fn export_monitors(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<ExportMonitorsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let format = query.format.as_deref().unwrap_or("yaml");
+    let document = service::export_monitors(&state, &project_id).await?;
+    let body = manifest::render_document(&document, format)?;
+    let content_type = if format == "json" { "application/json" } else { "application/yaml" };
+    Ok(([(header::CONTENT_TYPE, content_type)], body))
+}
+
+async fn apply_monitors(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<ApplyMonitorsQuery>,
+    body: String,
+) -> Result<Json<Value>, ApiError> {
+    let plan = service::apply_monitors(&state, &project_id, &body, query).await?;
+    Ok(Json(serde_json::to_value(plan).unwrap()))
+}
+
+async fn list_groups(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let groups = service::list_groups(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(groups).unwrap()))
+}
+
+async fn create_group(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreateMonitorGroup>,
+) -> Result<Json<Value>, ApiError> {
+    let group = service::create_group(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(group).unwrap()))
+}
+
+async fn update_group(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<UpdateMonitorGroup>,
+) -> Result<Json<Value>, ApiError> {
+    let group = service::update_group(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(group).unwrap()))
+}
+
+async fn delete_group(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_group(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Monitor group deleted" })))
+}
+
 async fn create_monitor_result(
     State(state): State<AppState>,
     Path(monitor_id): Path<String>,
@@ -70,14 +159,69 @@ async fn check_monitor(
     Path(monitor_id): Path<String>,
     Query(query): Query<CheckMonitorRequest>,
 ) -> Result<Json<Value>, ApiError> {
-    let result = service::run_monitor_check(&state, &monitor_id, query.region.as_deref()).await?;
-    Ok(Json(serde_json::to_value(result).unwrap()))
+    let outcome = service::run_monitor_check(&state, &monitor_id, query.region.as_deref()).await?;
+    Ok(Json(serde_json::to_value(outcome).unwrap()))
+}
+
+async fn list_monitor_checks(
+    State(state): State<AppState>,
+    Path(monitor_id): Path<String>,
+    Query(query): Query<ListMonitorChecksQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let checks = service::list_monitor_checks(&state, &monitor_id, query).await?;
+    Ok(Json(serde_json::to_value(checks).unwrap()))
+}
+
+async fn uptime_report(
+    State(state): State<AppState>,
+    Path(monitor_id): Path<String>,
+    Query(query): Query<UptimeQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let report = service::uptime_report(&state, &monitor_id, query).await?;
+    Ok(Json(serde_json::to_value(report).unwrap()))
+}
+
+async fn list_certificates(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let certificates = service::list_certificates(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(certificates).unwrap()))
+}
+
+async fn heartbeat_ping(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let result = service::record_heartbeat(&state, &token, heartbeat::parse_signal(None)?).await?;
+    Ok(Json(serde_json::json!({ "ok": true, "result": result })))
+}
+
+async fn heartbeat_ping_with_suffix(
+    State(state): State<AppState>,
+    Path((token, suffix)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let signal = heartbeat::parse_signal(Some(&suffix))?;
+    let result = service::record_heartbeat(&state, &token, signal).await?;
+    Ok(Json(serde_json::json!({ "ok": true, "result": result })))
 }
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/projects/{project_id}/monitors", get(list_monitors).post(create_monitor))
+        .route("/projects/{project_id}/monitors/bulk", post(bulk_monitors))
+        .route("/projects/{project_id}/monitors/export", get(export_monitors))
+        .route("/projects/{project_id}/monitors/apply", post(apply_monitors))
+        .route("/projects/{project_id}/monitor-groups", get(list_groups).post(create_group))
+        .route("/projects/{project_id}/certificates", get(list_certificates))
+        .route("/monitor-groups/{id}", put(update_group).delete(delete_group))
         .route("/monitors/{id}", get(get_monitor).put(update_monitor).delete(delete_monitor))
         .route("/monitors/{id}/check", post(check_monitor))
+        .route("/monitors/{id}/pause", post(pause_monitor))
+        .route("/monitors/{id}/resume", post(resume_monitor))
+        .route("/monitors/{monitor_id}/checks", get(list_monitor_checks))
+        .route("/monitors/{monitor_id}/uptime", get(uptime_report))
         .route("/monitors/{monitor_id}/results", post(create_monitor_result).get(list_monitor_results))
+        .route("/heartbeats/{token}", post(heartbeat_ping))
+        .route("/heartbeats/{token}/{suffix}", post(heartbeat_ping_with_suffix))
 }
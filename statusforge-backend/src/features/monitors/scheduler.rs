@@ -0,0 +1,38 @@
+//! Runs scheduled checks: every enabled, probed monitor is checked through
+//! `service::run_monitor_check` once its `interval_seconds` have passed
+//! since the previous check.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::shared::utils::parse_timestamp;
+use crate::AppState;
+
+use super::{repository, service};
+
+pub fn is_due(last_checked_at: Option<DateTime<Utc>>, interval_seconds: i32, now: DateTime<Utc>) -> bool {
+    last_checked_at.is_none_or(|last| now >= last + Duration::seconds(interval_seconds as i64))
+}
+
+/// Checks every due monitor in all of its regions. Checks run concurrently
+/// and a failing one is logged without holding up the others.
+pub async fn run(state: AppState) -> Result<(), crate::ApiError> {
+    let now = Utc::now();
+    let monitors = repository::list_scheduled(&state).await?;
+
+    let mut checks = tokio::task::JoinSet::new();
+    for monitor in monitors {
+        let last_checked_at = monitor.last_checked_at.as_deref().and_then(parse_timestamp);
+        if !is_due(last_checked_at, monitor.interval_seconds, now) {
+            continue;
+        }
+        let state = state.clone();
+        checks.spawn(async move {
+            if let Err(e) = service::run_monitor_check(&state, &monitor.id, None).await {
+                eprintln!("Scheduled check of monitor {} failed: {:?}", monitor.id, e);
+            }
+        });
+    }
+    checks.join_all().await;
+
+    Ok(())
+}
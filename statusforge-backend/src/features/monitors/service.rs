@@ -1,24 +1,384 @@
+use std::collections::HashMap;
+
+use crate::features::{alerts, incidents, maintenance, status_pages, webhooks};
 use crate::AppState;
 
-use super::{Monitor, MonitorResult, repository, CreateMonitor, UpdateMonitor, CreateMonitorResult, ListMonitorResultsQuery};
+use super::{Monitor, MonitorResult, CertificateSummary, CheckOutcome, ConfirmationSettings, MonitorCheck, ListMonitorChecksQuery, PingConfig, SslConfig, DomainConfig, HeartbeatConfig, HeartbeatSignal, HttpAssertion, HttpAuth, HttpConfig, MultistepConfig, ExtractSource, StatusSpec, checks, confirmation, heartbeat, jsonpath, repository, state::{incident_action, next_state, state_for_enabled, IncidentAction}, CreateMonitor, UpdateMonitor, CreateMonitorResult, ListMonitorResultsQuery, UptimeQuery, UptimeReport, MonitorResultsPage, rollups, uptime, pause, tags, PauseMonitor, MonitorSelector, BulkMonitorRequest, BulkMonitorResponse, BULK_ACTIONS, ListMonitorsQuery, MonitorFilter, MONITOR_SORT_FIELDS, MonitorGroup, CreateMonitorGroup, UpdateMonitorGroup, state::MONITOR_STATES, manifest, anomaly, ValidatedMonitor, MonitorsDocument, MonitorPlan, ApplyMonitorsQuery};
+
+pub const VALID_KINDS: [&str; 8] = ["http", "https", "ssl", "keyword", "ping", "heartbeat", "multistep", "domain"];
+
+pub const VALID_REGIONS: [&str; 3] = ["EU", "US", "ASIA"];
+
+const MAX_MULTISTEP_STEPS: usize = 10;
+
+const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+/// Resolves an expected status entry to an inclusive range of codes.
+fn status_range(spec: &StatusSpec) -> Result<(u16, u16), String> {
+    let (low, high) = match spec {
+        StatusSpec::Code(code) => (*code, *code),
+        StatusSpec::Pattern(pattern) => {
+            let pattern = pattern.trim();
+            if let Some(class) = pattern.strip_suffix("xx").or_else(|| pattern.strip_suffix("XX")) {
+                let class: u16 = class.parse().map_err(|_| format!("Invalid status class: {}", pattern))?;
+                (class * 100, class * 100 + 99)
+            } else if let Some((low, high)) = pattern.split_once('-') {
+                let low: u16 = low.trim().parse().map_err(|_| format!("Invalid status range: {}", pattern))?;
+                let high: u16 = high.trim().parse().map_err(|_| format!("Invalid status range: {}", pattern))?;
+                (low, high)
+            } else {
+                let code: u16 = pattern.parse().map_err(|_| format!("Invalid status code: {}", pattern))?;
+                (code, code)
+            }
+        }
+    };
 
-pub async fn list_monitors(state: &AppState, project_id: &str) -> Result<Vec<Monitor>, crate::ApiError> {
-    repository::list_by_project(state, project_id).await
+    if !(100..=599).contains(&low) || !(100..=599).contains(&high) || low > high {
+        return Err(format!("Status codes must be between 100 and 599: {:?}", spec));
+    }
+    Ok((low, high))
 }
 
-pub async fn get_monitor(state: &AppState, id: &str) -> Result<Monitor, crate::ApiError> {
-    repository::get_by_id(state, id).await
+pub fn status_matches(specs: &[StatusSpec], code: u16) -> bool {
+    specs
+        .iter()
+        .filter_map(|spec| status_range(spec).ok())
+        .any(|(low, high)| (low..=high).contains(&code))
 }
 
-pub async fn create_monitor(
+pub fn validate_http_config(config: &HttpConfig) -> Result<(), crate::ApiError> {
+    let bad = |msg: String| Err(crate::ApiError::BadRequest(msg));
+
+    if !HTTP_METHODS.contains(&config.method.as_str()) {
+        return bad(format!("Invalid HTTP method: {}. Must be one of: {:?}", config.method, HTTP_METHODS));
+    }
+    if config.body.is_some() && matches!(config.method.as_str(), "GET" | "HEAD") {
+        return bad(format!("{} requests cannot have a body", config.method));
+    }
+    for name in config.headers.keys() {
+        let valid = !name.is_empty()
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+        if !valid {
+            return bad(format!("Invalid header name: {:?}", name));
+        }
+    }
+    match &config.auth {
+        Some(HttpAuth::Basic { username, .. }) if username.trim().is_empty() => {
+            return bad("Basic auth username cannot be empty".to_string());
+        }
+        Some(HttpAuth::Bearer { token }) if token.trim().is_empty() => {
+            return bad("Bearer token cannot be empty".to_string());
+        }
+        _ => {}
+    }
+    if config.timeout_ms < 1_000 || config.timeout_ms > 60_000 {
+        return bad("timeout_ms must be between 1000 and 60000".to_string());
+    }
+    for spec in &config.expected_status {
+        status_range(spec).map_err(crate::ApiError::BadRequest)?;
+    }
+    for assertion in &config.assertions {
+        match assertion {
+            HttpAssertion::JsonPathEquals { path, .. } | HttpAssertion::JsonPathExists { path } => {
+                jsonpath::parse(path).map_err(crate::ApiError::BadRequest)?;
+            }
+            HttpAssertion::HeaderMatches { name, pattern } => {
+                if name.trim().is_empty() {
+                    return bad("Header assertion name cannot be empty".to_string());
+                }
+                regex::Regex::new(pattern)
+                    .map_err(|e| crate::ApiError::BadRequest(format!("Invalid header pattern: {}", e)))?;
+            }
+            HttpAssertion::BodyRegex { pattern } => {
+                regex::Regex::new(pattern)
+                    .map_err(|e| crate::ApiError::BadRequest(format!("Invalid body pattern: {}", e)))?;
+            }
+            HttpAssertion::ResponseTime { max_ms } => {
+                if *max_ms == 0 {
+                    return bad("Response time threshold must be greater than 0".to_string());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the kind-specific `config` object and returns it normalized
+/// (defaults filled in) so the stored JSON is always complete.
+pub fn validate_config(kind: &str, config: Option<&serde_json::Value>) -> Result<serde_json::Value, crate::ApiError> {
+    let config = config.cloned().unwrap_or_else(|| serde_json::json!({}));
+    if !config.is_object() {
+        return Err(crate::ApiError::BadRequest("Monitor config must be a JSON object".to_string()));
+    }
+
+    match kind {
+        "http" | "https" | "keyword" => {
+            let mut http: HttpConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid HTTP config: {}", e)))?;
+            http.method = http.method.to_uppercase();
+            validate_http_config(&http)?;
+            Ok(serde_json::to_value(http).unwrap())
+        }
+        "ping" => {
+            let ping: PingConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid ping config: {}", e)))?;
+            if ping.count == 0 || ping.count > 20 {
+                return Err(crate::ApiError::BadRequest("Ping count must be between 1 and 20".to_string()));
+            }
+            if ping.timeout_ms < 100 || ping.timeout_ms > 10_000 {
+                return Err(crate::ApiError::BadRequest("Ping timeout_ms must be between 100 and 10000".to_string()));
+            }
+            if !(0.0..=100.0).contains(&ping.degraded_loss_percent) {
+                return Err(crate::ApiError::BadRequest("degraded_loss_percent must be between 0 and 100".to_string()));
+            }
+            Ok(serde_json::to_value(ping).unwrap())
+        }
+        "ssl" => {
+            let ssl: SslConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid SSL config: {}", e)))?;
+            if ssl.warning_days.iter().any(|&d| d == 0 || d > 365) {
+                return Err(crate::ApiError::BadRequest("warning_days must be between 1 and 365".to_string()));
+            }
+            if ssl.timeout_ms < 1_000 || ssl.timeout_ms > 60_000 {
+                return Err(crate::ApiError::BadRequest("timeout_ms must be between 1000 and 60000".to_string()));
+            }
+            Ok(serde_json::to_value(ssl).unwrap())
+        }
+        "domain" => {
+            let domain: DomainConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid domain config: {}", e)))?;
+            if domain.warning_days.iter().any(|&d| d == 0 || d > 365) {
+                return Err(crate::ApiError::BadRequest("warning_days must be between 1 and 365".to_string()));
+            }
+            if domain.timeout_ms < 1_000 || domain.timeout_ms > 60_000 {
+                return Err(crate::ApiError::BadRequest("timeout_ms must be between 1000 and 60000".to_string()));
+            }
+            Ok(serde_json::to_value(domain).unwrap())
+        }
+        "heartbeat" => {
+            let heartbeat_config: HeartbeatConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid heartbeat config: {}", e)))?;
+            if heartbeat_config.grace_seconds > 86_400 {
+                return Err(crate::ApiError::BadRequest("grace_seconds cannot exceed 86400".to_string()));
+            }
+            Ok(serde_json::to_value(heartbeat_config).unwrap())
+        }
+        "multistep" => {
+            let mut multistep: MultistepConfig = serde_json::from_value(config)
+                .map_err(|e| crate::ApiError::BadRequest(format!("Invalid multi-step config: {}", e)))?;
+            for step in &mut multistep.steps {
+                step.request.method = step.request.method.to_uppercase();
+            }
+            validate_multistep_config(&multistep)?;
+            Ok(serde_json::to_value(multistep).unwrap())
+        }
+        _ => Ok(config),
+    }
+}
+
+/// Names of the `{{variable}}` placeholders used in a step template.
+pub fn template_variables(template: &str) -> Vec<String> {
+    let mut variables = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        variables.push(after[..end].trim().to_string());
+        rest = &after[end + 2..];
+    }
+    variables
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+pub fn validate_multistep_config(config: &MultistepConfig) -> Result<(), crate::ApiError> {
+    if config.steps.is_empty() {
+        return Err(crate::ApiError::BadRequest("Multi-step monitor needs at least one step".to_string()));
+    }
+    if config.steps.len() > MAX_MULTISTEP_STEPS {
+        return Err(crate::ApiError::BadRequest(
+            format!("Multi-step monitor cannot have more than {} steps", MAX_MULTISTEP_STEPS)
+        ));
+    }
+
+    let mut step_names = std::collections::HashSet::new();
+    let mut defined = std::collections::HashSet::new();
+
+    for step in &config.steps {
+        let name = step.name.trim();
+        if name.is_empty() {
+            return Err(crate::ApiError::BadRequest("Step name cannot be empty".to_string()));
+        }
+        if !step_names.insert(name.to_string()) {
+            return Err(crate::ApiError::BadRequest(format!("Duplicate step name: {}", name)));
+        }
+        let step_error = |msg: String| crate::ApiError::BadRequest(format!("Step '{}': {}", name, msg));
+
+        if step.url.trim().is_empty() {
+            return Err(step_error("URL cannot be empty".to_string()));
+        }
+        validate_http_config(&step.request).map_err(|e| match e {
+            crate::ApiError::BadRequest(msg) => step_error(msg),
+            other => other,
+        })?;
+
+        let mut templates: Vec<&str> = vec![&step.url];
+        templates.extend(step.request.headers.values().map(String::as_str));
+        templates.extend(step.request.body.as_deref());
+        match &step.request.auth {
+            Some(HttpAuth::Basic { username, password }) => templates.extend([username.as_str(), password.as_str()]),
+            Some(HttpAuth::Bearer { token }) => templates.push(token),
+            None => {}
+        }
+        for variable in templates.into_iter().flat_map(template_variables) {
+            if !defined.contains(&variable) {
+                return Err(step_error(format!("variable {{{{{}}}}} is not defined by an earlier step", variable)));
+            }
+        }
+
+        for extraction in &step.extract {
+            if !is_variable_name(&extraction.name) {
+                return Err(step_error(format!("invalid variable name: {:?}", extraction.name)));
+            }
+            match &extraction.source {
+                ExtractSource::JsonPath { path } => {
+                    jsonpath::parse(path).map_err(step_error)?;
+                }
+                ExtractSource::Header { header: source } | ExtractSource::Cookie { cookie: source } => {
+                    if source.trim().is_empty() {
+                        return Err(step_error(format!("extraction source for {} cannot be empty", extraction.name)));
+                    }
+                }
+            }
+        }
+        // Variables become visible only to the following steps.
+        defined.extend(step.extract.iter().map(|e| e.name.clone()));
+    }
+
+    Ok(())
+}
+
+/// Validates listing parameters; sorts by `created_at` unless asked otherwise.
+pub fn monitor_filter(query: ListMonitorsQuery) -> Result<MonitorFilter, crate::ApiError> {
+    if let Some(ref kind) = query.kind
+        && !VALID_KINDS.contains(&kind.as_str())
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid kind: {}. Must be one of: {:?}", kind, VALID_KINDS)
+        ));
+    }
+
+    if let Some(ref status) = query.status
+        && !MONITOR_STATES.contains(&status.as_str())
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid status: {}. Must be one of: {:?}", status, MONITOR_STATES)
+        ));
+    }
+
+    let sort = query.sort.as_deref().unwrap_or("created_at");
+    let (sort_field, descending) = match sort.strip_prefix('-') {
+        Some(field) => (field, true),
+        None => (sort, false),
+    };
+    if !MONITOR_SORT_FIELDS.contains(&sort_field) {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid sort: {}. Must be one of: {:?}", sort, MONITOR_SORT_FIELDS)
+        ));
+    }
+
+    Ok(MonitorFilter {
+        tags: query.tag.as_deref().map(tags::parse_selector).transpose()?.unwrap_or_default(),
+        group_id: query.group_id,
+        kind: query.kind,
+        enabled: query.enabled,
+        state: query.status,
+        sort_field: sort_field.to_string(),
+        descending,
+        limit: query.limit,
+        offset: query.offset,
+    })
+}
+
+pub async fn list_monitors(
     state: &AppState,
     project_id: &str,
-    data: CreateMonitor,
-) -> Result<Monitor, crate::ApiError> {
-    let valid_kinds = ["http", "https", "ssl", "keyword"];
-    if !valid_kinds.contains(&data.kind.as_str()) {
+    query: ListMonitorsQuery,
+) -> Result<Vec<Monitor>, crate::ApiError> {
+    let filter = monitor_filter(query)?;
+    repository::list_filtered(state, project_id, &filter).await
+}
+
+/// Checks that `group_id` names a group of the monitor's project.
+async fn validate_group(state: &AppState, project_id: &str, group_id: &str) -> Result<(), crate::ApiError> {
+    let group = repository::get_group(state, group_id).await.map_err(|_| {
+        crate::ApiError::BadRequest(format!("Monitor group {} does not exist", group_id))
+    })?;
+    if group.project_id != project_id {
+        return Err(crate::ApiError::BadRequest(
+            format!("Monitor group {} does not belong to this project", group_id)
+        ));
+    }
+    Ok(())
+}
+
+/// The monitor as the API returns it: credentials in its config are replaced
+/// with the placeholders of `manifest::redact_secrets`, which an update may
+/// send back to keep the stored values.
+pub fn redacted(mut monitor: Monitor) -> Monitor {
+    manifest::redact_secrets(&manifest::effective_key(&monitor), &mut monitor.config);
+    monitor
+}
+
+pub async fn get_monitor(state: &AppState, id: &str) -> Result<Monitor, crate::ApiError> {
+    repository::get_by_id(state, id).await
+}
+
+pub fn validate_confirmation(settings: &ConfirmationSettings) -> Result<(), crate::ApiError> {
+    if !(0..=confirmation::MAX_RETRIES).contains(&settings.confirmation_retries) {
         return Err(crate::ApiError::BadRequest(
-            format!("Invalid kind: {}. Must be one of: {:?}", data.kind, valid_kinds)
+            format!("confirmation_retries must be between 0 and {}", confirmation::MAX_RETRIES)
+        ));
+    }
+
+    if settings.regions.is_empty() {
+        return Err(crate::ApiError::BadRequest("At least one region is required".to_string()));
+    }
+
+    for (i, region) in settings.regions.iter().enumerate() {
+        if !VALID_REGIONS.contains(&region.as_str()) {
+            return Err(crate::ApiError::BadRequest(
+                format!("Invalid region: {}. Must be one of: {:?}", region, VALID_REGIONS)
+            ));
+        }
+        if settings.regions[..i].contains(region) {
+            return Err(crate::ApiError::BadRequest(format!("Duplicate region: {}", region)));
+        }
+    }
+
+    if settings.down_quorum < 1 || settings.down_quorum as usize > settings.regions.len() {
+        return Err(crate::ApiError::BadRequest(
+            format!("down_quorum must be between 1 and {}", settings.regions.len())
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks a new monitor against every rule that does not need the database
+/// and returns its normalized settings. Shared by `create_monitor` and
+/// `apply_monitors`.
+pub fn validate_new_monitor(data: &CreateMonitor) -> Result<ValidatedMonitor, crate::ApiError> {
+    if !VALID_KINDS.contains(&data.kind.as_str()) {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid kind: {}. Must be one of: {:?}", data.kind, VALID_KINDS)
         ));
     }
 
@@ -26,7 +386,7 @@ pub async fn create_monitor(
         return Err(crate::ApiError::BadRequest("Monitor name cannot be empty".to_string()));
     }
 
-    if data.url.trim().is_empty() {
+    if !matches!(data.kind.as_str(), "heartbeat" | "multistep") && data.url.trim().is_empty() {
         return Err(crate::ApiError::BadRequest("Monitor URL cannot be empty".to_string()));
     }
 
@@ -34,80 +394,489 @@ pub async fn create_monitor(
         return Err(crate::ApiError::BadRequest("Keyword is required for keyword monitoring".to_string()));
     }
 
+    if data.kind == "ping" && checks::ping::target_host(&data.url).is_none() {
+        return Err(crate::ApiError::BadRequest("Ping target must be a host name or IP address".to_string()));
+    }
+
+    if data.kind == "domain" && checks::domain::target_domain(&data.url).is_none() {
+        return Err(crate::ApiError::BadRequest("Domain target must be a domain name such as example.com".to_string()));
+    }
+
+    let config = validate_config(&data.kind, data.config.as_ref())?;
+
     let interval = data.interval_seconds.unwrap_or(300);
     if interval < 60 {
         return Err(crate::ApiError::BadRequest("Interval must be at least 60 seconds".to_string()));
     }
 
+    let defaults = ConfirmationSettings::default();
+    let confirmation = ConfirmationSettings {
+        confirmation_retries: data.confirmation_retries.unwrap_or(defaults.confirmation_retries),
+        regions: data.regions.clone().unwrap_or(defaults.regions),
+        down_quorum: data.down_quorum.unwrap_or(defaults.down_quorum),
+    };
+    validate_confirmation(&confirmation)?;
+
+    let tags = data.tags.clone().unwrap_or_default();
+    tags::validate_tags(&tags)?;
+
+    if let Some(ref key) = data.key {
+        manifest::validate_key(key)?;
+    }
+
+    let anomaly_settings = data.anomaly.clone().unwrap_or_default();
+    anomaly::validate_settings(&anomaly_settings)?;
+
+    Ok(ValidatedMonitor {
+        config,
+        interval_seconds: interval,
+        confirmation,
+        tags,
+        anomaly: anomaly_settings,
+    })
+}
+
+pub async fn create_monitor(
+    state: &AppState,
+    project_id: &str,
+    data: CreateMonitor,
+) -> Result<Monitor, crate::ApiError> {
+    let ValidatedMonitor { config, interval_seconds: interval, confirmation, tags, anomaly } = validate_new_monitor(&data)?;
+
+    if let Some(ref group_id) = data.group_id {
+        validate_group(state, project_id, group_id).await?;
+    }
+
+    // Heartbeat monitors are pushed to, their URL is the secret ping path.
+    let heartbeat_token = (data.kind == "heartbeat").then(crate::shared::utils::random_token);
+    let url = match heartbeat_token {
+        Some(ref token) => format!("/heartbeats/{}", token),
+        None if data.kind == "multistep" && data.url.trim().is_empty() => {
+            config["steps"][0]["url"].as_str().unwrap_or_default().to_string()
+        }
+        None => data.url.clone(),
+    };
+
     repository::create(
         state,
         project_id,
         &data.name,
         &data.kind,
-        &data.url,
+        &url,
         data.keyword.as_deref(),
         interval,
         data.enabled.unwrap_or(true),
+        &config,
+        heartbeat_token.as_deref(),
+        &confirmation,
+        &tags,
+        data.group_id.as_deref(),
+        data.key.as_deref(),
+        &anomaly,
     ).await
 }
 
+/// Checks the URL a monitor of `kind` will probe after an update. Heartbeat
+/// URLs are the generated ping path and cannot be changed.
+pub fn validate_target(kind: &str, current_url: &str, new_url: Option<&str>) -> Result<(), crate::ApiError> {
+    if kind == "heartbeat" {
+        if new_url.is_some_and(|url| url != current_url) {
+            return Err(crate::ApiError::BadRequest("The URL of a heartbeat monitor cannot be changed".to_string()));
+        }
+        return Ok(());
+    }
+
+    let url = new_url.unwrap_or(current_url);
+    if kind == "ping" && checks::ping::target_host(url).is_none() {
+        return Err(crate::ApiError::BadRequest("Ping target must be a host name or IP address".to_string()));
+    }
+    if kind == "domain" && checks::domain::target_domain(url).is_none() {
+        return Err(crate::ApiError::BadRequest("Domain target must be a domain name such as example.com".to_string()));
+    }
+    Ok(())
+}
+
 pub async fn update_monitor(
     state: &AppState,
     id: &str,
     data: UpdateMonitor,
 ) -> Result<Monitor, crate::ApiError> {
-    if let Some(ref name) = data.name {
-        if name.trim().is_empty() {
-            return Err(crate::ApiError::BadRequest("Monitor name cannot be empty".to_string()));
-        }
+    if let Some(ref name) = data.name
+        && name.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Monitor name cannot be empty".to_string()));
     }
 
-    if let Some(ref url) = data.url {
-        if url.trim().is_empty() {
-            return Err(crate::ApiError::BadRequest("Monitor URL cannot be empty".to_string()));
-        }
+    if let Some(ref url) = data.url
+        && url.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Monitor URL cannot be empty".to_string()));
     }
 
-    if let Some(ref kind) = data.kind {
-        let valid_kinds = ["http", "https", "ssl", "keyword"];
-        if !valid_kinds.contains(&kind.as_str()) {
-            return Err(crate::ApiError::BadRequest(
-                format!("Invalid kind: {}. Must be one of: {:?}", kind, valid_kinds)
-            ));
+    if let Some(ref kind) = data.kind
+        && !VALID_KINDS.contains(&kind.as_str())
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid kind: {}. Must be one of: {:?}", kind, VALID_KINDS)
+        ));
+    }
+
+    if let Some(interval) = data.interval_seconds
+        && interval < 60
+    {
+        return Err(crate::ApiError::BadRequest("Interval must be at least 60 seconds".to_string()));
+    }
+
+    if let Some(ref tags) = data.tags {
+        tags::validate_tags(tags)?;
+    }
+
+    if let Some(ref key) = data.key {
+        manifest::validate_key(key)?;
+    }
+
+    if let Some(ref settings) = data.anomaly {
+        anomaly::validate_settings(settings)?;
+    }
+
+    let confirmation_changed = data.confirmation_retries.is_some() || data.regions.is_some() || data.down_quorum.is_some();
+    let group_id = data.group_id.as_deref().map(|id| Some(id).filter(|id| !id.is_empty()));
+    let target_changed = data.url.is_some() || data.kind.is_some();
+    let existing = if data.config.is_some() || target_changed || confirmation_changed || group_id.flatten().is_some() {
+        Some(repository::get_by_id(state, id).await?)
+    } else {
+        None
+    };
+
+    if let Some(existing) = existing.as_ref().filter(|_| target_changed) {
+        let kind = data.kind.as_deref().unwrap_or(&existing.kind);
+        if (kind == "heartbeat") != (existing.kind == "heartbeat") {
+            return Err(crate::ApiError::BadRequest("Cannot convert between heartbeat and probed monitors".to_string()));
         }
+        validate_target(kind, &existing.url, data.url.as_deref())?;
     }
 
-    if let Some(interval) = data.interval_seconds {
-        if interval < 60 {
-            return Err(crate::ApiError::BadRequest("Interval must be at least 60 seconds".to_string()));
+    let config = if let Some(existing) = existing.as_ref().filter(|_| data.config.is_some() || data.kind.is_some()) {
+        let kind = data.kind.as_deref().unwrap_or(&existing.kind);
+        let mut config = data.config.clone().unwrap_or_else(|| existing.config.clone());
+        manifest::restore_secrets(&manifest::effective_key(existing), &mut config, Some(&existing.config))?;
+        Some(validate_config(kind, Some(&config))?)
+    } else {
+        None
+    };
+
+    if let (Some(existing), Some(Some(group_id))) = (existing.as_ref(), group_id) {
+        validate_group(state, &existing.project_id, group_id).await?;
+    }
+
+    let confirmation = match existing.filter(|_| confirmation_changed) {
+        Some(existing) => {
+            let current = existing.confirmation;
+            let merged = ConfirmationSettings {
+                confirmation_retries: data.confirmation_retries.unwrap_or(current.confirmation_retries),
+                regions: data.regions.clone().unwrap_or(current.regions),
+                down_quorum: data.down_quorum.unwrap_or(current.down_quorum),
+            };
+            validate_confirmation(&merged)?;
+            Some(merged)
         }
+        None => None,
+    };
+
+    let enabled = data.enabled;
+    let changes = UpdateMonitor {
+        config,
+        confirmation_retries: confirmation.as_ref().map(|c| c.confirmation_retries),
+        regions: confirmation.as_ref().map(|c| c.regions.clone()),
+        down_quorum: confirmation.as_ref().map(|c| c.down_quorum),
+        ..data
+    };
+    let monitor = repository::update(state, id, &changes).await?;
+
+    // Re-enabling a monitor paused with a reason ends that pause too.
+    if enabled == Some(true) && monitor.paused_at.is_some() {
+        return repository::set_paused(state, id, None).await;
     }
 
-    repository::update(
-        state,
-        id,
-        data.name.as_deref(),
-        data.kind.as_deref(),
-        data.url.as_deref(),
-        data.keyword.as_deref(),
-        data.interval_seconds,
-        data.enabled,
-    ).await
+    let current = monitor.state.as_deref().unwrap_or("unknown");
+    match enabled.and_then(|enabled| state_for_enabled(current, enabled)) {
+        Some(next) => repository::update_state(state, id, next).await,
+        None => Ok(monitor),
+    }
 }
 
 pub async fn delete_monitor(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
     repository::delete(state, id).await
 }
 
-pub async fn create_monitor_result(
+pub async fn pause_monitor(state: &AppState, id: &str, data: PauseMonitor) -> Result<Monitor, crate::ApiError> {
+    let until = pause::validate_pause(&data, chrono::Utc::now())?;
+    let data = PauseMonitor {
+        until: until.map(|until| until.to_rfc3339()),
+        ..data
+    };
+
+    repository::get_by_id(state, id).await?;
+    repository::set_paused(state, id, Some(&data)).await
+}
+
+pub async fn resume_monitor(state: &AppState, id: &str) -> Result<Monitor, crate::ApiError> {
+    let monitor = repository::get_by_id(state, id).await?;
+    if monitor.enabled {
+        return Ok(monitor);
+    }
+    repository::set_paused(state, id, None).await
+}
+
+/// Monitors of a project matching every criterion of `selector`. At least one
+/// criterion is required so a bulk action never hits a whole project by
+/// accident; listed ids must all belong to the project.
+pub fn select_monitors<'a>(monitors: &'a [Monitor], selector: &MonitorSelector) -> Result<Vec<&'a Monitor>, crate::ApiError> {
+    if selector.monitor_ids.is_none() && selector.kind.is_none() && selector.tag.is_none() && selector.group_id.is_none() {
+        return Err(crate::ApiError::BadRequest(
+            "Select monitors with monitor_ids, kind, tag or group_id".to_string()
+        ));
+    }
+
+    if let Some(ref kind) = selector.kind
+        && !VALID_KINDS.contains(&kind.as_str())
+    {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid kind: {}. Must be one of: {:?}", kind, VALID_KINDS)
+        ));
+    }
+
+    if let Some(ref ids) = selector.monitor_ids
+        && let Some(unknown) = ids.iter().find(|id| !monitors.iter().any(|m| &m.id == *id))
+    {
+        return Err(crate::ApiError::BadRequest(format!("Monitor {} does not belong to this project", unknown)));
+    }
+
+    let tag = selector.tag.as_deref().map(tags::parse_filter).transpose()?;
+
+    Ok(monitors
+        .iter()
+        .filter(|m| selector.monitor_ids.as_ref().is_none_or(|ids| ids.contains(&m.id)))
+        .filter(|m| selector.kind.as_ref().is_none_or(|kind| &m.kind == kind))
+        .filter(|m| selector.group_id.is_none() || m.group_id == selector.group_id)
+        .filter(|m| tag.as_ref().is_none_or(|(key, value)| tags::matches(&m.tags, key, value.as_deref())))
+        .collect())
+}
+
+/// Pauses, resumes, deletes or retags the selected monitors of a project.
+pub async fn bulk_monitors(
     state: &AppState,
-    monitor_id: &str,
-    data: CreateMonitorResult,
-) -> Result<MonitorResult, crate::ApiError> {
-    let valid_regions = ["EU", "US", "ASIA"];
-    if !valid_regions.contains(&data.region.as_str()) {
+    project_id: &str,
+    data: BulkMonitorRequest,
+) -> Result<BulkMonitorResponse, crate::ApiError> {
+    if !BULK_ACTIONS.contains(&data.action.as_str()) {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid action: {}. Must be one of: {:?}", data.action, BULK_ACTIONS)
+        ));
+    }
+
+    let pause_data = match data.action.as_str() {
+        "pause" => {
+            let until = pause::validate_pause(&data.pause, chrono::Utc::now())?;
+            Some(PauseMonitor {
+                until: until.map(|until| until.to_rfc3339()),
+                reason: data.pause.reason.clone(),
+                actor: data.pause.actor.clone(),
+            })
+        }
+        _ => None,
+    };
+
+    if data.action == "retag" {
+        if data.set_tags.is_empty() && data.remove_tags.is_empty() {
+            return Err(crate::ApiError::BadRequest("retag requires set_tags or remove_tags".to_string()));
+        }
+        tags::validate_tags(&data.set_tags)?;
+    }
+
+    let monitors = repository::list_by_project(state, project_id).await?;
+    let selected = select_monitors(&monitors, &data.selector)?;
+
+    // Every new tag set is checked before the first write, so an invalid one
+    // leaves all selected monitors unchanged.
+    let retagged = if data.action == "retag" {
+        let retagged: Vec<tags::Tags> = selected
+            .iter()
+            .map(|monitor| tags::retag(&monitor.tags, &data.set_tags, &data.remove_tags))
+            .collect();
+        for monitor_tags in &retagged {
+            tags::validate_tags(monitor_tags)?;
+        }
+        retagged
+    } else {
+        Vec::new()
+    };
+
+    let mut monitor_ids = Vec::with_capacity(selected.len());
+    for (i, monitor) in selected.into_iter().enumerate() {
+        match data.action.as_str() {
+            "pause" => {
+                repository::set_paused(state, &monitor.id, pause_data.as_ref()).await?;
+            }
+            "resume" => {
+                if monitor.enabled {
+                    continue;
+                }
+                repository::set_paused(state, &monitor.id, None).await?;
+            }
+            "delete" => repository::delete(state, &monitor.id).await?,
+            _ => {
+                let changes = UpdateMonitor { tags: Some(retagged[i].clone()), ..Default::default() };
+                repository::update(state, &monitor.id, &changes).await?;
+            }
+        }
+        monitor_ids.push(monitor.id.clone());
+    }
+
+    Ok(BulkMonitorResponse {
+        action: data.action,
+        affected: monitor_ids.len(),
+        monitor_ids,
+    })
+}
+
+pub async fn export_monitors(state: &AppState, project_id: &str) -> Result<MonitorsDocument, crate::ApiError> {
+    let monitors = repository::list_by_project(state, project_id).await?;
+    let groups = repository::list_groups(state, project_id).await?;
+
+    let mut specs: Vec<_> = monitors.iter().map(|m| manifest::spec_from_monitor(m, &groups)).collect();
+    for spec in &mut specs {
+        manifest::redact_secrets(&spec.key, &mut spec.config);
+    }
+    specs.sort_by(|a, b| a.key.cmp(&b.key));
+
+    Ok(MonitorsDocument {
+        version: manifest::DOCUMENT_VERSION,
+        monitors: specs,
+    })
+}
+
+/// Plans (and unless `dry_run`, applies) the changes that make the project's
+/// monitors match a monitors-as-code document. Every monitor is validated
+/// with the same rules as `create_monitor` before anything is written.
+pub async fn apply_monitors(
+    state: &AppState,
+    project_id: &str,
+    body: &str,
+    query: ApplyMonitorsQuery,
+) -> Result<MonitorPlan, crate::ApiError> {
+    let mut document = manifest::parse_document(body)?;
+    for spec in &mut document.monitors {
+        let validated = validate_new_monitor(&manifest::create_from_spec(spec)).map_err(|e| match e {
+            crate::ApiError::BadRequest(msg) => crate::ApiError::BadRequest(format!("{}: {}", spec.key, msg)),
+            other => other,
+        })?;
+        spec.config = validated.config;
+    }
+
+    let existing = repository::list_by_project(state, project_id).await?;
+    for spec in &mut document.monitors {
+        let current = existing.iter().find(|m| manifest::effective_key(m) == spec.key);
+        manifest::restore_secrets(&spec.key, &mut spec.config, current.map(|m| &m.config))?;
+    }
+
+    let mut groups = repository::list_groups(state, project_id).await?;
+    let (items, groups_to_create) = manifest::build_plan(&existing, &groups, &document.monitors)?;
+
+    let dry_run = query.dry_run.unwrap_or(true);
+    let plan = MonitorPlan { dry_run, groups_to_create, items };
+    if dry_run {
+        return Ok(plan);
+    }
+
+    for name in &plan.groups_to_create {
+        let group = CreateMonitorGroup { name: name.clone(), description: None };
+        groups.push(repository::create_group(state, project_id, &group).await?);
+    }
+    let group_id = |name: &Option<String>| {
+        name.as_ref()
+            .and_then(|name| groups.iter().find(|g| &g.name == name))
+            .map(|g| g.id.clone())
+    };
+
+    for item in &plan.items {
+        let spec = document.monitors.iter().find(|s| s.key == item.key);
+        match (item.action.as_str(), spec, item.monitor_id.as_deref()) {
+            ("create", Some(spec), _) => {
+                let data = CreateMonitor {
+                    group_id: group_id(&spec.group),
+                    ..manifest::create_from_spec(spec)
+                };
+                create_monitor(state, project_id, data).await?;
+            }
+            ("update", Some(spec), Some(id)) => {
+                let changed = |field: &str| item.changes.iter().any(|c| c == field);
+                let confirmation_changed = changed("confirmation_retries") || changed("regions") || changed("down_quorum");
+                let data = UpdateMonitor {
+                    key: changed("key").then(|| spec.key.clone()),
+                    name: changed("name").then(|| spec.name.clone()),
+                    kind: changed("kind").then(|| spec.kind.clone()),
+                    url: changed("url").then(|| spec.url.clone()),
+                    keyword: changed("keyword").then(|| spec.keyword.clone()).flatten(),
+                    interval_seconds: changed("interval_seconds").then_some(spec.interval_seconds),
+                    enabled: changed("enabled").then_some(spec.enabled),
+                    config: (changed("config") || changed("kind")).then(|| spec.config.clone()),
+                    confirmation_retries: confirmation_changed.then_some(spec.confirmation.confirmation_retries),
+                    regions: confirmation_changed.then(|| spec.confirmation.regions.clone()),
+                    down_quorum: confirmation_changed.then_some(spec.confirmation.down_quorum),
+                    tags: changed("tags").then(|| spec.tags.clone()),
+                    group_id: changed("group").then(|| group_id(&spec.group).unwrap_or_default()),
+                    anomaly: changed("anomaly").then(|| spec.anomaly.clone()),
+                };
+                update_monitor(state, id, data).await?;
+            }
+            ("delete", _, Some(id)) => delete_monitor(state, id).await?,
+            _ => {}
+        }
+    }
+
+    Ok(plan)
+}
+
+pub async fn list_groups(state: &AppState, project_id: &str) -> Result<Vec<MonitorGroup>, crate::ApiError> {
+    repository::list_groups(state, project_id).await
+}
+
+pub async fn create_group(
+    state: &AppState,
+    project_id: &str,
+    data: CreateMonitorGroup,
+) -> Result<MonitorGroup, crate::ApiError> {
+    if data.name.trim().is_empty() {
+        return Err(crate::ApiError::BadRequest("Group name cannot be empty".to_string()));
+    }
+
+    repository::create_group(state, project_id, &data).await
+}
+
+pub async fn update_group(
+    state: &AppState,
+    id: &str,
+    data: UpdateMonitorGroup,
+) -> Result<MonitorGroup, crate::ApiError> {
+    if let Some(ref name) = data.name
+        && name.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Group name cannot be empty".to_string()));
+    }
+
+    repository::update_group(state, id, &data).await
+}
+
+/// Deletes a group; its monitors are kept without a group.
+pub async fn delete_group(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete_group(state, id).await
+}
+
+fn validate_result(data: &CreateMonitorResult) -> Result<(), crate::ApiError> {
+    if !VALID_REGIONS.contains(&data.region.as_str()) {
         return Err(crate::ApiError::BadRequest(
-            format!("Invalid region: {}. Must be one of: {:?}", data.region, valid_regions)
+            format!("Invalid region: {}. Must be one of: {:?}", data.region, VALID_REGIONS)
         ));
     }
 
@@ -118,53 +887,421 @@ pub async fn create_monitor_result(
         ));
     }
 
-    repository::create_result(
-        state,
-        monitor_id,
-        &data.region,
-        &data.status,
-        data.response_time_ms,
-        data.http_status,
-        data.ssl_valid,
-        data.ssl_expires_at.as_deref(),
-        data.error_message.as_deref(),
-    ).await
+    if let Some(loss) = data.packet_loss
+        && !(0.0..=100.0).contains(&loss)
+    {
+        return Err(crate::ApiError::BadRequest("packet_loss must be between 0 and 100".to_string()));
+    }
+
+    Ok(())
+}
+
+/// Stores a single result and applies it to the monitor state, e.g. results
+/// pushed by heartbeats or posted directly to `/monitors/{id}/results`.
+pub async fn create_monitor_result(
+    state: &AppState,
+    monitor_id: &str,
+    mut data: CreateMonitorResult,
+) -> Result<MonitorResult, crate::ApiError> {
+    validate_result(&data)?;
+
+    let monitor = repository::get_by_id(state, monitor_id).await?;
+    data.in_maintenance = maintenance::is_in_maintenance(state, &monitor, chrono::Utc::now()).await?;
+    let streak = detect_anomaly(state, &monitor, &mut data).await?;
+    let result = repository::create_result(state, monitor_id, &data).await?;
+    apply_status(state, &monitor, &result.status, std::slice::from_ref(&result), data.in_maintenance, streak).await?;
+    Ok(result)
 }
 
+/// Scores the result's response time against the region's recent results
+/// and flags it (optionally downgrading `up` to `degraded`). Returns the
+/// number of consecutive anomalous results in the region, this one included.
+async fn detect_anomaly(
+    state: &AppState,
+    monitor: &Monitor,
+    data: &mut CreateMonitorResult,
+) -> Result<u32, crate::ApiError> {
+    let settings = &monitor.anomaly;
+    let Some(response_time) = data.response_time_ms.filter(|_| settings.enabled && data.status != "down") else {
+        return Ok(0);
+    };
+
+    let window = settings.window.min(anomaly::MAX_WINDOW) as u32;
+    let recent = repository::list_results(state, &monitor.id, Some(&data.region), None, None, Some(window), None).await?;
+    let history: Vec<f64> = recent
+        .iter()
+        .rev()
+        .filter(|r| r.status != "down")
+        .filter_map(|r| r.response_time_ms.map(f64::from))
+        .collect();
+
+    let Some(verdict) = anomaly::evaluate(&history, response_time as f64, settings) else {
+        return Ok(0);
+    };
+    data.anomaly_score = Some(verdict.score);
+    data.baseline_ms = Some(verdict.baseline.ewma);
+    if !verdict.anomalous {
+        return Ok(0);
+    }
+
+    data.anomalous = true;
+    if settings.mark_degraded && data.status == "up" {
+        data.status = "degraded".to_string();
+    }
+    Ok(1 + anomaly::streak(recent.iter().map(|r| r.anomalous)))
+}
+
+/// Moves the monitor to the state implied by `status` and opens, extends or
+/// resolves its incident accordingly; opening and resolving are sent to
+/// webhooks and alerts as `downtime` and `recovery`. No incident is opened or
+/// extended during maintenance; if the monitor is still down afterwards one
+/// is opened then and announced as `downtime` like any other.
+/// `anomaly_streak` (the longest run of anomalous results among `results`)
+/// opens or resolves latency incidents.
+async fn apply_status(
+    state: &AppState,
+    monitor: &Monitor,
+    status: &str,
+    results: &[MonitorResult],
+    in_maintenance: bool,
+    anomaly_streak: u32,
+) -> Result<(), crate::ApiError> {
+    let current = monitor.state.as_deref().unwrap_or("unknown");
+
+    if let Some(next) = next_state(current, status) {
+        repository::update_state(state, &monitor.id, next).await?;
+        status_pages::notify_monitor_change(state, monitor, current, next, in_maintenance);
+    }
+
+    match incident_action(current, status) {
+        IncidentAction::Open | IncidentAction::Extend if in_maintenance => {}
+        IncidentAction::Open => {
+            let incident = incidents::open_incident(state, monitor, results).await?;
+            announce_downtime(state, monitor, incident);
+        }
+        IncidentAction::Extend => {
+            let (incident, opened) = incidents::extend_incident(state, monitor, results).await?;
+            if opened {
+                announce_downtime(state, monitor, incident);
+            }
+        }
+        IncidentAction::Resolve => {
+            if let Some(incident) = incidents::resolve_incident(state, &monitor.id, chrono::Utc::now()).await? {
+                webhooks::dispatch(state, webhooks::events::recovery(monitor, &incident, chrono::Utc::now()));
+                let event = alerts::message::AlertEvent::Recovery { monitor: monitor.clone(), incident };
+                alerts::dispatch(state, &monitor.project_id, event);
+            }
+        }
+        IncidentAction::None => {}
+    }
+
+    if monitor.anomaly.enabled && status != "down" {
+        if anomaly_streak == 0 {
+            incidents::resolve_latency_incident(state, &monitor.id, chrono::Utc::now()).await?;
+        } else if !in_maintenance && anomaly::opens_incident(anomaly_streak, &monitor.anomaly) {
+            incidents::open_latency_incident(state, monitor, results, anomaly_streak).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn announce_downtime(state: &AppState, monitor: &Monitor, incident: incidents::Incident) {
+    webhooks::dispatch(state, webhooks::events::downtime(monitor, &incident, chrono::Utc::now()));
+    let event = alerts::message::AlertEvent::Downtime { monitor: monitor.clone(), incident };
+    alerts::dispatch(state, &monitor.project_id, event);
+}
+
+/// Lists raw results, or hourly/daily rollups when the requested window is
+/// long or reaches past the raw-result retention. Without `from` only recent
+/// raw rows are returned, as before rollups existed.
 pub async fn list_monitor_results(
     state: &AppState,
     monitor_id: &str,
     query: ListMonitorResultsQuery,
-) -> Result<Vec<MonitorResult>, crate::ApiError> {
-    repository::list_results(
+) -> Result<MonitorResultsPage, crate::ApiError> {
+    let requested = query.resolution.as_deref().unwrap_or("auto");
+    if !rollups::RESOLUTIONS.contains(&requested) {
+        return Err(crate::ApiError::BadRequest(
+            format!("Invalid resolution: {}. Must be one of: {:?}", requested, rollups::RESOLUTIONS)
+        ));
+    }
+
+    let now = chrono::Utc::now();
+    let range = match (query.from.as_deref(), query.to.as_deref()) {
+        (None, None) => None,
+        (from, to) => {
+            let invalid = |name: &str| crate::ApiError::BadRequest(format!("`{}` must be an RFC 3339 timestamp", name));
+            let from = from.and_then(crate::shared::utils::parse_timestamp).ok_or_else(|| invalid("from"))?;
+            let to = match to {
+                Some(to) => crate::shared::utils::parse_timestamp(to).ok_or_else(|| invalid("to"))?,
+                None => now,
+            };
+            if from >= to {
+                return Err(crate::ApiError::BadRequest("`from` must be before `to`".to_string()));
+            }
+            Some((from, to))
+        }
+    };
+
+    let resolution = match (requested, range) {
+        ("auto", Some((from, to))) => {
+            rollups::resolution_for(from, to, rollups::retention_cutoff(now, state.config.result_retention_days))
+        }
+        ("auto", None) => "raw",
+        (explicit, _) => explicit,
+    };
+
+    if resolution == "raw" {
+        let range = range.map(|(from, to)| (from.to_rfc3339(), to.to_rfc3339()));
+        return repository::list_results(
+            state,
+            monitor_id,
+            query.region.as_deref(),
+            query.status.as_deref(),
+            range.as_ref().map(|(from, to)| (from.as_str(), to.as_str())),
+            query.limit,
+            query.offset,
+        ).await.map(MonitorResultsPage::Raw);
+    }
+
+    if query.status.is_some() {
+        return Err(crate::ApiError::BadRequest("The status filter is only supported for raw results".to_string()));
+    }
+
+    let (from, to) = range.unwrap_or((now - chrono::Duration::days(rollups::HOURLY_MAX_WINDOW_DAYS), now));
+    repository::list_rollups(
         state,
         monitor_id,
+        resolution,
         query.region.as_deref(),
-        query.status.as_deref(),
+        (&from.to_rfc3339(), &to.to_rfc3339()),
         query.limit,
         query.offset,
-    ).await
+    ).await.map(MonitorResultsPage::Rollups)
 }
 
+/// Runs one confirmed check. Every configured region (or only `region` when
+/// given) is probed, a `down` region is re-checked up to
+/// `confirmation_retries` times, and the final per-region results are stored
+/// together with the aggregated status in `monitor_checks`.
 pub async fn run_monitor_check(
     state: &AppState,
     monitor_id: &str,
     region: Option<&str>,
-) -> Result<MonitorResult, crate::ApiError> {
+) -> Result<CheckOutcome, crate::ApiError> {
     let monitor = repository::get_by_id(state, monitor_id).await?;
 
     if !monitor.enabled {
         return Err(crate::ApiError::BadRequest("Monitor is disabled".to_string()));
     }
 
-    let check_region = region.unwrap_or("EU");
-    let valid_regions = ["EU", "US", "ASIA"];
-    if !valid_regions.contains(&check_region) {
-        return Err(crate::ApiError::BadRequest(
-            format!("Invalid region: {}. Must be one of: {:?}", check_region, valid_regions)
-        ));
+    if monitor.kind == "heartbeat" {
+        return Err(crate::ApiError::BadRequest("Heartbeat monitors are passive and cannot be checked on demand".to_string()));
+    }
+
+    let regions = match region {
+        Some(region) if !VALID_REGIONS.contains(&region) => {
+            return Err(crate::ApiError::BadRequest(
+                format!("Invalid region: {}. Must be one of: {:?}", region, VALID_REGIONS)
+            ));
+        }
+        Some(region) => vec![region.to_string()],
+        None => monitor.confirmation.regions.clone(),
+    };
+    let quorum = monitor.confirmation.down_quorum.min(regions.len() as i32);
+    let now = chrono::Utc::now();
+    repository::update_last_checked(state, monitor_id, &now.to_rfc3339()).await?;
+    let in_maintenance = maintenance::is_in_maintenance(state, &monitor, now).await?;
+
+    let mut probes = tokio::task::JoinSet::new();
+    for region in regions.clone() {
+        let state = state.clone();
+        let monitor = monitor.clone();
+        probes.spawn(async move { confirm_region(&state, &monitor, &region).await });
+    }
+
+    let mut attempts = 0;
+    let mut anomaly_streak = 0;
+    let mut results = Vec::with_capacity(regions.len());
+    while let Some(joined) = probes.join_next().await {
+        let (mut result_data, tries) = joined.map_err(|e| {
+            eprintln!("Check task error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })??;
+        result_data.in_maintenance = in_maintenance;
+        attempts += tries;
+        validate_result(&result_data)?;
+        anomaly_streak = anomaly_streak.max(detect_anomaly(state, &monitor, &mut result_data).await?);
+        results.push(repository::create_result(state, monitor_id, &result_data).await?);
     }
+    results.sort_by_key(|r| regions.iter().position(|region| *region == r.region));
+
+    let statuses: Vec<&str> = results.iter().map(|r| r.status.as_str()).collect();
+    let status = confirmation::aggregate(&statuses, quorum);
+    let failed = statuses.iter().filter(|&&s| s == "down").count() as i32;
+    let result_ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
 
+    let check = repository::create_check(
+        state,
+        monitor_id,
+        status,
+        results.len() as i32,
+        failed,
+        attempts,
+        &result_ids,
+    ).await?;
+    for result in &mut results {
+        repository::set_result_check(state, &result.id, &check.id).await?;
+        result.check_id = Some(check.id.clone());
+    }
+
+    apply_status(state, &monitor, status, &results, in_maintenance, anomaly_streak).await?;
+
+    Ok(CheckOutcome { check, results })
+}
+
+pub async fn list_monitor_checks(
+    state: &AppState,
+    monitor_id: &str,
+    query: ListMonitorChecksQuery,
+) -> Result<Vec<MonitorCheck>, crate::ApiError> {
+    repository::list_checks(state, monitor_id, query.limit, query.offset).await
+}
+
+async fn probe(
+    state: &AppState,
+    monitor: &Monitor,
+    region: &str,
+) -> Result<CreateMonitorResult, crate::ApiError> {
+    let result_data = match monitor.kind.as_str() {
+        "ping" => {
+            let config: PingConfig = serde_json::from_value(monitor.config.clone()).unwrap_or_default();
+            checks::ping::run(&monitor.url, &config, region).await
+        }
+        "ssl" => {
+            let config: SslConfig = serde_json::from_value(monitor.config.clone()).unwrap_or_default();
+            checks::ssl::run(&monitor.url, &config, region).await
+        }
+        "domain" => {
+            let config: DomainConfig = serde_json::from_value(monitor.config.clone()).unwrap_or_default();
+            checks::domain::run(&state.config.rdap_base_url, &monitor.url, &config, region).await
+        }
+        _ => invoke_edge_check(state, monitor, region).await?,
+    };
+
+    Ok(result_data)
+}
+
+/// Probes one region until it is not `down` or the retries run out. Returns
+/// the final result and the number of attempts made.
+async fn confirm_region(
+    state: &AppState,
+    monitor: &Monitor,
+    region: &str,
+) -> Result<(CreateMonitorResult, i32), crate::ApiError> {
+    let mut attempt = 1;
+    loop {
+        let result_data = probe(state, monitor, region).await?;
+        if !confirmation::needs_retry(&result_data.status, attempt, monitor.confirmation.confirmation_retries) {
+            return Ok((result_data, attempt));
+        }
+        attempt += 1;
+        tokio::time::sleep(confirmation::RETRY_DELAY).await;
+    }
+}
+
+pub async fn uptime_report(
+    state: &AppState,
+    monitor_id: &str,
+    query: UptimeQuery,
+) -> Result<UptimeReport, crate::ApiError> {
+    let window = query.window.as_deref().unwrap_or("24h");
+    let (from, to) = uptime::window_bounds(window, query.from.as_deref(), query.to.as_deref(), chrono::Utc::now())?;
+
+    // Surfaces NotFound for unknown monitors instead of an empty report.
+    repository::get_by_id(state, monitor_id).await?;
+
+    let cutoff = rollups::retention_cutoff(chrono::Utc::now(), state.config.result_retention_days);
+    let resolution = rollups::resolution_for(from, to, cutoff);
+
+    let mut report = repository::uptime_report(
+        state,
+        monitor_id,
+        &from.to_rfc3339(),
+        &to.to_rfc3339(),
+        query.degraded_as_up.unwrap_or(true),
+        resolution,
+    ).await?;
+    report.window = window.to_string();
+    report.resolution = resolution.to_string();
+    Ok(report)
+}
+
+/// Latest certificate data for every SSL monitor in a project, soonest
+/// expiry first. Monitors that have not been checked yet come last.
+pub async fn list_certificates(state: &AppState, project_id: &str) -> Result<Vec<CertificateSummary>, crate::ApiError> {
+    let monitors = repository::list_by_project(state, project_id).await?;
+    let mut latest_results: HashMap<String, MonitorResult> = repository::list_latest_ssl_results(state, project_id)
+        .await?
+        .into_iter()
+        .map(|r| (r.monitor_id.clone(), r))
+        .collect();
+    let mut certificates = Vec::new();
+
+    for monitor in monitors.into_iter().filter(|m| m.kind == "ssl") {
+        let latest = latest_results.remove(&monitor.id);
+        certificates.push(CertificateSummary {
+            monitor_id: monitor.id,
+            monitor_name: monitor.name,
+            url: monitor.url,
+            status: latest.as_ref().map(|r| r.status.clone()),
+            expires_at: latest.as_ref().and_then(|r| r.ssl_expires_at.clone()),
+            days_remaining: latest.as_ref().and_then(|r| r.ssl_days_remaining),
+            issuer: latest.as_ref().and_then(|r| r.ssl_issuer.clone()),
+            details: latest.as_ref().and_then(|r| r.ssl_details.clone()),
+            checked_at: latest.map(|r| r.created_at),
+        });
+    }
+
+    certificates.sort_by_key(|c| (c.days_remaining.is_none(), c.days_remaining));
+    Ok(certificates)
+}
+
+/// Handles a ping from a heartbeat monitor. Returns the written result, or
+/// `None` for `/start` pings which only mark the beginning of a run.
+pub async fn record_heartbeat(
+    state: &AppState,
+    token: &str,
+    signal: HeartbeatSignal,
+) -> Result<Option<MonitorResult>, crate::ApiError> {
+    let monitor = repository::get_by_heartbeat_token(state, token).await?;
+
+    if !monitor.enabled {
+        return Err(crate::ApiError::BadRequest("Monitor is disabled".to_string()));
+    }
+
+    let now = chrono::Utc::now();
+    let now_str = now.to_rfc3339();
+    let started_at = monitor
+        .heartbeat_started_at
+        .as_deref()
+        .and_then(crate::shared::utils::parse_timestamp);
+
+    let Some(result_data) = heartbeat::result_for_signal(signal, started_at, now) else {
+        repository::update_heartbeat_state(state, &monitor.id, &now_str, Some(&now_str)).await?;
+        return Ok(None);
+    };
+
+    repository::update_heartbeat_state(state, &monitor.id, &now_str, None).await?;
+    create_monitor_result(state, &monitor.id, result_data).await.map(Some)
+}
+
+async fn invoke_edge_check(
+    state: &AppState,
+    monitor: &Monitor,
+    region: &str,
+) -> Result<CreateMonitorResult, crate::ApiError> {
     let payload = serde_json::json!({
         "monitor_id": monitor.id,
         "project_id": monitor.project_id,
@@ -172,7 +1309,8 @@ pub async fn run_monitor_check(
         "kind": monitor.kind,
         "url": monitor.url,
         "keyword": monitor.keyword,
-        "region": check_region,
+        "config": monitor.config,
+        "region": region,
     });
 
     let function_name = "monitor-check";
@@ -186,11 +1324,9 @@ pub async fn run_monitor_check(
             crate::ApiError::InternalServerError
         })?;
 
-    let result_data: CreateMonitorResult = serde_json::from_value(response)
+    serde_json::from_value(response)
         .map_err(|e| {
             eprintln!("Failed to parse Edge Function response: {:?}", e);
             crate::ApiError::InternalServerError
-        })?;
-
-    create_monitor_result(state, monitor_id, result_data).await
+        })
 }
@@ -0,0 +1,45 @@
+//! Persisted monitor state (`monitors.state`) and the incident action implied
+//! by each new status.
+
+pub const MONITOR_STATES: [&str; 5] = ["up", "down", "degraded", "paused", "unknown"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncidentAction {
+    None,
+    /// The monitor just went down.
+    Open,
+    /// Still down; the new results belong to the open incident.
+    Extend,
+    /// Left `down` (to `up` or `degraded`).
+    Resolve,
+}
+
+/// The state after a result with `status`, or `None` when it is unchanged.
+/// Paused monitors ignore results until they are resumed.
+pub fn next_state<'a>(current: &str, status: &'a str) -> Option<&'a str> {
+    if current == "paused" || current == status {
+        None
+    } else {
+        Some(status)
+    }
+}
+
+pub fn incident_action(current: &str, status: &str) -> IncidentAction {
+    match (current, status) {
+        ("paused", _) => IncidentAction::None,
+        ("down", "down") => IncidentAction::Extend,
+        (_, "down") => IncidentAction::Open,
+        ("down", _) => IncidentAction::Resolve,
+        _ => IncidentAction::None,
+    }
+}
+
+/// State to store when a monitor is enabled or disabled.
+pub fn state_for_enabled(current: &str, enabled: bool) -> Option<&'static str> {
+    match (current, enabled) {
+        ("paused", true) => Some("unknown"),
+        ("paused", false) => None,
+        (_, false) => Some("paused"),
+        _ => None,
+    }
+}
@@ -0,0 +1,89 @@
+//! Key/value labels on monitors (`env=prod`, `team=payments`) used to select
+//! monitors in bulk operations and listings, and to scope alerts and status
+//! page components through a selector such as `env=prod,team`.
+
+use std::collections::BTreeMap;
+
+pub const MAX_TAGS: usize = 32;
+pub const MAX_TAG_KEY_LEN: usize = 64;
+pub const MAX_TAG_VALUE_LEN: usize = 128;
+
+pub type Tags = BTreeMap<String, String>;
+
+fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_TAG_KEY_LEN
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+}
+
+pub fn validate_tags(tags: &Tags) -> Result<(), crate::ApiError> {
+    if tags.len() > MAX_TAGS {
+        return Err(crate::ApiError::BadRequest(format!("A monitor can have at most {} tags", MAX_TAGS)));
+    }
+
+    for (key, value) in tags {
+        // Keys end up in PostgREST JSON paths (`tags->>key`), so keep them plain.
+        if !valid_key(key) {
+            return Err(crate::ApiError::BadRequest(format!(
+                "Invalid tag key: {}. Use up to {} letters, digits, '-' or '_'",
+                key, MAX_TAG_KEY_LEN
+            )));
+        }
+        if value.len() > MAX_TAG_VALUE_LEN {
+            return Err(crate::ApiError::BadRequest(
+                format!("Tag {} value is longer than {} characters", key, MAX_TAG_VALUE_LEN)
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a tag filter: `key` matches any value, `key=value` an exact one.
+pub fn parse_filter(filter: &str) -> Result<(String, Option<String>), crate::ApiError> {
+    let (key, value) = match filter.split_once('=') {
+        Some((key, value)) => (key.trim(), Some(value.trim().to_string())),
+        None => (filter.trim(), None),
+    };
+    if !valid_key(key) {
+        return Err(crate::ApiError::BadRequest(format!("Invalid tag filter: {}", filter)));
+    }
+    Ok((key.to_string(), value))
+}
+
+/// Parses a comma-separated list of tag filters.
+pub fn parse_selector(selector: &str) -> Result<Vec<(String, Option<String>)>, crate::ApiError> {
+    let filters = selector
+        .split(',')
+        .filter(|f| !f.trim().is_empty())
+        .map(parse_filter)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (i, (key, _)) in filters.iter().enumerate() {
+        if filters[..i].iter().any(|(k, _)| k == key) {
+            return Err(crate::ApiError::BadRequest(format!("Tag {} is filtered more than once", key)));
+        }
+    }
+    Ok(filters)
+}
+
+/// Whether `tags` satisfy every filter of a parsed selector.
+pub fn matches_selector(tags: &Tags, selector: &[(String, Option<String>)]) -> bool {
+    selector.iter().all(|(key, value)| matches(tags, key, value.as_deref()))
+}
+
+pub fn matches(tags: &Tags, key: &str, value: Option<&str>) -> bool {
+    match (tags.get(key), value) {
+        (Some(actual), Some(expected)) => actual == expected,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Tags after setting `set` (overwriting existing keys) and removing `remove`.
+pub fn retag(existing: &Tags, set: &Tags, remove: &[String]) -> Tags {
+    let mut tags = existing.clone();
+    tags.extend(set.iter().map(|(k, v)| (k.clone(), v.clone())));
+    tags.retain(|key, _| !remove.contains(key));
+    tags
+}
@@ -0,0 +1,52 @@
+//! Report windows for `GET /monitors/{id}/uptime`. The report itself is
+//! computed by the `monitor_uptime_report` database function so it never has
+//! to page through raw results.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::shared::utils::parse_timestamp;
+
+pub const UPTIME_WINDOWS: [&str; 5] = ["24h", "7d", "30d", "90d", "custom"];
+
+pub const MAX_CUSTOM_WINDOW_DAYS: i64 = 366;
+
+/// `[from, to)` for a named window ending now, or for a `custom` window given
+/// as RFC 3339 `from` / `to`.
+pub fn window_bounds(
+    window: &str,
+    from: Option<&str>,
+    to: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), crate::ApiError> {
+    let length = match window {
+        "24h" => Duration::hours(24),
+        "7d" => Duration::days(7),
+        "30d" => Duration::days(30),
+        "90d" => Duration::days(90),
+        "custom" => {
+            let parse = |name: &str, value: Option<&str>| {
+                value.and_then(parse_timestamp).ok_or_else(|| {
+                    crate::ApiError::BadRequest(format!("Custom window requires an RFC 3339 `{}` timestamp", name))
+                })
+            };
+            let from = parse("from", from)?;
+            let to = to.map(|_| parse("to", to)).transpose()?.unwrap_or(now);
+            if from >= to {
+                return Err(crate::ApiError::BadRequest("`from` must be before `to`".to_string()));
+            }
+            if to - from > Duration::days(MAX_CUSTOM_WINDOW_DAYS) {
+                return Err(crate::ApiError::BadRequest(
+                    format!("Custom window cannot exceed {} days", MAX_CUSTOM_WINDOW_DAYS)
+                ));
+            }
+            return Ok((from, to));
+        }
+        other => {
+            return Err(crate::ApiError::BadRequest(
+                format!("Invalid window: {}. Must be one of: {:?}", other, UPTIME_WINDOWS)
+            ));
+        }
+    };
+
+    Ok((now - length, now))
+}
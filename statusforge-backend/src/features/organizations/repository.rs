@@ -58,7 +58,7 @@ pub async fn create(
     name: &str,
     user_id: &str,
 ) -> Result<Organization, crate::ApiError> {
-    let slug = format!("{}-{}", slugify(name), uuid::Uuid::new_v4().to_string()[..8].to_string());
+    let slug = format!("{}-{}", slugify(name), &uuid::Uuid::new_v4().to_string()[..8]);
     
     let data = serde_json::json!({
         "name": name,
@@ -154,3 +154,43 @@ pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
 
     Ok(())
 }
+
+pub async fn is_member(state: &AppState, organization_id: &str, user_id: &str) -> Result<bool, crate::ApiError> {
+    let members = state
+        .supabase
+        .database()
+        .from("organization_members")
+        .select("id")
+        .eq("organization_id", organization_id)
+        .eq("user_id", user_id)
+        .execute::<Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(!members.is_empty())
+}
+
+/// User ids of the organization's owners and admins.
+pub async fn list_admin_ids(state: &AppState, organization_id: &str) -> Result<Vec<String>, crate::ApiError> {
+    let members = state
+        .supabase
+        .database()
+        .from("organization_members")
+        .select("user_id")
+        .eq("organization_id", organization_id)
+        .r#in("role", &["owner", "admin"])
+        .execute::<Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(members
+        .iter()
+        .filter_map(|member| member["user_id"].as_str().map(str::to_string))
+        .collect())
+}
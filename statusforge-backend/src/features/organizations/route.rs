@@ -1,4 +1,4 @@
-use axum::{extract::{Path, Query, State}, routing::{delete, get, post, put}, Json, Router};
+use axum::{extract::{Path, Query, State}, routing::get, Json, Router};
 use serde::Deserialize;
 use serde_json::Value;
 
@@ -26,10 +26,10 @@ pub async fn update_organization(
     id: &str,
     data: UpdateOrganization,
 ) -> Result<Organization, crate::ApiError> {
-    if let Some(ref name) = data.name {
-        if name.trim().is_empty() {
-            return Err(crate::ApiError::BadRequest("Organization name cannot be empty".to_string()));
-        }
+    if let Some(ref name) = data.name
+        && name.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Organization name cannot be empty".to_string()));
     }
     repository::update(state, id, data.name.as_deref()).await
 }
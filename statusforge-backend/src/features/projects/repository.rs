@@ -53,6 +53,23 @@ pub async fn get_by_id(state: &AppState, id: &str) -> Result<Project, crate::Api
     projects.pop().ok_or(crate::ApiError::NotFound)
 }
 
+pub async fn get_by_slug(state: &AppState, slug: &str) -> Result<Project, crate::ApiError> {
+    let mut projects = state
+        .supabase
+        .database()
+        .from("projects")
+        .select("*")
+        .eq("slug", slug)
+        .execute::<Project>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    projects.pop().ok_or(crate::ApiError::NotFound)
+}
+
 pub async fn create(
     state: &AppState,
     organization_id: &str,
@@ -61,7 +78,7 @@ pub async fn create(
     user_id: &str,
 ) -> Result<Project, crate::ApiError> {
     let base_slug = slugify(name);
-    let slug = format!("{}-{}", base_slug, uuid::Uuid::new_v4().to_string()[..8].to_string());
+    let slug = format!("{}-{}", base_slug, &uuid::Uuid::new_v4().to_string()[..8]);
     
     let mut data = serde_json::json!({
         "organization_id": organization_id,
@@ -27,10 +27,10 @@ pub async fn update_project(
     id: &str,
     data: UpdateProject,
 ) -> Result<Project, crate::ApiError> {
-    if let Some(ref name) = data.name {
-        if name.trim().is_empty() {
-            return Err(crate::ApiError::BadRequest("Project name cannot be empty".to_string()));
-        }
+    if let Some(ref name) = data.name
+        && name.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Project name cannot be empty".to_string()));
     }
     repository::update(state, id, data.name.as_deref(), data.description.as_deref()).await
 }
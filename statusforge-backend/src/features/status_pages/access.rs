@@ -0,0 +1,212 @@
+//! Who may see a status page. `public` pages are open to everyone;
+//! `password` pages to visitors holding a session cookie, handed out for the
+//! page password; `restricted` pages to members of the project's organization
+//! (with a Supabase access token) and to visitors from allowed IPs.
+
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::{header, request::Parts};
+use chrono::{DateTime, Duration, Utc};
+use ipnet::IpNet;
+
+use crate::shared::signing;
+use crate::AppState;
+
+use super::StatusPage;
+
+pub const VISIBILITIES: [&str; 3] = ["public", "password", "restricted"];
+
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+pub const MAX_PASSWORD_LEN: usize = 128;
+
+pub const MAX_ALLOWED_IPS: usize = 100;
+
+/// How long a password session lasts.
+pub const SESSION_DAYS: i64 = 7;
+
+pub fn validate_visibility(visibility: &str) -> Result<(), String> {
+    if !VISIBILITIES.contains(&visibility) {
+        return Err(format!(
+            "Invalid visibility: {}. Allowed: {}",
+            visibility,
+            VISIBILITIES.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_password(password: &str) -> Result<(), String> {
+    let length = password.chars().count();
+    if length < MIN_PASSWORD_LEN {
+        return Err(format!("Password must be at least {} characters", MIN_PASSWORD_LEN));
+    }
+    if length > MAX_PASSWORD_LEN {
+        return Err(format!("Password cannot be longer than {} characters", MAX_PASSWORD_LEN));
+    }
+    Ok(())
+}
+
+/// Parses IPs (`203.0.113.7`) and CIDR ranges (`10.0.0.0/8`) into their
+/// network form, without duplicates.
+pub fn normalize_allowed_ips(entries: &[String]) -> Result<Vec<String>, String> {
+    if entries.len() > MAX_ALLOWED_IPS {
+        return Err(format!("A status page cannot allow more than {} IP ranges", MAX_ALLOWED_IPS));
+    }
+
+    let mut normalized: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let entry = entry.trim();
+        let network = match entry.parse::<IpNet>() {
+            Ok(network) => network.trunc(),
+            Err(_) => entry
+                .parse::<IpAddr>()
+                .map(|ip| IpNet::from(ip.to_canonical()))
+                .map_err(|_| format!("Invalid IP address or CIDR range: {}", entry))?,
+        };
+        let network = network.to_string();
+        if !normalized.contains(&network) {
+            normalized.push(network);
+        }
+    }
+    Ok(normalized)
+}
+
+pub fn ip_allowed(allowed: &[String], ip: IpAddr) -> bool {
+    let ip = ip.to_canonical();
+    allowed
+        .iter()
+        .filter_map(|entry| entry.parse::<IpNet>().ok())
+        .any(|network| network.contains(&ip))
+}
+
+/// Argon2id hash in PHC format.
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Password hashing failed: {}", e))
+}
+
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+pub fn session_cookie_name(page: &StatusPage) -> String {
+    format!("statusforge_page_{}", page.id)
+}
+
+/// The password hash is signed along, so changing the password ends every
+/// session.
+fn session_message(page: &StatusPage, expires: i64) -> String {
+    format!("session:{}:{}:{}", page.id, expires, page.password_hash.as_deref().unwrap_or_default())
+}
+
+/// `{expires unix timestamp}.{signature}`.
+pub fn session_token(secret: &str, page: &StatusPage, expires: DateTime<Utc>) -> String {
+    let expires = expires.timestamp();
+    format!("{}.{}", expires, signing::sign(secret, session_message(page, expires).as_bytes()))
+}
+
+pub fn verify_session(secret: &str, page: &StatusPage, token: &str, now: DateTime<Utc>) -> bool {
+    let Some((expires, signature)) = token.split_once('.') else {
+        return false;
+    };
+    let Ok(expires) = expires.parse::<i64>() else {
+        return false;
+    };
+    page.password_hash.is_some()
+        && expires > now.timestamp()
+        && signing::verify(secret, session_message(page, expires).as_bytes(), signature)
+}
+
+/// `Set-Cookie` value of a new session; `secure` when the page is served
+/// over HTTPS.
+pub fn session_cookie(secret: &str, page: &StatusPage, now: DateTime<Utc>, secure: bool) -> String {
+    let token = session_token(secret, page, now + Duration::days(SESSION_DAYS));
+    let mut cookie = format!(
+        "{}={}; Max-Age={}; Path=/; HttpOnly; SameSite=Lax",
+        session_cookie_name(page),
+        token,
+        SESSION_DAYS * 24 * 60 * 60
+    );
+    if secure {
+        cookie.push_str("; Secure");
+    }
+    cookie
+}
+
+/// The value of cookie `name` in a `Cookie` header.
+pub fn cookie<'a>(cookies: &'a str, name: &str) -> Option<&'a str> {
+    cookies
+        .split(';')
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// The address a reverse proxy appended last to `X-Forwarded-For`; earlier
+/// entries come from the client and cannot be trusted.
+pub fn forwarded_ip(forwarded_for: &str) -> Option<IpAddr> {
+    forwarded_for.rsplit(',').next()?.trim().parse().ok()
+}
+
+/// What a request brings that can open a non-public page.
+#[derive(Debug, Clone, Default)]
+pub struct Viewer {
+    pub ip: Option<IpAddr>,
+    /// The raw `Cookie` headers.
+    pub cookies: String,
+    /// Supabase access token from `Authorization: Bearer`.
+    pub access_token: Option<String>,
+}
+
+impl Viewer {
+    pub fn has_session(&self, secret: &str, page: &StatusPage, now: DateTime<Utc>) -> bool {
+        cookie(&self.cookies, &session_cookie_name(page)).is_some_and(|token| verify_session(secret, page, token, now))
+    }
+}
+
+impl FromRequestParts<AppState> for Viewer {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        // Only the last header counts, as that is the one the proxy added.
+        let forwarded = parts
+            .headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .next_back()
+            .and_then(|v| v.to_str().ok())
+            .filter(|_| state.config.trust_forwarded_for)
+            .and_then(forwarded_ip);
+        let connected = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip());
+
+        Ok(Viewer {
+            ip: forwarded.or(connected),
+            cookies: parts
+                .headers
+                .get_all(header::COOKIE)
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .collect::<Vec<_>>()
+                .join("; "),
+            access_token: parts
+                .headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+        })
+    }
+}
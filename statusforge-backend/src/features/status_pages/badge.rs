@@ -0,0 +1,107 @@
+//! SVG badges in the usual two-part "label | value" style, for READMEs and
+//! dashboards: the status of a page or monitor, or its uptime.
+
+use super::render::{escape, fill};
+use super::{status, BadgeQuery};
+
+const BADGE_TEMPLATE: &str = include_str!("../../../templates/status_page/badge.svg");
+
+pub const BADGE_KINDS: [&str; 2] = ["status", "uptime"];
+
+pub const UPTIME_WINDOWS: [&str; 4] = ["24h", "7d", "30d", "90d"];
+
+pub const MAX_LABEL_LEN: usize = 40;
+
+const GREEN: &str = "#16a34a";
+const AMBER: &str = "#f59e0b";
+const RED: &str = "#dc2626";
+const INDIGO: &str = "#6366f1";
+const GREY: &str = "#9ca3af";
+
+/// What a badge shows, validated from its query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadgeSpec {
+    /// `status` or `uptime`.
+    pub kind: String,
+    pub window: String,
+    pub label: String,
+}
+
+pub fn badge_spec(query: &BadgeQuery) -> Result<BadgeSpec, String> {
+    let kind = query.show.clone().unwrap_or_else(|| "status".to_string());
+    if !BADGE_KINDS.contains(&kind.as_str()) {
+        return Err(format!("Invalid badge: {}. Must be one of: {:?}", kind, BADGE_KINDS));
+    }
+    let window = query.window.clone().unwrap_or_else(|| "30d".to_string());
+    if !UPTIME_WINDOWS.contains(&window.as_str()) {
+        return Err(format!("Invalid window: {}. Must be one of: {:?}", window, UPTIME_WINDOWS));
+    }
+    let label = match query.label.as_deref().map(str::trim) {
+        Some(label) if label.chars().count() > MAX_LABEL_LEN => {
+            return Err(format!("Badge label cannot be longer than {} characters", MAX_LABEL_LEN));
+        }
+        Some(label) if !label.is_empty() => label.to_string(),
+        _ => kind.clone(),
+    };
+    Ok(BadgeSpec { kind, window, label })
+}
+
+/// Colour for a page status, component status or monitor state.
+pub fn status_color(status: &str) -> &'static str {
+    match status {
+        "operational" | "up" => GREEN,
+        "degraded" => AMBER,
+        "partial_outage" | "major_outage" | "outage" | "down" => RED,
+        "maintenance" => INDIGO,
+        _ => GREY,
+    }
+}
+
+pub fn uptime_color(uptime_percent: Option<f64>) -> &'static str {
+    match status::uptime_level(uptime_percent) {
+        "up" => GREEN,
+        "degraded" => AMBER,
+        "down" => RED,
+        _ => GREY,
+    }
+}
+
+/// Approximate width of `text` in 11px Verdana.
+fn text_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'l' | 'j' | '.' | ',' | ':' | '|' | '!' | '\'' | ' ' => 4,
+            'm' | 'w' | 'M' | 'W' | '%' => 10,
+            c if c.is_uppercase() => 8,
+            _ => 7,
+        })
+        .sum()
+}
+
+pub fn render_badge(label: &str, value: &str, color: &str) -> String {
+    let label_width = text_width(label) + 10;
+    let value_width = text_width(value) + 10;
+    fill(BADGE_TEMPLATE, &[
+        ("width", &(label_width + value_width).to_string()),
+        ("label_width", &label_width.to_string()),
+        ("value_width", &value_width.to_string()),
+        ("label_x", &(label_width / 2).to_string()),
+        ("value_x", &(label_width + value_width / 2).to_string()),
+        ("label", &escape(label)),
+        ("value", &escape(value)),
+        ("color", color),
+    ])
+}
+
+/// `partial_outage` → `partial outage`.
+pub fn status_badge(label: &str, status: &str) -> String {
+    render_badge(label, &status.replace('_', " "), status_color(status))
+}
+
+pub fn uptime_badge(label: &str, uptime_percent: Option<f64>) -> String {
+    let value = match uptime_percent {
+        Some(p) => format!("{:.2}%", (p * 100.0).floor() / 100.0),
+        None => "no data".to_string(),
+    };
+    render_badge(label, &value, uptime_color(uptime_percent))
+}
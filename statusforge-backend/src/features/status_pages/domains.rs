@@ -0,0 +1,174 @@
+//! Custom status page domains. A page answers on its `custom_domain` once
+//! the domain is verified with a TXT record or a well-known file containing
+//! the page's `domain_token`. Hosts that are not the API's own (`APP_HOSTS`)
+//! are resolved to a status page before routing.
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::features::projects;
+use crate::AppState;
+
+use super::repository;
+
+pub const VERIFICATION_METHODS: [&str; 2] = ["dns", "http"];
+
+pub const TXT_RECORD_PREFIX: &str = "_statusforge";
+
+pub const TXT_VALUE_PREFIX: &str = "statusforge-verification=";
+
+pub const WELL_KNOWN_PATH: &str = "/.well-known/statusforge-verification.txt";
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+pub fn txt_record_name(domain: &str) -> String {
+    format!("{}.{}", TXT_RECORD_PREFIX, domain)
+}
+
+pub fn txt_record_value(token: &str) -> String {
+    format!("{}{}", TXT_VALUE_PREFIX, token)
+}
+
+pub fn well_known_url(domain: &str) -> String {
+    format!("http://{}{}", domain, WELL_KNOWN_PATH)
+}
+
+/// TXT strings from a DNS-over-HTTPS JSON answer; a record split into
+/// several quoted strings is joined back together.
+pub fn parse_txt_answers(body: &serde_json::Value) -> Vec<String> {
+    body["Answer"]
+        .as_array()
+        .map(|answers| {
+            answers
+                .iter()
+                .filter(|answer| answer["type"].as_u64() == Some(16))
+                .filter_map(|answer| answer["data"].as_str())
+                .map(|data| data.split("\" \"").map(|part| part.trim_matches('"')).collect())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn check_dns(doh_url: &str, domain: &str, token: &str) -> Result<bool, String> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| format!("DNS client setup failed: {}", e))?;
+
+    let body: serde_json::Value = client
+        .get(doh_url)
+        .query(&[("name", txt_record_name(domain).as_str()), ("type", "TXT")])
+        .header(header::ACCEPT, "application/dns-json")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("DNS lookup failed: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid DNS response: {}", e))?;
+
+    let expected = txt_record_value(token);
+    Ok(parse_txt_answers(&body).iter().any(|value| value.trim() == expected))
+}
+
+pub async fn check_http(url: &str, token: &str) -> Result<bool, String> {
+    let client = reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .build()
+        .map_err(|e| format!("HTTP client setup failed: {}", e))?;
+
+    let response = client.get(url).send().await.map_err(|e| format!("Request to {} failed: {}", url, e))?;
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+    let body = response.text().await.map_err(|e| format!("Reading {} failed: {}", url, e))?;
+    Ok(body.trim() == token)
+}
+
+/// The host a request was sent to, without port, or `None` when it is one
+/// of the API's own hosts. Without configured `app_hosts` custom domains are
+/// disabled; `localhost` and IP addresses always reach the API.
+pub fn custom_host(host: Option<&str>, app_hosts: &[String]) -> Option<String> {
+    if app_hosts.is_empty() {
+        return None;
+    }
+
+    let host = host?.trim().to_ascii_lowercase();
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default().to_string(),
+        None => host.split(':').next().unwrap_or_default().to_string(),
+    };
+    let host = host.trim_end_matches('.');
+
+    let own = host.is_empty()
+        || host == "localhost"
+        || host.parse::<IpAddr>().is_ok()
+        || app_hosts.iter().any(|app_host| app_host == host);
+    (!own).then(|| host.to_string())
+}
+
+/// `uri` moved under `/status/{slug}`, keeping the query string.
+pub fn status_page_uri(uri: &Uri, slug: &str) -> Option<Uri> {
+    let path = match uri.path() {
+        "/" => String::new(),
+        path => path.to_string(),
+    };
+    let rewritten = match uri.query() {
+        Some(query) => format!("/status/{}{}?{}", slug, path, query),
+        None => format!("/status/{}{}", slug, path),
+    };
+    rewritten.parse().ok()
+}
+
+/// Slug of the project whose enabled page is verified for `domain`.
+async fn verified_slug(state: &AppState, domain: &str) -> Result<Option<String>, crate::ApiError> {
+    let Some(page) = repository::get_by_domain(state, domain).await? else {
+        return Ok(None);
+    };
+    if !page.enabled || page.domain_verified_at.is_none() {
+        return Ok(None);
+    }
+    let project = projects::repository::get_by_id(state, &page.project_id).await?;
+    Ok(Some(project.slug))
+}
+
+/// Serves verified custom domains from their status page routes; other
+/// unknown hosts get a 404.
+pub async fn resolve_host(State(state): State<AppState>, mut request: Request, next: Next) -> Response {
+    let host = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| request.uri().host());
+    let Some(domain) = custom_host(host, &state.config.app_hosts) else {
+        return next.run(request).await;
+    };
+
+    match verified_slug(&state, &domain).await {
+        Ok(Some(slug)) => match status_page_uri(request.uri(), &slug) {
+            Some(uri) => {
+                *request.uri_mut() = uri;
+                next.run(request).await
+            }
+            None => StatusCode::BAD_REQUEST.into_response(),
+        },
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": format!("No verified status page for {}", domain) })),
+        )
+            .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,135 @@
+//! RSS 2.0 and Atom feeds of a status page's posts. Each entry is one
+//! incident or maintenance post with its timeline of updates.
+
+use crate::shared::utils::parse_timestamp;
+
+use super::render::{display_time, escape, state_label};
+use super::PublicPost;
+
+pub const MAX_FEED_POSTS: u32 = 50;
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub page_url: String,
+    pub updated_at: String,
+    /// Newest first.
+    pub posts: Vec<PublicPost>,
+}
+
+/// When a post last changed: its newest update, or when it was created.
+pub fn post_updated_at(post: &PublicPost) -> &str {
+    post.updates.first().map(|u| u.created_at.as_str()).unwrap_or(&post.created_at)
+}
+
+fn rfc3339(timestamp: &str) -> String {
+    parse_timestamp(timestamp)
+        .map(|at| at.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn rfc2822(timestamp: &str) -> String {
+    parse_timestamp(timestamp)
+        .map(|at| at.to_rfc2822())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn entry_link(feed: &Feed, post: &PublicPost) -> String {
+    format!("{}#post-{}", feed.page_url, post.id)
+}
+
+fn entry_title(post: &PublicPost) -> String {
+    format!("{} ({})", post.title, state_label(&post.state))
+}
+
+/// The entry body as HTML; feeds carry it escaped once more.
+fn entry_html(post: &PublicPost) -> String {
+    let mut summary = vec![format!("Impact: {}", state_label(&post.impact))];
+    if !post.components.is_empty() {
+        summary.push(format!("Affects: {}", post.components.join(", ")));
+    }
+    if let (Some(starts_at), Some(ends_at)) = (&post.scheduled_starts_at, &post.scheduled_ends_at) {
+        summary.push(format!("Scheduled {} – {}", display_time(starts_at), display_time(ends_at)));
+    }
+
+    let mut html = format!("<p>{}</p>", escape(&summary.join(" · ")));
+    for update in &post.updates {
+        html.push_str(&format!(
+            "<p><strong>{}</strong> – {} <small>{}</small></p>",
+            escape(&state_label(&update.state)),
+            escape(&update.body),
+            escape(&display_time(&update.created_at)),
+        ));
+    }
+    html
+}
+
+pub fn rss(feed: &Feed) -> String {
+    let items: String = feed
+        .posts
+        .iter()
+        .map(|post| {
+            format!(
+                "<item>\n<title>{}</title>\n<link>{}</link>\n<guid isPermaLink=\"false\">{}</guid>\n<pubDate>{}</pubDate>\n<description>{}</description>\n</item>\n",
+                escape(&entry_title(post)),
+                escape(&entry_link(feed, post)),
+                escape(&post.id),
+                escape(&rfc2822(&post.created_at)),
+                escape(&entry_html(post)),
+            )
+        })
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n\
+         <channel>\n\
+         <title>{title} status</title>\n\
+         <link>{link}</link>\n\
+         <description>Incidents and maintenance for {title}</description>\n\
+         <atom:link href=\"{link}/feed.rss\" rel=\"self\" type=\"application/rss+xml\"/>\n\
+         <lastBuildDate>{updated}</lastBuildDate>\n\
+         {items}\
+         </channel>\n\
+         </rss>\n",
+        title = escape(&feed.title),
+        link = escape(&feed.page_url),
+        updated = escape(&rfc2822(&feed.updated_at)),
+        items = items,
+    )
+}
+
+pub fn atom(feed: &Feed) -> String {
+    let entries: String = feed
+        .posts
+        .iter()
+        .map(|post| {
+            format!(
+                "<entry>\n<id>urn:uuid:{}</id>\n<title>{}</title>\n<link href=\"{}\"/>\n<published>{}</published>\n<updated>{}</updated>\n<content type=\"html\">{}</content>\n</entry>\n",
+                escape(&post.id),
+                escape(&entry_title(post)),
+                escape(&entry_link(feed, post)),
+                escape(&rfc3339(&post.created_at)),
+                escape(&rfc3339(post_updated_at(post))),
+                escape(&entry_html(post)),
+            )
+        })
+        .collect();
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+         <title>{title} status</title>\n\
+         <id>{link}</id>\n\
+         <link href=\"{link}\"/>\n\
+         <link rel=\"self\" href=\"{link}/feed.atom\"/>\n\
+         <updated>{updated}</updated>\n\
+         <author><name>{title}</name></author>\n\
+         {entries}\
+         </feed>\n",
+        title = escape(&feed.title),
+        link = escape(&feed.page_url),
+        updated = escape(&rfc3339(&feed.updated_at)),
+        entries = entries,
+    )
+}
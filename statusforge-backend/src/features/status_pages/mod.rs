@@ -0,0 +1,17 @@
+pub mod access;
+pub mod badge;
+pub mod domains;
+pub mod feeds;
+mod model;
+pub mod posts;
+pub mod render;
+pub mod repository;
+mod route;
+pub mod service;
+pub mod status;
+pub mod subscriptions;
+pub mod theme;
+
+pub use model::*;
+pub use route::*;
+pub use service::*;
@@ -0,0 +1,378 @@
+use serde::{Deserialize, Serialize};
+
+/// The public status page of a project (at most one per project), served
+/// under the project's slug.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPage {
+    pub id: String,
+    pub project_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub custom_domain: Option<String>,
+    /// Proves ownership of `custom_domain`, see `domains`.
+    #[serde(default)]
+    pub domain_token: Option<String>,
+    /// The page is served on `custom_domain` only once this is set.
+    #[serde(default)]
+    pub domain_verified_at: Option<String>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub theme: PageTheme,
+    /// `public`, `password` or `restricted`, see `access`.
+    #[serde(default = "default_visibility")]
+    pub visibility: String,
+    /// Argon2 hash of the password of a `password` page; never sent out.
+    #[serde(default, skip_serializing)]
+    pub password_hash: Option<String>,
+    /// IPs and CIDR ranges let into a `restricted` page without signing in.
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn default_visibility() -> String {
+    "public".to_string()
+}
+
+/// Look of the HTML status page. Colours are `#rgb` / `#rrggbb`; unset
+/// values keep the built-in theme, see `theme`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PageTheme {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_css: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateStatusPage {
+    pub title: String,
+    pub description: Option<String>,
+    pub custom_domain: Option<String>,
+    pub enabled: Option<bool>,
+    pub theme: Option<PageTheme>,
+    pub visibility: Option<String>,
+    /// Required for `password` pages; stored only as a hash.
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+    pub allowed_ips: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateStatusPage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<PageTheme>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    /// Sets a new password; existing sessions stop working.
+    #[serde(skip_serializing)]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_ips: Option<Vec<String>>,
+}
+
+/// The password form of a `password` page.
+#[derive(Debug, Deserialize)]
+pub struct PageLogin {
+    pub password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TxtRecord {
+    pub name: String,
+    pub value: String,
+}
+
+/// How to prove ownership of a page's custom domain: publish either the TXT
+/// record or the well-known file, then call verify.
+#[derive(Debug, Clone, Serialize)]
+pub struct DomainVerification {
+    pub domain: String,
+    pub verified: bool,
+    pub verified_at: Option<String>,
+    pub txt_record: TxtRecord,
+    pub well_known_url: String,
+    pub well_known_content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyDomainQuery {
+    /// `dns` or `http`; both are tried when omitted.
+    pub method: Option<String>,
+}
+
+/// A monitor shown on a status page. Without `display_name` the monitor name
+/// is shown, without `group_name` the name of the monitor's group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPageComponent {
+    pub id: String,
+    pub status_page_id: String,
+    pub monitor_id: String,
+    pub display_name: Option<String>,
+    pub group_name: Option<String>,
+    pub position: i32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentInput {
+    pub monitor_id: String,
+    pub display_name: Option<String>,
+    pub group_name: Option<String>,
+    /// Defaults to the index in the submitted list.
+    pub position: Option<i32>,
+}
+
+/// Replaces the full component list of a page. Monitors matching
+/// `tag_selector` (such as `env=prod,team`) are added after the listed
+/// components, sorted by name; newly tagged monitors join when the list is
+/// saved again.
+#[derive(Debug, Deserialize)]
+pub struct SetComponents {
+    #[serde(default)]
+    pub components: Vec<ComponentInput>,
+    pub tag_selector: Option<String>,
+}
+
+/// A manual post on a status page, see `posts`. `component_ids` are ids of
+/// the page's components.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPagePost {
+    pub id: String,
+    pub status_page_id: String,
+    /// `incident` or `maintenance`.
+    pub kind: String,
+    pub title: String,
+    pub impact: String,
+    /// State of the newest update.
+    pub state: String,
+    #[serde(default)]
+    pub component_ids: Vec<String>,
+    pub scheduled_starts_at: Option<String>,
+    pub scheduled_ends_at: Option<String>,
+    /// Set when the post reaches `resolved` / `completed`.
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPagePostUpdate {
+    pub id: String,
+    pub post_id: String,
+    pub state: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostWithUpdates {
+    #[serde(flatten)]
+    pub post: StatusPagePost,
+    /// Newest first.
+    pub updates: Vec<StatusPagePostUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePost {
+    pub kind: Option<String>,
+    pub title: String,
+    pub impact: Option<String>,
+    pub component_ids: Option<Vec<String>>,
+    /// Defaults to `investigating` / `scheduled`.
+    pub state: Option<String>,
+    /// Message of the first update.
+    pub body: String,
+    pub scheduled_starts_at: Option<String>,
+    pub scheduled_ends_at: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdatePost {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_starts_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_ends_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreatePostUpdate {
+    /// Keeps the current state when omitted.
+    pub state: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListPostsQuery {
+    pub kind: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusPageSubscriber {
+    pub id: String,
+    pub status_page_id: String,
+    /// `email` or `webhook`.
+    pub kind: String,
+    /// Email address or webhook URL.
+    pub target: String,
+    /// Components the subscriber follows; empty means all of them.
+    #[serde(default)]
+    pub component_ids: Vec<String>,
+    /// Set once the subscriber follows its confirmation link, see
+    /// `subscriptions`.
+    pub confirmed_at: Option<String>,
+    pub created_at: String,
+}
+
+/// Public subscribe request: exactly one of `email` and `webhook_url`.
+#[derive(Debug, Deserialize)]
+pub struct Subscribe {
+    pub email: Option<String>,
+    pub webhook_url: Option<String>,
+    pub component_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubscriptionTokenQuery {
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicPostUpdate {
+    pub state: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicPost {
+    pub id: String,
+    pub kind: String,
+    pub title: String,
+    pub impact: String,
+    pub state: String,
+    /// Names of the affected components.
+    pub components: Vec<String>,
+    pub scheduled_starts_at: Option<String>,
+    pub scheduled_ends_at: Option<String>,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    /// Newest first.
+    pub updates: Vec<PublicPostUpdate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicComponent {
+    pub id: String,
+    pub name: String,
+    pub group: Option<String>,
+    /// One of `status::COMPONENT_STATUSES`.
+    pub status: String,
+    /// Time of the newest result the status is based on.
+    pub checked_at: Option<String>,
+}
+
+/// Unauthenticated view of a status page (`GET /status/{slug}`).
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicStatusPage {
+    pub title: String,
+    pub description: Option<String>,
+    /// One of `status::PAGE_STATUSES`.
+    pub status: String,
+    pub components: Vec<PublicComponent>,
+    /// Open and recent incident posts and announced maintenance.
+    pub posts: Vec<PublicPost>,
+    pub updated_at: String,
+}
+
+/// Summary for embedding the page's status on another site.
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbedStatus {
+    pub status: String,
+    /// e.g. "All systems operational".
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BadgeQuery {
+    /// `status` (default) or `uptime`.
+    pub show: Option<String>,
+    /// Uptime window: `24h`, `7d`, `30d` (default) or `90d`.
+    pub window: Option<String>,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailyUptime {
+    /// `YYYY-MM-DD` (UTC).
+    pub date: String,
+    /// `None` for days without results.
+    pub uptime_percent: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentView {
+    #[serde(flatten)]
+    pub component: PublicComponent,
+    pub uptime_percent: Option<f64>,
+    /// Oldest first, ending today.
+    pub days: Vec<DailyUptime>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicIncident {
+    pub component: String,
+    pub cause: String,
+    pub status: String,
+    pub started_at: String,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublicMaintenance {
+    pub title: String,
+    pub description: Option<String>,
+    pub starts_at: String,
+    pub ends_at: String,
+}
+
+/// Everything shown on the HTML status page.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusPageView {
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub theme: PageTheme,
+    pub components: Vec<ComponentView>,
+    /// Open incidents and incidents resolved recently, newest first.
+    pub incidents: Vec<PublicIncident>,
+    /// Running and upcoming maintenance of the page's components.
+    pub maintenance: Vec<PublicMaintenance>,
+    pub posts: Vec<PublicPost>,
+    pub updated_at: String,
+}
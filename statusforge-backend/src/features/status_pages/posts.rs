@@ -0,0 +1,157 @@
+//! Manual status page posts: incidents with a timeline of updates, and
+//! scheduled maintenance announcements.
+
+use chrono::{DateTime, Utc};
+
+use crate::shared::utils::parse_timestamp;
+
+use super::{CreatePost, StatusPageComponent, StatusPagePost};
+
+pub const POST_KINDS: [&str; 2] = ["incident", "maintenance"];
+
+pub const IMPACTS: [&str; 4] = ["none", "minor", "major", "critical"];
+
+pub const INCIDENT_STATES: [&str; 4] = ["investigating", "identified", "monitoring", "resolved"];
+
+pub const MAINTENANCE_STATES: [&str; 3] = ["scheduled", "in_progress", "completed"];
+
+pub const MAX_TITLE_LEN: usize = 200;
+
+pub const MAX_BODY_LEN: usize = 10_000;
+
+pub fn states(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "maintenance" => &MAINTENANCE_STATES,
+        _ => &INCIDENT_STATES,
+    }
+}
+
+pub fn initial_state(kind: &str) -> &'static str {
+    states(kind)[0]
+}
+
+/// `resolved` incidents and `completed` maintenance.
+pub fn is_closed(kind: &str, state: &str) -> bool {
+    states(kind).last() == Some(&state)
+}
+
+pub fn validate_kind(kind: &str) -> Result<(), String> {
+    if POST_KINDS.contains(&kind) {
+        Ok(())
+    } else {
+        Err(format!("Invalid post kind: {}. Must be one of: {:?}", kind, POST_KINDS))
+    }
+}
+
+pub fn validate_impact(impact: &str) -> Result<(), String> {
+    if IMPACTS.contains(&impact) {
+        Ok(())
+    } else {
+        Err(format!("Invalid impact: {}. Must be one of: {:?}", impact, IMPACTS))
+    }
+}
+
+pub fn validate_state(kind: &str, state: &str) -> Result<(), String> {
+    if states(kind).contains(&state) {
+        Ok(())
+    } else {
+        Err(format!("Invalid {} state: {}. Must be one of: {:?}", kind, state, states(kind)))
+    }
+}
+
+pub fn validate_title(title: &str) -> Result<(), String> {
+    if title.trim().is_empty() {
+        Err("Post title cannot be empty".to_string())
+    } else if title.chars().count() > MAX_TITLE_LEN {
+        Err(format!("Post title cannot be longer than {} characters", MAX_TITLE_LEN))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn validate_body(body: &str) -> Result<(), String> {
+    if body.trim().is_empty() {
+        Err("Update message cannot be empty".to_string())
+    } else if body.chars().count() > MAX_BODY_LEN {
+        Err(format!("Update message cannot be longer than {} characters", MAX_BODY_LEN))
+    } else {
+        Ok(())
+    }
+}
+
+/// Maintenance needs a schedule window; incidents cannot have one.
+pub fn validate_schedule(kind: &str, starts_at: Option<&str>, ends_at: Option<&str>) -> Result<(), String> {
+    match (kind, starts_at, ends_at) {
+        ("maintenance", Some(starts_at), Some(ends_at)) => {
+            let starts_at = parse_timestamp(starts_at)
+                .ok_or_else(|| "scheduled_starts_at must be an RFC 3339 timestamp".to_string())?;
+            let ends_at = parse_timestamp(ends_at)
+                .ok_or_else(|| "scheduled_ends_at must be an RFC 3339 timestamp".to_string())?;
+            if ends_at <= starts_at {
+                return Err("scheduled_ends_at must be after scheduled_starts_at".to_string());
+            }
+            Ok(())
+        }
+        ("maintenance", _, _) => Err("Maintenance posts require scheduled_starts_at and scheduled_ends_at".to_string()),
+        (_, None, None) => Ok(()),
+        _ => Err("Only maintenance posts can be scheduled".to_string()),
+    }
+}
+
+pub fn validate_components(component_ids: &[String], components: &[StatusPageComponent]) -> Result<(), String> {
+    match component_ids.iter().find(|id| !components.iter().any(|c| &c.id == *id)) {
+        Some(unknown) => Err(format!("Component {} is not on this status page", unknown)),
+        None => Ok(()),
+    }
+}
+
+pub fn validate_new_post(data: &CreatePost, components: &[StatusPageComponent]) -> Result<(), String> {
+    let kind = data.kind.as_deref().unwrap_or("incident");
+    validate_kind(kind)?;
+    validate_title(&data.title)?;
+    validate_body(&data.body)?;
+    if let Some(ref impact) = data.impact {
+        validate_impact(impact)?;
+    }
+    if let Some(ref state) = data.state {
+        validate_state(kind, state)?;
+    }
+    validate_schedule(kind, data.scheduled_starts_at.as_deref(), data.scheduled_ends_at.as_deref())?;
+    validate_components(data.component_ids.as_deref().unwrap_or_default(), components)
+}
+
+/// Whether a post still belongs on the public page: open posts, posts closed
+/// after `since`, and maintenance that has not ended yet.
+pub fn is_visible(post: &StatusPagePost, since: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    if post.kind == "maintenance"
+        && !is_closed(&post.kind, &post.state)
+        && let Some(ends_at) = post.scheduled_ends_at.as_deref().and_then(parse_timestamp)
+    {
+        return ends_at > now;
+    }
+    match post.resolved_at.as_deref() {
+        None => true,
+        Some(resolved_at) => parse_timestamp(resolved_at).is_some_and(|at| at >= since),
+    }
+}
+
+/// Page status escalated by the impact of open incident posts.
+pub fn escalate(page_status: &'static str, open_impacts: &[&str]) -> &'static str {
+    let severity = |status: &str| match status {
+        "major_outage" => 4,
+        "partial_outage" => 3,
+        "degraded" => 2,
+        _ => 0,
+    };
+    let from_posts = if open_impacts.contains(&"critical") {
+        "major_outage"
+    } else if open_impacts.contains(&"major") {
+        "partial_outage"
+    } else if open_impacts.contains(&"minor") {
+        "degraded"
+    } else {
+        return page_status;
+    };
+
+    if severity(from_posts) > severity(page_status) { from_posts } else { page_status }
+}
@@ -0,0 +1,317 @@
+//! Server-rendered HTML status page. Templates live in
+//! `templates/status_page` and use `{{name}}` placeholders; every value is
+//! escaped before it is substituted, and the page needs no JavaScript.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use super::{status, theme, ComponentView, EmbedStatus, PageTheme, PublicIncident, PublicMaintenance, PublicPost, StatusPageView};
+
+const PAGE_TEMPLATE: &str = include_str!("../../../templates/status_page/page.html");
+const COMPONENT_TEMPLATE: &str = include_str!("../../../templates/status_page/component.html");
+const INCIDENT_TEMPLATE: &str = include_str!("../../../templates/status_page/incident.html");
+const MAINTENANCE_TEMPLATE: &str = include_str!("../../../templates/status_page/maintenance.html");
+const POST_TEMPLATE: &str = include_str!("../../../templates/status_page/post.html");
+const MESSAGE_TEMPLATE: &str = include_str!("../../../templates/status_page/message.html");
+const LOGIN_TEMPLATE: &str = include_str!("../../../templates/status_page/login.html");
+
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Substitutes `{{name}}` placeholders in a single pass, so substituted
+/// values are never expanded again; `values` must already be HTML.
+pub fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    let mut html = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end + 2) else {
+            break;
+        };
+        html.push_str(&rest[..start]);
+        let name = &rest[start + 2..end - 2];
+        match values.iter().find(|(n, _)| *n == name) {
+            Some((_, value)) => html.push_str(value),
+            None => html.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    html.push_str(rest);
+    html
+}
+
+pub fn page_status_label(status: &str) -> &'static str {
+    match status {
+        "operational" => "All systems operational",
+        "degraded" => "Degraded performance",
+        "partial_outage" => "Partial outage",
+        "major_outage" => "Major outage",
+        "maintenance" => "Under maintenance",
+        _ => "Status unknown",
+    }
+}
+
+pub fn component_status_label(status: &str) -> &'static str {
+    match status {
+        "operational" => "Operational",
+        "degraded" => "Degraded performance",
+        "outage" => "Outage",
+        "maintenance" => "Under maintenance",
+        _ => "No data",
+    }
+}
+
+/// `in_progress` → `In progress`.
+pub fn state_label(state: &str) -> String {
+    let label = state.replace('_', " ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+pub fn display_time(timestamp: &str) -> String {
+    crate::shared::utils::parse_timestamp(timestamp)
+        .map(format_time)
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn format_percent(uptime_percent: Option<f64>) -> String {
+    match uptime_percent {
+        Some(p) => format!("{:.2}%", (p * 100.0).floor() / 100.0),
+        None => "no data".to_string(),
+    }
+}
+
+fn render_component(view: &ComponentView) -> String {
+    let bars: String = view
+        .days
+        .iter()
+        .map(|day| {
+            format!(
+                r#"<span class="bar bar-{}" title="{}: {}"></span>"#,
+                status::uptime_level(day.uptime_percent),
+                escape(&day.date),
+                format_percent(day.uptime_percent),
+            )
+        })
+        .collect();
+    let uptime = match view.uptime_percent {
+        Some(_) => format!("{} uptime", format_percent(view.uptime_percent)),
+        None => "No data".to_string(),
+    };
+
+    fill(COMPONENT_TEMPLATE, &[
+        ("name", &escape(&view.component.name)),
+        ("status", &escape(&view.component.status)),
+        ("status_label", component_status_label(&view.component.status)),
+        ("bars", &bars),
+        ("days", &view.days.len().to_string()),
+        ("uptime", &uptime),
+    ])
+}
+
+fn render_components(components: &[ComponentView]) -> String {
+    let mut html = String::new();
+    let mut current_group: Option<&str> = None;
+    for (index, view) in components.iter().enumerate() {
+        let group = view.component.group.as_deref();
+        if index == 0 || group != current_group {
+            if let Some(name) = group {
+                html.push_str(&format!("<h2>{}</h2>\n", escape(name)));
+            }
+            current_group = group;
+        }
+        html.push_str(&render_component(view));
+    }
+    html
+}
+
+fn render_incident(incident: &PublicIncident) -> String {
+    let what = if incident.cause == "latency" { "Elevated response times" } else { "Outage" };
+    let period = match incident.resolved_at {
+        Some(ref resolved_at) => format!("{} – resolved {}", display_time(&incident.started_at), display_time(resolved_at)),
+        None => format!("Since {}", display_time(&incident.started_at)),
+    };
+
+    fill(INCIDENT_TEMPLATE, &[
+        ("title", &escape(&format!("{}: {}", what, incident.component))),
+        ("status", &escape(&incident.status)),
+        ("period", &escape(&period)),
+    ])
+}
+
+fn render_maintenance(maintenance: &PublicMaintenance) -> String {
+    let description = maintenance
+        .description
+        .as_deref()
+        .map(|d| format!("<p>{}</p>", escape(d)))
+        .unwrap_or_default();
+    let period = format!("{} – {}", display_time(&maintenance.starts_at), display_time(&maintenance.ends_at));
+
+    fill(MAINTENANCE_TEMPLATE, &[
+        ("title", &escape(&maintenance.title)),
+        ("description", &description),
+        ("period", &escape(&period)),
+    ])
+}
+
+fn render_post(post: &PublicPost) -> String {
+    let mut summary = vec![format!("Impact: {}", state_label(&post.impact))];
+    if !post.components.is_empty() {
+        summary.push(format!("Affects: {}", post.components.join(", ")));
+    }
+    if let (Some(starts_at), Some(ends_at)) = (&post.scheduled_starts_at, &post.scheduled_ends_at) {
+        summary.push(format!("Scheduled {} – {}", display_time(starts_at), display_time(ends_at)));
+    }
+
+    let updates: String = post
+        .updates
+        .iter()
+        .map(|update| {
+            format!(
+                "<li><strong>{}</strong> – {} <span class=\"meta\">{}</span></li>\n",
+                escape(&state_label(&update.state)),
+                escape(&update.body),
+                escape(&display_time(&update.created_at)),
+            )
+        })
+        .collect();
+
+    fill(POST_TEMPLATE, &[
+        ("id", &escape(&post.id)),
+        ("impact", &escape(&post.impact)),
+        ("title", &escape(&post.title)),
+        ("summary", &escape(&summary.join(" · "))),
+        ("updates", &updates),
+    ])
+}
+
+fn section(heading: &str, body: String) -> String {
+    if body.is_empty() {
+        return String::new();
+    }
+    format!("<section>\n<h2>{}</h2>\n{}</section>", heading, body)
+}
+
+pub fn render_page(view: &StatusPageView) -> String {
+    let logo = view
+        .theme
+        .logo_url
+        .as_deref()
+        .map(|url| format!(r#"<img src="{}" alt="">"#, escape(url)))
+        .unwrap_or_default();
+    let description = view
+        .description
+        .as_deref()
+        .map(|d| format!("<p>{}</p>", escape(d)))
+        .unwrap_or_default();
+    let custom_css = view
+        .theme
+        .custom_css
+        .as_deref()
+        .filter(|css| !css.contains('<'))
+        .map(|css| format!("<style>\n{}\n</style>", css))
+        .unwrap_or_default();
+
+    // Manual posts come first, then what monitoring detected on its own.
+    let posts_of = |kind: &str| -> String {
+        view.posts.iter().filter(|p| p.kind == kind).map(render_post).collect()
+    };
+    let incidents = posts_of("incident") + &view.incidents.iter().map(render_incident).collect::<String>();
+    let maintenance = posts_of("maintenance") + &view.maintenance.iter().map(render_maintenance).collect::<String>();
+
+    fill(PAGE_TEMPLATE, &[
+        ("theme_css", &theme::css_variables(&view.theme)),
+        ("custom_css", &custom_css),
+        ("title", &escape(&view.title)),
+        ("logo", &logo),
+        ("description", &description),
+        ("status", &escape(&view.status)),
+        ("status_label", page_status_label(&view.status)),
+        ("incidents", &section("Incidents", incidents)),
+        ("maintenance", &section("Scheduled maintenance", maintenance)),
+        ("components", &render_components(&view.components)),
+        ("updated_at", &escape(&display_time(&view.updated_at))),
+    ])
+}
+
+/// A one-line message in the page's theme, e.g. after following a
+/// subscription link from an email.
+pub fn render_message(title: &str, theme: &PageTheme, message: &str, page_url: &str) -> String {
+    fill(MESSAGE_TEMPLATE, &[
+        ("theme_css", &theme::css_variables(theme)),
+        ("title", &escape(title)),
+        ("message", &escape(message)),
+        ("page_url", &escape(page_url)),
+    ])
+}
+
+/// The password form of a `password` page, posting back to the page URL.
+pub fn render_login(title: &str, theme: &PageTheme, error: Option<&str>) -> String {
+    let error = error
+        .map(|error| format!("<p class=\"error\">{}</p>", escape(error)))
+        .unwrap_or_default();
+    fill(LOGIN_TEMPLATE, &[
+        ("theme_css", &theme::css_variables(theme)),
+        ("title", &escape(title)),
+        ("error", &error),
+    ])
+}
+
+/// Script that inserts a link showing the page status right after the
+/// `<script>` tag that loads it.
+pub fn embed_script(embed: &EmbedStatus) -> String {
+    let json = |value: &str| serde_json::to_string(value).unwrap_or_default();
+    format!(
+        r#"(function () {{
+  var script = document.currentScript;
+  if (!script) return;
+  var link = document.createElement("a");
+  link.href = {url};
+  link.className = "statusforge-status statusforge-status-" + {status};
+  link.textContent = {label};
+  script.parentNode.insertBefore(link, script.nextSibling);
+}})();
+"#,
+        url = json(&embed.url),
+        status = json(&embed.status),
+        label = json(&embed.label),
+    )
+}
+
+/// Whether the client asked for HTML (browsers do); API clients get JSON.
+pub fn wants_html(accept: Option<&str>) -> bool {
+    accept.is_some_and(|accept| accept.contains("text/html"))
+}
+
+/// Strong ETag of a rendered body.
+pub fn etag(body: &str) -> String {
+    let digest = Sha256::digest(body.as_bytes());
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("\"{}\"", hex)
+}
+
+/// Whether an `If-None-Match` header value matches `etag`.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
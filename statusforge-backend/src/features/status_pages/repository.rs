@@ -0,0 +1,523 @@
+use supabase::types::OrderDirection;
+
+use crate::AppState;
+
+use super::{
+    CreateStatusPage, StatusPage, StatusPageComponent, StatusPagePost, StatusPagePostUpdate, StatusPageSubscriber,
+};
+
+pub async fn get_by_project(state: &AppState, project_id: &str) -> Result<Option<StatusPage>, crate::ApiError> {
+    let mut pages = state
+        .supabase
+        .database()
+        .from("status_pages")
+        .select("*")
+        .eq("project_id", project_id)
+        .execute::<StatusPage>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(pages.pop())
+}
+
+pub async fn get_by_id(state: &AppState, id: &str) -> Result<StatusPage, crate::ApiError> {
+    let mut pages = state
+        .supabase
+        .database()
+        .from("status_pages")
+        .select("*")
+        .eq("id", id)
+        .execute::<StatusPage>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    pages.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn get_by_domain(state: &AppState, domain: &str) -> Result<Option<StatusPage>, crate::ApiError> {
+    let mut pages = state
+        .supabase
+        .database()
+        .from("status_pages")
+        .select("*")
+        .eq("custom_domain", domain)
+        .execute::<StatusPage>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(pages.pop())
+}
+
+pub async fn create(
+    state: &AppState,
+    project_id: &str,
+    page: &CreateStatusPage,
+    domain_token: Option<&str>,
+    password_hash: Option<&str>,
+) -> Result<StatusPage, crate::ApiError> {
+    let mut data = serde_json::to_value(page).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    data["project_id"] = serde_json::Value::String(project_id.to_string());
+    data["domain_token"] = serde_json::json!(domain_token);
+    data["password_hash"] = serde_json::json!(password_hash);
+
+    let mut pages = state
+        .supabase
+        .database()
+        .insert("status_pages")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<StatusPage>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create status page: {:?}", e))
+        })?;
+
+    pages.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update(state: &AppState, id: &str, data: serde_json::Value) -> Result<StatusPage, crate::ApiError> {
+    let mut pages = state
+        .supabase
+        .database()
+        .update("status_pages")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<StatusPage>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to update status page: {:?}", e))
+        })?;
+
+    pages.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("status_pages")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
+
+pub async fn list_components(state: &AppState, status_page_id: &str) -> Result<Vec<StatusPageComponent>, crate::ApiError> {
+    let components = state
+        .supabase
+        .database()
+        .from("status_page_components")
+        .select("*")
+        .eq("status_page_id", status_page_id)
+        .order("position", OrderDirection::Ascending)
+        .execute::<StatusPageComponent>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(components)
+}
+
+/// Components showing `monitor_id`, across all status pages.
+pub async fn list_components_by_monitor(
+    state: &AppState,
+    monitor_id: &str,
+) -> Result<Vec<StatusPageComponent>, crate::ApiError> {
+    let components = state
+        .supabase
+        .database()
+        .from("status_page_components")
+        .select("*")
+        .eq("monitor_id", monitor_id)
+        .execute::<StatusPageComponent>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(components)
+}
+
+pub async fn create_components(
+    state: &AppState,
+    components: Vec<serde_json::Value>,
+) -> Result<Vec<StatusPageComponent>, crate::ApiError> {
+    if components.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let created = state
+        .supabase
+        .database()
+        .insert("status_page_components")
+        .values(components)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<StatusPageComponent>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to save status page components: {:?}", e))
+        })?;
+
+    Ok(created)
+}
+
+pub async fn update_component(
+    state: &AppState,
+    id: &str,
+    data: serde_json::Value,
+) -> Result<StatusPageComponent, crate::ApiError> {
+    let mut components = state
+        .supabase
+        .database()
+        .update("status_page_components")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<StatusPageComponent>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    components.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete_component(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("status_page_components")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
+
+pub async fn list_posts(
+    state: &AppState,
+    status_page_id: &str,
+    kind: Option<&str>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<StatusPagePost>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("status_page_posts")
+        .select("*")
+        .eq("status_page_id", status_page_id);
+
+    if let Some(kind) = kind {
+        query = query.eq("kind", kind);
+    }
+
+    query = query
+        .order("created_at", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(100));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let posts = query
+        .execute::<StatusPagePost>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(posts)
+}
+
+pub async fn get_post(state: &AppState, id: &str) -> Result<StatusPagePost, crate::ApiError> {
+    let mut posts = state
+        .supabase
+        .database()
+        .from("status_page_posts")
+        .select("*")
+        .eq("id", id)
+        .execute::<StatusPagePost>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    posts.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create_post(state: &AppState, data: serde_json::Value) -> Result<StatusPagePost, crate::ApiError> {
+    let mut posts = state
+        .supabase
+        .database()
+        .insert("status_page_posts")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<StatusPagePost>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create post: {:?}", e))
+        })?;
+
+    posts.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update_post(state: &AppState, id: &str, data: serde_json::Value) -> Result<StatusPagePost, crate::ApiError> {
+    let mut posts = state
+        .supabase
+        .database()
+        .update("status_page_posts")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<StatusPagePost>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    posts.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete_post(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("status_page_posts")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
+
+pub async fn list_post_updates(state: &AppState, post_id: &str) -> Result<Vec<StatusPagePostUpdate>, crate::ApiError> {
+    let updates = state
+        .supabase
+        .database()
+        .from("status_page_post_updates")
+        .select("*")
+        .eq("post_id", post_id)
+        .order("created_at", OrderDirection::Descending)
+        .execute::<StatusPagePostUpdate>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(updates)
+}
+
+pub async fn create_post_update(
+    state: &AppState,
+    post_id: &str,
+    post_state: &str,
+    body: &str,
+) -> Result<StatusPagePostUpdate, crate::ApiError> {
+    let mut updates = state
+        .supabase
+        .database()
+        .insert("status_page_post_updates")
+        .values(serde_json::json!({
+            "post_id": post_id,
+            "state": post_state,
+            "body": body,
+        }))
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<StatusPagePostUpdate>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create post update: {:?}", e))
+        })?;
+
+    updates.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn list_subscribers(state: &AppState, status_page_id: &str) -> Result<Vec<StatusPageSubscriber>, crate::ApiError> {
+    let subscribers = state
+        .supabase
+        .database()
+        .from("status_page_subscribers")
+        .select("*")
+        .eq("status_page_id", status_page_id)
+        .order("created_at", OrderDirection::Ascending)
+        .execute::<StatusPageSubscriber>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(subscribers)
+}
+
+pub async fn find_subscriber(
+    state: &AppState,
+    status_page_id: &str,
+    kind: &str,
+    target: &str,
+) -> Result<Option<StatusPageSubscriber>, crate::ApiError> {
+    let mut subscribers = state
+        .supabase
+        .database()
+        .from("status_page_subscribers")
+        .select("*")
+        .eq("status_page_id", status_page_id)
+        .eq("kind", kind)
+        .eq("target", target)
+        .execute::<StatusPageSubscriber>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(subscribers.pop())
+}
+
+pub async fn get_subscriber(state: &AppState, id: &str) -> Result<StatusPageSubscriber, crate::ApiError> {
+    let mut subscribers = state
+        .supabase
+        .database()
+        .from("status_page_subscribers")
+        .select("*")
+        .eq("id", id)
+        .execute::<StatusPageSubscriber>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    subscribers.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create_subscriber(state: &AppState, data: serde_json::Value) -> Result<StatusPageSubscriber, crate::ApiError> {
+    let mut subscribers = state
+        .supabase
+        .database()
+        .insert("status_page_subscribers")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<StatusPageSubscriber>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create subscription: {:?}", e))
+        })?;
+
+    subscribers.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update_subscriber(
+    state: &AppState,
+    id: &str,
+    data: serde_json::Value,
+) -> Result<StatusPageSubscriber, crate::ApiError> {
+    let mut subscribers = state
+        .supabase
+        .database()
+        .update("status_page_subscribers")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<StatusPageSubscriber>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    subscribers.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete_subscriber(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("status_page_subscribers")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
@@ -0,0 +1,406 @@
+use axum::{
+    extract::Path,
+    extract::Query,
+    extract::State,
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Form, Json, Router,
+};
+use serde_json::Value;
+
+use crate::{AppState, ApiError};
+
+use super::access::Viewer;
+use super::{
+    feeds, render, service, subscriptions, BadgeQuery, CreatePost, CreatePostUpdate, CreateStatusPage, ListPostsQuery, PageLogin, SetComponents,
+    StatusPage, Subscribe, SubscriptionTokenQuery, UpdatePost, UpdateStatusPage, VerifyDomainQuery,
+};
+
+/// Public pages may be cached briefly by browsers and CDNs.
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=60";
+
+/// Private and password-protected pages only by the viewer's browser.
+const PRIVATE_CACHE_CONTROL: &str = "private, max-age=60";
+
+async fn get_page(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let page = service::get_page(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(page).unwrap()))
+}
+
+async fn create_page(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreateStatusPage>,
+) -> Result<Json<Value>, ApiError> {
+    let page = service::create_page(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(page).unwrap()))
+}
+
+async fn update_page(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<UpdateStatusPage>,
+) -> Result<Json<Value>, ApiError> {
+    let page = service::update_page(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(page).unwrap()))
+}
+
+async fn delete_page(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_page(&state, &project_id).await?;
+    Ok(Json(serde_json::json!({ "message": "Status page deleted" })))
+}
+
+async fn list_components(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let components = service::list_components(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(components).unwrap()))
+}
+
+async fn set_components(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<SetComponents>,
+) -> Result<Json<Value>, ApiError> {
+    let components = service::set_components(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(components).unwrap()))
+}
+
+async fn get_domain(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let verification = service::get_domain(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(verification).unwrap()))
+}
+
+async fn verify_domain(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<VerifyDomainQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let verification = service::verify_domain(&state, &project_id, query).await?;
+    Ok(Json(serde_json::to_value(verification).unwrap()))
+}
+
+async fn list_posts(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<ListPostsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let posts = service::list_posts(&state, &project_id, query).await?;
+    Ok(Json(serde_json::to_value(posts).unwrap()))
+}
+
+async fn create_post(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreatePost>,
+) -> Result<Json<Value>, ApiError> {
+    let post = service::create_post(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(post).unwrap()))
+}
+
+async fn get_post(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let post = service::get_post(&state, &id).await?;
+    Ok(Json(serde_json::to_value(post).unwrap()))
+}
+
+async fn update_post(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<UpdatePost>,
+) -> Result<Json<Value>, ApiError> {
+    let post = service::update_post(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(post).unwrap()))
+}
+
+async fn delete_post(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_post(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Post deleted" })))
+}
+
+async fn add_post_update(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<CreatePostUpdate>,
+) -> Result<Json<Value>, ApiError> {
+    let post = service::add_post_update(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(post).unwrap()))
+}
+
+async fn list_subscribers(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let subscribers = service::list_subscribers(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(subscribers).unwrap()))
+}
+
+async fn delete_subscriber(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_subscriber(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Subscriber deleted" })))
+}
+
+async fn subscribe(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    Json(data): Json<Subscribe>,
+) -> Result<Json<Value>, ApiError> {
+    let message = service::subscribe(&state, &slug, &viewer, data).await?;
+    Ok(Json(serde_json::json!({ "message": message })))
+}
+
+/// Subscription links are followed from emails: HTML for browsers, JSON
+/// otherwise.
+fn subscription_response(state: &AppState, headers: &HeaderMap, page: &StatusPage, slug: &str, message: &str) -> Response {
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+    if render::wants_html(accept) {
+        let page_url = subscriptions::page_url(&state.config.public_url, slug, page);
+        let html = render::render_message(&page.title, &page.theme, message, &page_url);
+        return ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], html).into_response();
+    }
+    Json(serde_json::json!({ "message": message })).into_response()
+}
+
+async fn confirm_subscription(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<SubscriptionTokenQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::confirm_subscription(&state, &slug, &query.token).await?;
+    Ok(subscription_response(&state, &headers, &page, &slug, "Your subscription is confirmed."))
+}
+
+async fn unsubscribe(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<SubscriptionTokenQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::unsubscribe(&state, &slug, &query.token).await?;
+    Ok(subscription_response(&state, &headers, &page, &slug, "You have been unsubscribed."))
+}
+
+/// Response with caching headers, or `304 Not Modified` when the client
+/// already has this body. Only `public` responses may be stored by shared
+/// caches.
+fn cached(request: &HeaderMap, public: bool, content_type: &'static str, body: String) -> Response {
+    let etag = render::etag(&body);
+    let headers: [(HeaderName, String); 3] = if public {
+        [
+            (header::CACHE_CONTROL, PUBLIC_CACHE_CONTROL.to_string()),
+            (header::VARY, "Accept".to_string()),
+            (header::ETAG, etag.clone()),
+        ]
+    } else {
+        [
+            (header::CACHE_CONTROL, PRIVATE_CACHE_CONTROL.to_string()),
+            (header::VARY, "Accept, Cookie, Authorization".to_string()),
+            (header::ETAG, etag.clone()),
+        ]
+    };
+
+    let not_modified = request
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| render::etag_matches(v, &etag));
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    (headers, [(header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+/// The password form, answered with `401` so the page is not mistaken for
+/// the status page itself.
+fn login_form(page: &StatusPage, error: Option<&str>) -> Response {
+    let html = render::render_login(&page.title, &page.theme, error);
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8"), (header::CACHE_CONTROL, "no-store")],
+        html,
+    )
+        .into_response()
+}
+
+/// HTML for browsers, JSON otherwise. Browsers without a session on a
+/// password-protected page get the password form.
+async fn public_status(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+    let html = render::wants_html(accept);
+    let page = match service::visible_page(&state, &slug, &viewer).await {
+        Ok(page) => page,
+        Err(ApiError::Unauthorized(message)) if html => {
+            return match service::password_page(&state, &slug).await? {
+                Some(page) => Ok(login_form(&page, None)),
+                None => Err(ApiError::Unauthorized(message)),
+            };
+        }
+        Err(e) => return Err(e),
+    };
+
+    let public = page.visibility == "public";
+    if html {
+        let view = service::page_view(&state, page).await?;
+        return Ok(cached(&headers, public, "text/html; charset=utf-8", render::render_page(&view)));
+    }
+    let status = service::public_status(&state, page).await?;
+    Ok(cached(&headers, public, "application/json", serde_json::to_string(&status).unwrap()))
+}
+
+/// Password form submissions: a session cookie and back to the page, or the
+/// form again.
+async fn login(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    headers: HeaderMap,
+    Form(data): Form<PageLogin>,
+) -> Result<Response, ApiError> {
+    match service::login(&state, &slug, &data.password).await {
+        Ok((page, cookie)) => {
+            let page_url = subscriptions::page_url(&state.config.public_url, &slug, &page);
+            Ok((StatusCode::SEE_OTHER, [(header::LOCATION, page_url), (header::SET_COOKIE, cookie)]).into_response())
+        }
+        Err(ApiError::Unauthorized(message)) => {
+            let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+            match service::password_page(&state, &slug).await? {
+                Some(page) if render::wants_html(accept) => Ok(login_form(&page, Some(&message))),
+                _ => Err(ApiError::Unauthorized(message)),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+async fn rss_feed(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::visible_page(&state, &slug, &viewer).await?;
+    let public = page.visibility == "public";
+    let feed = service::feed(&state, &slug, page).await?;
+    Ok(cached(&headers, public, "application/rss+xml; charset=utf-8", feeds::rss(&feed)))
+}
+
+async fn atom_feed(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::visible_page(&state, &slug, &viewer).await?;
+    let public = page.visibility == "public";
+    let feed = service::feed(&state, &slug, page).await?;
+    Ok(cached(&headers, public, "application/atom+xml; charset=utf-8", feeds::atom(&feed)))
+}
+
+async fn page_badge(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<BadgeQuery>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::visible_page(&state, &slug, &viewer).await?;
+    let public = page.visibility == "public";
+    let svg = service::page_badge(&state, page, query).await?;
+    Ok(cached(&headers, public, "image/svg+xml", svg))
+}
+
+async fn monitor_badge(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<BadgeQuery>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::component_page(&state, &id, &viewer).await?;
+    let public = page.visibility == "public";
+    let svg = service::monitor_badge(&state, &id, query).await?;
+    Ok(cached(&headers, public, "image/svg+xml", svg))
+}
+
+/// Embeds are fetched from other origins.
+fn cross_origin(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+    response
+}
+
+async fn embed_json(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::visible_page(&state, &slug, &viewer).await?;
+    let public = page.visibility == "public";
+    let embed = service::embed_status(&state, &slug, page).await?;
+    Ok(cross_origin(cached(&headers, public, "application/json", serde_json::to_string(&embed).unwrap())))
+}
+
+async fn embed_js(
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    viewer: Viewer,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let page = service::visible_page(&state, &slug, &viewer).await?;
+    let public = page.visibility == "public";
+    let embed = service::embed_status(&state, &slug, page).await?;
+    Ok(cross_origin(cached(&headers, public, "text/javascript; charset=utf-8", render::embed_script(&embed))))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/status-page",
+            get(get_page).post(create_page).put(update_page).delete(delete_page),
+        )
+        .route("/projects/{project_id}/status-page/components", get(list_components).put(set_components))
+        .route("/projects/{project_id}/status-page/domain", get(get_domain))
+        .route("/projects/{project_id}/status-page/domain/verify", post(verify_domain))
+        .route("/projects/{project_id}/status-page/posts", get(list_posts).post(create_post))
+        .route("/status-page-posts/{id}", get(get_post).put(update_post).delete(delete_post))
+        .route("/status-page-posts/{id}/updates", post(add_post_update))
+        .route("/projects/{project_id}/status-page/subscribers", get(list_subscribers))
+        .route("/status-page-subscribers/{id}", delete(delete_subscriber))
+        .route("/status/{slug}", get(public_status).post(login))
+        .route("/status/{slug}/feed.rss", get(rss_feed))
+        .route("/status/{slug}/feed.atom", get(atom_feed))
+        .route("/status/{slug}/badge.svg", get(page_badge))
+        .route("/status/{slug}/embed.json", get(embed_json))
+        .route("/status/{slug}/embed.js", get(embed_js))
+        .route("/monitors/{id}/badge.svg", get(monitor_badge))
+        .route("/status/{slug}/subscriptions", post(subscribe))
+        .route("/status/{slug}/subscriptions/confirm", get(confirm_subscription))
+        // POST is the one-click unsubscribe of `List-Unsubscribe-Post`.
+        .route("/status/{slug}/subscriptions/unsubscribe", get(unsubscribe).post(unsubscribe))
+}
@@ -0,0 +1,1074 @@
+use std::future::Future;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::features::auth;
+use crate::features::incidents::{self, Incident};
+use crate::features::maintenance::{self, schedule, MaintenanceWindow};
+use crate::features::monitors::{self, Monitor, MonitorResult};
+use crate::features::organizations;
+use crate::features::projects;
+use crate::shared::mailer;
+use crate::shared::utils::parse_timestamp;
+use crate::AppState;
+
+use super::access::{self, Viewer};
+use super::subscriptions::{self, Notification, PageLink};
+use super::{badge, domains, feeds, posts, render, status, theme};
+use super::{
+    repository, BadgeQuery, ComponentInput, ComponentView, CreatePost, CreatePostUpdate, CreateStatusPage, DomainVerification,
+    EmbedStatus, ListPostsQuery, PostWithUpdates, PublicComponent, PublicIncident, PublicMaintenance, PublicPost, PublicPostUpdate,
+    PublicStatusPage, SetComponents, StatusPage, StatusPageComponent, StatusPagePost, StatusPagePostUpdate,
+    StatusPageSubscriber, StatusPageView, Subscribe, TxtRecord, UpdatePost, UpdateStatusPage, VerifyDomainQuery,
+};
+
+pub const MAX_COMPONENTS: usize = 100;
+
+pub const MAX_NAME_LEN: usize = 100;
+
+pub const RECENT_INCIDENT_DAYS: i64 = 7;
+
+pub const UPCOMING_MAINTENANCE_DAYS: i64 = 14;
+
+const MAX_MAINTENANCE_ITEMS: usize = 10;
+
+/// Posts shown on the public page at most.
+const MAX_PUBLIC_POSTS: usize = 20;
+
+/// Results fetched per region when computing a component status.
+const RESULTS_PER_REGION: usize = 3;
+
+/// Lowercases a custom domain and checks it is a plain hostname
+/// (`status.example.com`), without scheme, port or path.
+pub fn normalize_domain(domain: &str) -> Result<String, String> {
+    let domain = domain.trim().trim_end_matches('.').to_ascii_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    let valid_label = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    if domain.len() > 253 || labels.len() < 2 || !labels.iter().all(valid_label) {
+        return Err(format!("Invalid custom domain: {}", domain));
+    }
+    Ok(domain)
+}
+
+fn validate_name(field: &str, name: Option<&str>) -> Result<(), crate::ApiError> {
+    match name {
+        Some(name) if name.trim().is_empty() => {
+            Err(crate::ApiError::BadRequest(format!("{} cannot be empty", field)))
+        }
+        Some(name) if name.chars().count() > MAX_NAME_LEN => Err(crate::ApiError::BadRequest(
+            format!("{} cannot be longer than {} characters", field, MAX_NAME_LEN)
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Components must reference distinct monitors of the page's project.
+pub fn validate_components(components: &[ComponentInput], monitors: &[Monitor]) -> Result<(), crate::ApiError> {
+    if components.len() > MAX_COMPONENTS {
+        return Err(crate::ApiError::BadRequest(
+            format!("A status page cannot have more than {} components", MAX_COMPONENTS)
+        ));
+    }
+
+    for (index, component) in components.iter().enumerate() {
+        if !monitors.iter().any(|m| m.id == component.monitor_id) {
+            return Err(crate::ApiError::BadRequest(
+                format!("Monitor {} does not belong to this project", component.monitor_id)
+            ));
+        }
+        if components[..index].iter().any(|c| c.monitor_id == component.monitor_id) {
+            return Err(crate::ApiError::BadRequest(
+                format!("Monitor {} is listed more than once", component.monitor_id)
+            ));
+        }
+        validate_name("display_name", component.display_name.as_deref())?;
+        validate_name("group_name", component.group_name.as_deref())?;
+    }
+    Ok(())
+}
+
+/// The listed components followed by every other monitor matching
+/// `tag_selector`, in name order.
+pub fn select_components(
+    components: Vec<ComponentInput>,
+    tag_selector: Option<&str>,
+    monitors: &[Monitor],
+) -> Result<Vec<ComponentInput>, crate::ApiError> {
+    let Some(selector) = tag_selector else {
+        return Ok(components);
+    };
+    let filters = monitors::tags::parse_selector(selector)?;
+    if filters.is_empty() {
+        return Err(crate::ApiError::BadRequest("tag_selector cannot be empty".to_string()));
+    }
+
+    let mut matching: Vec<&Monitor> = monitors
+        .iter()
+        .filter(|m| monitors::tags::matches_selector(&m.tags, &filters))
+        .filter(|m| !components.iter().any(|c| c.monitor_id == m.id))
+        .collect();
+    matching.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut selected = components;
+    selected.extend(matching.into_iter().map(|m| ComponentInput {
+        monitor_id: m.id.clone(),
+        display_name: None,
+        group_name: None,
+        position: None,
+    }));
+    Ok(selected)
+}
+
+/// The public view of one component; `results` are the monitor's newest
+/// results, newest first.
+pub fn public_component(
+    component: &StatusPageComponent,
+    monitor: &Monitor,
+    monitor_group: Option<&str>,
+    results: &[MonitorResult],
+    in_maintenance: bool,
+    now: DateTime<Utc>,
+) -> PublicComponent {
+    let latest = status::latest_per_region(results, status::fresh_since(now, monitor.interval_seconds));
+    let statuses: Vec<&str> = latest.iter().map(|r| r.status.as_str()).collect();
+
+    PublicComponent {
+        id: component.id.clone(),
+        name: component.display_name.clone().unwrap_or_else(|| monitor.name.clone()),
+        group: component.group_name.clone().or_else(|| monitor_group.map(str::to_string)),
+        status: status::component_status(&statuses, monitor.confirmation.down_quorum, in_maintenance).to_string(),
+        checked_at: latest.first().map(|r| r.created_at.clone()),
+    }
+}
+
+pub async fn get_page(state: &AppState, project_id: &str) -> Result<StatusPage, crate::ApiError> {
+    repository::get_by_project(state, project_id).await?.ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create_page(
+    state: &AppState,
+    project_id: &str,
+    data: CreateStatusPage,
+) -> Result<StatusPage, crate::ApiError> {
+    if data.title.trim().is_empty() {
+        return Err(crate::ApiError::BadRequest("Status page title cannot be empty".to_string()));
+    }
+    if repository::get_by_project(state, project_id).await?.is_some() {
+        return Err(crate::ApiError::BadRequest("This project already has a status page".to_string()));
+    }
+
+    let custom_domain = data
+        .custom_domain
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+        .map(normalize_domain)
+        .transpose()
+        .map_err(crate::ApiError::BadRequest)?;
+
+    let page_theme = data.theme.clone().unwrap_or_default();
+    theme::validate_theme(&page_theme).map_err(crate::ApiError::BadRequest)?;
+
+    let visibility = data.visibility.clone().unwrap_or_else(|| "public".to_string());
+    access::validate_visibility(&visibility).map_err(crate::ApiError::BadRequest)?;
+    let password_hash = match data.password.as_deref() {
+        Some(password) => Some(new_password_hash(password)?),
+        None if visibility == "password" => {
+            return Err(crate::ApiError::BadRequest("Password-protected status pages need a password".to_string()));
+        }
+        None => None,
+    };
+    let allowed_ips = access::normalize_allowed_ips(data.allowed_ips.as_deref().unwrap_or_default())
+        .map_err(crate::ApiError::BadRequest)?;
+
+    let domain_token = custom_domain.as_ref().map(|_| domains::new_token());
+    let normalized = CreateStatusPage {
+        custom_domain,
+        enabled: Some(data.enabled.unwrap_or(true)),
+        theme: Some(page_theme),
+        visibility: Some(visibility),
+        allowed_ips: Some(allowed_ips),
+        ..data
+    };
+    repository::create(state, project_id, &normalized, domain_token.as_deref(), password_hash.as_deref()).await
+}
+
+fn new_password_hash(password: &str) -> Result<String, crate::ApiError> {
+    access::validate_password(password).map_err(crate::ApiError::BadRequest)?;
+    access::hash_password(password).map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })
+}
+
+/// An empty `custom_domain` removes the domain.
+pub async fn update_page(
+    state: &AppState,
+    project_id: &str,
+    data: UpdateStatusPage,
+) -> Result<StatusPage, crate::ApiError> {
+    if let Some(ref title) = data.title
+        && title.trim().is_empty()
+    {
+        return Err(crate::ApiError::BadRequest("Status page title cannot be empty".to_string()));
+    }
+    if let Some(ref page_theme) = data.theme {
+        theme::validate_theme(page_theme).map_err(crate::ApiError::BadRequest)?;
+    }
+    if let Some(ref visibility) = data.visibility {
+        access::validate_visibility(visibility).map_err(crate::ApiError::BadRequest)?;
+    }
+
+    let page = get_page(state, project_id).await?;
+    if data.visibility.as_deref() == Some("password") && data.password.is_none() && page.password_hash.is_none() {
+        return Err(crate::ApiError::BadRequest("Password-protected status pages need a password".to_string()));
+    }
+
+    let mut update = serde_json::to_value(&data).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    if let Some(ref domain) = data.custom_domain {
+        let domain = match domain.trim() {
+            "" => None,
+            domain => Some(normalize_domain(domain).map_err(crate::ApiError::BadRequest)?),
+        };
+        // A new domain has to be verified again.
+        if domain != page.custom_domain {
+            update["domain_token"] = serde_json::json!(domain.as_ref().map(|_| domains::new_token()));
+            update["domain_verified_at"] = serde_json::Value::Null;
+        }
+        update["custom_domain"] = serde_json::json!(domain);
+    }
+    if let Some(ref password) = data.password {
+        update["password_hash"] = serde_json::json!(new_password_hash(password)?);
+    } else if data.visibility.as_deref().is_some_and(|visibility| visibility != "password") {
+        // Going back to password protection asks for a new password.
+        update["password_hash"] = serde_json::Value::Null;
+    }
+    if let Some(ref allowed_ips) = data.allowed_ips {
+        update["allowed_ips"] = serde_json::json!(access::normalize_allowed_ips(allowed_ips).map_err(crate::ApiError::BadRequest)?);
+    }
+
+    repository::update(state, &page.id, update).await
+}
+
+pub async fn delete_page(state: &AppState, project_id: &str) -> Result<(), crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    repository::delete(state, &page.id).await
+}
+
+pub fn domain_verification(page: &StatusPage) -> Option<DomainVerification> {
+    let domain = page.custom_domain.as_deref()?;
+    let token = page.domain_token.as_deref()?;
+    Some(DomainVerification {
+        domain: domain.to_string(),
+        verified: page.domain_verified_at.is_some(),
+        verified_at: page.domain_verified_at.clone(),
+        txt_record: TxtRecord {
+            name: domains::txt_record_name(domain),
+            value: domains::txt_record_value(token),
+        },
+        well_known_url: domains::well_known_url(domain),
+        well_known_content: token.to_string(),
+    })
+}
+
+pub async fn get_domain(state: &AppState, project_id: &str) -> Result<DomainVerification, crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    domain_verification(&page).ok_or(crate::ApiError::NotFound)
+}
+
+/// Checks the TXT record and/or the well-known file and marks the domain
+/// verified when either contains the page's token.
+pub async fn verify_domain(
+    state: &AppState,
+    project_id: &str,
+    query: VerifyDomainQuery,
+) -> Result<DomainVerification, crate::ApiError> {
+    let methods: Vec<&str> = match query.method.as_deref() {
+        None => domains::VERIFICATION_METHODS.to_vec(),
+        Some(method) if domains::VERIFICATION_METHODS.contains(&method) => vec![method],
+        Some(method) => {
+            return Err(crate::ApiError::BadRequest(format!(
+                "Invalid verification method: {}. Must be one of: {:?}",
+                method,
+                domains::VERIFICATION_METHODS
+            )));
+        }
+    };
+
+    let page = get_page(state, project_id).await?;
+    let verification = domain_verification(&page)
+        .ok_or_else(|| crate::ApiError::BadRequest("The status page has no custom domain".to_string()))?;
+    if verification.verified {
+        return Ok(verification);
+    }
+
+    let mut failures = Vec::new();
+    for method in methods {
+        let outcome = match method {
+            "dns" => domains::check_dns(&state.config.dns_over_https_url, &verification.domain, &verification.well_known_content).await,
+            _ => domains::check_http(&verification.well_known_url, &verification.well_known_content).await,
+        };
+        match outcome {
+            Ok(true) => {
+                let verified = repository::update(
+                    state,
+                    &page.id,
+                    serde_json::json!({ "domain_verified_at": Utc::now().to_rfc3339() }),
+                ).await?;
+                return domain_verification(&verified).ok_or(crate::ApiError::InternalServerError);
+            }
+            Ok(false) if method == "dns" => failures.push(format!("TXT record {} not found", verification.txt_record.name)),
+            Ok(false) => failures.push(format!("{} does not contain the token", verification.well_known_url)),
+            Err(e) => failures.push(e),
+        }
+    }
+
+    Err(crate::ApiError::BadRequest(format!("Domain verification failed: {}", failures.join("; "))))
+}
+
+pub async fn list_components(state: &AppState, project_id: &str) -> Result<Vec<StatusPageComponent>, crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    repository::list_components(state, &page.id).await
+}
+
+pub async fn set_components(
+    state: &AppState,
+    project_id: &str,
+    data: SetComponents,
+) -> Result<Vec<StatusPageComponent>, crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    let monitors = monitors::repository::list_by_project(state, project_id).await?;
+    let data = SetComponents {
+        components: select_components(data.components, data.tag_selector.as_deref(), &monitors)?,
+        tag_selector: None,
+    };
+    validate_components(&data.components, &monitors)?;
+
+    // Components are updated in place so their ids (referenced by posts)
+    // survive reordering and renaming.
+    let existing = repository::list_components(state, &page.id).await?;
+    for component in &existing {
+        if !data.components.iter().any(|c| c.monitor_id == component.monitor_id) {
+            repository::delete_component(state, &component.id).await?;
+        }
+    }
+
+    let mut saved = Vec::with_capacity(data.components.len());
+    let mut new_rows = Vec::new();
+    for (index, component) in data.components.into_iter().enumerate() {
+        let row = serde_json::json!({
+            "display_name": component.display_name.map(|n| n.trim().to_string()),
+            "group_name": component.group_name.map(|n| n.trim().to_string()),
+            "position": component.position.unwrap_or(index as i32),
+        });
+        match existing.iter().find(|c| c.monitor_id == component.monitor_id) {
+            Some(current) => saved.push(repository::update_component(state, &current.id, row).await?),
+            None => {
+                let mut row = row;
+                row["status_page_id"] = serde_json::Value::String(page.id.clone());
+                row["monitor_id"] = serde_json::Value::String(component.monitor_id);
+                new_rows.push(row);
+            }
+        }
+    }
+    saved.extend(repository::create_components(state, new_rows).await?);
+
+    saved.sort_by_key(|c| c.position);
+    Ok(saved)
+}
+
+pub async fn list_posts(
+    state: &AppState,
+    project_id: &str,
+    query: ListPostsQuery,
+) -> Result<Vec<StatusPagePost>, crate::ApiError> {
+    if let Some(ref kind) = query.kind {
+        posts::validate_kind(kind).map_err(crate::ApiError::BadRequest)?;
+    }
+    let page = get_page(state, project_id).await?;
+    repository::list_posts(state, &page.id, query.kind.as_deref(), query.limit, query.offset).await
+}
+
+pub async fn get_post(state: &AppState, id: &str) -> Result<PostWithUpdates, crate::ApiError> {
+    let post = repository::get_post(state, id).await?;
+    let updates = repository::list_post_updates(state, id).await?;
+    Ok(PostWithUpdates { post, updates })
+}
+
+/// Creates a post together with its first update.
+pub async fn create_post(
+    state: &AppState,
+    project_id: &str,
+    data: CreatePost,
+) -> Result<PostWithUpdates, crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    let components = repository::list_components(state, &page.id).await?;
+    posts::validate_new_post(&data, &components).map_err(crate::ApiError::BadRequest)?;
+
+    let kind = data.kind.unwrap_or_else(|| "incident".to_string());
+    let post_state = data.state.unwrap_or_else(|| posts::initial_state(&kind).to_string());
+    let resolved_at = posts::is_closed(&kind, &post_state).then(|| Utc::now().to_rfc3339());
+
+    let post = repository::create_post(state, serde_json::json!({
+        "status_page_id": page.id,
+        "kind": kind,
+        "title": data.title.trim(),
+        "impact": data.impact.unwrap_or_else(|| "none".to_string()),
+        "state": post_state,
+        "component_ids": data.component_ids.unwrap_or_default(),
+        "scheduled_starts_at": data.scheduled_starts_at,
+        "scheduled_ends_at": data.scheduled_ends_at,
+        "resolved_at": resolved_at,
+    })).await?;
+    let update = repository::create_post_update(state, &post.id, &post.state, data.body.trim()).await?;
+    spawn_notification(notify_post(state.clone(), post.id.clone(), false));
+
+    Ok(PostWithUpdates { post, updates: vec![update] })
+}
+
+pub async fn update_post(state: &AppState, id: &str, data: UpdatePost) -> Result<StatusPagePost, crate::ApiError> {
+    let post = repository::get_post(state, id).await?;
+
+    let validate = || -> Result<(), String> {
+        if let Some(ref title) = data.title {
+            posts::validate_title(title)?;
+        }
+        if let Some(ref impact) = data.impact {
+            posts::validate_impact(impact)?;
+        }
+        posts::validate_schedule(
+            &post.kind,
+            data.scheduled_starts_at.as_deref().or(post.scheduled_starts_at.as_deref()),
+            data.scheduled_ends_at.as_deref().or(post.scheduled_ends_at.as_deref()),
+        )
+    };
+    validate().map_err(crate::ApiError::BadRequest)?;
+
+    if let Some(ref component_ids) = data.component_ids {
+        let components = repository::list_components(state, &post.status_page_id).await?;
+        posts::validate_components(component_ids, &components).map_err(crate::ApiError::BadRequest)?;
+    }
+
+    let update = serde_json::to_value(&data).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    repository::update_post(state, id, update).await
+}
+
+pub async fn delete_post(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete_post(state, id).await
+}
+
+/// Appends an update to the timeline and moves the post to its state;
+/// reaching `resolved` / `completed` stamps `resolved_at`, leaving it clears it.
+pub async fn add_post_update(
+    state: &AppState,
+    id: &str,
+    data: CreatePostUpdate,
+) -> Result<PostWithUpdates, crate::ApiError> {
+    let post = repository::get_post(state, id).await?;
+    let post_state = data.state.unwrap_or_else(|| post.state.clone());
+    posts::validate_state(&post.kind, &post_state).map_err(crate::ApiError::BadRequest)?;
+    posts::validate_body(&data.body).map_err(crate::ApiError::BadRequest)?;
+
+    let was_closed = posts::is_closed(&post.kind, &post.state);
+    let resolved_at = match (was_closed, posts::is_closed(&post.kind, &post_state)) {
+        (false, true) => Some(serde_json::json!(Utc::now().to_rfc3339())),
+        (true, false) => Some(serde_json::Value::Null),
+        _ => None,
+    };
+
+    repository::create_post_update(state, id, &post_state, data.body.trim()).await?;
+    let mut changes = serde_json::json!({ "state": post_state });
+    if let Some(resolved_at) = resolved_at {
+        changes["resolved_at"] = resolved_at;
+    }
+    repository::update_post(state, id, changes).await?;
+    spawn_notification(notify_post(state.clone(), id.to_string(), true));
+
+    get_post(state, id).await
+}
+
+pub fn public_post(post: &StatusPagePost, updates: &[StatusPagePostUpdate], components: &[PublicComponent]) -> PublicPost {
+    PublicPost {
+        id: post.id.clone(),
+        kind: post.kind.clone(),
+        title: post.title.clone(),
+        impact: post.impact.clone(),
+        state: post.state.clone(),
+        components: post
+            .component_ids
+            .iter()
+            .filter_map(|id| components.iter().find(|c| &c.id == id).map(|c| c.name.clone()))
+            .collect(),
+        scheduled_starts_at: post.scheduled_starts_at.clone(),
+        scheduled_ends_at: post.scheduled_ends_at.clone(),
+        resolved_at: post.resolved_at.clone(),
+        created_at: post.created_at.clone(),
+        updates: updates
+            .iter()
+            .map(|u| PublicPostUpdate {
+                state: u.state.clone(),
+                body: u.body.clone(),
+                created_at: u.created_at.clone(),
+            })
+            .collect(),
+    }
+}
+
+async fn public_posts(
+    state: &AppState,
+    page: &StatusPage,
+    components: &[PublicComponent],
+    now: DateTime<Utc>,
+) -> Result<Vec<PublicPost>, crate::ApiError> {
+    let since = now - Duration::days(RECENT_INCIDENT_DAYS);
+    let visible: Vec<StatusPagePost> = repository::list_posts(state, &page.id, None, Some(100), None)
+        .await?
+        .into_iter()
+        .filter(|post| posts::is_visible(post, since, now))
+        .take(MAX_PUBLIC_POSTS)
+        .collect();
+
+    let mut public = Vec::with_capacity(visible.len());
+    for post in &visible {
+        let updates = repository::list_post_updates(state, &post.id).await?;
+        public.push(public_post(post, &updates, components));
+    }
+    Ok(public)
+}
+
+/// Page status from components, escalated by open incident posts.
+fn page_status(components: &[&PublicComponent], posts: &[PublicPost]) -> &'static str {
+    let statuses: Vec<&str> = components.iter().map(|c| c.status.as_str()).collect();
+    let open_impacts: Vec<&str> = posts
+        .iter()
+        .filter(|p| p.kind == "incident" && !posts::is_closed(&p.kind, &p.state))
+        .map(|p| p.impact.as_str())
+        .collect();
+    posts::escalate(status::page_status(&statuses), &open_impacts)
+}
+
+async fn enabled_page(state: &AppState, slug: &str) -> Result<StatusPage, crate::ApiError> {
+    let project = projects::repository::get_by_slug(state, slug).await?;
+    repository::get_by_project(state, &project.id)
+        .await?
+        .filter(|page| page.enabled)
+        .ok_or(crate::ApiError::NotFound)
+}
+
+/// An enabled page the viewer may see. `Unauthorized` means they have to
+/// enter the password or sign in first, `Forbidden` that signing in did not
+/// help.
+pub async fn visible_page(state: &AppState, slug: &str, viewer: &Viewer) -> Result<StatusPage, crate::ApiError> {
+    let page = enabled_page(state, slug).await?;
+    match page.visibility.as_str() {
+        "public" => Ok(page),
+        "password" if viewer.has_session(&state.config.signing_secret, &page, Utc::now()) => Ok(page),
+        "password" => Err(crate::ApiError::Unauthorized("This status page is password protected".to_string())),
+        _ => {
+            if viewer.ip.is_some_and(|ip| access::ip_allowed(&page.allowed_ips, ip)) {
+                return Ok(page);
+            }
+            let Some(ref token) = viewer.access_token else {
+                return Err(crate::ApiError::Unauthorized("Sign in to view this status page".to_string()));
+            };
+            let user_id = auth::validate_jwt(state, token).await?;
+            let project = projects::repository::get_by_id(state, &page.project_id).await?;
+            if !organizations::repository::is_member(state, &project.organization_id, &user_id).await? {
+                return Err(crate::ApiError::Forbidden("This status page is only visible to members of its organization".to_string()));
+            }
+            Ok(page)
+        }
+    }
+}
+
+/// The page that lists `monitor_id` as a component, if the viewer may see
+/// it. Monitors on no page are not shown to anyone.
+pub async fn component_page(state: &AppState, monitor_id: &str, viewer: &Viewer) -> Result<StatusPage, crate::ApiError> {
+    let components = repository::list_components_by_monitor(state, monitor_id).await?;
+    if components.is_empty() {
+        return Err(crate::ApiError::NotFound);
+    }
+    let monitor = monitors::repository::get_by_id(state, monitor_id).await?;
+    let project = projects::repository::get_by_id(state, &monitor.project_id).await?;
+    let page = visible_page(state, &project.slug, viewer).await?;
+    if !components.iter().any(|component| component.status_page_id == page.id) {
+        return Err(crate::ApiError::NotFound);
+    }
+    Ok(page)
+}
+
+/// The enabled page behind `slug` if it asks for a password, to show the
+/// password form on.
+pub async fn password_page(state: &AppState, slug: &str) -> Result<Option<StatusPage>, crate::ApiError> {
+    let page = enabled_page(state, slug).await?;
+    Ok((page.visibility == "password").then_some(page))
+}
+
+/// Checks the password of a `password` page; returns the page and the
+/// `Set-Cookie` value of a new session.
+pub async fn login(state: &AppState, slug: &str, password: &str) -> Result<(StatusPage, String), crate::ApiError> {
+    let page = password_page(state, slug).await?.ok_or(crate::ApiError::NotFound)?;
+    let valid = page
+        .password_hash
+        .as_deref()
+        .is_some_and(|hash| access::verify_password(hash, password));
+    if !valid {
+        return Err(crate::ApiError::Unauthorized("Wrong password".to_string()));
+    }
+
+    let secure = subscriptions::page_url(&state.config.public_url, slug, &page).starts_with("https://");
+    let cookie = access::session_cookie(&state.config.signing_secret, &page, Utc::now(), secure);
+    Ok((page, cookie))
+}
+
+/// The page's components in display order with their monitors; components
+/// whose monitor no longer exists are skipped.
+async fn current_components(
+    state: &AppState,
+    page: &StatusPage,
+    windows: &[MaintenanceWindow],
+    now: DateTime<Utc>,
+) -> Result<Vec<(Monitor, PublicComponent)>, crate::ApiError> {
+    let components = repository::list_components(state, &page.id).await?;
+    let monitors = monitors::repository::list_by_project(state, &page.project_id).await?;
+    let groups = monitors::repository::list_groups(state, &page.project_id).await?;
+
+    let mut current = Vec::with_capacity(components.len());
+    for component in &components {
+        let Some(monitor) = monitors.iter().find(|m| m.id == component.monitor_id) else {
+            continue;
+        };
+        let limit = (monitor.confirmation.regions.len().max(1) * RESULTS_PER_REGION).min(100) as u32;
+        let results = monitors::repository::list_results(state, &monitor.id, None, None, None, Some(limit), None).await?;
+        let group = monitor
+            .group_id
+            .as_deref()
+            .and_then(|id| groups.iter().find(|g| g.id == id))
+            .map(|g| g.name.as_str());
+        let in_maintenance = windows.iter().any(|w| maintenance::covers(w, &monitor.id, now));
+
+        let public = public_component(component, monitor, group, &results, in_maintenance, now);
+        current.push((monitor.clone(), public));
+    }
+    Ok(current)
+}
+
+/// Subscribes to a page the viewer may see. Email addresses get a
+/// confirmation link and are notified only once they follow it; the response
+/// is the same whether or not the address was already subscribed.
+pub async fn subscribe(state: &AppState, slug: &str, viewer: &Viewer, data: Subscribe) -> Result<String, crate::ApiError> {
+    let page = visible_page(state, slug, viewer).await?;
+    let components = repository::list_components(state, &page.id).await?;
+    let (kind, target) = subscriptions::validate_subscribe(&data, &components).map_err(crate::ApiError::BadRequest)?;
+    let component_ids = data.component_ids.unwrap_or_default();
+    let confirm_message = match kind {
+        "email" => "Check your inbox to confirm the subscription",
+        _ => "Fetch the confirm_url sent to your webhook to confirm the subscription",
+    }
+    .to_string();
+
+    let subscriber = match repository::find_subscriber(state, &page.id, kind, &target).await? {
+        Some(existing) if existing.confirmed_at.is_some() => return Ok(confirm_message),
+        Some(existing) => {
+            repository::update_subscriber(state, &existing.id, serde_json::json!({ "component_ids": component_ids }))
+                .await?
+        }
+        None => {
+            repository::create_subscriber(state, serde_json::json!({
+                "status_page_id": page.id,
+                "kind": kind,
+                "target": target,
+                "component_ids": component_ids,
+            }))
+            .await?
+        }
+    };
+
+    let link = page_link(state, &page).await?;
+    let confirm_url = subscriptions::confirm_url(&link.url, &state.config.signing_secret, &subscriber.id);
+    if kind == "webhook" {
+        let payload = subscriptions::webhook_confirmation_payload(&link, &confirm_url);
+        subscriptions::post_webhook(&subscriber.target, &payload, state.config.allow_private_webhooks)
+            .await
+            .map_err(crate::ApiError::BadRequest)?;
+        return Ok(confirm_message);
+    }
+
+    mailer::from_config(&state.config)
+        .send(&subscriptions::confirmation_email(&link, &subscriber.target, &confirm_url))
+        .await
+        .map_err(|e| {
+            eprintln!("Mail error: {}", e);
+            crate::ApiError::InternalServerError
+        })?;
+    Ok(confirm_message)
+}
+
+/// The subscriber a signed token points at, if it belongs to `page`.
+async fn token_subscriber(
+    state: &AppState,
+    page: &StatusPage,
+    purpose: &str,
+    token: &str,
+) -> Result<StatusPageSubscriber, crate::ApiError> {
+    let id = subscriptions::verify_token(&state.config.signing_secret, purpose, token)
+        .ok_or_else(|| crate::ApiError::BadRequest("Invalid subscription link".to_string()))?;
+    let subscriber = repository::get_subscriber(state, &id).await?;
+    if subscriber.status_page_id != page.id {
+        return Err(crate::ApiError::NotFound);
+    }
+    Ok(subscriber)
+}
+
+/// Confirms an email or webhook subscription; returns the page for the
+/// response.
+pub async fn confirm_subscription(state: &AppState, slug: &str, token: &str) -> Result<StatusPage, crate::ApiError> {
+    let page = enabled_page(state, slug).await?;
+    let subscriber = token_subscriber(state, &page, subscriptions::CONFIRM, token).await?;
+    if subscriber.confirmed_at.is_none() {
+        repository::update_subscriber(state, &subscriber.id, serde_json::json!({ "confirmed_at": Utc::now().to_rfc3339() }))
+            .await?;
+    }
+    Ok(page)
+}
+
+/// Removes a subscription; following the link again is not an error.
+pub async fn unsubscribe(state: &AppState, slug: &str, token: &str) -> Result<StatusPage, crate::ApiError> {
+    let page = enabled_page(state, slug).await?;
+    match token_subscriber(state, &page, subscriptions::UNSUBSCRIBE, token).await {
+        Ok(subscriber) => repository::delete_subscriber(state, &subscriber.id).await?,
+        Err(crate::ApiError::NotFound) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(page)
+}
+
+pub async fn list_subscribers(state: &AppState, project_id: &str) -> Result<Vec<StatusPageSubscriber>, crate::ApiError> {
+    let page = get_page(state, project_id).await?;
+    repository::list_subscribers(state, &page.id).await
+}
+
+pub async fn delete_subscriber(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete_subscriber(state, id).await
+}
+
+async fn page_link(state: &AppState, page: &StatusPage) -> Result<PageLink, crate::ApiError> {
+    let project = projects::repository::get_by_id(state, &page.project_id).await?;
+    Ok(PageLink {
+        title: page.title.clone(),
+        url: subscriptions::page_url(&state.config.public_url, &project.slug, page),
+    })
+}
+
+/// Runs a notification in the background, so the request that caused it
+/// does not wait on mail servers and webhooks.
+fn spawn_notification<F>(notification: F)
+where
+    F: Future<Output = Result<(), crate::ApiError>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        if let Err(e) = notification.await {
+            eprintln!("Failed to notify subscribers: {:?}", e);
+        }
+    });
+}
+
+async fn deliver(
+    state: &AppState,
+    page: &StatusPage,
+    subscribers: &[StatusPageSubscriber],
+    notification: &Notification,
+) -> Result<(), crate::ApiError> {
+    let link = page_link(state, page).await?;
+    let mailer = mailer::from_config(&state.config);
+    subscriptions::fan_out(
+        mailer.as_ref(),
+        &state.config.signing_secret,
+        &link,
+        subscribers,
+        notification,
+        state.config.allow_private_webhooks,
+    )
+    .await;
+    Ok(())
+}
+
+/// Tells the page's subscribers about a new post (or a new update to one).
+pub async fn notify_post(state: AppState, post_id: String, update: bool) -> Result<(), crate::ApiError> {
+    let post = repository::get_post(&state, &post_id).await?;
+    let page = repository::get_by_id(&state, &post.status_page_id).await?;
+    let subscribers = repository::list_subscribers(&state, &page.id).await?;
+    if !page.enabled || subscribers.is_empty() {
+        return Ok(());
+    }
+
+    let updates = repository::list_post_updates(&state, &post.id).await?;
+    let windows = maintenance::repository::list_by_project(&state, &page.project_id).await?;
+    let components: Vec<PublicComponent> = current_components(&state, &page, &windows, Utc::now())
+        .await?
+        .into_iter()
+        .map(|(_, component)| component)
+        .collect();
+    let notification = Notification::Post {
+        post: public_post(&post, &updates, &components),
+        component_ids: post.component_ids.clone(),
+        update,
+    };
+    deliver(&state, &page, &subscribers, &notification).await
+}
+
+/// Called when a monitor's stored state changes: subscribers of every page
+/// showing it hear about the component's new status. Changes from or to
+/// paused / unchecked monitors, and anything during maintenance, are quiet.
+pub fn notify_monitor_change(state: &AppState, monitor: &Monitor, previous: &str, next: &str, in_maintenance: bool) {
+    let (Some(previous_status), Some(next_status)) = (
+        status::state_component_status(previous, in_maintenance),
+        status::state_component_status(next, in_maintenance),
+    ) else {
+        return;
+    };
+    if previous_status == next_status {
+        return;
+    }
+
+    let state = state.clone();
+    let monitor = monitor.clone();
+    spawn_notification(async move {
+        let now = Utc::now().to_rfc3339();
+        for component in repository::list_components_by_monitor(&state, &monitor.id).await? {
+            let page = repository::get_by_id(&state, &component.status_page_id).await?;
+            let subscribers = repository::list_subscribers(&state, &page.id).await?;
+            if !page.enabled || subscribers.is_empty() {
+                continue;
+            }
+
+            let notification = Notification::Component {
+                component: PublicComponent {
+                    id: component.id.clone(),
+                    name: component.display_name.clone().unwrap_or_else(|| monitor.name.clone()),
+                    group: component.group_name.clone(),
+                    status: next_status.to_string(),
+                    checked_at: Some(now.clone()),
+                },
+                previous_status: previous_status.to_string(),
+            };
+            deliver(&state, &page, &subscribers, &notification).await?;
+        }
+        Ok(())
+    });
+}
+
+/// Current status of a page, see `visible_page`.
+pub async fn public_status(state: &AppState, page: StatusPage) -> Result<PublicStatusPage, crate::ApiError> {
+    let now = Utc::now();
+    let windows = maintenance::repository::list_by_project(state, &page.project_id).await?;
+    let components: Vec<PublicComponent> = current_components(state, &page, &windows, now)
+        .await?
+        .into_iter()
+        .map(|(_, component)| component)
+        .collect();
+    let posts = public_posts(state, &page, &components, now).await?;
+
+    Ok(PublicStatusPage {
+        title: page.title,
+        description: page.description,
+        status: page_status(&components.iter().collect::<Vec<_>>(), &posts).to_string(),
+        components,
+        posts,
+        updated_at: now.to_rfc3339(),
+    })
+}
+
+/// The page's newest posts with their updates, for RSS and Atom.
+pub async fn feed(state: &AppState, slug: &str, page: StatusPage) -> Result<feeds::Feed, crate::ApiError> {
+    let windows = maintenance::repository::list_by_project(state, &page.project_id).await?;
+    let components: Vec<PublicComponent> = current_components(state, &page, &windows, Utc::now())
+        .await?
+        .into_iter()
+        .map(|(_, component)| component)
+        .collect();
+
+    let mut posts = Vec::new();
+    for post in repository::list_posts(state, &page.id, None, Some(feeds::MAX_FEED_POSTS), None).await? {
+        let updates = repository::list_post_updates(state, &post.id).await?;
+        posts.push(public_post(&post, &updates, &components));
+    }
+    // Only real changes move the feed's timestamp, so it caches well.
+    let updated_at = posts
+        .iter()
+        .filter_map(|post| parse_timestamp(feeds::post_updated_at(post)))
+        .max()
+        .map(|at| at.to_rfc3339())
+        .unwrap_or_else(|| page.updated_at.clone());
+
+    Ok(feeds::Feed {
+        title: page.title.clone(),
+        page_url: subscriptions::page_url(&state.config.public_url, slug, &page),
+        updated_at,
+        posts,
+    })
+}
+
+pub async fn embed_status(state: &AppState, slug: &str, page: StatusPage) -> Result<EmbedStatus, crate::ApiError> {
+    let url = subscriptions::page_url(&state.config.public_url, slug, &page);
+    let current = public_status(state, page).await?;
+    Ok(EmbedStatus {
+        label: render::page_status_label(&current.status).to_string(),
+        status: current.status,
+        url,
+    })
+}
+
+async fn monitor_uptime(state: &AppState, monitor_id: &str, window: &str) -> Result<Option<f64>, crate::ApiError> {
+    let query = monitors::UptimeQuery {
+        window: Some(window.to_string()),
+        from: None,
+        to: None,
+        degraded_as_up: None,
+    };
+    Ok(monitors::service::uptime_report(state, monitor_id, query).await?.uptime_percent)
+}
+
+/// Badge with the page status, or the mean uptime of its components.
+pub async fn page_badge(state: &AppState, page: StatusPage, query: BadgeQuery) -> Result<String, crate::ApiError> {
+    let spec = badge::badge_spec(&query).map_err(crate::ApiError::BadRequest)?;
+    if spec.kind == "status" {
+        let current = public_status(state, page).await?;
+        return Ok(badge::status_badge(&spec.label, &current.status));
+    }
+
+    let mut percents = Vec::new();
+    for component in repository::list_components(state, &page.id).await? {
+        match monitor_uptime(state, &component.monitor_id, &spec.window).await {
+            Ok(percent) => percents.extend(percent),
+            Err(crate::ApiError::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    let mean = (!percents.is_empty()).then(|| percents.iter().sum::<f64>() / percents.len() as f64);
+    Ok(badge::uptime_badge(&spec.label, mean))
+}
+
+/// Badge with a monitor's state or uptime.
+pub async fn monitor_badge(state: &AppState, monitor_id: &str, query: BadgeQuery) -> Result<String, crate::ApiError> {
+    let spec = badge::badge_spec(&query).map_err(crate::ApiError::BadRequest)?;
+    if spec.kind == "status" {
+        let monitor = monitors::repository::get_by_id(state, monitor_id).await?;
+        return Ok(badge::status_badge(&spec.label, monitor.state.as_deref().unwrap_or("unknown")));
+    }
+    let uptime = monitor_uptime(state, monitor_id, &spec.window).await?;
+    Ok(badge::uptime_badge(&spec.label, uptime))
+}
+
+/// Open incidents of the listed monitors and those resolved within
+/// `RECENT_INCIDENT_DAYS`, named after their components.
+pub fn recent_incidents(incidents: &[Incident], names: &[(&str, &str)], now: DateTime<Utc>) -> Vec<PublicIncident> {
+    let since = now - Duration::days(RECENT_INCIDENT_DAYS);
+    incidents
+        .iter()
+        .filter(|incident| {
+            incident.resolved_at.as_deref().is_none_or(|resolved_at| {
+                parse_timestamp(resolved_at).is_some_and(|resolved_at| resolved_at >= since)
+            })
+        })
+        .filter_map(|incident| {
+            let (_, name) = names.iter().find(|(monitor_id, _)| *monitor_id == incident.monitor_id)?;
+            Some(PublicIncident {
+                component: name.to_string(),
+                cause: incident.cause.clone(),
+                status: incident.status.clone(),
+                started_at: incident.started_at.clone(),
+                resolved_at: incident.resolved_at.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Running and upcoming occurrences (within `UPCOMING_MAINTENANCE_DAYS`) of
+/// windows covering the whole project or any of `monitor_ids`.
+pub fn upcoming_maintenance(windows: &[MaintenanceWindow], monitor_ids: &[&str], now: DateTime<Utc>) -> Vec<PublicMaintenance> {
+    let horizon = now + Duration::days(UPCOMING_MAINTENANCE_DAYS);
+    let mut occurrences: Vec<(DateTime<Utc>, DateTime<Utc>, &MaintenanceWindow)> = windows
+        .iter()
+        .filter(|w| w.monitor_ids.is_empty() || w.monitor_ids.iter().any(|id| monitor_ids.contains(&id.as_str())))
+        .filter_map(|w| maintenance::definition(w).ok().map(|definition| (w, definition)))
+        .flat_map(|(w, definition)| {
+            schedule::next_occurrences(&definition, now, MAX_MAINTENANCE_ITEMS)
+                .into_iter()
+                .filter(|(start, _)| *start < horizon)
+                .map(move |(start, end)| (start, end, w))
+        })
+        .collect();
+    occurrences.sort_by_key(|(start, _, _)| *start);
+
+    occurrences
+        .into_iter()
+        .take(MAX_MAINTENANCE_ITEMS)
+        .map(|(start, end, w)| PublicMaintenance {
+            title: w.title.clone(),
+            description: w.description.clone(),
+            starts_at: start.to_rfc3339(),
+            ends_at: end.to_rfc3339(),
+        })
+        .collect()
+}
+
+/// Everything the HTML status page shows, see `render`.
+pub async fn page_view(state: &AppState, page: StatusPage) -> Result<StatusPageView, crate::ApiError> {
+    let now = Utc::now();
+    let today = now.date_naive();
+    let windows = maintenance::repository::list_by_project(state, &page.project_id).await?;
+    let current = current_components(state, &page, &windows, now).await?;
+
+    let from = (today - Duration::days(status::UPTIME_DAYS as i64 - 1))
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc();
+    let mut components = Vec::with_capacity(current.len());
+    for (monitor, component) in &current {
+        let rollups = monitors::repository::list_rollups(
+            state,
+            &monitor.id,
+            "day",
+            None,
+            (&from.to_rfc3339(), &now.to_rfc3339()),
+            Some(1000),
+            None,
+        ).await?;
+        components.push(ComponentView {
+            component: component.clone(),
+            uptime_percent: status::uptime_percent(&rollups.iter().collect::<Vec<_>>()),
+            days: status::daily_uptime(&rollups, today, status::UPTIME_DAYS),
+        });
+    }
+
+    let names: Vec<(&str, &str)> = current.iter().map(|(m, c)| (m.id.as_str(), c.name.as_str())).collect();
+    let monitor_ids: Vec<&str> = current.iter().map(|(m, _)| m.id.as_str()).collect();
+    let incidents = incidents::repository::list_by_project(state, &page.project_id, None, Some(50), None).await?;
+    let public: Vec<PublicComponent> = current.iter().map(|(_, c)| c.clone()).collect();
+    let posts = public_posts(state, &page, &public, now).await?;
+
+    Ok(StatusPageView {
+        title: page.title.clone(),
+        description: page.description.clone(),
+        status: page_status(&public.iter().collect::<Vec<_>>(), &posts).to_string(),
+        theme: page.theme.clone(),
+        incidents: recent_incidents(&incidents, &names, now),
+        maintenance: upcoming_maintenance(&windows, &monitor_ids, now),
+        posts,
+        components,
+        updated_at: now.to_rfc3339(),
+    })
+}
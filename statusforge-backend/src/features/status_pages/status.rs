@@ -0,0 +1,131 @@
+//! Public status of status page components, derived from the newest result
+//! of each region, of the page as a whole, and daily uptime for the bars.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::features::monitors::{confirmation, MonitorResult, ResultRollup};
+
+use super::DailyUptime;
+
+pub const COMPONENT_STATUSES: [&str; 5] = ["operational", "degraded", "outage", "maintenance", "unknown"];
+
+pub const PAGE_STATUSES: [&str; 6] = [
+    "operational",
+    "degraded",
+    "partial_outage",
+    "major_outage",
+    "maintenance",
+    "unknown",
+];
+
+/// A region whose newest result is older than this many check intervals no
+/// longer counts towards the component status.
+pub const STALE_AFTER_INTERVALS: i64 = 3;
+
+const MIN_FRESHNESS_SECONDS: i64 = 300;
+
+/// Oldest result time that still counts as current for a monitor.
+pub fn fresh_since(now: DateTime<Utc>, interval_seconds: i32) -> DateTime<Utc> {
+    let seconds = (interval_seconds as i64 * STALE_AFTER_INTERVALS).max(MIN_FRESHNESS_SECONDS);
+    now - Duration::seconds(seconds)
+}
+
+/// The newest result of every region (`results` newest-first), skipping
+/// results older than `since`.
+pub fn latest_per_region(results: &[MonitorResult], since: DateTime<Utc>) -> Vec<&MonitorResult> {
+    let mut latest: Vec<&MonitorResult> = Vec::new();
+    for result in results {
+        let fresh = crate::shared::utils::parse_timestamp(&result.created_at).is_some_and(|at| at >= since);
+        if fresh && !latest.iter().any(|r| r.region == result.region) {
+            latest.push(result);
+        }
+    }
+    latest
+}
+
+/// Component status from the latest regional statuses, using the same quorum
+/// rule as confirmed checks.
+pub fn component_status(statuses: &[&str], down_quorum: i32, in_maintenance: bool) -> &'static str {
+    if in_maintenance {
+        return "maintenance";
+    }
+    if statuses.is_empty() {
+        return "unknown";
+    }
+    match confirmation::aggregate(statuses, down_quorum) {
+        "down" => "outage",
+        "degraded" => "degraded",
+        _ => "operational",
+    }
+}
+
+/// Component status implied by a stored monitor state (`monitors.state`);
+/// `None` for paused or not yet checked monitors.
+pub fn state_component_status(monitor_state: &str, in_maintenance: bool) -> Option<&'static str> {
+    let status = match monitor_state {
+        "up" => "operational",
+        "degraded" => "degraded",
+        "down" => "outage",
+        _ => return None,
+    };
+    Some(if in_maintenance { "maintenance" } else { status })
+}
+
+/// Overall page status. Components without a known status are ignored; a
+/// page where every known component is out is a major outage.
+pub fn page_status(components: &[&str]) -> &'static str {
+    let known: Vec<&str> = components.iter().copied().filter(|s| *s != "unknown").collect();
+    let outages = known.iter().filter(|s| **s == "outage").count();
+
+    if known.is_empty() {
+        "unknown"
+    } else if outages == known.len() {
+        "major_outage"
+    } else if outages > 0 {
+        "partial_outage"
+    } else if known.contains(&"degraded") {
+        "degraded"
+    } else if known.contains(&"maintenance") {
+        "maintenance"
+    } else {
+        "operational"
+    }
+}
+
+/// Days shown in the uptime bars, including today.
+pub const UPTIME_DAYS: u32 = 90;
+
+/// Daily uptime (degraded counts as up) across all regions for the
+/// `days` days ending with `today`, from daily rollups.
+pub fn daily_uptime(rollups: &[ResultRollup], today: NaiveDate, days: u32) -> Vec<DailyUptime> {
+    (0..days)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset as i64);
+            let day: Vec<&ResultRollup> = rollups
+                .iter()
+                .filter(|r| crate::shared::utils::parse_timestamp(&r.bucket_start).is_some_and(|at| at.date_naive() == date))
+                .collect();
+            DailyUptime {
+                date: date.format("%Y-%m-%d").to_string(),
+                uptime_percent: uptime_percent(&day),
+            }
+        })
+        .collect()
+}
+
+pub fn uptime_percent(rollups: &[&ResultRollup]) -> Option<f64> {
+    let total: i64 = rollups.iter().map(|r| r.total).sum();
+    let up: i64 = rollups.iter().map(|r| r.up + r.degraded).sum();
+    (total > 0).then(|| 100.0 * up as f64 / total as f64)
+}
+
+/// Bar colour for a day: `up`, `degraded`, `down` or `nodata`.
+pub fn uptime_level(uptime_percent: Option<f64>) -> &'static str {
+    match uptime_percent {
+        None => "nodata",
+        Some(p) if p >= 99.9 => "up",
+        Some(p) if p >= 95.0 => "degraded",
+        Some(_) => "down",
+    }
+}
@@ -0,0 +1,267 @@
+//! Status page subscriptions. Subscribers confirm before they get anything:
+//! email subscribers follow a link in a confirmation email (double opt-in),
+//! webhook subscribers get a `subscription.confirm` request and must fetch
+//! its `confirm_url`. Webhooks never go to private or local addresses.
+//! Subscribers may follow only some components, and every message carries an
+//! unsubscribe link with a signed token.
+
+use std::time::Duration;
+
+use crate::shared::mailer::{Email, Mailer};
+use crate::shared::{net, signing};
+
+use super::{posts, render, PublicComponent, PublicPost, StatusPage, StatusPageComponent, StatusPageSubscriber, Subscribe};
+
+pub const SUBSCRIBER_KINDS: [&str; 2] = ["email", "webhook"];
+
+pub const MAX_EMAIL_LEN: usize = 254;
+
+pub const MAX_WEBHOOK_URL_LEN: usize = 2048;
+
+/// Token purposes, so a confirmation token cannot unsubscribe and vice versa.
+pub const CONFIRM: &str = "confirm";
+pub const UNSUBSCRIBE: &str = "unsubscribe";
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// `{subscriber_id}.{hex HMAC-SHA256 of "purpose:subscriber_id"}`.
+pub fn sign_token(secret: &str, purpose: &str, id: &str) -> String {
+    format!("{}.{}", id, signing::sign(secret, format!("{}:{}", purpose, id).as_bytes()))
+}
+
+/// The subscriber id of a valid token.
+pub fn verify_token(secret: &str, purpose: &str, token: &str) -> Option<String> {
+    let (id, signature) = token.rsplit_once('.')?;
+    signing::verify(secret, format!("{}:{}", purpose, id).as_bytes(), signature).then(|| id.to_string())
+}
+
+/// Lowercased address with a single `@` and a dotted domain.
+pub fn normalize_email(email: &str) -> Result<String, String> {
+    let email = email.trim().to_ascii_lowercase();
+    let invalid = || format!("Invalid email address: {}", email);
+    if email.len() > MAX_EMAIL_LEN || email.chars().any(|c| c.is_whitespace() || c.is_control() || "<>,;\"".contains(c)) {
+        return Err(invalid());
+    }
+    match email.split_once('@') {
+        Some((local, domain))
+            if !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.') =>
+        {
+            Ok(email)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| format!("Invalid webhook URL: {}", url))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Webhook URL must be an http(s) URL".to_string());
+    }
+    if url.len() > MAX_WEBHOOK_URL_LEN {
+        return Err(format!("Webhook URL cannot be longer than {} characters", MAX_WEBHOOK_URL_LEN));
+    }
+    Ok(())
+}
+
+/// The subscriber's `(kind, target)`.
+pub fn validate_subscribe(data: &Subscribe, components: &[StatusPageComponent]) -> Result<(&'static str, String), String> {
+    let subscriber = match (&data.email, &data.webhook_url) {
+        (Some(email), None) => ("email", normalize_email(email)?),
+        (None, Some(url)) => {
+            let url = url.trim();
+            validate_webhook_url(url)?;
+            ("webhook", url.to_string())
+        }
+        _ => return Err("Provide either email or webhook_url".to_string()),
+    };
+    posts::validate_components(data.component_ids.as_deref().unwrap_or_default(), components)?;
+    Ok(subscriber)
+}
+
+/// Where subscribers are sent: the verified custom domain, or the page
+/// under the API's public URL.
+pub fn page_url(public_url: &str, slug: &str, page: &StatusPage) -> String {
+    match (&page.custom_domain, &page.domain_verified_at) {
+        (Some(domain), Some(_)) => format!("https://{}", domain),
+        _ => format!("{}/status/{}", public_url, slug),
+    }
+}
+
+pub fn confirm_url(page_url: &str, secret: &str, subscriber_id: &str) -> String {
+    format!("{}/subscriptions/confirm?token={}", page_url, sign_token(secret, CONFIRM, subscriber_id))
+}
+
+pub fn unsubscribe_url(page_url: &str, secret: &str, subscriber_id: &str) -> String {
+    format!("{}/subscriptions/unsubscribe?token={}", page_url, sign_token(secret, UNSUBSCRIBE, subscriber_id))
+}
+
+/// Something subscribers hear about.
+#[derive(Debug, Clone)]
+pub enum Notification {
+    /// A post was published, or (`update`) got a new timeline entry.
+    Post {
+        post: PublicPost,
+        component_ids: Vec<String>,
+        update: bool,
+    },
+    /// A component changed status.
+    Component {
+        component: PublicComponent,
+        previous_status: String,
+    },
+}
+
+impl Notification {
+    pub fn event(&self) -> &'static str {
+        match self {
+            Notification::Post { update: false, .. } => "post.created",
+            Notification::Post { update: true, .. } => "post.updated",
+            Notification::Component { .. } => "component.updated",
+        }
+    }
+}
+
+/// Whether a confirmed subscriber follows what `notification` is about. Posts
+/// without components concern the whole page.
+pub fn wants(subscriber: &StatusPageSubscriber, notification: &Notification) -> bool {
+    if subscriber.confirmed_at.is_none() {
+        return false;
+    }
+    if subscriber.component_ids.is_empty() {
+        return true;
+    }
+    let follows = |id: &String| subscriber.component_ids.contains(id);
+    match notification {
+        Notification::Post { component_ids, .. } => component_ids.is_empty() || component_ids.iter().any(follows),
+        Notification::Component { component, .. } => follows(&component.id),
+    }
+}
+
+/// The status page as named in messages.
+#[derive(Debug, Clone)]
+pub struct PageLink {
+    pub title: String,
+    pub url: String,
+}
+
+pub fn confirmation_email(page: &PageLink, to: &str, confirm_url: &str) -> Email {
+    Email {
+        to: to.to_string(),
+        subject: format!("Confirm your subscription to {}", page.title),
+        body: format!(
+            "Confirm that you want to receive status updates for {}:\n\n{}\n\nIf you did not ask for this, ignore this email.\n",
+            page.title, confirm_url
+        ),
+        unsubscribe_url: None,
+    }
+}
+
+pub fn notification_email(page: &PageLink, to: &str, notification: &Notification, unsubscribe_url: &str) -> Email {
+    let (subject, text) = match notification {
+        Notification::Post { post, .. } => {
+            let mut summary = vec![render::state_label(&post.state), format!("Impact: {}", render::state_label(&post.impact))];
+            if !post.components.is_empty() {
+                summary.push(format!("Affects: {}", post.components.join(", ")));
+            }
+            let mut text = format!("{}\n{}\n", post.title, summary.join(" · "));
+            if let (Some(starts_at), Some(ends_at)) = (&post.scheduled_starts_at, &post.scheduled_ends_at) {
+                text.push_str(&format!(
+                    "Scheduled {} – {}\n",
+                    render::display_time(starts_at),
+                    render::display_time(ends_at)
+                ));
+            }
+            if let Some(latest) = post.updates.first() {
+                text.push_str(&format!("\n{}\n", latest.body));
+            }
+            (format!("[{}] {} – {}", page.title, post.title, render::state_label(&post.state)), text)
+        }
+        Notification::Component { component, previous_status } => (
+            format!("[{}] {}: {}", page.title, component.name, render::component_status_label(&component.status)),
+            format!(
+                "{} is now {} (was {}).\n",
+                component.name,
+                render::component_status_label(&component.status),
+                render::component_status_label(previous_status)
+            ),
+        ),
+    };
+
+    Email {
+        to: to.to_string(),
+        subject,
+        body: format!("{}\n{}\n\n--\nUnsubscribe: {}\n", text, page.url, unsubscribe_url),
+        unsubscribe_url: Some(unsubscribe_url.to_string()),
+    }
+}
+
+/// Sent to a new webhook subscriber, which confirms by fetching
+/// `confirm_url`.
+pub fn webhook_confirmation_payload(page: &PageLink, confirm_url: &str) -> serde_json::Value {
+    serde_json::json!({
+        "event": "subscription.confirm",
+        "page": { "title": page.title, "url": page.url },
+        "confirm_url": confirm_url,
+    })
+}
+
+pub fn webhook_payload(page: &PageLink, notification: &Notification, unsubscribe_url: &str) -> serde_json::Value {
+    let mut payload = serde_json::json!({
+        "event": notification.event(),
+        "page": { "title": page.title, "url": page.url },
+        "unsubscribe_url": unsubscribe_url,
+    });
+    match notification {
+        Notification::Post { post, .. } => payload["post"] = serde_json::json!(post),
+        Notification::Component { component, previous_status } => {
+            payload["component"] = serde_json::json!(component);
+            payload["component"]["previous_status"] = serde_json::json!(previous_status);
+        }
+    }
+    payload
+}
+
+/// Posts `payload` to a subscriber's webhook, refusing internal addresses
+/// unless `allow_internal`.
+pub async fn post_webhook(url: &str, payload: &serde_json::Value, allow_internal: bool) -> Result<(), String> {
+    net::public_client(url, WEBHOOK_TIMEOUT, allow_internal)
+        .await
+        .map_err(|e| format!("Webhook {} refused: {}", url, e))?
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map(|_| ())
+        .map_err(|e| format!("Webhook {} failed: {}", url, e))
+}
+
+/// Sends `notification` to every subscriber that wants it and returns how
+/// many deliveries succeeded; failures are logged and do not stop the rest.
+/// Webhook hosts are resolved again for every delivery, see `post_webhook`.
+pub async fn fan_out(
+    mailer: &dyn Mailer,
+    secret: &str,
+    page: &PageLink,
+    subscribers: &[StatusPageSubscriber],
+    notification: &Notification,
+    allow_internal: bool,
+) -> usize {
+    let mut delivered = 0;
+    for subscriber in subscribers.iter().filter(|s| wants(s, notification)) {
+        let unsubscribe = unsubscribe_url(&page.url, secret, &subscriber.id);
+        let result = match subscriber.kind.as_str() {
+            "email" => mailer.send(&notification_email(page, &subscriber.target, notification, &unsubscribe)).await,
+            _ => post_webhook(&subscriber.target, &webhook_payload(page, notification, &unsubscribe), allow_internal).await,
+        };
+        match result {
+            Ok(()) => delivered += 1,
+            Err(e) => eprintln!("Failed to notify subscriber {}: {}", subscriber.id, e),
+        }
+    }
+    delivered
+}
@@ -0,0 +1,67 @@
+//! Per-page theme settings. Values end up inside the page's `<style>` and
+//! `<img>` tags, so they are validated strictly instead of escaped.
+
+use super::PageTheme;
+
+pub const MAX_CUSTOM_CSS_LEN: usize = 20_000;
+
+pub const MAX_LOGO_URL_LEN: usize = 2048;
+
+fn is_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub fn validate_theme(theme: &PageTheme) -> Result<(), String> {
+    let colors = [
+        ("accent_color", &theme.accent_color),
+        ("background_color", &theme.background_color),
+        ("text_color", &theme.text_color),
+    ];
+    for (field, value) in colors {
+        if let Some(value) = value
+            && !is_color(value)
+        {
+            return Err(format!("Invalid {}: {}. Must be a #rgb or #rrggbb colour", field, value));
+        }
+    }
+
+    if let Some(ref url) = theme.logo_url
+        && (url.len() > MAX_LOGO_URL_LEN
+            || !(url.starts_with("https://") || url.starts_with("http://"))
+            || url.chars().any(|c| c.is_whitespace() || c.is_control()))
+    {
+        return Err("logo_url must be an http(s) URL".to_string());
+    }
+
+    if let Some(ref css) = theme.custom_css {
+        if css.len() > MAX_CUSTOM_CSS_LEN {
+            return Err(format!("custom_css cannot be longer than {} bytes", MAX_CUSTOM_CSS_LEN));
+        }
+        // Keeps the stylesheet from closing its <style> element.
+        if css.contains('<') {
+            return Err("custom_css cannot contain `<`".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// CSS variable overrides for the colours that are set.
+pub fn css_variables(theme: &PageTheme) -> String {
+    let overrides: Vec<String> = [
+        ("--accent", &theme.accent_color),
+        ("--background", &theme.background_color),
+        ("--text", &theme.text_color),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| value.as_deref().filter(|v| is_color(v)).map(|v| format!("{}: {};", name, v)))
+    .collect();
+
+    if overrides.is_empty() {
+        String::new()
+    } else {
+        format!(":root {{ {} }}", overrides.join(" "))
+    }
+}
@@ -0,0 +1,140 @@
+//! Sending events to webhooks. Each delivery is a POST of the versioned JSON
+//! payload; webhooks with a secret also get
+//! `X-StatusForge-Signature: t={unix time},v1={hex HMAC-SHA256 of "{t}.{body}"}`.
+//! Receivers should check the signature and reject timestamps older than
+//! `SIGNATURE_TOLERANCE_SECONDS`, so captured requests cannot be replayed.
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::shared::signing;
+
+use super::{Webhook, WebhookEvent};
+
+pub const PAYLOAD_VERSION: u32 = 1;
+
+pub const SIGNATURE_HEADER: &str = "X-StatusForge-Signature";
+
+pub const EVENT_HEADER: &str = "X-StatusForge-Event";
+
+/// The event id, equal for every delivery of the same event.
+pub const DELIVERY_HEADER: &str = "X-StatusForge-Delivery";
+
+pub const SIGNATURE_TOLERANCE_SECONDS: i64 = 300;
+
+/// Response bodies are stored up to this many bytes.
+pub const MAX_RESPONSE_BODY: usize = 2048;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn payload(event: &WebhookEvent) -> serde_json::Value {
+    serde_json::json!({
+        "version": PAYLOAD_VERSION,
+        "id": event.id,
+        "event": event.event,
+        "project_id": event.project_id,
+        "created_at": event.created_at,
+        "data": event.data,
+    })
+}
+
+/// The event a stored payload was made from, for resending it.
+pub fn event_from_payload(payload: &serde_json::Value) -> Option<WebhookEvent> {
+    serde_json::from_value(payload.clone()).ok()
+}
+
+pub fn signature_header(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("t={},v1={}", timestamp, signing::sign(secret, format!("{}.{}", timestamp, body).as_bytes()))
+}
+
+/// What receivers do: checks the signature and that it is recent.
+pub fn verify_signature(secret: &str, header: &str, body: &str, now: DateTime<Utc>) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+    if (now.timestamp() - timestamp).abs() > SIGNATURE_TOLERANCE_SECONDS {
+        return false;
+    }
+    let message = format!("{}.{}", timestamp, body);
+    signatures.iter().any(|signature| signing::verify(secret, message.as_bytes(), signature))
+}
+
+/// The outcome of one POST.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attempt {
+    pub status_code: Option<u16>,
+    pub response_body: Option<String>,
+    pub error_message: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl Attempt {
+    pub fn succeeded(&self) -> bool {
+        self.status_code.is_some_and(|code| (200..300).contains(&code))
+    }
+}
+
+fn truncate(body: &str) -> String {
+    if body.len() <= MAX_RESPONSE_BODY {
+        return body.to_string();
+    }
+    let mut end = MAX_RESPONSE_BODY;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    body[..end].to_string()
+}
+
+pub fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(DELIVERY_TIMEOUT)
+        .user_agent("StatusForge-Webhooks")
+        // A redirect would re-send the signed payload somewhere else.
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| format!("HTTP client setup failed: {}", e))
+}
+
+/// POSTs `body` (the serialized `payload`) to the webhook; never fails, the
+/// outcome is in the `Attempt`.
+pub async fn send(client: &reqwest::Client, webhook: &Webhook, event: &WebhookEvent, body: &str, now: DateTime<Utc>) -> Attempt {
+    let mut request = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &event.event)
+        .header(DELIVERY_HEADER, &event.id)
+        .body(body.to_string());
+    if let Some(ref secret) = webhook.secret {
+        request = request.header(SIGNATURE_HEADER, signature_header(secret, now.timestamp(), body));
+    }
+
+    let started = Instant::now();
+    match request.send().await {
+        Ok(response) => {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            Attempt {
+                status_code: Some(status.as_u16()),
+                response_body: (!text.is_empty()).then(|| truncate(&text)),
+                error_message: (!status.is_success()).then(|| format!("Webhook responded with {}", status)),
+                duration_ms: started.elapsed().as_millis() as i64,
+            }
+        }
+        Err(e) => Attempt {
+            status_code: None,
+            response_body: None,
+            error_message: Some(format!("Request to {} failed: {}", webhook.url, e)),
+            duration_ms: started.elapsed().as_millis() as i64,
+        },
+    }
+}
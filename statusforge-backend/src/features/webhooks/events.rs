@@ -0,0 +1,98 @@
+//! Events webhooks can subscribe to: `downtime` when a monitor goes down and
+//! an incident is opened, `recovery` when that incident is resolved, and
+//! `error_spike` when a project logs `ERROR_SPIKE_THRESHOLD` errors within
+//! `ERROR_SPIKE_MINUTES`.
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::features::incidents::Incident;
+use crate::features::ingest::Log;
+use crate::features::monitors::Monitor;
+
+use super::{Webhook, WebhookEvent};
+
+pub const WEBHOOK_EVENTS: [&str; 3] = ["downtime", "recovery", "error_spike"];
+
+/// Sent on request to check an endpoint; not something to subscribe to.
+pub const TEST_EVENT: &str = "test";
+
+/// `error` and `fatal` logs within this many minutes that make a spike.
+pub const ERROR_SPIKE_THRESHOLD: usize = 10;
+
+pub const ERROR_SPIKE_MINUTES: i64 = 5;
+
+/// Known, distinct and at least one.
+pub fn validate_events(events: &[String]) -> Result<(), String> {
+    if events.is_empty() {
+        return Err("A webhook needs at least one event".to_string());
+    }
+    for (index, event) in events.iter().enumerate() {
+        if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+            return Err(format!(
+                "Invalid event: {}. Must be one of: {:?}",
+                event, WEBHOOK_EVENTS
+            ));
+        }
+        if events[..index].contains(event) {
+            return Err(format!("Event {} is listed more than once", event));
+        }
+    }
+    Ok(())
+}
+
+/// Only the error that brings the count to the threshold reports a spike,
+/// so an ongoing flood is reported once.
+pub fn is_error_spike(errors_in_window: usize) -> bool {
+    errors_in_window == ERROR_SPIKE_THRESHOLD
+}
+
+pub fn is_error_level(level: &str) -> bool {
+    matches!(level, "error" | "fatal")
+}
+
+fn event(kind: &str, project_id: &str, data: serde_json::Value, now: DateTime<Utc>) -> WebhookEvent {
+    WebhookEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        event: kind.to_string(),
+        project_id: project_id.to_string(),
+        created_at: now.to_rfc3339(),
+        data,
+    }
+}
+
+fn monitor_summary(monitor: &Monitor) -> serde_json::Value {
+    json!({
+        "id": monitor.id,
+        "name": monitor.name,
+        "kind": monitor.kind,
+        "url": monitor.url,
+    })
+}
+
+pub fn downtime(monitor: &Monitor, incident: &Incident, now: DateTime<Utc>) -> WebhookEvent {
+    let data = json!({ "monitor": monitor_summary(monitor), "incident": incident });
+    event("downtime", &monitor.project_id, data, now)
+}
+
+pub fn recovery(monitor: &Monitor, incident: &Incident, now: DateTime<Utc>) -> WebhookEvent {
+    let data = json!({ "monitor": monitor_summary(monitor), "incident": incident });
+    event("recovery", &monitor.project_id, data, now)
+}
+
+pub fn error_spike(latest: &Log, errors: usize, now: DateTime<Utc>) -> WebhookEvent {
+    let data = json!({
+        "errors": errors,
+        "window_minutes": ERROR_SPIKE_MINUTES,
+        "latest": latest,
+    });
+    event("error_spike", &latest.project_id, data, now)
+}
+
+pub fn test(webhook: &Webhook, now: DateTime<Utc>) -> WebhookEvent {
+    let data = json!({
+        "webhook_id": webhook.id,
+        "message": "This is a test event from StatusForge.",
+    });
+    event(TEST_EVENT, &webhook.project_id, data, now)
+}
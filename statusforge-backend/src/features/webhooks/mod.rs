@@ -0,0 +1,11 @@
+pub mod delivery;
+pub mod events;
+mod model;
+pub mod repository;
+pub mod retry;
+mod route;
+pub mod service;
+
+pub use model::*;
+pub use route::*;
+pub use service::*;
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// An HTTP endpoint of a project that receives the events it subscribed to,
+/// see `delivery`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: String,
+    pub project_id: String,
+    pub url: String,
+    /// Some of `events::WEBHOOK_EVENTS`.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Key of the payload signature; deliveries are unsigned without one.
+    pub secret: Option<String>,
+    pub enabled: bool,
+    /// Failed automatic attempts since the last success, see `retry`.
+    #[serde(default)]
+    pub consecutive_failures: i32,
+    /// Set when the webhook was disabled for failing; cleared by enabling it.
+    #[serde(default)]
+    pub disabled_at: Option<String>,
+    #[serde(default)]
+    pub disabled_reason: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateWebhook {
+    pub url: String,
+    /// Defaults to all events.
+    pub events: Option<Vec<String>>,
+    /// Generated when omitted.
+    pub secret: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UpdateWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+/// Something that happened in a project, as sent to webhooks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Shared by every delivery of the event, so receivers can drop
+    /// duplicates.
+    pub id: String,
+    /// One of `events::WEBHOOK_EVENTS`.
+    pub event: String,
+    pub project_id: String,
+    pub created_at: String,
+    pub data: serde_json::Value,
+}
+
+/// One delivery attempt of an event to a webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookLog {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_type: String,
+    #[serde(default)]
+    pub event_id: Option<String>,
+    /// The payload as sent.
+    #[serde(default)]
+    pub payload: Option<serde_json::Value>,
+    #[serde(default = "default_attempt")]
+    pub attempt: i32,
+    /// Unset when no response arrived.
+    pub status_code: Option<i32>,
+    pub response_body: Option<String>,
+    pub error_message: Option<String>,
+    #[serde(default)]
+    pub duration_ms: Option<i64>,
+    /// When the failed attempt is retried, or a pending delivery is due;
+    /// unset once the attempt ran.
+    #[serde(default)]
+    pub next_attempt_at: Option<String>,
+    /// The last attempt of an event that ran out of retries, or whose
+    /// webhook was disabled meanwhile.
+    #[serde(default)]
+    pub dead_letter: bool,
+    /// The log a manual redelivery was made from.
+    #[serde(default)]
+    pub redelivery_of: Option<String>,
+    pub created_at: String,
+}
+
+impl WebhookLog {
+    pub fn succeeded(&self) -> bool {
+        self.status_code.is_some_and(|code| (200..300).contains(&code))
+    }
+
+    /// A delivery logged before it was sent and not attempted yet; every
+    /// attempt records its duration.
+    pub fn is_pending(&self) -> bool {
+        self.duration_ms.is_none()
+    }
+}
+
+fn default_attempt() -> i32 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookLogsQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
@@ -0,0 +1,312 @@
+use supabase::types::OrderDirection;
+
+use crate::AppState;
+
+use super::{CreateWebhook, UpdateWebhook, Webhook, WebhookLog};
+
+pub async fn list_by_project(state: &AppState, project_id: &str) -> Result<Vec<Webhook>, crate::ApiError> {
+    let webhooks = state
+        .supabase
+        .database()
+        .from("webhooks")
+        .select("*")
+        .eq("project_id", project_id)
+        .order("created_at", OrderDirection::Ascending)
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(webhooks)
+}
+
+pub async fn list_enabled(state: &AppState, project_id: &str) -> Result<Vec<Webhook>, crate::ApiError> {
+    let webhooks = state
+        .supabase
+        .database()
+        .from("webhooks")
+        .select("*")
+        .eq("project_id", project_id)
+        .eq("enabled", "true")
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(webhooks)
+}
+
+pub async fn get_by_id(state: &AppState, id: &str) -> Result<Webhook, crate::ApiError> {
+    let mut webhooks = state
+        .supabase
+        .database()
+        .from("webhooks")
+        .select("*")
+        .eq("id", id)
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    webhooks.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn create(state: &AppState, project_id: &str, webhook: &CreateWebhook) -> Result<Webhook, crate::ApiError> {
+    let mut data = serde_json::to_value(webhook).map_err(|e| {
+        eprintln!("Serialization error: {:?}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    data["project_id"] = serde_json::Value::String(project_id.to_string());
+
+    let mut webhooks = state
+        .supabase
+        .database()
+        .insert("webhooks")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::BadRequest(format!("Failed to create webhook: {:?}", e))
+        })?;
+
+    webhooks.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn update(state: &AppState, id: &str, webhook: &UpdateWebhook) -> Result<Webhook, crate::ApiError> {
+    let mut webhooks = state
+        .supabase
+        .database()
+        .update("webhooks")
+        .set(webhook)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    webhooks.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn delete(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .delete("webhooks")
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::NotFound
+        })?;
+
+    Ok(())
+}
+
+pub async fn create_log(state: &AppState, data: serde_json::Value) -> Result<WebhookLog, crate::ApiError> {
+    let mut logs = state
+        .supabase
+        .database()
+        .insert("webhook_logs")
+        .values(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .returning("*")
+        .execute::<WebhookLog>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    logs.pop().ok_or(crate::ApiError::InternalServerError)
+}
+
+pub async fn list_logs(
+    state: &AppState,
+    webhook_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<WebhookLog>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("webhook_logs")
+        .select("*")
+        .eq("webhook_id", webhook_id)
+        .order("created_at", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(100));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let logs = query
+        .execute::<WebhookLog>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(logs)
+}
+
+/// Writes the delivery bookkeeping columns (`consecutive_failures`,
+/// `enabled`, `disabled_at`, `disabled_reason`).
+pub async fn update_health(state: &AppState, id: &str, data: serde_json::Value) -> Result<Webhook, crate::ApiError> {
+    let mut webhooks = state
+        .supabase
+        .database()
+        .update("webhooks")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .returning("*")
+        .execute::<Webhook>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    webhooks.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn get_log(state: &AppState, id: &str) -> Result<WebhookLog, crate::ApiError> {
+    let mut logs = state
+        .supabase
+        .database()
+        .from("webhook_logs")
+        .select("*")
+        .eq("id", id)
+        .execute::<WebhookLog>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    logs.pop().ok_or(crate::ApiError::NotFound)
+}
+
+pub async fn update_log(state: &AppState, id: &str, data: serde_json::Value) -> Result<(), crate::ApiError> {
+    state
+        .supabase
+        .database()
+        .update("webhook_logs")
+        .set(data)
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(())
+}
+
+/// Failed attempts whose retry is due at `now`, oldest first.
+pub async fn list_due_retries(state: &AppState, now: &str, limit: u32) -> Result<Vec<WebhookLog>, crate::ApiError> {
+    let logs = state
+        .supabase
+        .database()
+        .from("webhook_logs")
+        .select("*")
+        .lte("next_attempt_at", now)
+        .order("next_attempt_at", OrderDirection::Ascending)
+        .limit(limit)
+        .execute::<WebhookLog>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(logs)
+}
+
+/// Moves a due retry to `lease` unless another run already did; `true` when
+/// this caller got it.
+pub async fn claim_retry(state: &AppState, id: &str, scheduled: &str, lease: &str) -> Result<bool, crate::ApiError> {
+    let claimed = state
+        .supabase
+        .database()
+        .update("webhook_logs")
+        .set(serde_json::json!({ "next_attempt_at": lease }))
+        .map_err(|e| {
+            eprintln!("Serialization error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?
+        .eq("id", id)
+        .eq("next_attempt_at", scheduled)
+        .returning("id")
+        .execute::<serde_json::Value>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(!claimed.is_empty())
+}
+
+pub async fn list_dead_letters(
+    state: &AppState,
+    webhook_id: &str,
+    limit: Option<u32>,
+    offset: Option<u32>,
+) -> Result<Vec<WebhookLog>, crate::ApiError> {
+    let mut query = state
+        .supabase
+        .database()
+        .from("webhook_logs")
+        .select("*")
+        .eq("webhook_id", webhook_id)
+        .eq("dead_letter", "true")
+        .order("created_at", OrderDirection::Descending)
+        .limit(limit.unwrap_or(50).min(100));
+
+    if let Some(offset) = offset {
+        query = query.offset(offset);
+    }
+
+    let logs = query
+        .execute::<WebhookLog>()
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            crate::ApiError::InternalServerError
+        })?;
+
+    Ok(logs)
+}
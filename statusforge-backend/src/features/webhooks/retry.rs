@@ -0,0 +1,73 @@
+//! Durable retries. An automatic delivery is logged as pending, due at once,
+//! before it is sent, and a failed one is logged with `next_attempt_at`; the
+//! `webhook-retries` job sends either once that is due, so neither new
+//! events nor pending retries are lost on restarts. Attempt `n` is followed by a
+//! wait of `RETRY_BASE_SECONDS * 2^(n-1)`, capped at `RETRY_MAX_SECONDS`;
+//! after `MAX_ATTEMPTS` the event is dead-lettered. A webhook that fails
+//! `AUTO_DISABLE_AFTER` attempts in a row is disabled and the owners and
+//! admins of its organization are emailed.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::AppState;
+
+use super::{delivery, repository, service};
+
+pub const MAX_ATTEMPTS: i32 = 8;
+
+pub const RETRY_BASE_SECONDS: i64 = 30;
+
+pub const RETRY_MAX_SECONDS: i64 = 6 * 60 * 60;
+
+pub const AUTO_DISABLE_AFTER: i32 = 15;
+
+/// How long a claimed retry is hidden from other runs. Should the process
+/// stop mid-delivery, the retry is picked up again after this.
+pub const CLAIM_SECONDS: i64 = 120;
+
+/// Retries sent per run.
+const BATCH_SIZE: u32 = 100;
+
+/// The wait after failed attempt `attempt`.
+pub fn backoff(attempt: i32) -> Duration {
+    let exponent = (attempt - 1).clamp(0, 30) as u32;
+    Duration::seconds(RETRY_BASE_SECONDS.saturating_mul(1 << exponent).min(RETRY_MAX_SECONDS))
+}
+
+/// When failed attempt `attempt` is retried; `None` when it was the last.
+pub fn next_attempt_at(attempt: i32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    (attempt < MAX_ATTEMPTS).then(|| now + backoff(attempt))
+}
+
+pub fn should_disable(consecutive_failures: i32) -> bool {
+    consecutive_failures >= AUTO_DISABLE_AFTER
+}
+
+/// Sends every retry that is due. Each is claimed first, so concurrent runs
+/// (several API instances) do not send it twice.
+pub async fn run(state: AppState) -> Result<(), crate::ApiError> {
+    let now = Utc::now();
+    let due = repository::list_due_retries(&state, &now.to_rfc3339(), BATCH_SIZE).await?;
+    if due.is_empty() {
+        return Ok(());
+    }
+
+    let client = delivery::client().map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    let lease = (now + Duration::seconds(CLAIM_SECONDS)).to_rfc3339();
+    for log in due {
+        let Some(ref scheduled) = log.next_attempt_at else {
+            continue;
+        };
+        if !repository::claim_retry(&state, &log.id, scheduled, &lease).await? {
+            continue;
+        }
+        if let Err(e) = service::send_retry(&state, &client, &log).await {
+            eprintln!("Failed to retry webhook delivery {}: {:?}", log.id, e);
+        }
+    }
+
+    Ok(())
+}
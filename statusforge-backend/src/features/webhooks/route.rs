@@ -0,0 +1,96 @@
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::Value;
+
+use crate::{AppState, ApiError};
+
+use super::{service, CreateWebhook, ListWebhookLogsQuery, UpdateWebhook};
+
+async fn list_webhooks(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let webhooks = service::list_webhooks(&state, &project_id).await?;
+    Ok(Json(serde_json::to_value(webhooks).unwrap()))
+}
+
+async fn create_webhook(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(data): Json<CreateWebhook>,
+) -> Result<Json<Value>, ApiError> {
+    let webhook = service::create_webhook(&state, &project_id, data).await?;
+    Ok(Json(serde_json::to_value(webhook).unwrap()))
+}
+
+async fn get_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let webhook = service::get_webhook(&state, &id).await?;
+    Ok(Json(serde_json::to_value(webhook).unwrap()))
+}
+
+async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(data): Json<UpdateWebhook>,
+) -> Result<Json<Value>, ApiError> {
+    let webhook = service::update_webhook(&state, &id, data).await?;
+    Ok(Json(serde_json::to_value(webhook).unwrap()))
+}
+
+async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    service::delete_webhook(&state, &id).await?;
+    Ok(Json(serde_json::json!({ "message": "Webhook deleted" })))
+}
+
+async fn list_logs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ListWebhookLogsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let logs = service::list_logs(&state, &id, query).await?;
+    Ok(Json(serde_json::to_value(logs).unwrap()))
+}
+
+async fn list_dead_letters(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ListWebhookLogsQuery>,
+) -> Result<Json<Value>, ApiError> {
+    let logs = service::list_dead_letters(&state, &id, query).await?;
+    Ok(Json(serde_json::to_value(logs).unwrap()))
+}
+
+async fn redeliver(
+    State(state): State<AppState>,
+    Path((id, log_id)): Path<(String, String)>,
+) -> Result<Json<Value>, ApiError> {
+    let log = service::redeliver(&state, &id, &log_id).await?;
+    Ok(Json(serde_json::to_value(log).unwrap()))
+}
+
+async fn send_test(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let log = service::send_test(&state, &id).await?;
+    Ok(Json(serde_json::to_value(log).unwrap()))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/projects/{project_id}/webhooks", get(list_webhooks).post(create_webhook))
+        .route("/webhooks/{id}", get(get_webhook).put(update_webhook).delete(delete_webhook))
+        .route("/webhooks/{id}/logs", get(list_logs))
+        .route("/webhooks/{id}/logs/{log_id}/redeliver", post(redeliver))
+        .route("/webhooks/{id}/dead-letters", get(list_dead_letters))
+        .route("/webhooks/{id}/test", post(send_test))
+}
@@ -0,0 +1,431 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::features::alerts::{self, message::AlertEvent};
+use crate::features::ingest::{self, Log};
+use crate::features::projects::{self, Project};
+use crate::features::status_pages::subscriptions::validate_webhook_url;
+use crate::features::{auth, organizations};
+use crate::shared::mailer::{self, Email, Mailer};
+use crate::AppState;
+
+use super::delivery::{self, Attempt};
+use super::retry;
+use super::events::{self, WEBHOOK_EVENTS};
+use super::{repository, CreateWebhook, ListWebhookLogsQuery, UpdateWebhook, Webhook, WebhookEvent, WebhookLog};
+
+pub const MIN_SECRET_LEN: usize = 16;
+
+pub const MAX_SECRET_LEN: usize = 256;
+
+fn validate_secret(secret: &str) -> Result<(), crate::ApiError> {
+    let length = secret.chars().count();
+    if !(MIN_SECRET_LEN..=MAX_SECRET_LEN).contains(&length) {
+        return Err(crate::ApiError::BadRequest(format!(
+            "Webhook secret must be {} to {} characters",
+            MIN_SECRET_LEN, MAX_SECRET_LEN
+        )));
+    }
+    Ok(())
+}
+
+fn new_secret() -> String {
+    format!("whsec_{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+pub async fn list_webhooks(state: &AppState, project_id: &str) -> Result<Vec<Webhook>, crate::ApiError> {
+    repository::list_by_project(state, project_id).await
+}
+
+pub async fn get_webhook(state: &AppState, id: &str) -> Result<Webhook, crate::ApiError> {
+    repository::get_by_id(state, id).await
+}
+
+pub async fn create_webhook(state: &AppState, project_id: &str, data: CreateWebhook) -> Result<Webhook, crate::ApiError> {
+    let url = data.url.trim().to_string();
+    validate_webhook_url(&url).map_err(crate::ApiError::BadRequest)?;
+    let events = data
+        .events
+        .clone()
+        .unwrap_or_else(|| WEBHOOK_EVENTS.iter().map(|e| e.to_string()).collect());
+    events::validate_events(&events).map_err(crate::ApiError::BadRequest)?;
+    if let Some(ref secret) = data.secret {
+        validate_secret(secret)?;
+    }
+
+    let normalized = CreateWebhook {
+        url,
+        events: Some(events),
+        secret: Some(data.secret.unwrap_or_else(new_secret)),
+        enabled: Some(data.enabled.unwrap_or(true)),
+    };
+    repository::create(state, project_id, &normalized).await
+}
+
+pub async fn update_webhook(state: &AppState, id: &str, data: UpdateWebhook) -> Result<Webhook, crate::ApiError> {
+    let url = data.url.as_deref().map(str::trim).map(str::to_string);
+    if let Some(ref url) = url {
+        validate_webhook_url(url).map_err(crate::ApiError::BadRequest)?;
+    }
+    if let Some(ref events) = data.events {
+        events::validate_events(events).map_err(crate::ApiError::BadRequest)?;
+    }
+    if let Some(ref secret) = data.secret {
+        validate_secret(secret)?;
+    }
+
+    let current = repository::get_by_id(state, id).await?;
+    let enable = data.enabled == Some(true);
+    let webhook = repository::update(state, id, &UpdateWebhook { url, ..data }).await?;
+    // Enabling a webhook that was disabled for failing gives it a fresh start.
+    if enable && current.disabled_at.is_some() {
+        let reset = serde_json::json!({ "consecutive_failures": 0, "disabled_at": null, "disabled_reason": null });
+        return repository::update_health(state, id, reset).await;
+    }
+    Ok(webhook)
+}
+
+pub async fn delete_webhook(state: &AppState, id: &str) -> Result<(), crate::ApiError> {
+    repository::delete(state, id).await
+}
+
+pub async fn list_logs(state: &AppState, id: &str, query: ListWebhookLogsQuery) -> Result<Vec<WebhookLog>, crate::ApiError> {
+    repository::get_by_id(state, id).await?;
+    repository::list_logs(state, id, query.limit, query.offset).await
+}
+
+/// The `webhook_logs` row of one attempt.
+pub fn log_entry(webhook: &Webhook, event: &WebhookEvent, payload: &serde_json::Value, attempt_number: i32, attempt: &Attempt) -> serde_json::Value {
+    serde_json::json!({
+        "webhook_id": webhook.id,
+        "event_type": event.event,
+        "event_id": event.id,
+        "payload": payload,
+        "attempt": attempt_number,
+        "status_code": attempt.status_code,
+        "response_body": attempt.response_body,
+        "error_message": attempt.error_message,
+        "duration_ms": attempt.duration_ms,
+    })
+}
+
+/// How an attempt came about. Only automatic ones are retried and count
+/// towards disabling the webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Automatic,
+    /// A redelivery or test event asked for through the API.
+    Manual,
+}
+
+/// The `webhook_logs` row of a delivery that is yet to be sent. It is due
+/// at once, so the `webhook-retries` job sends it should its sender stop
+/// before the attempt.
+pub fn pending_entry(webhook: &Webhook, event: &WebhookEvent, payload: &serde_json::Value, now: DateTime<Utc>) -> serde_json::Value {
+    serde_json::json!({
+        "webhook_id": webhook.id,
+        "event_type": event.event,
+        "event_id": event.id,
+        "payload": payload,
+        "attempt": 1,
+        "next_attempt_at": now.to_rfc3339(),
+    })
+}
+
+/// Sends `payload` once and returns the fields logging the attempt, with
+/// its retry scheduled or the event dead-lettered when an automatic
+/// attempt fails.
+async fn send_attempt(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    event: &WebhookEvent,
+    payload: &serde_json::Value,
+    attempt_number: i32,
+    trigger: Trigger,
+) -> (bool, serde_json::Value) {
+    let now = Utc::now();
+    let attempt = delivery::send(client, webhook, event, &payload.to_string(), now).await;
+    if !attempt.succeeded() {
+        eprintln!("Webhook {} delivery failed: {}", webhook.id, attempt.error_message.as_deref().unwrap_or_default());
+    }
+
+    let mut entry = log_entry(webhook, event, payload, attempt_number, &attempt);
+    entry["next_attempt_at"] = serde_json::Value::Null;
+    if trigger == Trigger::Automatic && !attempt.succeeded() {
+        match retry::next_attempt_at(attempt_number, now) {
+            Some(at) => entry["next_attempt_at"] = serde_json::json!(at.to_rfc3339()),
+            None => entry["dead_letter"] = serde_json::json!(true),
+        }
+    }
+    (attempt.succeeded(), entry)
+}
+
+/// Sends `payload` once and logs the attempt, scheduling its retry or
+/// dead-lettering it when an automatic attempt fails.
+#[allow(clippy::too_many_arguments)]
+pub async fn attempt_delivery(
+    state: &AppState,
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    event: &WebhookEvent,
+    payload: &serde_json::Value,
+    attempt_number: i32,
+    trigger: Trigger,
+    redelivery_of: Option<&str>,
+) -> Result<WebhookLog, crate::ApiError> {
+    let (succeeded, mut entry) = send_attempt(client, webhook, event, payload, attempt_number, trigger).await;
+    if let Some(id) = redelivery_of {
+        entry["redelivery_of"] = serde_json::json!(id);
+    }
+    let log = repository::create_log(state, entry).await?;
+
+    if trigger == Trigger::Automatic {
+        record_outcome(state, webhook, succeeded).await;
+    }
+    Ok(log)
+}
+
+/// Sends the claimed pending delivery `log` and writes the attempt into it.
+/// Returns whether it succeeded.
+async fn send_pending(
+    state: &AppState,
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    event: &WebhookEvent,
+    payload: &serde_json::Value,
+    log: &WebhookLog,
+) -> Result<bool, crate::ApiError> {
+    let (succeeded, entry) = send_attempt(client, webhook, event, payload, log.attempt, Trigger::Automatic).await;
+    repository::update_log(state, &log.id, entry).await?;
+    record_outcome(state, webhook, succeeded).await;
+    Ok(succeeded)
+}
+
+/// Logs the delivery as pending before sending it, so an event is not lost
+/// when the process stops in between. The pending row is claimed like a
+/// retry; should the `webhook-retries` job claim it first, it sends it.
+async fn deliver_to(
+    state: &AppState,
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    event: &WebhookEvent,
+    payload: &serde_json::Value,
+) -> Result<bool, crate::ApiError> {
+    let now = Utc::now();
+    let log = repository::create_log(state, pending_entry(webhook, event, payload, now)).await?;
+    let Some(ref scheduled) = log.next_attempt_at else {
+        return Err(crate::ApiError::InternalServerError);
+    };
+    let lease = (now + Duration::seconds(retry::CLAIM_SECONDS)).to_rfc3339();
+    if !repository::claim_retry(state, &log.id, scheduled, &lease).await? {
+        return Ok(false);
+    }
+    send_pending(state, client, webhook, event, payload, &log).await
+}
+
+/// Keeps `consecutive_failures` up to date and disables the webhook when it
+/// reaches `retry::AUTO_DISABLE_AFTER`.
+async fn record_outcome(state: &AppState, webhook: &Webhook, succeeded: bool) {
+    let update = if succeeded {
+        if webhook.consecutive_failures == 0 {
+            return;
+        }
+        serde_json::json!({ "consecutive_failures": 0 })
+    } else {
+        let failures = webhook.consecutive_failures + 1;
+        if webhook.enabled && retry::should_disable(failures) {
+            serde_json::json!({
+                "consecutive_failures": failures,
+                "enabled": false,
+                "disabled_at": Utc::now().to_rfc3339(),
+                "disabled_reason": format!("Disabled after {} consecutive failed deliveries", failures),
+            })
+        } else {
+            serde_json::json!({ "consecutive_failures": failures })
+        }
+    };
+
+    match repository::update_health(state, &webhook.id, update).await {
+        Ok(updated) if webhook.enabled && !updated.enabled => {
+            if let Err(e) = notify_disabled(state, mailer::from_config(&state.config).as_ref(), &updated).await {
+                eprintln!("Failed to notify about disabled webhook {}: {:?}", webhook.id, e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("Failed to update webhook {}: {:?}", webhook.id, e),
+    }
+}
+
+/// Sends `event` to every enabled webhook of its project subscribed to it.
+/// Returns how many deliveries succeeded; failed ones are retried by the
+/// `webhook-retries` job.
+pub async fn deliver_event(state: &AppState, event: &WebhookEvent) -> Result<usize, crate::ApiError> {
+    let webhooks: Vec<Webhook> = repository::list_enabled(state, &event.project_id)
+        .await?
+        .into_iter()
+        .filter(|webhook| webhook.events.contains(&event.event))
+        .collect();
+    if webhooks.is_empty() {
+        return Ok(0);
+    }
+
+    let client = delivery::client().map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    let payload = delivery::payload(event);
+
+    let mut delivered = 0;
+    for webhook in &webhooks {
+        // A lost log entry must not keep the event from the other webhooks.
+        match deliver_to(state, &client, webhook, event, &payload).await {
+            Ok(true) => delivered += 1,
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to record webhook delivery: {:?}", e),
+        }
+    }
+    Ok(delivered)
+}
+
+/// Sends the next attempt of a claimed retry, or the first one of a pending
+/// delivery that was never sent. Retries of a webhook that was disabled or
+/// deleted meanwhile are dead-lettered instead.
+pub async fn send_retry(state: &AppState, client: &reqwest::Client, log: &WebhookLog) -> Result<(), crate::ApiError> {
+    let webhook = match repository::get_by_id(state, &log.webhook_id).await {
+        Ok(webhook) => Some(webhook),
+        Err(crate::ApiError::NotFound) => None,
+        Err(e) => return Err(e),
+    };
+    let resend = webhook.filter(|webhook| webhook.enabled).and_then(|webhook| {
+        let payload = log.payload.clone()?;
+        let event = delivery::event_from_payload(&payload)?;
+        Some((webhook, event, payload))
+    });
+
+    let Some((webhook, event, payload)) = resend else {
+        return repository::update_log(state, &log.id, serde_json::json!({ "next_attempt_at": null, "dead_letter": true })).await;
+    };
+    if log.is_pending() {
+        return send_pending(state, client, &webhook, &event, &payload, log).await.map(|_| ());
+    }
+    attempt_delivery(state, client, &webhook, &event, &payload, log.attempt + 1, Trigger::Automatic, None).await?;
+    repository::update_log(state, &log.id, serde_json::json!({ "next_attempt_at": null })).await
+}
+
+pub async fn list_dead_letters(state: &AppState, id: &str, query: ListWebhookLogsQuery) -> Result<Vec<WebhookLog>, crate::ApiError> {
+    repository::get_by_id(state, id).await?;
+    repository::list_dead_letters(state, id, query.limit, query.offset).await
+}
+
+/// Sends the payload of a past delivery again, with the same event id. A
+/// successful redelivery takes the original off the dead-letter list and
+/// cancels its pending retry.
+pub async fn redeliver(state: &AppState, id: &str, log_id: &str) -> Result<WebhookLog, crate::ApiError> {
+    let webhook = repository::get_by_id(state, id).await?;
+    let original = repository::get_log(state, log_id).await?;
+    if original.webhook_id != webhook.id {
+        return Err(crate::ApiError::NotFound);
+    }
+    let (event, payload) = original
+        .payload
+        .clone()
+        .and_then(|payload| Some((delivery::event_from_payload(&payload)?, payload)))
+        .ok_or_else(|| crate::ApiError::BadRequest("This delivery has no stored payload to send again".to_string()))?;
+
+    let client = delivery::client().map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    let log = attempt_delivery(state, &client, &webhook, &event, &payload, 1, Trigger::Manual, Some(&original.id)).await?;
+    if log.succeeded() && (original.dead_letter || original.next_attempt_at.is_some()) {
+        repository::update_log(state, &original.id, serde_json::json!({ "next_attempt_at": null, "dead_letter": false })).await?;
+    }
+    Ok(log)
+}
+
+/// Sends a `test` event to the webhook, enabled or not, and returns the
+/// logged attempt.
+pub async fn send_test(state: &AppState, id: &str) -> Result<WebhookLog, crate::ApiError> {
+    let webhook = repository::get_by_id(state, id).await?;
+    let event = events::test(&webhook, Utc::now());
+    let client = delivery::client().map_err(|e| {
+        eprintln!("{}", e);
+        crate::ApiError::InternalServerError
+    })?;
+    attempt_delivery(state, &client, &webhook, &event, &delivery::payload(&event), 1, Trigger::Manual, None).await
+}
+
+/// The email telling an organization admin that `webhook` was disabled.
+pub fn disabled_email(public_url: &str, project: &Project, webhook: &Webhook, to: &str) -> Email {
+    let base = public_url.trim_end_matches('/');
+    let body = format!(
+        "The webhook {url} of project {project} was disabled: {reason}.\n\n\
+         Events that could not be delivered are listed at {base}/webhooks/{id}/dead-letters \
+         and can be sent again once the endpoint works. To resume deliveries, enable the webhook \
+         with PUT {base}/webhooks/{id} and {{\"enabled\": true}}.\n",
+        url = webhook.url,
+        project = project.name,
+        reason = webhook.disabled_reason.as_deref().unwrap_or("too many failed deliveries"),
+        base = base,
+        id = webhook.id,
+    );
+    Email {
+        to: to.to_string(),
+        subject: format!("[{}] Webhook disabled after failed deliveries", project.name),
+        body,
+        unsubscribe_url: None,
+    }
+}
+
+/// Emails the owners and admins of the webhook's organization that it was
+/// disabled. Returns how many emails were sent.
+pub async fn notify_disabled(state: &AppState, mailer: &dyn Mailer, webhook: &Webhook) -> Result<usize, crate::ApiError> {
+    let project = projects::repository::get_by_id(state, &webhook.project_id).await?;
+    let admins = organizations::repository::list_admin_ids(state, &project.organization_id).await?;
+
+    let mut sent = 0;
+    for user_id in admins {
+        let Some(email) = auth::user_email(state, &user_id).await? else {
+            continue;
+        };
+        match mailer.send(&disabled_email(&state.config.public_url, &project, webhook, &email)).await {
+            Ok(()) => sent += 1,
+            Err(e) => eprintln!("Failed to email {}: {}", email, e),
+        }
+    }
+    Ok(sent)
+}
+
+/// Delivers `event` in the background, so the check or request that caused
+/// it does not wait on webhook endpoints.
+pub fn dispatch(state: &AppState, event: WebhookEvent) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = deliver_event(&state, &event).await {
+            eprintln!("Failed to deliver {} event: {:?}", event.event, e);
+        }
+    });
+}
+
+/// Called for every ingested log: an error that completes a spike is sent
+/// to webhooks and alerts as `error_spike`.
+pub fn watch_log(state: &AppState, log: &Log) {
+    if !events::is_error_level(&log.level) {
+        return;
+    }
+
+    let state = state.clone();
+    let log = log.clone();
+    tokio::spawn(async move {
+        let since = Utc::now() - Duration::minutes(events::ERROR_SPIKE_MINUTES);
+        let limit = events::ERROR_SPIKE_THRESHOLD as u32 + 1;
+        match ingest::repository::count_errors_since(&state, &log.project_id, &since.to_rfc3339(), limit).await {
+            Ok(errors) if events::is_error_spike(errors) => {
+                dispatch(&state, events::error_spike(&log, errors, Utc::now()));
+                let event = AlertEvent::ErrorSpike { latest: log.clone(), errors, window_minutes: events::ERROR_SPIKE_MINUTES };
+                alerts::dispatch(&state, &log.project_id, event);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to count errors: {:?}", e),
+        }
+    });
+}
@@ -6,10 +6,12 @@ use serde_json::{json, Value};
 use supabase::Client;
 
 pub use shared::ApiError;
+use shared::config::Config;
 
 #[derive(Clone)]
 pub struct AppState {
     pub supabase: Client,
+    pub config: Config,
 }
 
 async fn root() -> Result<Json<Value>, ApiError> {
@@ -28,7 +30,7 @@ async fn health_check(State(state): State<AppState>) -> Json<Value> {
 }
 
 pub fn router(state: AppState) -> Router {
-    Router::new()
+    let app = Router::new()
         .route("/", get(root))
         .route("/health", get(health_check))
         .nest("/auth", features::auth::routes())
@@ -36,5 +38,15 @@ pub fn router(state: AppState) -> Router {
         .merge(features::projects::routes())
         .merge(features::ingest::routes())
         .merge(features::monitors::routes())
-        .with_state(state)
+        .merge(features::incidents::routes())
+        .merge(features::maintenance::routes())
+        .merge(features::status_pages::routes())
+        .merge(features::webhooks::routes())
+        .merge(features::alerts::routes())
+        .with_state(state.clone());
+
+    // Custom status page domains are rewritten before `app` routes them.
+    Router::new()
+        .fallback_service(app)
+        .layer(axum::middleware::from_fn_with_state(state, features::status_pages::domains::resolve_host))
 }
@@ -1,3 +1,5 @@
+use std::net::SocketAddr;
+
 use statusforge_backend::{router, AppState};
 
 #[tokio::main]
@@ -9,10 +11,59 @@ async fn main() {
     let supabase = statusforge_backend::shared::supabase::create_client(&config)
         .expect("Failed to create Supabase client");
 
-    let state = AppState { supabase };
+    let state = AppState { supabase, config: config.clone() };
+
+    // Jobs work across all projects, so they run as the service role.
+    match statusforge_backend::shared::supabase::create_client_with_secret(&config) {
+        Ok(supabase) => spawn_jobs(AppState { supabase, config: config.clone() }),
+        Err(_) => eprintln!(
+            "SUPABASE_SECRET_KEY is not set; scheduled checks, heartbeat sweeps, rollups, pause expiry and webhook retries are disabled"
+        ),
+    }
+
     let app = router(state);
 
     let addr = format!("0.0.0.0:{}", config.port);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    // Client addresses are needed for status page IP allowlists.
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
+
+fn spawn_jobs(state: AppState) {
+    statusforge_backend::shared::jobs::spawn_every(
+        "monitor-checks",
+        std::time::Duration::from_secs(15),
+        state.clone(),
+        statusforge_backend::features::monitors::scheduler::run,
+    );
+
+    statusforge_backend::shared::jobs::spawn_every(
+        "heartbeat-sweep",
+        std::time::Duration::from_secs(30),
+        state.clone(),
+        statusforge_backend::features::monitors::heartbeat::sweep,
+    );
+
+    statusforge_backend::shared::jobs::spawn_every(
+        "result-rollups",
+        std::time::Duration::from_secs(15 * 60),
+        state.clone(),
+        statusforge_backend::features::monitors::rollups::run,
+    );
+
+    statusforge_backend::shared::jobs::spawn_every(
+        "pause-expiry",
+        std::time::Duration::from_secs(60),
+        state.clone(),
+        statusforge_backend::features::monitors::pause::resume_expired,
+    );
+
+    statusforge_backend::shared::jobs::spawn_every(
+        "webhook-retries",
+        std::time::Duration::from_secs(30),
+        state,
+        statusforge_backend::features::webhooks::retry::run,
+    );
 }
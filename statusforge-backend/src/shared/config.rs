@@ -4,6 +4,64 @@ pub struct Config {
     pub supabase_url: String,
     pub supabase_publishable_key: String,
     pub supabase_secret_key: Option<String>,
+    pub rdap_base_url: String,
+    /// Days raw `monitor_results` rows are kept before only rollups remain.
+    pub result_retention_days: u32,
+    /// Hosts the API itself is served on. Requests for any other host are
+    /// treated as custom status page domains; empty disables that.
+    pub app_hosts: Vec<String>,
+    /// DNS-over-HTTPS JSON endpoint used to verify custom domains.
+    pub dns_over_https_url: String,
+    /// Base URL of the API as seen by users, for links in emails.
+    pub public_url: String,
+    /// Key for signed tokens such as unsubscribe links.
+    pub signing_secret: String,
+    /// `smtp://` or `smtps://` URL of the mail server; without it emails
+    /// are only logged.
+    pub smtp_url: Option<String>,
+    pub mail_from: String,
+    /// Take the client IP from the last `X-Forwarded-For` entry, for when
+    /// the API runs behind a reverse proxy that sets it.
+    pub trust_forwarded_for: bool,
+    /// Let status page webhook subscribers point at private and local
+    /// addresses, for local development.
+    pub allow_private_webhooks: bool,
+    /// Slack and Discord alert webhook URLs must start with these.
+    pub slack_webhook_base_url: String,
+    pub discord_webhook_base_url: String,
+    /// Base URL of the Twilio-compatible SMS API; SMS alerts also need the
+    /// account SID, auth token and sender number.
+    pub sms_api_url: String,
+    pub sms_account_sid: Option<String>,
+    pub sms_auth_token: Option<String>,
+    pub sms_from: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            port: 3001,
+            supabase_url: String::new(),
+            supabase_publishable_key: String::new(),
+            supabase_secret_key: None,
+            rdap_base_url: "https://rdap.org".to_string(),
+            result_retention_days: 30,
+            app_hosts: Vec::new(),
+            dns_over_https_url: "https://dns.google/resolve".to_string(),
+            public_url: "http://localhost:3001".to_string(),
+            signing_secret: format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple()),
+            smtp_url: None,
+            mail_from: "StatusForge <noreply@statusforge.pl>".to_string(),
+            trust_forwarded_for: false,
+            allow_private_webhooks: false,
+            slack_webhook_base_url: "https://hooks.slack.com/services/".to_string(),
+            discord_webhook_base_url: "https://discord.com/api/webhooks/".to_string(),
+            sms_api_url: "https://api.twilio.com".to_string(),
+            sms_account_sid: None,
+            sms_auth_token: None,
+            sms_from: None,
+        }
+    }
 }
 
 impl Config {
@@ -14,13 +72,30 @@ impl Config {
     }
 }
 
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, Debug, Default)]
 pub struct ConfigEnv {
     pub port: Option<u16>,
     pub supabase_url: String,
     pub supabase_publishable_key: Option<String>,
     pub supabase_anon_key: Option<String>,
     pub supabase_secret_key: Option<String>,
+    pub rdap_base_url: Option<String>,
+    pub result_retention_days: Option<u32>,
+    /// Comma-separated.
+    pub app_hosts: Option<String>,
+    pub dns_over_https_url: Option<String>,
+    pub public_url: Option<String>,
+    pub signing_secret: Option<String>,
+    pub smtp_url: Option<String>,
+    pub mail_from: Option<String>,
+    pub trust_forwarded_for: Option<bool>,
+    pub allow_private_webhooks: Option<bool>,
+    pub slack_webhook_base_url: Option<String>,
+    pub discord_webhook_base_url: Option<String>,
+    pub sms_api_url: Option<String>,
+    pub sms_account_sid: Option<String>,
+    pub sms_auth_token: Option<String>,
+    pub sms_from: Option<String>,
 }
 
 impl From<ConfigEnv> for Config {
@@ -29,11 +104,63 @@ impl From<ConfigEnv> for Config {
             .supabase_publishable_key
             .or(e.supabase_anon_key)
             .expect("Missing env: SUPABASE_PUBLISHABLE_KEY or SUPABASE_ANON_KEY");
+        // Tokens signed with a random key stop verifying after a restart.
+        let signing_secret = e
+            .signing_secret
+            .or_else(|| e.supabase_secret_key.clone())
+            .unwrap_or_else(|| {
+                eprintln!("SIGNING_SECRET is not set; signed links will not survive a restart");
+                Config::default().signing_secret
+            });
         Config {
             port: e.port.unwrap_or(3001),
             supabase_url: e.supabase_url,
             supabase_publishable_key,
             supabase_secret_key: e.supabase_secret_key,
+            rdap_base_url: e
+                .rdap_base_url
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| Config::default().rdap_base_url),
+            // Rollups are recomputed from raw rows of the last couple of days.
+            result_retention_days: e
+                .result_retention_days
+                .unwrap_or(Config::default().result_retention_days)
+                .max(7),
+            app_hosts: e
+                .app_hosts
+                .map(|hosts| {
+                    hosts
+                        .split(',')
+                        .map(|host| host.trim().to_ascii_lowercase())
+                        .filter(|host| !host.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            dns_over_https_url: e
+                .dns_over_https_url
+                .unwrap_or_else(|| Config::default().dns_over_https_url),
+            public_url: e
+                .public_url
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| Config::default().public_url),
+            signing_secret,
+            smtp_url: e.smtp_url.filter(|url| !url.trim().is_empty()),
+            mail_from: e.mail_from.unwrap_or_else(|| Config::default().mail_from),
+            trust_forwarded_for: e.trust_forwarded_for.unwrap_or(false),
+            allow_private_webhooks: e.allow_private_webhooks.unwrap_or(false),
+            slack_webhook_base_url: e
+                .slack_webhook_base_url
+                .unwrap_or_else(|| Config::default().slack_webhook_base_url),
+            discord_webhook_base_url: e
+                .discord_webhook_base_url
+                .unwrap_or_else(|| Config::default().discord_webhook_base_url),
+            sms_api_url: e
+                .sms_api_url
+                .map(|url| url.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| Config::default().sms_api_url),
+            sms_account_sid: e.sms_account_sid.filter(|sid| !sid.trim().is_empty()),
+            sms_auth_token: e.sms_auth_token.filter(|token| !token.trim().is_empty()),
+            sms_from: e.sms_from.filter(|from| !from.trim().is_empty()),
         }
     }
 }
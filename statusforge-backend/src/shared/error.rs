@@ -5,6 +5,8 @@ use serde_json::json;
 pub enum ApiError {
     NotFound,
     BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
     InternalServerError,
 }
 
@@ -13,6 +15,8 @@ impl IntoResponse for ApiError {
         let (status, error_message) = match self {
             ApiError::NotFound => (StatusCode::NOT_FOUND, "Data not found".to_string()),
             ApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
             ApiError::InternalServerError => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string())
             }
@@ -0,0 +1,23 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::AppState;
+
+/// Runs `job` every `period` on the Tokio runtime for the lifetime of the
+/// process. Errors are logged and the loop keeps going.
+pub fn spawn_every<F, Fut>(name: &'static str, period: Duration, state: AppState, job: F)
+where
+    F: Fn(AppState) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), crate::ApiError>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = job(state.clone()).await {
+                eprintln!("Job {} failed: {:?}", name, e);
+            }
+        }
+    });
+}
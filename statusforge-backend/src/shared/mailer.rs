@@ -0,0 +1,95 @@
+//! Outgoing email. `SmtpMailer` sends through `SMTP_URL`; without it
+//! `LogMailer` only prints messages, so flows that send email can be tried
+//! locally, and tests can pass their own `Mailer`.
+
+use async_trait::async_trait;
+use lettre::message::header::{ContentType, HeaderName, HeaderValue};
+use lettre::message::Mailbox;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use super::config::Config;
+
+/// A plain-text email.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+    /// Sent as `List-Unsubscribe` so mail clients can offer one-click
+    /// unsubscribe.
+    pub unsubscribe_url: Option<String>,
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, email: &Email) -> Result<(), String>;
+}
+
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        eprintln!("Email to {}: {}\n{}", email.to, email.subject, email.body);
+        Ok(())
+    }
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(smtp_url: &str, from: &str) -> Result<Self, String> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(smtp_url)
+            .map_err(|e| format!("Invalid SMTP_URL: {}", e))?
+            .build();
+        let from = from.parse().map_err(|e| format!("Invalid MAIL_FROM: {}", e))?;
+        Ok(SmtpMailer { transport, from })
+    }
+
+    fn message(&self, email: &Email) -> Result<Message, String> {
+        let to: Mailbox = email.to.parse().map_err(|e| format!("Invalid recipient {}: {}", email.to, e))?;
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(email.subject.as_str())
+            .header(ContentType::TEXT_PLAIN);
+        if let Some(ref url) = email.unsubscribe_url {
+            builder = builder
+                .raw_header(HeaderValue::new(HeaderName::new_from_ascii_str("List-Unsubscribe"), format!("<{}>", url)))
+                .raw_header(HeaderValue::new(
+                    HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                    "List-Unsubscribe=One-Click".to_string(),
+                ));
+        }
+        builder.body(email.body.clone()).map_err(|e| format!("Invalid email: {}", e))
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        let message = self.message(email)?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("SMTP error: {}", e))
+    }
+}
+
+/// The mailer configured by `SMTP_URL`, falling back to logging.
+pub fn from_config(config: &Config) -> Box<dyn Mailer> {
+    let Some(ref smtp_url) = config.smtp_url else {
+        return Box::new(LogMailer);
+    };
+    match SmtpMailer::new(smtp_url, &config.mail_from) {
+        Ok(mailer) => Box::new(mailer),
+        Err(e) => {
+            eprintln!("{}; emails will only be logged", e);
+            Box::new(LogMailer)
+        }
+    }
+}
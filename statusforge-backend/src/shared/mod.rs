@@ -1,5 +1,9 @@
 pub mod config;
 pub mod error;
+pub mod jobs;
+pub mod mailer;
+pub mod net;
+pub mod signing;
 pub mod supabase;
 pub mod utils;
 
@@ -0,0 +1,71 @@
+//! Outgoing requests to URLs given by anonymous users, such as status page
+//! webhook subscribers. Those must not reach the API's own network.
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+/// Loopback, private, link-local, unspecified and similar addresses that are
+/// not on the public internet.
+pub fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // Carrier-grade NAT, 100.64.0.0/10.
+                || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_internal(IpAddr::V4(mapped)),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local, fc00::/7.
+                    || ip.segments()[0] & 0xfe00 == 0xfc00
+                    // Link-local, fe80::/10.
+                    || ip.segments()[0] & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// Resolves the host of `url`, failing if it has no address or any of its
+/// addresses is internal.
+pub async fn resolve_public(url: &reqwest::Url) -> Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or_else(|| format!("URL has no host: {}", url))?;
+    let port = url.port_or_known_default().unwrap_or(443);
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Cannot resolve {}: {}", host, e))?
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(format!("Cannot resolve {}", host));
+    }
+    if addrs.iter().any(|addr| is_internal(addr.ip())) {
+        return Err(format!("{} resolves to a private or local address", host));
+    }
+    Ok(addrs)
+}
+
+/// A client for requests to `url` that connects only to the addresses checked
+/// by `resolve_public`, so the host cannot be re-pointed between the check
+/// and the request, and that does not follow redirects. With
+/// `allow_internal` the check is skipped, for local development.
+pub async fn public_client(url: &str, timeout: Duration, allow_internal: bool) -> Result<reqwest::Client, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|_| format!("Invalid URL: {}", url))?;
+    let mut builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .user_agent("StatusForge")
+        .redirect(reqwest::redirect::Policy::none());
+    if !allow_internal {
+        let addrs = resolve_public(&parsed).await?;
+        if let Some(domain) = parsed.domain() {
+            builder = builder.resolve_to_addrs(domain, &addrs);
+        }
+    }
+    builder.build().map_err(|e| format!("HTTP client error: {}", e))
+}
@@ -2,18 +2,20 @@ use supabase::Client;
 
 use super::config::Config;
 
+#[allow(clippy::result_large_err)]
 pub fn create_client(config: &Config) -> Result<Client, supabase::Error> {
     Client::new(&config.supabase_url, &config.supabase_publishable_key)
 }
 
+/// Client that authenticates with the secret key, so queries run as the
+/// service role and bypass RLS. For background jobs, which work across all
+/// projects without a user.
+#[allow(clippy::result_large_err)]
 pub fn create_client_with_secret(config: &Config) -> Result<Client, supabase::Error> {
     let secret = config
         .supabase_secret_key
         .as_deref()
         .ok_or_else(|| supabase::Error::config("SUPABASE_SECRET_KEY required"))?;
-    Client::new_with_service_role(
-        &config.supabase_url,
-        &config.supabase_publishable_key,
-        secret,
-    )
+    // `new_with_service_role` keeps sending the publishable key to PostgREST.
+    Client::new(&config.supabase_url, secret)
 }
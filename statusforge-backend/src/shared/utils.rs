@@ -1,3 +1,15 @@
 pub fn uuid_v4() -> String {
     uuid::Uuid::new_v4().to_string()
 }
+
+/// Unguessable token for secret URLs (heartbeat pings etc.).
+pub fn random_token() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// Parses a timestamp as returned by PostgREST (RFC 3339).
+pub fn parse_timestamp(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+}
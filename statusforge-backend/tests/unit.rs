@@ -1,3 +1,6 @@
+#[path = "unit/support.rs"]
+mod support;
+
 #[path = "unit/config.rs"]
 mod config;
 #[path = "unit/error.rs"]
@@ -26,3 +29,57 @@ mod monitors;
 
 #[path = "unit/monitors_models.rs"]
 mod monitors_models;
+
+#[path = "unit/monitors_checks.rs"]
+mod monitors_checks;
+
+#[path = "unit/monitors_heartbeat.rs"]
+mod monitors_heartbeat;
+
+#[path = "unit/monitors_config.rs"]
+mod monitors_config;
+
+#[path = "unit/incidents.rs"]
+mod incidents;
+
+#[path = "unit/maintenance.rs"]
+mod maintenance;
+
+#[path = "unit/monitors_bulk.rs"]
+mod monitors_bulk;
+
+#[path = "unit/monitors_manifest.rs"]
+mod monitors_manifest;
+
+#[path = "unit/monitors_anomaly.rs"]
+mod monitors_anomaly;
+
+#[path = "unit/status_pages.rs"]
+mod status_pages;
+
+#[path = "unit/status_pages_html.rs"]
+mod status_pages_html;
+
+#[path = "unit/status_pages_domains.rs"]
+mod status_pages_domains;
+
+#[path = "unit/status_pages_posts.rs"]
+mod status_pages_posts;
+
+#[path = "unit/status_pages_subscriptions.rs"]
+mod status_pages_subscriptions;
+
+#[path = "unit/status_pages_feeds.rs"]
+mod status_pages_feeds;
+
+#[path = "unit/status_pages_access.rs"]
+mod status_pages_access;
+
+#[path = "unit/webhooks.rs"]
+mod webhooks;
+
+#[path = "unit/webhooks_retries.rs"]
+mod webhooks_retries;
+
+#[path = "unit/alerts.rs"]
+mod alerts;
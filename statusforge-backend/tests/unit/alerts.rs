@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Form;
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use statusforge_backend::features::alerts::message::{self, AlertEvent, Message, Severity};
+use statusforge_backend::features::alerts::notifier::{
+    self, DiscordNotifier, EmailNotifier, Notifier, Notifiers, SlackNotifier, SmsNotifier,
+};
+use statusforge_backend::features::alerts::{channels, service, Alert};
+use statusforge_backend::features::monitors::Monitor;
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::shared::mailer::{Email, Mailer};
+
+use crate::support::{error_log, incident, monitor, serve, PostgRest};
+
+fn alert(id: &str, channel: &str, config: Value) -> Alert {
+    Alert {
+        id: id.to_string(),
+        project_id: "p1".to_string(),
+        name: format!("{} alert", channel),
+        channel: channel.to_string(),
+        config,
+        enabled: true,
+        tag_selector: None,
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+        updated_at: "2026-04-01T00:00:00Z".to_string(),
+    }
+}
+
+fn now() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 4, 10, 12, 5, 0).unwrap()
+}
+
+#[test]
+fn test_validate_email_config() {
+    let config = Config::default();
+    let valid = channels::validate_config(&config, "email", &json!({ "to": [" Ops@Acme.test ", "ops@acme.test", "dev@acme.test"] }));
+    assert_eq!(valid.unwrap(), json!({ "to": ["ops@acme.test", "dev@acme.test"] }));
+
+    assert!(channels::validate_config(&config, "email", &json!({ "to": [] })).is_err());
+    assert!(channels::validate_config(&config, "email", &json!({ "to": ["not-an-email"] })).is_err());
+    assert!(channels::validate_config(&config, "email", &json!({ "to": "ops@acme.test" })).is_err());
+    assert!(channels::validate_config(&config, "email", &json!({ "to": ["ops@acme.test"], "cc": [] })).is_err());
+    let too_many: Vec<String> = (0..=channels::MAX_EMAIL_RECIPIENTS).map(|i| format!("ops{}@acme.test", i)).collect();
+    assert!(channels::validate_config(&config, "email", &json!({ "to": too_many })).is_err());
+}
+
+#[test]
+fn test_validate_chat_config() {
+    let config = Config::default();
+    let slack = json!({ "webhook_url": "https://hooks.slack.com/services/T000/B000/XXXX" });
+    assert_eq!(channels::validate_config(&config, "slack", &slack).unwrap(), slack);
+    let discord = json!({ "webhook_url": " https://discord.com/api/webhooks/123/abc " });
+    assert_eq!(
+        channels::validate_config(&config, "discord", &discord).unwrap(),
+        json!({ "webhook_url": "https://discord.com/api/webhooks/123/abc" })
+    );
+
+    // Only the configured services are allowed.
+    assert!(channels::validate_config(&config, "slack", &json!({ "webhook_url": "https://evil.test/hook" })).is_err());
+    assert!(channels::validate_config(&config, "slack", &json!({ "webhook_url": "https://hooks.slack.com/services/" })).is_err());
+    assert!(channels::validate_config(&config, "discord", &slack).is_err());
+    assert!(channels::validate_config(&config, "slack", &json!({})).is_err());
+
+    let stand_in = Config { slack_webhook_base_url: "http://127.0.0.1:9/slack/".to_string(), ..Default::default() };
+    assert!(channels::validate_config(&stand_in, "slack", &json!({ "webhook_url": "http://127.0.0.1:9/slack/hook" })).is_ok());
+}
+
+#[test]
+fn test_validate_sms_config() {
+    let config = Config::default();
+    let valid = channels::validate_config(&config, "sms", &json!({ "to": ["+48 123-456-789", "+48123456789", "+1 (555) 010-0000"] }));
+    assert_eq!(valid.unwrap(), json!({ "to": ["+48123456789", "+15550100000"] }));
+
+    assert!(channels::normalize_phone("123456789").is_err());
+    assert!(channels::normalize_phone("+0123456789").is_err());
+    assert!(channels::normalize_phone("+4812").is_err());
+    assert!(channels::normalize_phone("+48abc456789").is_err());
+    assert!(channels::validate_config(&config, "sms", &json!({ "to": [] })).is_err());
+}
+
+#[test]
+fn test_validate_channel_and_name() {
+    assert!(channels::validate_channel("pagerduty").is_err());
+    assert!(channels::validate_config(&Config::default(), "pagerduty", &json!({})).is_err());
+    assert_eq!(channels::validate_name("  On-call  ").unwrap(), "On-call");
+    assert!(channels::validate_name(" ").is_err());
+    assert!(channels::validate_name(&"x".repeat(channels::MAX_NAME_LEN + 1)).is_err());
+}
+
+#[test]
+fn test_tag_selector_scope() {
+    assert_eq!(service::normalize_tag_selector(" env = prod , team ").unwrap(), Some("env=prod,team".to_string()));
+    assert_eq!(service::normalize_tag_selector(" ").unwrap(), None);
+    assert!(service::normalize_tag_selector("env=prod,env=dev").is_err());
+
+    let mut tagged = monitor();
+    tagged.tags.insert("env".to_string(), "prod".to_string());
+    tagged.tags.insert("team".to_string(), "payments".to_string());
+    let downtime = |monitor: Monitor| AlertEvent::Downtime { monitor, incident: incident(None) };
+    let spike = AlertEvent::ErrorSpike { latest: error_log(), errors: 12, window_minutes: 5 };
+    let test = AlertEvent::Test { alert_name: "Ops".to_string(), channel: "email".to_string() };
+
+    let unscoped = alert("a1", "email", json!({ "to": ["ops@acme.test"] }));
+    assert!(service::alert_applies(&unscoped, &downtime(monitor())));
+    assert!(service::alert_applies(&unscoped, &spike));
+
+    let scoped = Alert { tag_selector: Some("env=prod,team".to_string()), ..unscoped };
+    assert!(service::alert_applies(&scoped, &downtime(tagged.clone())));
+    assert!(service::alert_applies(&scoped, &AlertEvent::Recovery { monitor: tagged, incident: incident(Some(60)) }));
+    assert!(!service::alert_applies(&scoped, &downtime(monitor())));
+    assert!(!service::alert_applies(&scoped, &spike));
+    assert!(service::alert_applies(&scoped, &test));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(message::format_duration(45), "45s");
+    assert_eq!(message::format_duration(200), "3m 20s");
+    assert_eq!(message::format_duration(3900), "1h 5m");
+    assert_eq!(message::format_duration(90000), "1d 1h");
+    assert_eq!(message::format_duration(-5), "0s");
+}
+
+#[test]
+fn test_truncate() {
+    assert_eq!(message::truncate("short", 10), "short");
+    assert_eq!(message::truncate("łódź łódź", 5), "łódź…");
+    assert_eq!(message::truncate("łódź łódź", 5).chars().count(), 5);
+}
+
+#[test]
+fn test_messages() {
+    let downtime = message::format(&AlertEvent::Downtime { monitor: monitor(), incident: incident(None) }, "Acme", now());
+    assert_eq!(downtime.event, "downtime");
+    assert_eq!(downtime.severity, Severity::Critical);
+    assert_eq!(downtime.title, "[Acme] API is down");
+    assert_eq!(
+        downtime.text,
+        "API (https://api.acme.test/health) is down since 2026-04-10 12:00 UTC.\nError: connection refused\nRegions: eu, us"
+    );
+
+    let recovery = message::format(&AlertEvent::Recovery { monitor: monitor(), incident: incident(Some(312)) }, "Acme", now());
+    assert_eq!(recovery.severity, Severity::Resolved);
+    assert_eq!(recovery.title, "[Acme] API is back up");
+    assert_eq!(recovery.text, "API (https://api.acme.test/health) is back up after 5m 12s of downtime.");
+
+    let spike = message::format(&AlertEvent::ErrorSpike { latest: error_log(), errors: 10, window_minutes: 5 }, "Acme", now());
+    assert_eq!(spike.event, "error_spike");
+    assert_eq!(spike.severity, Severity::Warning);
+    assert_eq!(spike.title, "[Acme] Error spike: 10 errors in 5 minutes");
+    assert_eq!(
+        spike.text,
+        "10 errors were logged in the last 5 minutes.\nLatest (fatal): payment failed\nSource: checkout / production"
+    );
+
+    let test = message::format(&AlertEvent::Test { alert_name: "On-call".to_string(), channel: "sms".to_string() }, "Acme", now());
+    assert_eq!(test.severity, Severity::Info);
+    assert_eq!(test.title, "[Acme] Test alert: On-call");
+}
+
+fn downtime_message() -> Message {
+    message::format(&AlertEvent::Downtime { monitor: monitor(), incident: incident(None) }, "Acme", now())
+}
+
+#[test]
+fn test_channel_layouts() {
+    let message = downtime_message();
+
+    let slack = notifier::slack_payload(&message);
+    assert_eq!(slack["text"], "[Acme] API is down");
+    assert_eq!(slack["attachments"][0]["color"], "#d92d20");
+    assert_eq!(slack["attachments"][0]["text"], message.text);
+    assert_eq!(slack["attachments"][0]["ts"], now().timestamp());
+
+    let discord = notifier::discord_payload(&message);
+    assert_eq!(discord["embeds"][0]["title"], "[Acme] API is down");
+    assert_eq!(discord["embeds"][0]["description"], message.text);
+    assert_eq!(discord["embeds"][0]["color"], 0xD92D20);
+    assert_eq!(discord["embeds"][0]["timestamp"], now().to_rfc3339());
+
+    assert_eq!(
+        notifier::sms_text(&message),
+        "[Acme] API is down: API (https://api.acme.test/health) is down since 2026-04-10 12:00 UTC."
+    );
+    let long = Message { text: "x".repeat(1000), ..message.clone() };
+    assert_eq!(notifier::sms_text(&long).chars().count(), notifier::MAX_SMS_CHARS);
+
+    let email = notifier::email(&alert("a1", "email", json!({})), &message, "ops@acme.test");
+    assert_eq!(email.subject, "[Acme] API is down");
+    assert!(email.body.starts_with(&message.text));
+    assert!(email.body.contains("Sent by the StatusForge alert \"email alert\"."));
+}
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        if email.to.starts_with("bounce") {
+            return Err("mailbox unavailable".to_string());
+        }
+        self.sent.lock().unwrap().push(email.clone());
+        Ok(())
+    }
+}
+
+/// What the Slack, Discord and SMS stand-ins received, by path.
+type Received = Arc<Mutex<Vec<(String, Value)>>>;
+
+async fn chat(State(received): State<Received>, Path(hook): Path<String>, axum::Json(body): axum::Json<Value>) -> StatusCode {
+    received.lock().unwrap().push((hook.clone(), body));
+    if hook == "broken" {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::NO_CONTENT
+    }
+}
+
+async fn sms(
+    State(received): State<Received>,
+    Path(account): Path<String>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> StatusCode {
+    // Basic auth of "AC123:token".
+    if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Basic QUMxMjM6dG9rZW4=") {
+        return StatusCode::UNAUTHORIZED;
+    }
+    received.lock().unwrap().push((account, json!(form)));
+    if form["To"] == "+15550100000" {
+        StatusCode::BAD_REQUEST
+    } else {
+        StatusCode::CREATED
+    }
+}
+
+async fn stand_ins(received: Received) -> String {
+    serve(
+        axum::Router::new()
+            .route("/slack/{hook}", post(chat))
+            .route("/discord/{hook}", post(chat))
+            .route("/sms/2010-04-01/Accounts/{account}/Messages.json", post(sms))
+            .with_state(received),
+    )
+    .await
+}
+
+fn sms_notifier(base: &str, configured: bool) -> SmsNotifier {
+    SmsNotifier {
+        client: reqwest::Client::new(),
+        api_url: format!("{}/sms", base),
+        account_sid: configured.then(|| "AC123".to_string()),
+        auth_token: configured.then(|| "token".to_string()),
+        from: configured.then(|| "+48000000000".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_notifiers() {
+    let received: Received = Arc::default();
+    let base = stand_ins(received.clone()).await;
+    let message = downtime_message();
+    let client = reqwest::Client::new();
+
+    let slack = SlackNotifier { client: client.clone() };
+    let slack_alert = alert("a1", "slack", json!({ "webhook_url": format!("{}/slack/ok", base) }));
+    slack.send(&slack_alert, &message).await.unwrap();
+    let broken = alert("a2", "slack", json!({ "webhook_url": format!("{}/slack/broken", base) }));
+    assert!(slack.send(&broken, &message).await.unwrap_err().contains("404"));
+
+    let discord = DiscordNotifier { client: client.clone() };
+    let discord_alert = alert("a3", "discord", json!({ "webhook_url": format!("{}/discord/ok", base) }));
+    discord.send(&discord_alert, &message).await.unwrap();
+
+    let sms = sms_notifier(&base, true);
+    let sms_alert = alert("a4", "sms", json!({ "to": ["+48123456789"] }));
+    sms.send(&sms_alert, &message).await.unwrap();
+    let partly = alert("a5", "sms", json!({ "to": ["+48123456789", "+15550100000"] }));
+    let error = sms.send(&partly, &message).await.unwrap_err();
+    assert!(error.contains("+15550100000") && !error.contains("+48123456789"));
+    let unconfigured = sms_notifier(&base, false).send(&sms_alert, &message).await.unwrap_err();
+    assert!(unconfigured.contains("not configured"));
+
+    {
+        let received = received.lock().unwrap();
+        let bodies: Vec<&Value> = received.iter().map(|(_, body)| body).collect();
+        assert_eq!(bodies[0], &notifier::slack_payload(&message));
+        assert_eq!(bodies[2], &notifier::discord_payload(&message));
+        assert_eq!(received[3].0, "AC123");
+        assert_eq!(
+            bodies[3],
+            &json!({ "To": "+48123456789", "From": "+48000000000", "Body": notifier::sms_text(&message) })
+        );
+        assert_eq!(received.len(), 6);
+    }
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let email = EmailNotifier { mailer: Box::new(SharedMailer(mailer.clone())) };
+    let email_alert = alert("a6", "email", json!({ "to": ["ops@acme.test", "bounce@acme.test", "dev@acme.test"] }));
+    let error = email.send(&email_alert, &message).await.unwrap_err();
+    assert!(error.contains("bounce@acme.test"));
+    let sent: Vec<String> = mailer.sent.lock().unwrap().iter().map(|email| email.to.clone()).collect();
+    assert_eq!(sent, vec!["ops@acme.test", "dev@acme.test"]);
+}
+
+struct SharedMailer(Arc<RecordingMailer>);
+
+#[async_trait]
+impl Mailer for SharedMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        self.0.send(email).await
+    }
+}
+
+/// The alerts of project `p1`.
+fn stand_in_postgrest(alerts: &[Alert]) -> PostgRest {
+    PostgRest::new()
+        .with("alerts", alerts.iter().map(|alert| json!(alert)).collect())
+        .with(
+            "projects",
+            vec![json!({
+                "id": "p1",
+                "organization_id": "o1",
+                "name": "Acme",
+                "slug": "acme",
+                "description": null,
+                "created_by": "u1",
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z",
+            })],
+        )
+}
+
+#[tokio::test]
+async fn test_notify_project_alerts() {
+    let received: Received = Arc::default();
+    let base = stand_ins(received.clone()).await;
+    let mut disabled = alert("a3", "discord", json!({ "webhook_url": format!("{}/discord/off", base) }));
+    disabled.enabled = false;
+    let alerts = vec![
+        alert("a1", "slack", json!({ "webhook_url": format!("{}/slack/ok", base) })),
+        alert("a2", "discord", json!({ "webhook_url": format!("{}/discord/broken", base) })),
+        disabled,
+        alert("a4", "email", json!({ "to": ["ops@acme.test"] })),
+        alert("a5", "sms", json!({ "to": ["+48123456789"] })),
+    ];
+    let state = stand_in_postgrest(&alerts).state(Config::default()).await;
+
+    let mailer = Arc::new(RecordingMailer::default());
+    let notifiers = Notifiers {
+        email: Box::new(EmailNotifier { mailer: Box::new(SharedMailer(mailer.clone())) }),
+        slack: Box::new(SlackNotifier { client: reqwest::Client::new() }),
+        discord: Box::new(DiscordNotifier { client: reqwest::Client::new() }),
+        sms: Box::new(sms_notifier(&base, true)),
+    };
+    let event = AlertEvent::Recovery { monitor: monitor(), incident: incident(Some(60)) };
+
+    // Slack, email and SMS go out; the broken Discord webhook fails and a3 is disabled.
+    assert_eq!(service::notify(&state, &notifiers, "p1", &event).await.unwrap(), 3);
+    {
+        let received = received.lock().unwrap();
+        let hooks: Vec<&str> = received.iter().map(|(hook, _)| hook.as_str()).collect();
+        assert_eq!(hooks, vec!["ok", "broken", "AC123"]);
+        assert_eq!(received[0].1["text"], "[Acme] API is back up");
+    }
+    assert_eq!(mailer.sent.lock().unwrap()[0].subject, "[Acme] API is back up");
+
+    assert_eq!(service::notify(&state, &notifiers, "p2", &event).await.unwrap(), 0);
+
+    // The test endpoint reports what went wrong.
+    let app_url = serve(statusforge_backend::router(state)).await;
+    let client = reqwest::Client::new();
+    let response = client.post(format!("{}/alerts/a2/test", app_url)).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert!(body.to_string().contains("404"));
+    let response = client.post(format!("{}/alerts/missing/test", app_url)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_create_alert_validation() {
+    let state = PostgRest::new().state(Config::default()).await;
+    let app_url = serve(statusforge_backend::router(state)).await;
+    let client = reqwest::Client::new();
+
+    for body in [
+        json!({ "name": "On-call", "channel": "pagerduty", "config": {} }),
+        json!({ "name": "On-call", "channel": "slack", "config": { "webhook_url": "https://evil.test/hook" } }),
+        json!({ "name": "", "channel": "email", "config": { "to": ["ops@acme.test"] } }),
+        json!({ "name": "On-call", "channel": "sms", "config": { "to": ["123"] } }),
+    ] {
+        let response = client.post(format!("{}/projects/p1/alerts", app_url)).json(&body).send().await.unwrap();
+        assert_eq!(response.status(), 400, "{}", body);
+    }
+}
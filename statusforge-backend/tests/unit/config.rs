@@ -9,6 +9,7 @@ fn test_config_from_env_default_port() {
         supabase_publishable_key: Some("test-key".to_string()),
         supabase_anon_key: None,
         supabase_secret_key: None,
+        ..Default::default()
     };
     let config = Config::from(env);
     assert_eq!(config.port, 3001);
@@ -22,6 +23,7 @@ fn test_config_from_env_custom_port() {
         supabase_publishable_key: Some("test-key".to_string()),
         supabase_anon_key: None,
         supabase_secret_key: None,
+        ..Default::default()
     };
     let config = Config::from(env);
     assert_eq!(config.port, 8080);
@@ -35,6 +37,7 @@ fn test_config_prefers_publishable_key_over_anon_key() {
         supabase_publishable_key: Some("publishable-key".to_string()),
         supabase_anon_key: Some("anon-key".to_string()),
         supabase_secret_key: None,
+        ..Default::default()
     };
     let config = Config::from(env);
     assert_eq!(config.supabase_publishable_key, "publishable-key");
@@ -48,7 +51,74 @@ fn test_config_falls_back_to_anon_key() {
         supabase_publishable_key: None,
         supabase_anon_key: Some("anon-key".to_string()),
         supabase_secret_key: None,
+        ..Default::default()
     };
     let config = Config::from(env);
     assert_eq!(config.supabase_publishable_key, "anon-key");
 }
+
+#[test]
+fn test_config_rdap_base_url_default_and_override() {
+    let env = ConfigEnv {
+        supabase_url: "https://test.supabase.co".to_string(),
+        supabase_publishable_key: Some("test-key".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(Config::from(env).rdap_base_url, "https://rdap.org");
+
+    let env = ConfigEnv {
+        supabase_url: "https://test.supabase.co".to_string(),
+        supabase_publishable_key: Some("test-key".to_string()),
+        rdap_base_url: Some("http://127.0.0.1:9000/".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(Config::from(env).rdap_base_url, "http://127.0.0.1:9000");
+}
+
+#[test]
+fn test_config_result_retention_days_has_floor() {
+    let env = ConfigEnv {
+        supabase_url: "https://test.supabase.co".to_string(),
+        supabase_publishable_key: Some("test-key".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(Config::from(env).result_retention_days, 30);
+
+    let env = ConfigEnv {
+        supabase_url: "https://test.supabase.co".to_string(),
+        supabase_publishable_key: Some("test-key".to_string()),
+        result_retention_days: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(Config::from(env).result_retention_days, 7);
+}
+
+#[tokio::test]
+async fn test_secret_client_sends_secret_key() {
+    use axum::http::HeaderMap;
+
+    let seen: std::sync::Arc<std::sync::Mutex<Vec<HeaderMap>>> = Default::default();
+    let recorded = seen.clone();
+    let backend = axum::Router::new().route(
+        "/rest/v1/monitors",
+        axum::routing::get(move |headers: HeaderMap| async move {
+            recorded.lock().unwrap().push(headers);
+            axum::Json(serde_json::json!([]))
+        }),
+    );
+    let config = Config {
+        supabase_url: crate::support::serve(backend).await,
+        supabase_publishable_key: "sb_publishable_test".to_string(),
+        supabase_secret_key: Some("sb_secret_test".to_string()),
+        ..Default::default()
+    };
+
+    let client = statusforge_backend::shared::supabase::create_client_with_secret(&config).unwrap();
+    client.database().from("monitors").select("*").execute::<serde_json::Value>().await.unwrap();
+    let headers = seen.lock().unwrap().pop().unwrap();
+    assert_eq!(headers["apikey"], "sb_secret_test");
+    assert_eq!(headers["authorization"], "Bearer sb_secret_test");
+
+    let without_secret = Config { supabase_secret_key: None, ..config };
+    assert!(statusforge_backend::shared::supabase::create_client_with_secret(&without_secret).is_err());
+}
@@ -15,6 +15,14 @@ fn test_bad_request_error_status() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+#[test]
+fn test_unauthorized_and_forbidden_error_status() {
+    let response = ApiError::Unauthorized("Sign in".to_string()).into_response();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = ApiError::Forbidden("No access".to_string()).into_response();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
 #[test]
 fn test_internal_server_error_status() {
     let error = ApiError::InternalServerError;
@@ -0,0 +1,221 @@
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::routing::post;
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use statusforge_backend::features::incidents::{self, ListIncidentsQuery};
+use statusforge_backend::features::monitors::state::{self, IncidentAction};
+use statusforge_backend::features::monitors::{self, MonitorResult};
+
+use crate::support::{monitor, serve, PostgRest};
+
+#[test]
+fn test_monitor_state_transitions() {
+    assert_eq!(state::next_state("unknown", "up"), Some("up"));
+    assert_eq!(state::next_state("up", "up"), None);
+    assert_eq!(state::next_state("up", "down"), Some("down"));
+    assert_eq!(state::next_state("paused", "down"), None);
+}
+
+#[test]
+fn test_incident_actions() {
+    assert_eq!(state::incident_action("up", "down"), IncidentAction::Open);
+    assert_eq!(state::incident_action("unknown", "down"), IncidentAction::Open);
+    assert_eq!(state::incident_action("down", "down"), IncidentAction::Extend);
+    assert_eq!(state::incident_action("down", "up"), IncidentAction::Resolve);
+    assert_eq!(state::incident_action("down", "degraded"), IncidentAction::Resolve);
+    assert_eq!(state::incident_action("up", "degraded"), IncidentAction::None);
+    assert_eq!(state::incident_action("paused", "down"), IncidentAction::None);
+}
+
+#[test]
+fn test_state_for_enabled() {
+    assert_eq!(state::state_for_enabled("down", false), Some("paused"));
+    assert_eq!(state::state_for_enabled("paused", false), None);
+    assert_eq!(state::state_for_enabled("paused", true), Some("unknown"));
+    assert_eq!(state::state_for_enabled("up", true), None);
+}
+
+#[test]
+fn test_incident_region_merge_and_duration() {
+    let merged = incidents::merge_regions(&["EU".to_string()], &["US", "EU", "ASIA"]);
+    assert_eq!(merged, vec!["EU", "US", "ASIA"]);
+
+    let resolved_at = Utc.with_ymd_and_hms(2026, 3, 1, 12, 30, 0).unwrap();
+    assert_eq!(incidents::duration_seconds("2026-03-01T12:00:00+00:00", resolved_at), Some(1800));
+    assert_eq!(incidents::duration_seconds("not a date", resolved_at), None);
+}
+
+#[tokio::test]
+async fn test_list_incidents_rejects_unknown_status() {
+    let state = crate::support::offline_state();
+
+    let query = ListIncidentsQuery {
+        status: Some("closed".to_string()),
+        limit: None,
+        offset: None,
+    };
+    let result = incidents::list_incidents(&state, "test-project-id", query).await;
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+}
+
+fn down(id: &str, region: &str, created_at: &str) -> MonitorResult {
+    serde_json::from_value(json!({
+        "id": id,
+        "monitor_id": "m1",
+        "region": region,
+        "status": "down",
+        "response_time_ms": null,
+        "http_status": 503,
+        "ssl_valid": null,
+        "ssl_expires_at": null,
+        "error_message": "HTTP 503",
+        "created_at": created_at,
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_downtime_supersedes_latency_incident() {
+    let postgrest = PostgRest::new().with(
+        "incidents",
+        vec![json!({
+            "id": "i1",
+            "monitor_id": "m1",
+            "project_id": "p1",
+            "status": "open",
+            "cause": "latency",
+            "started_at": "2026-04-10T12:00:00+00:00",
+            "resolved_at": null,
+            "duration_seconds": null,
+            "affected_regions": ["EU"],
+            "first_error": "Response time anomalous for 3 consecutive checks (900 ms, baseline 120 ms)",
+            "result_ids": ["r0"],
+            "created_at": "2026-04-10T12:00:00+00:00",
+            "updated_at": "2026-04-10T12:00:00+00:00",
+        })],
+    );
+    let state = postgrest.state(Default::default()).await;
+
+    let opened = incidents::open_incident(&state, &monitor(), &[down("r1", "EU", "2026-04-10T12:10:00+00:00")])
+        .await
+        .unwrap();
+    assert_ne!(opened.id, "i1");
+    assert_eq!(opened.cause, "down");
+    assert_eq!(opened.started_at, "2026-04-10T12:10:00+00:00");
+    assert_eq!(opened.result_ids, vec!["r1"]);
+    assert_eq!(opened.first_error.as_deref(), Some("HTTP 503"));
+
+    let rows = postgrest.rows("incidents");
+    assert_eq!(rows[0]["status"], "resolved");
+    assert_eq!(rows[0]["duration_seconds"], 600);
+    assert_eq!(rows[0]["result_ids"], json!(["r0"]));
+
+    // Further failures extend the downtime incident, not a new one.
+    let (extended, new) = incidents::extend_incident(&state, &monitor(), &[down("r2", "US", "2026-04-10T12:11:00+00:00")])
+        .await
+        .unwrap();
+    assert!(!new);
+    assert_eq!(extended.id, opened.id);
+    assert_eq!(extended.result_ids, vec!["r1", "r2"]);
+    assert_eq!(extended.affected_regions, vec!["EU", "US"]);
+    assert_eq!(postgrest.rows("incidents").len(), 2);
+}
+
+/// Polls `table` until a row satisfies `found`, as webhooks are sent in the
+/// background.
+async fn eventually(postgrest: &PostgRest, table: &str, found: impl Fn(&serde_json::Value) -> bool) -> serde_json::Value {
+    for _ in 0..100 {
+        if let Some(row) = postgrest.rows(table).into_iter().find(&found) {
+            return row;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("No matching row in {}", table);
+}
+
+type Received = Arc<Mutex<Vec<serde_json::Value>>>;
+
+async fn record(State(received): State<Received>, axum::Json(body): axum::Json<serde_json::Value>) -> &'static str {
+    received.lock().unwrap().push(body);
+    "ok"
+}
+
+#[tokio::test]
+async fn test_incident_opened_on_extend_is_announced() {
+    let slack: Received = Arc::default();
+    let hooks = serve(
+        axum::Router::new()
+            .route("/hook", post(|| async { "ok" }))
+            .route("/slack", post(record))
+            .with_state(slack.clone()),
+    )
+    .await;
+    let mut down_monitor = serde_json::to_value(monitor()).unwrap();
+    down_monitor["state"] = json!("down");
+    let postgrest = PostgRest::new().with("monitors", vec![down_monitor]).with(
+        "webhooks",
+        vec![json!({
+            "id": "w1",
+            "project_id": "p1",
+            "url": format!("{}/hook", hooks),
+            "events": ["downtime", "recovery"],
+            "secret": null,
+            "enabled": true,
+            "created_at": "2026-04-01T00:00:00Z",
+            "updated_at": "2026-04-01T00:00:00Z",
+        })],
+    )
+    .with(
+        "alerts",
+        vec![json!({
+            "id": "a1",
+            "project_id": "p1",
+            "name": "On-call",
+            "channel": "slack",
+            "config": { "webhook_url": format!("{}/slack", hooks) },
+            "enabled": true,
+            "created_at": "2026-04-01T00:00:00Z",
+            "updated_at": "2026-04-01T00:00:00Z",
+        })],
+    )
+    .with(
+        "projects",
+        vec![json!({
+            "id": "p1",
+            "organization_id": "o1",
+            "name": "Acme",
+            "slug": "acme",
+            "description": null,
+            "created_by": "u1",
+            "created_at": "2026-04-01T00:00:00Z",
+            "updated_at": "2026-04-01T00:00:00Z",
+        })],
+    );
+    let state = postgrest.state(Default::default()).await;
+
+    // Down through a maintenance window, so no incident is open yet.
+    let data = serde_json::from_value(json!({ "region": "EU", "status": "down", "error_message": "HTTP 503" })).unwrap();
+    monitors::service::create_monitor_result(&state, "m1", data).await.unwrap();
+
+    let incident = &postgrest.rows("incidents")[0];
+    assert_eq!(incident["cause"], "down");
+    let log = eventually(&postgrest, "webhook_logs", |log| log["event_type"] == "downtime").await;
+    assert_eq!(log["payload"]["data"]["incident"]["id"], incident["id"]);
+    for _ in 0..100 {
+        if !slack.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(slack.lock().unwrap()[0]["text"], "[Acme] API is down");
+
+    // Later failures extend the incident without announcing it again.
+    let data = serde_json::from_value(json!({ "region": "US", "status": "down", "error_message": "HTTP 503" })).unwrap();
+    monitors::service::create_monitor_result(&state, "m1", data).await.unwrap();
+    assert_eq!(postgrest.rows("incidents").len(), 1);
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    assert_eq!(postgrest.rows("webhook_logs").len(), 1);
+    assert_eq!(slack.lock().unwrap().len(), 1);
+}
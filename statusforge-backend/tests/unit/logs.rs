@@ -18,7 +18,9 @@ async fn test_ingest_log_validates_empty_message() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::ingest_log(&state, "project-id", data).await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -40,7 +42,9 @@ async fn test_ingest_log_validates_invalid_level() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::ingest_log(&state, "project-id", data).await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -64,7 +68,9 @@ async fn test_ingest_log_accepts_valid_levels() {
                 supabase_url: "http://localhost".to_string(),
                 supabase_publishable_key: "test".to_string(),
                 supabase_secret_key: None,
-            }).unwrap()
+                ..Default::default()
+            }).unwrap(),
+            config: Default::default(),
         };
         let result: Result<_, statusforge_backend::ApiError> = service::ingest_log(&state, "project-id", data).await;
         assert!(result.is_err());
@@ -87,7 +93,9 @@ async fn test_list_logs_with_filters() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result: Result<_, statusforge_backend::ApiError> = service::list_logs(&state, "project-id", query).await;
     assert!(result.is_err());
@@ -109,7 +117,9 @@ async fn test_list_logs_without_filters() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result: Result<_, statusforge_backend::ApiError> = service::list_logs(&state, "project-id", query).await;
     assert!(result.is_err());
@@ -1,4 +1,3 @@
-use serde_json;
 use statusforge_backend::features::ingest::{CreateLog, Log};
 
 #[test]
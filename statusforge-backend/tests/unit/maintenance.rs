@@ -0,0 +1,160 @@
+use chrono::{Duration, TimeZone, Utc, Weekday};
+use statusforge_backend::features::maintenance::schedule::{self, Frequency, Recurrence, WindowDefinition};
+use statusforge_backend::features::maintenance::{self, MaintenanceWindow};
+
+fn window(schedule: &str, rule: Option<&str>, monitor_ids: Vec<String>) -> MaintenanceWindow {
+    MaintenanceWindow {
+        id: "w1".to_string(),
+        project_id: "p1".to_string(),
+        title: "Weekly deploy".to_string(),
+        description: None,
+        monitor_ids,
+        starts_at: "2026-03-17T21:00:00Z".to_string(),
+        ends_at: "2026-03-17T23:00:00Z".to_string(),
+        schedule: schedule.to_string(),
+        rule: rule.map(str::to_string),
+        until: None,
+        timezone: "Europe/Warsaw".to_string(),
+        created_at: "2026-03-01T00:00:00Z".to_string(),
+        updated_at: "2026-03-01T00:00:00Z".to_string(),
+    }
+}
+
+fn weekly_tuesday() -> WindowDefinition {
+    WindowDefinition {
+        // Tuesday 22:00 in Warsaw (CET)
+        starts_at: Utc.with_ymd_and_hms(2026, 3, 17, 21, 0, 0).unwrap(),
+        duration: Duration::hours(2),
+        until: None,
+        timezone: schedule::parse_timezone("Europe/Warsaw").unwrap(),
+        recurrence: Recurrence::Rule(schedule::parse_rrule("FREQ=WEEKLY;BYDAY=TU").unwrap()),
+    }
+}
+
+#[test]
+fn test_parse_rrule() {
+    let rule = schedule::parse_rrule("RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;COUNT=4").unwrap();
+    assert_eq!(rule.frequency, Frequency::Weekly);
+    assert_eq!(rule.interval, 2);
+    assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Thu]);
+    assert_eq!(rule.count, Some(4));
+
+    let rule = schedule::parse_rrule("FREQ=DAILY;UNTIL=20260401").unwrap();
+    assert_eq!(rule.until, Some(Utc.with_ymd_and_hms(2026, 4, 1, 23, 59, 59).unwrap()));
+
+    assert!(schedule::parse_rrule("INTERVAL=2").is_err());
+    assert!(schedule::parse_rrule("FREQ=YEARLY").is_err());
+    assert!(schedule::parse_rrule("FREQ=DAILY;BYDAY=MO").is_err());
+    assert!(schedule::parse_rrule("FREQ=DAILY;COUNT=2;UNTIL=20260401").is_err());
+    assert!(schedule::parse_rrule("FREQ=WEEKLY;BYDAY=XX").is_err());
+}
+
+#[test]
+fn test_parse_recurrence() {
+    assert!(matches!(schedule::parse_recurrence("once", None), Ok(Recurrence::Once)));
+    assert!(schedule::parse_recurrence("rrule", None).is_err());
+    assert!(schedule::parse_recurrence("cron", Some(" ")).is_err());
+    assert!(schedule::parse_recurrence("cron", Some("0 22 * *")).is_err());
+    assert!(schedule::parse_recurrence("yearly", Some("FREQ=DAILY")).is_err());
+    assert!(schedule::parse_timezone("Mars/Olympus").is_err());
+}
+
+#[test]
+fn test_weekly_rrule_keeps_local_time_across_dst() {
+    let definition = weekly_tuesday();
+    let after = Utc.with_ymd_and_hms(2026, 3, 18, 0, 0, 0).unwrap();
+    let next = schedule::next_occurrences(&definition, after, 2);
+
+    // 22:00 CET before the switch on March 29, 22:00 CEST after it
+    assert_eq!(next[0].0, Utc.with_ymd_and_hms(2026, 3, 24, 21, 0, 0).unwrap());
+    assert_eq!(next[1].0, Utc.with_ymd_and_hms(2026, 3, 31, 20, 0, 0).unwrap());
+    assert_eq!(next[1].1, Utc.with_ymd_and_hms(2026, 3, 31, 22, 0, 0).unwrap());
+}
+
+#[test]
+fn test_active_occurrence() {
+    let definition = weekly_tuesday();
+
+    let inside = Utc.with_ymd_and_hms(2026, 3, 31, 21, 30, 0).unwrap();
+    assert_eq!(
+        schedule::active_occurrence(&definition, inside),
+        Some((
+            Utc.with_ymd_and_hms(2026, 3, 31, 20, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 3, 31, 22, 0, 0).unwrap(),
+        ))
+    );
+    assert!(schedule::active_occurrence(&definition, Utc.with_ymd_and_hms(2026, 3, 31, 22, 0, 0).unwrap()).is_none());
+    assert!(schedule::active_occurrence(&definition, Utc.with_ymd_and_hms(2026, 3, 16, 21, 30, 0).unwrap()).is_none());
+}
+
+#[test]
+fn test_rrule_count_and_until() {
+    let mut definition = weekly_tuesday();
+    definition.recurrence = Recurrence::Rule(schedule::parse_rrule("FREQ=DAILY;COUNT=3").unwrap());
+    let start = definition.starts_at;
+    assert_eq!(schedule::next_occurrences(&definition, start, 10).len(), 3);
+
+    definition.recurrence = Recurrence::Rule(schedule::parse_rrule("FREQ=DAILY").unwrap());
+    definition.until = Some(start + Duration::days(1));
+    assert_eq!(schedule::next_occurrences(&definition, start, 10).len(), 2);
+}
+
+#[test]
+fn test_cron_schedule_in_timezone() {
+    let mut definition = weekly_tuesday();
+    // Standard cron weekday numbering: 2 = Tuesday
+    definition.recurrence = schedule::parse_recurrence("cron", Some("0 22 * * 2")).unwrap();
+
+    let after = Utc.with_ymd_and_hms(2026, 3, 25, 0, 0, 0).unwrap();
+    let next = schedule::next_occurrences(&definition, after, 1);
+    assert_eq!(next[0].0, Utc.with_ymd_and_hms(2026, 3, 31, 20, 0, 0).unwrap());
+
+    let sunday = schedule::parse_recurrence("cron", Some("30 3 * * 0")).unwrap();
+    definition.recurrence = sunday;
+    let next = schedule::next_occurrences(&definition, after, 1);
+    assert_eq!(next[0].0, Utc.with_ymd_and_hms(2026, 3, 29, 1, 30, 0).unwrap());
+}
+
+#[test]
+fn test_once_window() {
+    let mut definition = weekly_tuesday();
+    definition.recurrence = Recurrence::Once;
+
+    let inside = definition.starts_at + Duration::minutes(30);
+    assert!(schedule::active_occurrence(&definition, inside).is_some());
+    assert!(schedule::active_occurrence(&definition, inside + Duration::days(7)).is_none());
+    assert!(schedule::next_occurrences(&definition, inside + Duration::hours(2), 5).is_empty());
+}
+
+#[test]
+fn test_window_covers_monitors() {
+    let at = Utc.with_ymd_and_hms(2026, 3, 24, 21, 30, 0).unwrap();
+
+    let project_wide = window("rrule", Some("FREQ=WEEKLY"), vec![]);
+    assert!(maintenance::covers(&project_wide, "m1", at));
+    assert!(!maintenance::covers(&project_wide, "m1", at + Duration::hours(2)));
+
+    let scoped = window("rrule", Some("FREQ=WEEKLY"), vec!["m2".to_string()]);
+    assert!(!maintenance::covers(&scoped, "m1", at));
+    assert!(maintenance::covers(&scoped, "m2", at));
+
+    let invalid = window("rrule", Some("FREQ=HOURLY"), vec![]);
+    assert!(!maintenance::covers(&invalid, "m1", at));
+}
+
+#[test]
+fn test_window_definition_validation() {
+    let mut inverted = window("once", None, vec![]);
+    inverted.ends_at = inverted.starts_at.clone();
+    assert!(maintenance::definition(&inverted).is_err());
+
+    let mut too_long = window("once", None, vec![]);
+    too_long.ends_at = "2026-05-01T00:00:00Z".to_string();
+    assert!(maintenance::definition(&too_long).is_err());
+
+    let mut bad_timezone = window("once", None, vec![]);
+    bad_timezone.timezone = "Europe/Nowhere".to_string();
+    assert!(maintenance::definition(&bad_timezone).is_err());
+
+    assert!(maintenance::definition(&window("cron", Some("0 22 * * 2"), vec![])).is_ok());
+}
@@ -2,9 +2,7 @@ use statusforge_backend::features::monitors::service;
 
 #[tokio::test]
 async fn test_create_monitor_validation() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let invalid_kind = statusforge_backend::features::monitors::CreateMonitor {
         name: "Test Monitor".to_string(),
@@ -13,6 +11,8 @@ async fn test_create_monitor_validation() {
         keyword: None,
         interval_seconds: Some(300),
         enabled: Some(true),
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor(&state, "test-project-id", invalid_kind).await;
@@ -26,9 +26,7 @@ async fn test_create_monitor_validation() {
 
 #[tokio::test]
 async fn test_create_monitor_empty_name() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let empty_name = statusforge_backend::features::monitors::CreateMonitor {
         name: "   ".to_string(),
@@ -37,6 +35,8 @@ async fn test_create_monitor_empty_name() {
         keyword: None,
         interval_seconds: Some(300),
         enabled: Some(true),
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor(&state, "test-project-id", empty_name).await;
@@ -50,9 +50,7 @@ async fn test_create_monitor_empty_name() {
 
 #[tokio::test]
 async fn test_create_monitor_empty_url() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let empty_url = statusforge_backend::features::monitors::CreateMonitor {
         name: "Test Monitor".to_string(),
@@ -61,6 +59,8 @@ async fn test_create_monitor_empty_url() {
         keyword: None,
         interval_seconds: Some(300),
         enabled: Some(true),
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor(&state, "test-project-id", empty_url).await;
@@ -74,9 +74,7 @@ async fn test_create_monitor_empty_url() {
 
 #[tokio::test]
 async fn test_create_monitor_keyword_required() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let keyword_monitor = statusforge_backend::features::monitors::CreateMonitor {
         name: "Test Monitor".to_string(),
@@ -85,6 +83,8 @@ async fn test_create_monitor_keyword_required() {
         keyword: None,
         interval_seconds: Some(300),
         enabled: Some(true),
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor(&state, "test-project-id", keyword_monitor).await;
@@ -98,9 +98,7 @@ async fn test_create_monitor_keyword_required() {
 
 #[tokio::test]
 async fn test_create_monitor_interval_too_low() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let low_interval = statusforge_backend::features::monitors::CreateMonitor {
         name: "Test Monitor".to_string(),
@@ -109,6 +107,8 @@ async fn test_create_monitor_interval_too_low() {
         keyword: None,
         interval_seconds: Some(30),
         enabled: Some(true),
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor(&state, "test-project-id", low_interval).await;
@@ -122,9 +122,7 @@ async fn test_create_monitor_interval_too_low() {
 
 #[tokio::test]
 async fn test_update_monitor_validation() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let invalid_kind = statusforge_backend::features::monitors::UpdateMonitor {
         name: None,
@@ -133,6 +131,8 @@ async fn test_update_monitor_validation() {
         keyword: None,
         interval_seconds: None,
         enabled: None,
+        config: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::update_monitor(&state, "test-id", invalid_kind).await;
@@ -146,9 +146,7 @@ async fn test_update_monitor_validation() {
 
 #[tokio::test]
 async fn test_create_monitor_result_validation() {
-    let state = statusforge_backend::AppState {
-        supabase: statusforge_backend::shared::supabase::create_client().unwrap(),
-    };
+    let state = crate::support::offline_state();
 
     let invalid_region = statusforge_backend::features::monitors::CreateMonitorResult {
         region: "INVALID".to_string(),
@@ -158,6 +156,7 @@ async fn test_create_monitor_result_validation() {
         ssl_valid: None,
         ssl_expires_at: None,
         error_message: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor_result(&state, "test-monitor-id", invalid_region).await;
@@ -176,6 +175,7 @@ async fn test_create_monitor_result_validation() {
         ssl_valid: None,
         ssl_expires_at: None,
         error_message: None,
+        ..Default::default()
     };
 
     let result: Result<_, statusforge_backend::ApiError> = service::create_monitor_result(&state, "test-monitor-id", invalid_status).await;
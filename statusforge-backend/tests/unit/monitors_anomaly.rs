@@ -0,0 +1,113 @@
+use statusforge_backend::features::monitors::{anomaly, AnomalySettings};
+
+fn settings() -> AnomalySettings {
+    AnomalySettings {
+        enabled: true,
+        min_samples: 5,
+        window: 10,
+        ..Default::default()
+    }
+}
+
+/// A stable endpoint around 200 ms with a few milliseconds of jitter.
+fn stable_history() -> Vec<f64> {
+    vec![198.0, 202.0, 200.0, 205.0, 195.0, 201.0, 199.0, 203.0, 197.0, 200.0]
+}
+
+#[test]
+fn test_statistics() {
+    assert_eq!(anomaly::median(&[3.0, 1.0, 2.0]), Some(2.0));
+    assert_eq!(anomaly::median(&[4.0, 1.0, 3.0, 2.0]), Some(2.5));
+    assert_eq!(anomaly::median(&[]), None);
+
+    assert_eq!(anomaly::ewma(&[100.0, 200.0], 0.5), Some(150.0));
+    assert_eq!(anomaly::ewma(&[100.0, 200.0, 200.0], 0.5), Some(175.0));
+    assert_eq!(anomaly::ewma(&[], 0.5), None);
+}
+
+#[test]
+fn test_baseline_uses_recent_window() {
+    let mut history = vec![5000.0; 20];
+    history.extend(stable_history());
+
+    let baseline = anomaly::baseline(&history, &settings()).unwrap();
+    assert_eq!(baseline.median, 200.0);
+    assert_eq!(baseline.mad, 2.0);
+    assert!((195.0..=205.0).contains(&baseline.ewma));
+
+    assert!(anomaly::baseline(&stable_history()[..4], &settings()).is_none());
+}
+
+#[test]
+fn test_evaluate_flags_slow_outliers_only() {
+    let history = stable_history();
+
+    let slow = anomaly::evaluate(&history, 900.0, &settings()).unwrap();
+    assert!(slow.anomalous);
+    assert!(slow.score > 100.0);
+
+    let normal = anomaly::evaluate(&history, 204.0, &settings()).unwrap();
+    assert!(!normal.anomalous);
+
+    let fast = anomaly::evaluate(&history, 20.0, &settings()).unwrap();
+    assert!(!fast.anomalous);
+    assert!(fast.score < 0.0);
+
+    assert!(anomaly::evaluate(&history[..3], 900.0, &settings()).is_none());
+}
+
+#[test]
+fn test_min_delta_ignores_small_jumps() {
+    // Flat history: MAD is 0 and only the relative floor keeps the score finite.
+    let flat = vec![50.0; 10];
+
+    let small_jump = anomaly::evaluate(&flat, 80.0, &settings()).unwrap();
+    assert!(small_jump.score.is_finite());
+    assert!(!small_jump.anomalous);
+
+    let big_jump = anomaly::evaluate(&flat, 400.0, &settings()).unwrap();
+    assert!(big_jump.anomalous);
+}
+
+#[test]
+fn test_streaks_and_incidents() {
+    assert_eq!(anomaly::streak([true, true, false, true]), 2);
+    assert_eq!(anomaly::streak([false, true]), 0);
+    assert_eq!(anomaly::streak(Vec::<bool>::new()), 0);
+
+    let mut settings = settings();
+    assert!(!anomaly::opens_incident(10, &settings));
+    settings.incident_after = 3;
+    assert!(!anomaly::opens_incident(2, &settings));
+    assert!(anomaly::opens_incident(3, &settings));
+}
+
+#[test]
+fn test_validate_settings() {
+    assert!(anomaly::validate_settings(&AnomalySettings::default()).is_ok());
+    assert!(anomaly::validate_settings(&settings()).is_ok());
+
+    let invalid = |change: fn(&mut AnomalySettings)| {
+        let mut settings = settings();
+        change(&mut settings);
+        anomaly::validate_settings(&settings).is_err()
+    };
+    assert!(invalid(|s| s.alpha = 0.0));
+    assert!(invalid(|s| s.alpha = f64::NAN));
+    assert!(invalid(|s| s.threshold = -1.0));
+    assert!(invalid(|s| s.window = 2));
+    assert!(invalid(|s| s.window = anomaly::MAX_WINDOW + 1));
+    assert!(invalid(|s| s.min_samples = 20));
+    assert!(invalid(|s| s.min_delta_ms = -5.0));
+    assert!(invalid(|s| s.incident_after = 500));
+}
+
+#[test]
+fn test_settings_deserialize_with_defaults() {
+    let settings: AnomalySettings = serde_json::from_value(serde_json::json!({ "enabled": true, "incident_after": 3 })).unwrap();
+    assert!(settings.enabled);
+    assert_eq!(settings.incident_after, 3);
+    assert_eq!(settings.window, AnomalySettings::default().window);
+    assert!(AnomalySettings::default().is_default());
+    assert!(!settings.is_default());
+}
@@ -0,0 +1,228 @@
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::json;
+use statusforge_backend::features::monitors::tags::{self, Tags};
+use statusforge_backend::features::monitors::{pause, service, BulkMonitorRequest, ListMonitorsQuery, Monitor, MonitorSelector, PauseMonitor};
+
+fn monitor(id: &str, kind: &str, tags: serde_json::Value) -> Monitor {
+    serde_json::from_value(json!({
+        "id": id,
+        "project_id": "p1",
+        "name": id,
+        "kind": kind,
+        "url": "https://example.com",
+        "keyword": null,
+        "interval_seconds": 300,
+        "enabled": true,
+        "tags": tags,
+        "created_at": "2026-02-17T10:00:00Z",
+        "updated_at": "2026-02-17T10:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn tag_map(pairs: &[(&str, &str)]) -> Tags {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn test_validate_tags() {
+    assert!(tags::validate_tags(&tag_map(&[("env", "prod"), ("team_owner", "payments")])).is_ok());
+    assert!(tags::validate_tags(&tag_map(&[("team/owner", "payments")])).is_err());
+    assert!(tags::validate_tags(&tag_map(&[("", "prod")])).is_err());
+    assert!(tags::validate_tags(&tag_map(&[("has space", "x")])).is_err());
+    assert!(tags::validate_tags(&tag_map(&[("env", &"x".repeat(tags::MAX_TAG_VALUE_LEN + 1))])).is_err());
+
+    let too_many: Tags = (0..=tags::MAX_TAGS).map(|i| (format!("k{}", i), "v".to_string())).collect();
+    assert!(tags::validate_tags(&too_many).is_err());
+}
+
+#[test]
+fn test_tag_filters() {
+    assert_eq!(tags::parse_filter("env=prod").unwrap(), ("env".to_string(), Some("prod".to_string())));
+    assert_eq!(tags::parse_filter("env").unwrap(), ("env".to_string(), None));
+    assert!(tags::parse_filter("=prod").is_err());
+
+    let labels = tag_map(&[("env", "prod")]);
+    assert!(tags::matches(&labels, "env", Some("prod")));
+    assert!(tags::matches(&labels, "env", None));
+    assert!(!tags::matches(&labels, "env", Some("staging")));
+    assert!(!tags::matches(&labels, "team", None));
+}
+
+#[test]
+fn test_retag() {
+    let existing = tag_map(&[("env", "staging"), ("team", "web")]);
+    let retagged = tags::retag(&existing, &tag_map(&[("env", "prod"), ("tier", "1")]), &["team".to_string()]);
+    assert_eq!(retagged, tag_map(&[("env", "prod"), ("tier", "1")]));
+}
+
+#[test]
+fn test_select_monitors() {
+    let monitors = vec![
+        monitor("a", "http", json!({ "env": "prod" })),
+        monitor("b", "ping", json!({ "env": "prod" })),
+        monitor("c", "http", json!({ "env": "staging" })),
+    ];
+    let ids = |selected: Vec<&Monitor>| selected.iter().map(|m| m.id.clone()).collect::<Vec<_>>();
+
+    let by_tag = MonitorSelector { tag: Some("env=prod".to_string()), ..Default::default() };
+    assert_eq!(ids(service::select_monitors(&monitors, &by_tag).unwrap()), vec!["a", "b"]);
+
+    let by_tag_and_kind = MonitorSelector {
+        tag: Some("env=prod".to_string()),
+        kind: Some("http".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(ids(service::select_monitors(&monitors, &by_tag_and_kind).unwrap()), vec!["a"]);
+
+    let by_group = MonitorSelector { group_id: Some("g1".to_string()), ..Default::default() };
+    assert!(service::select_monitors(&monitors, &by_group).unwrap().is_empty());
+
+    let by_ids = MonitorSelector { monitor_ids: Some(vec!["c".to_string()]), ..Default::default() };
+    assert_eq!(ids(service::select_monitors(&monitors, &by_ids).unwrap()), vec!["c"]);
+
+    assert!(service::select_monitors(&monitors, &MonitorSelector::default()).is_err());
+    let foreign = MonitorSelector { monitor_ids: Some(vec!["z".to_string()]), ..Default::default() };
+    assert!(service::select_monitors(&monitors, &foreign).is_err());
+    let bad_kind = MonitorSelector { kind: Some("smtp".to_string()), ..Default::default() };
+    assert!(service::select_monitors(&monitors, &bad_kind).is_err());
+}
+
+#[test]
+fn test_validate_pause() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 20, 12, 0, 0).unwrap();
+
+    let open_ended = PauseMonitor { reason: Some("Migrating DB".to_string()), ..Default::default() };
+    assert_eq!(pause::validate_pause(&open_ended, now).unwrap(), None);
+
+    let until = PauseMonitor { until: Some("2026-03-20T15:00:00+02:00".to_string()), ..Default::default() };
+    assert_eq!(pause::validate_pause(&until, now).unwrap(), Some(now + Duration::hours(1)));
+    let until = PauseMonitor { until: Some(now.to_rfc3339()), ..Default::default() };
+    assert!(pause::validate_pause(&until, now).is_err());
+
+    let past = PauseMonitor { until: Some("2026-03-20T11:00:00Z".to_string()), ..Default::default() };
+    assert!(pause::validate_pause(&past, now).is_err());
+    let garbage = PauseMonitor { until: Some("tomorrow".to_string()), ..Default::default() };
+    assert!(pause::validate_pause(&garbage, now).is_err());
+    let long_reason = PauseMonitor { reason: Some("x".repeat(pause::MAX_REASON_LEN + 1)), ..Default::default() };
+    assert!(pause::validate_pause(&long_reason, now).is_err());
+}
+
+#[test]
+fn test_bulk_request_deserialization() {
+    let request: BulkMonitorRequest = serde_json::from_value(json!({
+        "action": "pause",
+        "tag": "env=prod",
+        "kind": "http",
+        "until": "2026-03-20T14:00:00Z",
+        "reason": "Weekly deploy",
+        "actor": "ci@example.com"
+    }))
+    .unwrap();
+    assert_eq!(request.action, "pause");
+    assert_eq!(request.selector.tag.as_deref(), Some("env=prod"));
+    assert_eq!(request.selector.kind.as_deref(), Some("http"));
+    assert!(request.selector.monitor_ids.is_none());
+    assert_eq!(request.pause.actor.as_deref(), Some("ci@example.com"));
+    assert!(request.set_tags.is_empty());
+}
+
+#[tokio::test]
+async fn test_bulk_action_validation() {
+    let state = crate::support::offline_state();
+
+    let invalid = BulkMonitorRequest { action: "archive".to_string(), ..Default::default() };
+    match service::bulk_monitors(&state, "p1", invalid).await {
+        Err(statusforge_backend::ApiError::BadRequest(msg)) => assert!(msg.contains("Invalid action")),
+        other => panic!("Expected BadRequest error, got {:?}", other.map(|r| r.affected)),
+    }
+
+    let empty_retag = BulkMonitorRequest { action: "retag".to_string(), ..Default::default() };
+    assert!(matches!(
+        service::bulk_monitors(&state, "p1", empty_retag).await,
+        Err(statusforge_backend::ApiError::BadRequest(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_invalid_retag_writes_nothing() {
+    let full: serde_json::Map<String, serde_json::Value> =
+        (0..tags::MAX_TAGS).map(|i| (format!("k{}", i), json!("v"))).collect();
+    let postgrest = crate::support::PostgRest::new().with(
+        "monitors",
+        vec![
+            serde_json::to_value(monitor("m1", "http", json!({}))).unwrap(),
+            serde_json::to_value(monitor("m2", "http", json!(full))).unwrap(),
+        ],
+    );
+    let state = postgrest.state(Default::default()).await;
+
+    // m2 already has the maximum number of tags, so adding one is rejected.
+    let retag = BulkMonitorRequest {
+        action: "retag".to_string(),
+        selector: MonitorSelector { monitor_ids: Some(vec!["m1".to_string(), "m2".to_string()]), ..Default::default() },
+        set_tags: tag_map(&[("env", "prod")]),
+        ..Default::default()
+    };
+    assert!(matches!(
+        service::bulk_monitors(&state, "p1", retag).await,
+        Err(statusforge_backend::ApiError::BadRequest(_))
+    ));
+
+    let rows = postgrest.rows("monitors");
+    assert_eq!(rows[0]["tags"], json!({}));
+    assert_eq!(rows[1]["tags"], json!(full));
+}
+
+#[test]
+fn test_tag_selectors() {
+    let selector = tags::parse_selector("env=prod, team").unwrap();
+    assert_eq!(selector, vec![("env".to_string(), Some("prod".to_string())), ("team".to_string(), None)]);
+    assert!(tags::parse_selector("env=prod,env=staging").is_err());
+    assert!(tags::parse_selector("team/owner").is_err());
+    assert!(tags::parse_selector("").unwrap().is_empty());
+
+    assert!(tags::matches_selector(&tag_map(&[("env", "prod"), ("team", "web")]), &selector));
+    assert!(!tags::matches_selector(&tag_map(&[("env", "prod")]), &selector));
+    assert!(tags::matches_selector(&tag_map(&[]), &[]));
+}
+
+#[test]
+fn test_monitor_filter() {
+    let filter = service::monitor_filter(ListMonitorsQuery {
+        tag: Some("env=prod".to_string()),
+        kind: Some("http".to_string()),
+        status: Some("down".to_string()),
+        enabled: Some(true),
+        sort: Some("-name".to_string()),
+        limit: Some(25),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(filter.tags, vec![("env".to_string(), Some("prod".to_string()))]);
+    assert_eq!(filter.state.as_deref(), Some("down"));
+    assert_eq!(filter.sort_field, "name");
+    assert!(filter.descending);
+    assert_eq!(filter.limit, Some(25));
+
+    let default = service::monitor_filter(ListMonitorsQuery::default()).unwrap();
+    assert_eq!(default.sort_field, "created_at");
+    assert!(!default.descending);
+    assert!(default.tags.is_empty());
+
+    let invalid = |query: ListMonitorsQuery| service::monitor_filter(query).is_err();
+    assert!(invalid(ListMonitorsQuery { sort: Some("url".to_string()), ..Default::default() }));
+    assert!(invalid(ListMonitorsQuery { status: Some("broken".to_string()), ..Default::default() }));
+    assert!(invalid(ListMonitorsQuery { kind: Some("smtp".to_string()), ..Default::default() }));
+    assert!(invalid(ListMonitorsQuery { tag: Some("a.b".to_string()), ..Default::default() }));
+}
+
+#[test]
+fn test_list_monitors_query_deserialization() {
+    let uri = "/projects/p1/monitors?tag=env%3Dprod&group_id=g1&enabled=false&sort=-state&offset=50".parse().unwrap();
+    let axum::extract::Query(query) = axum::extract::Query::<ListMonitorsQuery>::try_from_uri(&uri).unwrap();
+    assert_eq!(query.tag.as_deref(), Some("env=prod"));
+    assert_eq!(query.group_id.as_deref(), Some("g1"));
+    assert_eq!(query.enabled, Some(false));
+    assert_eq!(query.offset, Some(50));
+}
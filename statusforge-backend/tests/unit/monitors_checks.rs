@@ -0,0 +1,432 @@
+use chrono::{Duration, TimeZone, Utc};
+use statusforge_backend::features::monitors::checks::{domain, ping, ssl};
+use statusforge_backend::features::monitors::{service, DomainConfig, PingConfig, SslConfig};
+
+#[test]
+fn test_ping_target_host_variants() {
+    assert_eq!(ping::target_host("example.com"), Some("example.com".to_string()));
+    assert_eq!(ping::target_host("example.com:8080"), Some("example.com".to_string()));
+    assert_eq!(ping::target_host("https://example.com/health"), Some("example.com".to_string()));
+    assert_eq!(ping::target_host("[2001:db8::1]:443"), Some("2001:db8::1".to_string()));
+    assert_eq!(ping::target_host("   "), None);
+}
+
+#[test]
+fn test_ping_summarize_with_loss() {
+    let stats = ping::summarize(&[Some(10.0), None, Some(20.0), Some(30.0)]);
+    assert_eq!(stats.sent, 4);
+    assert_eq!(stats.received, 3);
+    assert_eq!(stats.loss_percent, 25.0);
+    assert_eq!(stats.min_ms, Some(10.0));
+    assert_eq!(stats.max_ms, Some(30.0));
+    assert_eq!(stats.avg_ms, Some(20.0));
+    assert_eq!(stats.jitter_ms, Some(10.0));
+}
+
+#[test]
+fn test_ping_summarize_all_lost() {
+    let stats = ping::summarize(&[None, None]);
+    assert_eq!(stats.loss_percent, 100.0);
+    assert!(stats.avg_ms.is_none());
+    assert_eq!(ping::classify(&stats, &PingConfig::default()), "down");
+}
+
+#[test]
+fn test_ping_classify_thresholds() {
+    let config = PingConfig::default();
+    let healthy = ping::summarize(&[Some(12.0), Some(14.0), Some(13.0), Some(12.0)]);
+    assert_eq!(ping::classify(&healthy, &config), "up");
+
+    let lossy = ping::summarize(&[Some(12.0), None, None, Some(12.0)]);
+    assert_eq!(ping::classify(&lossy, &config), "degraded");
+
+    let slow = ping::summarize(&[Some(900.0), Some(950.0)]);
+    assert_eq!(ping::classify(&slow, &config), "degraded");
+}
+
+#[test]
+fn test_ping_to_result_fields() {
+    let stats = ping::summarize(&[Some(10.0), Some(20.0)]);
+    let result = ping::to_result("EU", &stats, &PingConfig::default(), ping::ProbeMethod::TcpConnect);
+    assert_eq!(result.status, "up");
+    assert_eq!(result.response_time_ms, Some(15));
+    assert_eq!(result.min_response_time_ms, Some(10));
+    assert_eq!(result.max_response_time_ms, Some(20));
+    assert_eq!(result.packet_loss, Some(0.0));
+    assert_eq!(result.jitter_ms, Some(10.0));
+    assert!(result.error_message.is_none());
+}
+
+#[test]
+fn test_icmp_echo_request_checksum() {
+    let packet = ping::echo_request(0x1234, 7);
+    assert_eq!(packet[0], 8);
+    assert_eq!(&packet[4..8], &[0x12, 0x34, 0x00, 0x07]);
+    assert_eq!(ping::checksum(&packet), 0);
+}
+
+#[test]
+fn test_validate_ping_config_defaults_and_bounds() {
+    let config = service::validate_config("ping", None).unwrap();
+    assert_eq!(config["count"], 4);
+
+    let result = service::validate_config("ping", Some(&serde_json::json!({ "count": 0 })));
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+
+    let result = service::validate_config("http", Some(&serde_json::json!([1, 2])));
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+}
+
+fn inspection(days_left: i64, host: &str) -> ssl::SslInspection {
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap();
+    ssl::SslInspection {
+        host: host.to_string(),
+        certificate: ssl::CertificateInfo {
+            not_after: now + Duration::days(days_left) + Duration::hours(1),
+            subject: Some("example.com".to_string()),
+            issuer: Some("Let's Encrypt".to_string()),
+            sans: vec!["example.com".to_string(), "*.example.com".to_string()],
+            signature_oid: "1.2.840.113549.1.1.11".to_string(),
+            key_type: "RSA".to_string(),
+            key_bits: Some(2048),
+        },
+        protocol: Some("TLSv1.3".to_string()),
+        trust_error: None,
+        chain_complete: true,
+    }
+}
+
+fn ssl_now() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap()
+}
+
+#[test]
+fn test_ssl_hostname_matching() {
+    let names = vec!["example.com".to_string(), "*.example.com".to_string()];
+    assert!(ssl::hostname_matches("example.com", &names));
+    assert!(ssl::hostname_matches("API.example.com", &names));
+    assert!(!ssl::hostname_matches("a.b.example.com", &names));
+    assert!(!ssl::hostname_matches("example.org", &names));
+}
+
+#[test]
+fn test_ssl_days_remaining_and_thresholds() {
+    let now = ssl_now();
+    assert_eq!(ssl::days_remaining(now + Duration::hours(30), now), 1);
+    assert_eq!(ssl::days_remaining(now - Duration::hours(1), now), -1);
+    assert_eq!(ssl::crossed_threshold(10, &[14, 7, 1]), Some(14));
+    assert_eq!(ssl::crossed_threshold(5, &[14, 7, 1]), Some(7));
+    assert_eq!(ssl::crossed_threshold(30, &[14, 7, 1]), None);
+}
+
+#[test]
+fn test_ssl_result_healthy_certificate() {
+    let result = ssl::to_result("EU", &inspection(60, "www.example.com"), &SslConfig::default(), ssl_now());
+    assert_eq!(result.status, "up");
+    assert_eq!(result.ssl_valid, Some(true));
+    assert_eq!(result.ssl_days_remaining, Some(60));
+    assert_eq!(result.ssl_issuer, Some("Let's Encrypt".to_string()));
+    let details = result.ssl_details.unwrap();
+    assert!(details.hostname_match);
+    assert_eq!(details.signature_algorithm, "sha256WithRSAEncryption");
+}
+
+#[test]
+fn test_ssl_result_degraded_within_warning_threshold() {
+    let result = ssl::to_result("EU", &inspection(6, "example.com"), &SslConfig::default(), ssl_now());
+    assert_eq!(result.status, "degraded");
+    assert!(result.error_message.unwrap().contains("threshold 7 days"));
+}
+
+#[test]
+fn test_ssl_result_down_on_mismatch_and_incomplete_chain() {
+    let mismatch = ssl::to_result("EU", &inspection(60, "example.org"), &SslConfig::default(), ssl_now());
+    assert_eq!(mismatch.status, "down");
+    assert_eq!(mismatch.ssl_valid, Some(false));
+
+    let mut broken = inspection(60, "example.com");
+    broken.chain_complete = false;
+    broken.trust_error = Some("invalid peer certificate: UnknownIssuer".to_string());
+    let result = ssl::to_result("EU", &broken, &SslConfig::default(), ssl_now());
+    assert_eq!(result.status, "down");
+    assert!(!result.ssl_details.unwrap().chain_complete);
+}
+
+#[test]
+fn test_ssl_weak_signature_and_key_are_degraded() {
+    let mut weak = inspection(60, "example.com");
+    weak.certificate.signature_oid = "1.2.840.113549.1.1.5".to_string();
+    weak.certificate.key_bits = Some(1024);
+    let result = ssl::to_result("EU", &weak, &SslConfig::default(), ssl_now());
+    assert_eq!(result.status, "degraded");
+    assert_eq!(result.ssl_details.unwrap().weaknesses.len(), 2);
+}
+
+#[tokio::test]
+async fn test_list_certificates_from_latest_results() {
+    use serde_json::json;
+
+    let monitor = |id: &str, kind: &str| {
+        json!({
+            "id": id,
+            "project_id": "p1",
+            "name": id,
+            "kind": kind,
+            "url": format!("{}.example.com", id),
+            "keyword": null,
+            "interval_seconds": 300,
+            "enabled": true,
+            "created_at": "2026-04-01T00:00:00Z",
+            "updated_at": "2026-04-01T00:00:00Z",
+        })
+    };
+    let result = |monitor_id: &str, days: i32| {
+        json!({
+            "id": format!("r-{}", monitor_id),
+            "monitor_id": monitor_id,
+            "region": "EU",
+            "status": "up",
+            "response_time_ms": null,
+            "http_status": null,
+            "ssl_valid": true,
+            "ssl_expires_at": null,
+            "error_message": null,
+            "ssl_days_remaining": days,
+            "created_at": "2026-04-10T12:00:00Z",
+        })
+    };
+    let postgrest = crate::support::PostgRest::new().with(
+        "monitors",
+        vec![monitor("far", "ssl"), monitor("near", "ssl"), monitor("unchecked", "ssl"), monitor("api", "http")],
+    );
+    // The latest result of every SSL monitor comes from one RPC call.
+    let latest = json!([result("far", 80), result("near", 12)]);
+    let backend = postgrest
+        .router()
+        .route("/rest/v1/rpc/latest_ssl_results", axum::routing::post(move || async move { axum::Json(latest) }));
+    let state = crate::support::app_state(backend, Default::default()).await;
+
+    let certificates = service::list_certificates(&state, "p1").await.unwrap();
+    let summary: Vec<_> = certificates.iter().map(|c| (c.monitor_id.as_str(), c.days_remaining)).collect();
+    assert_eq!(summary, vec![("near", Some(12)), ("far", Some(80)), ("unchecked", None)]);
+    assert_eq!(certificates[0].status.as_deref(), Some("up"));
+    assert_eq!(certificates[2].checked_at, None);
+}
+
+fn rdap_body(expires: &str, statuses: &[&str]) -> serde_json::Value {
+    serde_json::json!({
+        "objectClassName": "domain",
+        "ldhName": "EXAMPLE.COM",
+        "status": statuses,
+        "events": [
+            { "eventAction": "registration", "eventDate": "1995-08-14T04:00:00Z" },
+            { "eventAction": "expiration", "eventDate": expires }
+        ],
+        "entities": [{
+            "objectClassName": "entity",
+            "roles": ["registrar"],
+            "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Example Registrar, Inc."]]]
+        }]
+    })
+}
+
+#[test]
+fn test_domain_target_and_status_normalization() {
+    assert_eq!(domain::target_domain("https://www.Example.com/path"), Some("example.com".to_string()));
+    assert_eq!(domain::target_domain("example.co.uk"), Some("example.co.uk".to_string()));
+    assert_eq!(domain::target_domain("https://api.example.com"), Some("example.com".to_string()));
+    assert_eq!(domain::target_domain("https://a.b.example.co.uk:8443/x"), Some("example.co.uk".to_string()));
+    assert_eq!(domain::target_domain("docs.acme.github.io"), Some("github.io".to_string()));
+    assert_eq!(domain::target_domain("co.uk"), None);
+    assert_eq!(domain::target_domain("localhost"), None);
+    assert_eq!(domain::target_domain("192.168.0.1"), None);
+    assert_eq!(domain::normalize_status("client hold"), "clienthold");
+    assert_eq!(domain::normalize_status("redemptionPeriod"), "redemptionperiod");
+}
+
+#[test]
+fn test_domain_parse_rdap_response() {
+    let registration = domain::parse_rdap(&rdap_body("2026-08-13T04:00:00Z", &["client transfer prohibited"]));
+    assert_eq!(registration.expires_at, Some(Utc.with_ymd_and_hms(2026, 8, 13, 4, 0, 0).unwrap()));
+    assert_eq!(registration.registrar, Some("Example Registrar, Inc.".to_string()));
+    assert_eq!(registration.statuses, vec!["client transfer prohibited".to_string()]);
+}
+
+#[test]
+fn test_domain_result_thresholds_and_bad_statuses() {
+    let now = ssl_now();
+    let config = DomainConfig::default();
+
+    let healthy = domain::parse_rdap(&rdap_body("2027-03-01T00:00:00Z", &["active"]));
+    let result = domain::to_result("EU", &healthy, &config, now);
+    assert_eq!(result.status, "up");
+    assert_eq!(result.domain_days_remaining, Some(365));
+
+    let expiring = domain::parse_rdap(&rdap_body("2026-03-11T00:00:00Z", &["active"]));
+    let result = domain::to_result("EU", &expiring, &config, now);
+    assert_eq!(result.status, "degraded");
+    assert!(result.error_message.unwrap().contains("threshold 14 days"));
+
+    let held = domain::parse_rdap(&rdap_body("2027-03-01T00:00:00Z", &["client hold"]));
+    assert_eq!(domain::to_result("EU", &held, &config, now).status, "down");
+
+    let expired = domain::parse_rdap(&rdap_body("2026-02-20T00:00:00Z", &["redemption period"]));
+    let result = domain::to_result("EU", &expired, &config, now);
+    assert_eq!(result.status, "down");
+    assert!(result.error_message.unwrap().contains("expired"));
+}
+
+#[test]
+fn test_validate_domain_config() {
+    let config = service::validate_config("domain", None).unwrap();
+    assert_eq!(config["warning_days"], serde_json::json!([30, 14, 7]));
+
+    let result = service::validate_config("domain", Some(&serde_json::json!({ "warning_days": [0] })));
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_validate_target_on_update() {
+    let bad_request = |result| matches!(result, Err(statusforge_backend::ApiError::BadRequest(_)));
+
+    assert!(service::validate_target("ping", "1.1.1.1", Some("example.com")).is_ok());
+    assert!(bad_request(service::validate_target("ping", "1.1.1.1", Some("http://"))));
+    assert!(service::validate_target("domain", "example.com", None).is_ok());
+    assert!(bad_request(service::validate_target("domain", "example.com", Some("http://"))));
+    assert!(bad_request(service::validate_target("domain", "https://example.com", Some("localhost"))));
+    assert!(service::validate_target("http", "https://example.com", Some("https://example.org")).is_ok());
+
+    assert!(service::validate_target("heartbeat", "/heartbeats/abc", Some("/heartbeats/abc")).is_ok());
+    assert!(bad_request(service::validate_target("heartbeat", "/heartbeats/abc", Some("https://example.com"))));
+}
+
+async fn stand_in_rdap_server() -> String {
+    use axum::extract::Path;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+
+    let app = axum::Router::new().route(
+        "/domain/{name}",
+        get(|Path(name): Path<String>| async move {
+            match name.as_str() {
+                "example.com" => Ok(axum::Json(rdap_body("2099-01-01T00:00:00Z", &["active"]))),
+                "held.com" => Ok(axum::Json(rdap_body("2099-01-01T00:00:00Z", &["serverHold"]))),
+                _ => Err(StatusCode::NOT_FOUND),
+            }
+        }),
+    );
+    crate::support::serve(app).await
+}
+
+#[tokio::test]
+async fn test_domain_run_against_stand_in_rdap_server() {
+    let base_url = stand_in_rdap_server().await;
+    let config = DomainConfig::default();
+
+    let result = domain::run(&base_url, "https://www.example.com", &config, "EU").await;
+    assert_eq!(result.status, "up");
+    assert_eq!(result.domain_registrar, Some("Example Registrar, Inc.".to_string()));
+    assert!(result.domain_expires_at.unwrap().starts_with("2099-01-01"));
+
+    let held = domain::run(&base_url, "held.com", &config, "EU").await;
+    assert_eq!(held.status, "down");
+
+    let missing = domain::run(&base_url, "missing.com", &config, "EU").await;
+    assert_eq!(missing.status, "down");
+    assert!(missing.error_message.unwrap().contains("not registered"));
+}
+
+#[test]
+fn test_scheduler_is_due_after_interval() {
+    use statusforge_backend::features::monitors::scheduler;
+
+    let last = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    assert!(scheduler::is_due(None, 300, last));
+    assert!(!scheduler::is_due(Some(last), 300, last + Duration::seconds(299)));
+    assert!(scheduler::is_due(Some(last), 300, last + Duration::seconds(300)));
+}
+
+#[tokio::test]
+async fn test_scheduler_checks_due_monitors() {
+    use serde_json::json;
+    use statusforge_backend::features::monitors::scheduler;
+
+    let monitor = |id: &str, kind: &str, last_checked_at: Option<String>| {
+        json!({
+            "id": id,
+            "project_id": "p1",
+            "name": id,
+            "kind": kind,
+            "url": "example.com",
+            "keyword": null,
+            "interval_seconds": 300,
+            "enabled": true,
+            "last_checked_at": last_checked_at,
+            "created_at": "2026-04-01T00:00:00Z",
+            "updated_at": "2026-04-01T00:00:00Z",
+        })
+    };
+    let postgrest = crate::support::PostgRest::new().with(
+        "monitors",
+        vec![
+            monitor("due", "domain", Some("2026-04-01T00:00:00Z".to_string())),
+            monitor("recent", "domain", Some(Utc::now().to_rfc3339())),
+            monitor("heartbeat", "heartbeat", None),
+        ],
+    );
+    let config = statusforge_backend::shared::config::Config {
+        rdap_base_url: stand_in_rdap_server().await,
+        ..Default::default()
+    };
+    let state = postgrest.state(config).await;
+
+    scheduler::run(state).await.unwrap();
+
+    let checks = postgrest.rows("monitor_checks");
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks[0]["monitor_id"], "due");
+    assert_eq!(checks[0]["status"], "up");
+    let results = postgrest.rows("monitor_results");
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|r| r["monitor_id"] == "due" && r["check_id"] == checks[0]["id"]));
+    let due = postgrest.rows("monitors").into_iter().find(|m| m["id"] == "due").unwrap();
+    assert_ne!(due["last_checked_at"], "2026-04-01T00:00:00Z");
+}
+
+#[test]
+fn test_confirmation_retry_and_quorum() {
+    use statusforge_backend::features::monitors::confirmation;
+
+    assert!(confirmation::needs_retry("down", 1, 2));
+    assert!(confirmation::needs_retry("down", 2, 2));
+    assert!(!confirmation::needs_retry("down", 3, 2));
+    assert!(!confirmation::needs_retry("down", 1, 0));
+    assert!(!confirmation::needs_retry("degraded", 1, 2));
+
+    assert_eq!(confirmation::aggregate(&["up", "up", "up"], 2), "up");
+    assert_eq!(confirmation::aggregate(&["down", "up", "up"], 2), "degraded");
+    assert_eq!(confirmation::aggregate(&["down", "down", "up"], 2), "down");
+    assert_eq!(confirmation::aggregate(&["degraded", "up"], 1), "degraded");
+    assert_eq!(confirmation::aggregate(&["down"], 1), "down");
+}
+
+#[test]
+fn test_validate_confirmation_settings() {
+    use statusforge_backend::features::monitors::ConfirmationSettings;
+
+    let valid = ConfirmationSettings {
+        confirmation_retries: 2,
+        regions: vec!["EU".to_string(), "US".to_string(), "ASIA".to_string()],
+        down_quorum: 2,
+    };
+    assert!(service::validate_confirmation(&valid).is_ok());
+    assert!(service::validate_confirmation(&ConfirmationSettings::default()).is_ok());
+
+    let too_many_retries = ConfirmationSettings { confirmation_retries: 6, ..valid.clone() };
+    let quorum_above_regions = ConfirmationSettings { down_quorum: 4, ..valid.clone() };
+    let duplicate_region = ConfirmationSettings { regions: vec!["EU".to_string(), "EU".to_string()], down_quorum: 1, ..valid.clone() };
+    let unknown_region = ConfirmationSettings { regions: vec!["MARS".to_string()], down_quorum: 1, ..valid };
+    for settings in [too_many_retries, quorum_above_regions, duplicate_region, unknown_region] {
+        assert!(matches!(service::validate_confirmation(&settings), Err(statusforge_backend::ApiError::BadRequest(_))));
+    }
+}
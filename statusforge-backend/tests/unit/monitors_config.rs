@@ -0,0 +1,273 @@
+use serde_json::json;
+use statusforge_backend::features::monitors::jsonpath::{self, Segment};
+use statusforge_backend::features::monitors::{service, StatusSpec};
+use statusforge_backend::ApiError;
+
+#[test]
+fn test_jsonpath_parse_segments() {
+    let segments = jsonpath::parse("$.data['items'][2].name").unwrap();
+    assert_eq!(
+        segments,
+        vec![
+            Segment::Key("data".to_string()),
+            Segment::Key("items".to_string()),
+            Segment::Index(2),
+            Segment::Key("name".to_string()),
+        ]
+    );
+    assert!(jsonpath::parse("data.items").is_err());
+    assert!(jsonpath::parse("$.items[").is_err());
+    assert!(jsonpath::parse("$..items").is_err());
+}
+
+#[test]
+fn test_jsonpath_select() {
+    let value = json!({ "data": { "items": [{ "id": 1 }, { "id": 2 }] } });
+    let segments = jsonpath::parse("$.data.items[1].id").unwrap();
+    assert_eq!(jsonpath::select(&value, &segments), Some(&json!(2)));
+    let missing = jsonpath::parse("$.data.items[5]").unwrap();
+    assert!(jsonpath::select(&value, &missing).is_none());
+}
+
+#[test]
+fn test_status_matches_specs() {
+    let specs = vec![StatusSpec::Code(204), StatusSpec::Pattern("3xx".to_string()), StatusSpec::Pattern("200-201".to_string())];
+    assert!(service::status_matches(&specs, 204));
+    assert!(service::status_matches(&specs, 301));
+    assert!(service::status_matches(&specs, 201));
+    assert!(!service::status_matches(&specs, 202));
+    assert!(!service::status_matches(&specs, 500));
+}
+
+#[test]
+fn test_validate_http_config_normalizes_method() {
+    let config = service::validate_config(
+        "http",
+        Some(&json!({
+            "method": "post",
+            "headers": { "X-Api-Key": "secret" },
+            "body": "{}",
+            "auth": { "type": "bearer", "token": "abc" },
+            "expected_status": [200, "2xx"],
+            "assertions": [
+                { "type": "json_path_equals", "path": "$.status", "value": "ok" },
+                { "type": "response_time", "max_ms": 800 }
+            ]
+        })),
+    )
+    .unwrap();
+    assert_eq!(config["method"], "POST");
+    assert_eq!(config["follow_redirects"], true);
+    assert_eq!(config["timeout_ms"], 10000);
+}
+
+#[test]
+fn test_validate_http_config_rejects_invalid_settings() {
+    let cases = vec![
+        json!({ "method": "FETCH" }),
+        json!({ "method": "GET", "body": "payload" }),
+        json!({ "headers": { "Bad Header": "x" } }),
+        json!({ "auth": { "type": "bearer", "token": " " } }),
+        json!({ "timeout_ms": 100 }),
+        json!({ "expected_status": ["600-700"] }),
+        json!({ "assertions": [{ "type": "json_path_exists", "path": "status" }] }),
+        json!({ "assertions": [{ "type": "body_regex", "pattern": "(unclosed" }] }),
+    ];
+    for case in cases {
+        let result = service::validate_config("http", Some(&case));
+        assert!(matches!(result, Err(ApiError::BadRequest(_))), "expected rejection for {}", case);
+    }
+}
+
+fn login_flow() -> serde_json::Value {
+    json!({
+        "steps": [
+            {
+                "name": "login",
+                "url": "https://api.example.com/login",
+                "method": "POST",
+                "body": "{\"user\":\"probe\"}",
+                "extract": [
+                    { "name": "token", "from": "json_path", "path": "$.access_token" },
+                    { "name": "session", "from": "cookie", "cookie": "sid" }
+                ]
+            },
+            {
+                "name": "checkout",
+                "url": "https://api.example.com/cart/{{session}}",
+                "auth": { "type": "bearer", "token": "{{ token }}" },
+                "expected_status": ["2xx"]
+            }
+        ]
+    })
+}
+
+#[test]
+fn test_template_variables() {
+    assert_eq!(
+        service::template_variables("https://x/{{a}}/{{ b }}?q={{c"),
+        vec!["a".to_string(), "b".to_string()]
+    );
+}
+
+#[test]
+fn test_validate_multistep_config_accepts_flow() {
+    let config = service::validate_config("multistep", Some(&login_flow())).unwrap();
+    assert_eq!(config["steps"][1]["method"], "GET");
+    assert_eq!(config["steps"][0]["extract"][0]["from"], "json_path");
+}
+
+#[test]
+fn test_validate_multistep_rejects_undefined_variable() {
+    let mut flow = login_flow();
+    flow["steps"][1]["url"] = json!("https://api.example.com/orders/{{order_id}}");
+    let result = service::validate_config("multistep", Some(&flow));
+    match result {
+        Err(ApiError::BadRequest(msg)) => {
+            assert!(msg.contains("checkout"));
+            assert!(msg.contains("order_id"));
+        }
+        _ => panic!("Expected BadRequest error"),
+    }
+}
+
+#[test]
+fn test_validate_multistep_variable_not_visible_in_same_step() {
+    let mut flow = login_flow();
+    flow["steps"][0]["url"] = json!("https://api.example.com/login?t={{token}}");
+    assert!(matches!(service::validate_config("multistep", Some(&flow)), Err(ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_validate_multistep_rejects_bad_steps() {
+    assert!(matches!(service::validate_config("multistep", Some(&json!({ "steps": [] }))), Err(ApiError::BadRequest(_))));
+
+    let mut duplicate = login_flow();
+    duplicate["steps"][1]["name"] = json!("login");
+    assert!(matches!(service::validate_config("multistep", Some(&duplicate)), Err(ApiError::BadRequest(_))));
+
+    let mut bad_path = login_flow();
+    bad_path["steps"][0]["extract"][0]["path"] = json!("access_token");
+    assert!(matches!(service::validate_config("multistep", Some(&bad_path)), Err(ApiError::BadRequest(_))));
+
+    let mut bad_name = login_flow();
+    bad_name["steps"][0]["extract"][0]["name"] = json!("1token");
+    assert!(matches!(service::validate_config("multistep", Some(&bad_name)), Err(ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_uptime_named_windows() {
+    use chrono::{Duration, TimeZone, Utc};
+    use statusforge_backend::features::monitors::uptime;
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+    assert_eq!(uptime::window_bounds("24h", None, None, now).unwrap(), (now - Duration::hours(24), now));
+    assert_eq!(uptime::window_bounds("90d", None, None, now).unwrap().0, now - Duration::days(90));
+    assert!(matches!(
+        uptime::window_bounds("1y", None, None, now),
+        Err(statusforge_backend::ApiError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_uptime_custom_window() {
+    use chrono::{TimeZone, Utc};
+    use statusforge_backend::features::monitors::uptime;
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 15, 12, 0, 0).unwrap();
+    let (from, to) = uptime::window_bounds("custom", Some("2026-03-01T00:00:00Z"), None, now).unwrap();
+    assert_eq!(from, Utc.with_ymd_and_hms(2026, 3, 1, 0, 0, 0).unwrap());
+    assert_eq!(to, now);
+
+    for (from, to) in [
+        (None, None),
+        (Some("2026-03-10T00:00:00Z"), Some("2026-03-01T00:00:00Z")),
+        (Some("2024-01-01T00:00:00Z"), Some("2026-01-01T00:00:00Z")),
+        (Some("yesterday"), None),
+    ] {
+        assert!(matches!(
+            uptime::window_bounds("custom", from, to, now),
+            Err(statusforge_backend::ApiError::BadRequest(_))
+        ));
+    }
+}
+
+#[test]
+fn test_uptime_report_deserializes_database_payload() {
+    use statusforge_backend::features::monitors::UptimeReport;
+
+    let report: UptimeReport = serde_json::from_value(serde_json::json!({
+        "monitor_id": "m-1",
+        "from": "2026-03-14T12:00:00+00:00",
+        "to": "2026-03-15T12:00:00+00:00",
+        "uptime_percent": 99.5,
+        "total_checks": 200,
+        "up_checks": 198,
+        "degraded_checks": 1,
+        "down_checks": 1,
+        "incidents": 1,
+        "downtime_seconds": 300.0,
+        "mttr_seconds": 300.0,
+        "mtbf_seconds": 86100.0,
+        "latency": { "p50": 120.0, "p90": 240.5, "p99": null },
+        "days": [{ "date": "2026-03-14", "total_checks": 100, "up_checks": 100, "degraded_checks": 0, "down_checks": 0, "uptime_percent": 100.0 }]
+    }))
+    .unwrap();
+    assert_eq!(report.window, "");
+    assert_eq!(report.latency.p99, None);
+    assert_eq!(report.days.len(), 1);
+}
+
+#[test]
+fn test_rollup_resolution_for_window() {
+    use chrono::{Duration, TimeZone, Utc};
+    use statusforge_backend::features::monitors::rollups;
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 17, 12, 0, 0).unwrap();
+    let cutoff = rollups::retention_cutoff(now, 30);
+    assert_eq!(cutoff, now - Duration::days(30));
+
+    assert_eq!(rollups::resolution_for(now - Duration::hours(24), now, cutoff), "raw");
+    assert_eq!(rollups::resolution_for(now - Duration::days(7), now, cutoff), "hour");
+    assert_eq!(rollups::resolution_for(now - Duration::days(90), now, cutoff), "day");
+
+    // A short window older than the retention cutoff has no raw rows left.
+    let old = now - Duration::days(45);
+    assert_eq!(rollups::resolution_for(old, old + Duration::hours(6), cutoff), "hour");
+}
+
+#[test]
+fn test_rollup_refresh_ranges_cover_recent_buckets() {
+    use chrono::{TimeZone, Utc};
+    use statusforge_backend::features::monitors::rollups;
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 17, 12, 34, 56).unwrap();
+    let [(hourly, hour_from, hour_to), (daily, day_from, day_to)] = rollups::refresh_ranges(now);
+    assert_eq!((hourly, hour_from, hour_to), ("hour", Utc.with_ymd_and_hms(2026, 3, 17, 10, 0, 0).unwrap(), now));
+    assert_eq!((daily, day_from, day_to), ("day", Utc.with_ymd_and_hms(2026, 3, 16, 0, 0, 0).unwrap(), now));
+}
+
+#[tokio::test]
+async fn test_list_monitor_results_rejects_invalid_ranges() {
+    use statusforge_backend::features::monitors::{service, ListMonitorResultsQuery};
+
+    let state = crate::support::offline_state();
+    let query = |resolution: Option<&str>, from: Option<&str>, to: Option<&str>| ListMonitorResultsQuery {
+        region: None,
+        status: None,
+        limit: None,
+        offset: None,
+        from: from.map(str::to_string),
+        to: to.map(str::to_string),
+        resolution: resolution.map(str::to_string),
+    };
+
+    for query in [
+        query(Some("minute"), None, None),
+        query(None, None, Some("2026-03-01T00:00:00Z")),
+        query(None, Some("2026-03-02T00:00:00Z"), Some("2026-03-01T00:00:00Z")),
+    ] {
+        let result = service::list_monitor_results(&state, "test-monitor-id", query).await;
+        assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,50 @@
+use chrono::{Duration, TimeZone, Utc};
+use statusforge_backend::features::monitors::heartbeat;
+use statusforge_backend::features::monitors::HeartbeatSignal;
+
+#[test]
+fn test_parse_signal_suffixes() {
+    assert_eq!(heartbeat::parse_signal(None).unwrap(), HeartbeatSignal::Success);
+    assert_eq!(heartbeat::parse_signal(Some("start")).unwrap(), HeartbeatSignal::Start);
+    assert_eq!(heartbeat::parse_signal(Some("fail")).unwrap(), HeartbeatSignal::Fail);
+    assert_eq!(heartbeat::parse_signal(Some("0")).unwrap(), HeartbeatSignal::ExitCode(0));
+    assert_eq!(heartbeat::parse_signal(Some("137")).unwrap(), HeartbeatSignal::ExitCode(137));
+    assert!(matches!(
+        heartbeat::parse_signal(Some("finish")),
+        Err(statusforge_backend::ApiError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_is_overdue_respects_grace_period() {
+    let last = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    assert!(!heartbeat::is_overdue(last, 300, 60, last + Duration::seconds(359)));
+    assert!(heartbeat::is_overdue(last, 300, 60, last + Duration::seconds(361)));
+}
+
+#[test]
+fn test_start_signal_writes_no_result() {
+    let now = Utc::now();
+    assert!(heartbeat::result_for_signal(HeartbeatSignal::Start, None, now).is_none());
+}
+
+#[test]
+fn test_success_records_run_duration() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 42).unwrap();
+    let started = now - Duration::milliseconds(41_500);
+    let result = heartbeat::result_for_signal(HeartbeatSignal::Success, Some(started), now).unwrap();
+    assert_eq!(result.status, "up");
+    assert_eq!(result.response_time_ms, Some(41_500));
+    assert!(result.error_message.is_none());
+}
+
+#[test]
+fn test_non_zero_exit_code_is_down() {
+    let now = Utc::now();
+    let result = heartbeat::result_for_signal(HeartbeatSignal::ExitCode(2), None, now).unwrap();
+    assert_eq!(result.status, "down");
+    assert_eq!(result.error_message, Some("Job exited with code 2".to_string()));
+
+    let ok = heartbeat::result_for_signal(HeartbeatSignal::ExitCode(0), None, now).unwrap();
+    assert_eq!(ok.status, "up");
+}
@@ -0,0 +1,248 @@
+use serde_json::json;
+use statusforge_backend::features::monitors::{manifest, service, Monitor, MonitorGroup, MonitorSpec};
+
+fn monitor(id: &str, key: Option<&str>, name: &str) -> Monitor {
+    serde_json::from_value(json!({
+        "id": id,
+        "project_id": "p1",
+        "key": key,
+        "name": name,
+        "kind": "http",
+        "url": "https://example.com",
+        "keyword": null,
+        "interval_seconds": 300,
+        "enabled": true,
+        "config": service::validate_config("http", None).unwrap(),
+        "tags": { "env": "prod" },
+        "group_id": "g1",
+        "created_at": "2026-02-17T10:00:00Z",
+        "updated_at": "2026-02-17T10:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn groups() -> Vec<MonitorGroup> {
+    vec![MonitorGroup {
+        id: "g1".to_string(),
+        project_id: "p1".to_string(),
+        name: "API".to_string(),
+        description: None,
+        created_at: "2026-02-17T10:00:00Z".to_string(),
+        updated_at: "2026-02-17T10:00:00Z".to_string(),
+    }]
+}
+
+fn spec(key: &str) -> MonitorSpec {
+    manifest::spec_from_monitor(&monitor("m1", Some(key), "API health"), &groups())
+}
+
+#[test]
+fn test_keys() {
+    assert!(manifest::validate_key("api-health_1").is_ok());
+    assert!(manifest::validate_key("").is_err());
+    assert!(manifest::validate_key("API").is_err());
+    assert!(manifest::validate_key(&"a".repeat(manifest::MAX_KEY_LEN + 1)).is_err());
+
+    assert_eq!(manifest::slugify("API – Health check!"), "api-health-check");
+    assert_eq!(manifest::slugify("  ***  "), "monitor");
+    assert_eq!(manifest::effective_key(&monitor("m1", None, "Checkout API")), "checkout-api");
+    assert_eq!(manifest::effective_key(&monitor("m1", Some("co"), "Checkout API")), "co");
+}
+
+#[test]
+fn test_export_round_trip() {
+    let document = statusforge_backend::features::monitors::MonitorsDocument {
+        version: manifest::DOCUMENT_VERSION,
+        monitors: vec![spec("api")],
+    };
+    assert_eq!(document.monitors[0].group.as_deref(), Some("API"));
+
+    for format in manifest::EXPORT_FORMATS {
+        let rendered = manifest::render_document(&document, format).unwrap();
+        assert_eq!(manifest::parse_document(&rendered).unwrap(), document);
+    }
+    assert!(manifest::render_document(&document, "toml").is_err());
+}
+
+#[test]
+fn test_secrets_are_redacted_on_export() {
+    let stored = json!({
+        "method": "GET",
+        "headers": { "Authorization": "Bearer abc", "Accept": "application/json" },
+        "auth": { "type": "basic", "username": "ops", "password": "hunter2" }
+    });
+    let mut config = stored.clone();
+    manifest::redact_secrets("api-health", &mut config);
+    assert_eq!(config["auth"]["password"], "${API_HEALTH_AUTH_PASSWORD}");
+    assert_eq!(config["auth"]["username"], "ops");
+    assert_eq!(config["headers"]["Authorization"], "${API_HEALTH_HEADER_AUTHORIZATION}");
+    assert_eq!(config["headers"]["Accept"], "application/json");
+
+    let mut steps = json!({ "steps": [
+        { "name": "login", "url": "https://example.com/login", "auth": { "type": "bearer", "token": "t0k" } },
+        { "name": "me", "url": "https://example.com/me" }
+    ] });
+    manifest::redact_secrets("flow", &mut steps);
+    assert_eq!(steps["steps"][0]["auth"]["token"], "${FLOW_STEP1_AUTH_TOKEN}");
+    assert_eq!(steps["steps"][1].get("auth"), None);
+}
+
+#[test]
+fn test_placeholders_keep_stored_secrets_on_apply() {
+    let stored = json!({
+        "headers": { "Authorization": "Bearer abc" },
+        "auth": { "type": "bearer", "token": "t0k" }
+    });
+    let mut config = stored.clone();
+    manifest::redact_secrets("api", &mut config);
+    manifest::restore_secrets("api", &mut config, Some(&stored)).unwrap();
+    assert_eq!(config, stored);
+
+    // A real value replaces the stored one.
+    let mut config = json!({ "headers": { "Authorization": "Bearer new" }, "auth": { "type": "bearer", "token": "${API_AUTH_TOKEN}" } });
+    manifest::restore_secrets("api", &mut config, Some(&stored)).unwrap();
+    assert_eq!(config["headers"]["Authorization"], "Bearer new");
+    assert_eq!(config["auth"]["token"], "t0k");
+
+    let stored_steps = json!({ "steps": [
+        { "name": "me", "url": "https://example.com/me" },
+        { "name": "login", "url": "https://example.com/login", "auth": { "type": "bearer", "token": "t0k" } }
+    ] });
+    let mut steps = json!({ "steps": [
+        { "name": "login", "url": "https://example.com/login", "auth": { "type": "bearer", "token": "${FLOW_STEP1_AUTH_TOKEN}" } }
+    ] });
+    manifest::restore_secrets("flow", &mut steps, Some(&stored_steps)).unwrap();
+    assert_eq!(steps["steps"][0]["auth"]["token"], "t0k");
+
+    let mut unknown = json!({ "auth": { "type": "bearer", "token": "${NEW_AUTH_TOKEN}" } });
+    let result = manifest::restore_secrets("new", &mut unknown, None);
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(msg)) if msg.contains("${NEW_AUTH_TOKEN}")));
+}
+
+#[test]
+fn test_parse_document_defaults_and_errors() {
+    let document = manifest::parse_document(
+        "version: 1\nmonitors:\n  - key: homepage\n    name: Homepage\n    kind: https\n    url: https://example.com\n",
+    )
+    .unwrap();
+    let homepage = &document.monitors[0];
+    assert_eq!(homepage.interval_seconds, 300);
+    assert!(homepage.enabled);
+    assert_eq!(homepage.confirmation.regions, vec!["EU"]);
+    assert!(homepage.tags.is_empty());
+
+    assert!(manifest::parse_document("version: 2\nmonitors: []\n").is_err());
+    assert!(manifest::parse_document("monitors: []\n").is_err());
+    let duplicate = "version: 1\nmonitors:\n  - {key: a, name: A, kind: ping, url: example.com}\n  - {key: a, name: B, kind: ping, url: example.org}\n";
+    assert!(manifest::parse_document(duplicate).is_err());
+}
+
+#[test]
+fn test_changed_fields() {
+    let current = spec("api");
+    assert!(manifest::changed_fields(&current, &current).is_empty());
+
+    let mut desired = current.clone();
+    desired.interval_seconds = 60;
+    desired.tags.insert("team".to_string(), "web".to_string());
+    desired.group = None;
+    desired.keyword = None;
+    assert_eq!(manifest::changed_fields(&current, &desired), vec!["interval_seconds", "tags", "group"]);
+
+    let mut heartbeat = current.clone();
+    heartbeat.kind = "heartbeat".to_string();
+    let mut moved = heartbeat.clone();
+    moved.url = "https://elsewhere.example".to_string();
+    assert!(manifest::changed_fields(&heartbeat, &moved).is_empty());
+}
+
+#[test]
+fn test_build_plan() {
+    let existing = vec![
+        monitor("m1", Some("api"), "API health"),
+        monitor("m2", None, "Old homepage"),
+        monitor("m3", Some("docs"), "Docs"),
+    ];
+
+    let unchanged = spec("api");
+    let mut renamed = manifest::spec_from_monitor(&existing[2], &groups());
+    renamed.name = "Documentation".to_string();
+    let mut created = spec("status");
+    created.group = Some("Public".to_string());
+
+    let (items, groups_to_create) = manifest::build_plan(&existing, &groups(), &[unchanged, renamed, created]).unwrap();
+    let actions: Vec<(&str, &str)> = items.iter().map(|i| (i.action.as_str(), i.key.as_str())).collect();
+    assert_eq!(
+        actions,
+        vec![("unchanged", "api"), ("update", "docs"), ("create", "status"), ("delete", "old-homepage")]
+    );
+    assert_eq!(items[1].changes, vec!["name"]);
+    assert_eq!(items[3].monitor_id.as_deref(), Some("m2"));
+    assert_eq!(groups_to_create, vec!["Public"]);
+
+    // A monitor without a stored key gets its derived key persisted.
+    let adopted = manifest::spec_from_monitor(&existing[1], &groups());
+    let (items, _) = manifest::build_plan(&existing[1..2], &groups(), &[adopted]).unwrap();
+    assert_eq!(items[0].changes, vec!["key"]);
+}
+
+#[test]
+fn test_build_plan_rejects_ambiguous_and_heartbeat_conversion() {
+    let clashing = vec![monitor("m1", None, "API"), monitor("m2", None, "api")];
+    assert!(manifest::build_plan(&clashing, &[], &[]).is_err());
+
+    let mut heartbeat = spec("api");
+    heartbeat.kind = "heartbeat".to_string();
+    assert!(manifest::build_plan(&[monitor("m1", Some("api"), "API")], &groups(), &[heartbeat]).is_err());
+}
+
+#[tokio::test]
+async fn test_apply_validates_with_create_rules() {
+    let state = crate::support::offline_state();
+
+    let body = r#"{"version": 1, "monitors": [{"key": "fast", "name": "Fast", "kind": "https", "url": "https://example.com", "interval_seconds": 10}]}"#;
+    match service::apply_monitors(&state, "p1", body, Default::default()).await {
+        Err(statusforge_backend::ApiError::BadRequest(msg)) => {
+            assert!(msg.starts_with("fast: "));
+            assert!(msg.contains("Interval"));
+        }
+        other => panic!("Expected BadRequest error, got {:?}", other.map(|p| p.items)),
+    }
+}
+
+#[tokio::test]
+async fn test_monitor_responses_redact_secrets() {
+    let config = service::validate_config(
+        "http",
+        Some(&json!({
+            "headers": { "Authorization": "Bearer abc" },
+            "auth": { "type": "basic", "username": "ops", "password": "hunter2" }
+        })),
+    )
+    .unwrap();
+    let mut stored = serde_json::to_value(monitor("m1", Some("api"), "API")).unwrap();
+    stored["config"] = config;
+    let postgrest = crate::support::PostgRest::new().with("monitors", vec![stored]);
+    let state = postgrest.state(Default::default()).await;
+    let app = crate::support::serve(statusforge_backend::router(state)).await;
+    let client = reqwest::Client::new();
+
+    let fetched: serde_json::Value = client.get(format!("{}/monitors/m1", app)).send().await.unwrap().json().await.unwrap();
+    assert_eq!(fetched["config"]["auth"]["password"], "${API_AUTH_PASSWORD}");
+    assert_eq!(fetched["config"]["auth"]["username"], "ops");
+    assert_eq!(fetched["config"]["headers"]["Authorization"], "${API_HEADER_AUTHORIZATION}");
+    let listed: serde_json::Value = client.get(format!("{}/projects/p1/monitors", app)).send().await.unwrap().json().await.unwrap();
+    assert_eq!(listed[0]["config"], fetched["config"]);
+
+    // Sending the fetched config back with placeholders keeps the stored secrets.
+    let mut config = fetched["config"].clone();
+    config["timeout_ms"] = json!(5000);
+    let response = client.put(format!("{}/monitors/m1", app)).json(&json!({ "config": config })).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let updated: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(updated["config"]["auth"]["password"], "${API_AUTH_PASSWORD}");
+    let row = &postgrest.rows("monitors")[0];
+    assert_eq!(row["config"]["timeout_ms"], 5000);
+    assert_eq!(row["config"]["auth"]["password"], "hunter2");
+    assert_eq!(row["config"]["headers"]["Authorization"], "Bearer abc");
+}
@@ -13,7 +13,9 @@ async fn test_create_organization_validates_empty_name() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_organization(&state, data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -30,7 +32,9 @@ async fn test_create_organization_validates_empty_string() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_organization(&state, data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -47,7 +51,9 @@ async fn test_create_organization_validates_whitespace_only() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_organization(&state, data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -64,7 +70,9 @@ async fn test_update_organization_validates_empty_name() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::update_organization(&state, "test-id", data).await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -79,7 +87,9 @@ async fn test_update_organization_allows_none() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result: Result<_, statusforge_backend::ApiError> = service::update_organization(&state, "test-id", data).await;
     assert!(result.is_err());
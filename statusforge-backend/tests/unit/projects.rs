@@ -14,7 +14,9 @@ async fn test_create_project_validates_empty_name() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_project(&state, "org-id", data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -32,7 +34,9 @@ async fn test_create_project_validates_empty_string() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_project(&state, "org-id", data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -50,7 +54,9 @@ async fn test_create_project_validates_whitespace_only() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::create_project(&state, "org-id", data, "test-user-id").await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -68,7 +74,9 @@ async fn test_update_project_validates_empty_name() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result = service::update_project(&state, "test-id", data).await;
     assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
@@ -86,7 +94,9 @@ async fn test_update_project_allows_none_name() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result: Result<_, statusforge_backend::ApiError> = service::update_project(&state, "test-id", data).await;
     assert!(result.is_err());
@@ -104,7 +114,9 @@ async fn test_create_project_allows_description() {
             supabase_url: "http://localhost".to_string(),
             supabase_publishable_key: "test".to_string(),
             supabase_secret_key: None,
-        }).unwrap()
+            ..Default::default()
+        }).unwrap(),
+        config: Default::default(),
     };
     let result: Result<_, statusforge_backend::ApiError> = service::create_project(&state, "org-id", data, "test-user-id").await;
     assert!(result.is_err());
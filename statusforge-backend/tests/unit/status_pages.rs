@@ -0,0 +1,172 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use statusforge_backend::features::monitors::{Monitor, MonitorResult};
+use statusforge_backend::features::status_pages::{self, status, ComponentInput, StatusPageComponent};
+
+fn monitor(id: &str, regions: &[&str], down_quorum: i32) -> Monitor {
+    serde_json::from_value(json!({
+        "id": id,
+        "project_id": "p1",
+        "name": format!("Monitor {}", id),
+        "kind": "http",
+        "url": "https://example.com",
+        "keyword": null,
+        "interval_seconds": 60,
+        "enabled": true,
+        "regions": regions,
+        "down_quorum": down_quorum,
+        "created_at": "2026-03-01T00:00:00Z",
+        "updated_at": "2026-03-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn result(region: &str, status: &str, created_at: &str) -> MonitorResult {
+    serde_json::from_value(json!({
+        "id": format!("{}-{}", region, created_at),
+        "monitor_id": "m1",
+        "region": region,
+        "status": status,
+        "response_time_ms": 120,
+        "http_status": 200,
+        "ssl_valid": null,
+        "ssl_expires_at": null,
+        "error_message": null,
+        "created_at": created_at
+    }))
+    .unwrap()
+}
+
+fn component(display_name: Option<&str>, group_name: Option<&str>) -> StatusPageComponent {
+    StatusPageComponent {
+        id: "c1".to_string(),
+        status_page_id: "sp1".to_string(),
+        monitor_id: "m1".to_string(),
+        display_name: display_name.map(str::to_string),
+        group_name: group_name.map(str::to_string),
+        position: 0,
+        created_at: "2026-03-01T00:00:00Z".to_string(),
+    }
+}
+
+fn input(monitor_id: &str) -> ComponentInput {
+    ComponentInput {
+        monitor_id: monitor_id.to_string(),
+        display_name: None,
+        group_name: None,
+        position: None,
+    }
+}
+
+#[test]
+fn test_latest_per_region_skips_stale_results() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 29, 12, 0, 0).unwrap();
+    let results = vec![
+        result("EU", "up", "2026-03-29T11:59:00Z"),
+        result("US", "down", "2026-03-29T11:58:30Z"),
+        result("EU", "down", "2026-03-29T11:58:00Z"),
+        result("ASIA", "down", "2026-03-29T10:00:00Z"),
+    ];
+
+    let since = status::fresh_since(now, 60);
+    assert_eq!(since, Utc.with_ymd_and_hms(2026, 3, 29, 11, 55, 0).unwrap());
+
+    let latest = status::latest_per_region(&results, since);
+    let picked: Vec<(&str, &str)> = latest.iter().map(|r| (r.region.as_str(), r.status.as_str())).collect();
+    assert_eq!(picked, vec![("EU", "up"), ("US", "down")]);
+}
+
+#[test]
+fn test_component_status() {
+    assert_eq!(status::component_status(&["up", "up"], 1, false), "operational");
+    assert_eq!(status::component_status(&["up", "down"], 2, false), "degraded");
+    assert_eq!(status::component_status(&["down", "down"], 2, false), "outage");
+    assert_eq!(status::component_status(&["degraded"], 1, false), "degraded");
+    assert_eq!(status::component_status(&[], 1, false), "unknown");
+    assert_eq!(status::component_status(&["down"], 1, true), "maintenance");
+}
+
+#[test]
+fn test_page_status() {
+    assert_eq!(status::page_status(&["operational", "operational"]), "operational");
+    assert_eq!(status::page_status(&["operational", "degraded"]), "degraded");
+    assert_eq!(status::page_status(&["operational", "outage", "unknown"]), "partial_outage");
+    assert_eq!(status::page_status(&["outage", "unknown"]), "major_outage");
+    assert_eq!(status::page_status(&["operational", "maintenance"]), "maintenance");
+    assert_eq!(status::page_status(&["unknown"]), "unknown");
+    assert_eq!(status::page_status(&[]), "unknown");
+}
+
+#[test]
+fn test_public_component_names_and_status() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 29, 12, 0, 0).unwrap();
+    let monitor = monitor("m1", &["EU", "US"], 2);
+    let results = vec![
+        result("EU", "down", "2026-03-29T11:59:00Z"),
+        result("US", "up", "2026-03-29T11:58:00Z"),
+    ];
+
+    let public = status_pages::public_component(&component(None, None), &monitor, Some("API"), &results, false, now);
+    assert_eq!(public.name, "Monitor m1");
+    assert_eq!(public.group.as_deref(), Some("API"));
+    assert_eq!(public.status, "degraded");
+    assert_eq!(public.checked_at.as_deref(), Some("2026-03-29T11:59:00Z"));
+
+    let named = component(Some("Public API"), Some("Core"));
+    let public = status_pages::public_component(&named, &monitor, Some("API"), &[], false, now);
+    assert_eq!(public.name, "Public API");
+    assert_eq!(public.group.as_deref(), Some("Core"));
+    assert_eq!(public.status, "unknown");
+    assert_eq!(public.checked_at, None);
+}
+
+#[test]
+fn test_validate_components() {
+    let monitors = vec![monitor("m1", &["EU"], 1), monitor("m2", &["EU"], 1)];
+
+    assert!(status_pages::validate_components(&[input("m1"), input("m2")], &monitors).is_ok());
+    assert!(status_pages::validate_components(&[input("m1"), input("m1")], &monitors).is_err());
+    assert!(status_pages::validate_components(&[input("other")], &monitors).is_err());
+
+    let mut blank = input("m1");
+    blank.display_name = Some("  ".to_string());
+    assert!(status_pages::validate_components(&[blank], &monitors).is_err());
+
+    let mut long = input("m1");
+    long.group_name = Some("g".repeat(status_pages::MAX_NAME_LEN + 1));
+    assert!(status_pages::validate_components(&[long], &monitors).is_err());
+}
+
+#[test]
+fn test_select_components_by_tag_selector() {
+    let tagged = |id: &str, name: &str, env: &str| {
+        let mut monitor = monitor(id, &["EU"], 1);
+        monitor.name = name.to_string();
+        monitor.tags.insert("env".to_string(), env.to_string());
+        monitor
+    };
+    let monitors = vec![tagged("m1", "Web", "prod"), tagged("m2", "API", "prod"), tagged("m3", "Staging", "dev")];
+
+    let unchanged = status_pages::select_components(vec![input("m3")], None, &monitors).unwrap();
+    assert_eq!(unchanged.len(), 1);
+
+    let selected = status_pages::select_components(vec![input("m1")], Some("env=prod"), &monitors).unwrap();
+    let ids: Vec<&str> = selected.iter().map(|c| c.monitor_id.as_str()).collect();
+    assert_eq!(ids, vec!["m1", "m2"]);
+
+    let selected = status_pages::select_components(Vec::new(), Some("env"), &monitors).unwrap();
+    let ids: Vec<&str> = selected.iter().map(|c| c.monitor_id.as_str()).collect();
+    assert_eq!(ids, vec!["m2", "m3", "m1"]);
+
+    assert!(status_pages::select_components(Vec::new(), Some(" "), &monitors).is_err());
+    assert!(status_pages::select_components(Vec::new(), Some("env=prod,env"), &monitors).is_err());
+}
+
+#[test]
+fn test_normalize_domain() {
+    assert_eq!(status_pages::normalize_domain(" Status.Example.com. ").unwrap(), "status.example.com");
+    assert!(status_pages::normalize_domain("localhost").is_err());
+    assert!(status_pages::normalize_domain("https://status.example.com").is_err());
+    assert!(status_pages::normalize_domain("status.example.com:8080").is_err());
+    assert!(status_pages::normalize_domain("-bad.example.com").is_err());
+}
@@ -0,0 +1,321 @@
+use std::net::IpAddr;
+
+use axum::http::HeaderMap;
+use axum::routing::get;
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::{json, Value};
+use statusforge_backend::features::status_pages::{access, render, PageTheme, StatusPage};
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::router;
+
+use crate::support::{app_state, serve, PostgRest};
+
+fn page(visibility: &str, password_hash: Option<String>) -> StatusPage {
+    serde_json::from_value(json!({
+        "id": "sp1",
+        "project_id": "p1",
+        "title": "Acme",
+        "description": null,
+        "custom_domain": null,
+        "enabled": true,
+        "visibility": visibility,
+        "password_hash": password_hash,
+        "allowed_ips": ["203.0.113.0/24"],
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_validate_visibility_and_password() {
+    for visibility in access::VISIBILITIES {
+        assert!(access::validate_visibility(visibility).is_ok());
+    }
+    assert!(access::validate_visibility("secret").is_err());
+
+    assert!(access::validate_password("correct horse").is_ok());
+    assert!(access::validate_password("short").is_err());
+    assert!(access::validate_password(&"x".repeat(access::MAX_PASSWORD_LEN + 1)).is_err());
+}
+
+#[test]
+fn test_status_page_visibility_fields() {
+    let old: StatusPage = serde_json::from_value(json!({
+        "id": "sp1",
+        "project_id": "p1",
+        "title": "Acme",
+        "description": null,
+        "custom_domain": null,
+        "enabled": true,
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    }))
+    .unwrap();
+    assert_eq!(old.visibility, "public");
+    assert!(old.allowed_ips.is_empty());
+
+    // The hash is read from the database but never sent out.
+    let protected = page("password", Some("$argon2id$hash".to_string()));
+    assert_eq!(protected.password_hash.as_deref(), Some("$argon2id$hash"));
+    assert!(serde_json::to_value(&protected).unwrap().get("password_hash").is_none());
+}
+
+#[test]
+fn test_allowed_ips() {
+    let entries: Vec<String> = [" 203.0.113.7 ", "10.1.2.3/8", "2001:db8::/32", "::ffff:198.51.100.1", "203.0.113.7"]
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect();
+    let allowed = access::normalize_allowed_ips(&entries).unwrap();
+    assert_eq!(allowed, vec!["203.0.113.7/32", "10.0.0.0/8", "2001:db8::/32", "198.51.100.1/32"]);
+
+    assert!(access::normalize_allowed_ips(&["10.0.0.0/33".to_string()]).is_err());
+    assert!(access::normalize_allowed_ips(&["example.com".to_string()]).is_err());
+    assert!(access::normalize_allowed_ips(&vec!["10.0.0.1".to_string(); access::MAX_ALLOWED_IPS + 1]).is_err());
+
+    let ip = |ip: &str| ip.parse::<IpAddr>().unwrap();
+    assert!(access::ip_allowed(&allowed, ip("10.200.0.1")));
+    assert!(access::ip_allowed(&allowed, ip("2001:db8::1")));
+    // IPv4 clients of dual-stack listeners arrive IPv4-mapped.
+    assert!(access::ip_allowed(&allowed, ip("::ffff:203.0.113.7")));
+    assert!(!access::ip_allowed(&allowed, ip("203.0.113.8")));
+    assert!(!access::ip_allowed(&[], ip("10.0.0.1")));
+}
+
+#[test]
+fn test_password_hashes() {
+    let hash = access::hash_password("correct horse").unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+    assert_ne!(hash, access::hash_password("correct horse").unwrap());
+    assert!(access::verify_password(&hash, "correct horse"));
+    assert!(!access::verify_password(&hash, "wrong horse"));
+    assert!(!access::verify_password("not a hash", "correct horse"));
+}
+
+#[test]
+fn test_sessions() {
+    let now = Utc.with_ymd_and_hms(2026, 4, 8, 12, 0, 0).unwrap();
+    let page = page("password", Some("hash-1".to_string()));
+    let token = access::session_token("secret", &page, now + Duration::days(1));
+
+    assert!(access::verify_session("secret", &page, &token, now));
+    assert!(!access::verify_session("other", &page, &token, now));
+    assert!(!access::verify_session("secret", &page, &token, now + Duration::days(2)));
+    assert!(!access::verify_session("secret", &page, "garbage", now));
+    // Moving the expiry invalidates the signature.
+    let (_, signature) = token.split_once('.').unwrap();
+    let extended = format!("{}.{}", (now + Duration::days(30)).timestamp(), signature);
+    assert!(!access::verify_session("secret", &page, &extended, now));
+    // A new password ends existing sessions.
+    let changed = StatusPage { password_hash: Some("hash-2".to_string()), ..page.clone() };
+    assert!(!access::verify_session("secret", &changed, &token, now));
+
+    let cookie = access::session_cookie("secret", &page, now, true);
+    assert!(cookie.starts_with("statusforge_page_sp1="));
+    assert!(cookie.contains("; Max-Age=604800; Path=/; HttpOnly; SameSite=Lax"));
+    assert!(cookie.ends_with("; Secure"));
+    assert!(!access::session_cookie("secret", &page, now, false).contains("Secure"));
+
+    let value = cookie.split(';').next().unwrap();
+    let viewer = access::Viewer {
+        cookies: format!("theme=dark; {}", value),
+        ..Default::default()
+    };
+    assert!(viewer.has_session("secret", &page, now));
+    assert!(!access::Viewer::default().has_session("secret", &page, now));
+}
+
+#[test]
+fn test_cookie_and_forwarded_ip() {
+    assert_eq!(access::cookie("a=1; b=2;c=3", "b"), Some("2"));
+    assert_eq!(access::cookie("a=1; b=2", "c"), None);
+    assert_eq!(access::cookie("", "a"), None);
+
+    assert_eq!(access::forwarded_ip("198.51.100.1, 203.0.113.7"), "203.0.113.7".parse().ok());
+    assert_eq!(access::forwarded_ip("2001:db8::1"), "2001:db8::1".parse().ok());
+    assert_eq!(access::forwarded_ip("unknown"), None);
+}
+
+#[test]
+fn test_render_login() {
+    let html = render::render_login("Acme <Status>", &PageTheme::default(), Some("Wrong password"));
+    assert!(html.contains("<title>Acme &lt;Status&gt;</title>"));
+    assert!(html.contains("<form method=\"post\" action=\"\">"));
+    assert!(html.contains("name=\"password\" type=\"password\""));
+    assert!(html.contains("<p class=\"error\">Wrong password</p>"));
+    assert!(!html.contains("{{"));
+    assert!(!render::render_login("Acme", &PageTheme::default(), None).contains("class=\"error\""));
+}
+
+fn project(id: &str, slug: &str) -> Value {
+    json!({
+        "id": id,
+        "organization_id": "o1",
+        "name": slug,
+        "slug": slug,
+        "description": null,
+        "created_by": "u1",
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+fn monitor(id: &str, project_id: &str) -> Value {
+    json!({
+        "id": id,
+        "project_id": project_id,
+        "name": "API",
+        "kind": "http",
+        "url": "https://api.acme.test/health",
+        "keyword": null,
+        "interval_seconds": 60,
+        "enabled": true,
+        "state": "up",
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+fn component(id: &str, status_page_id: &str, monitor_id: &str) -> Value {
+    json!({
+        "id": id,
+        "status_page_id": status_page_id,
+        "monitor_id": monitor_id,
+        "display_name": null,
+        "group_name": null,
+        "position": 0,
+        "created_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+fn status_page(id: &str, project_id: &str, visibility: &str, password_hash: &str) -> Value {
+    json!({
+        "id": id,
+        "project_id": project_id,
+        "title": "Acme",
+        "description": null,
+        "custom_domain": null,
+        "domain_token": null,
+        "domain_verified_at": null,
+        "enabled": true,
+        "visibility": visibility,
+        "password_hash": (visibility == "password").then_some(password_hash),
+        "allowed_ips": ["203.0.113.0/24"],
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+/// A password-protected page (`acme`) and a members-only page (`internal`)
+/// of organization `o1`, whose only member is `member`, each showing one
+/// monitor.
+fn stand_in_postgrest(password_hash: &str) -> PostgRest {
+    PostgRest::new()
+        .with("projects", vec![project("p1", "acme"), project("p2", "internal")])
+        .with(
+            "status_pages",
+            vec![
+                status_page("sp1", "p1", "password", password_hash),
+                status_page("sp2", "p2", "restricted", password_hash),
+            ],
+        )
+        .with("monitors", vec![monitor("m1", "p1"), monitor("m2", "p2")])
+        .with("status_page_components", vec![component("c1", "sp1", "m1"), component("c2", "sp2", "m2")])
+        .with("organization_members", vec![json!({ "id": "om1", "organization_id": "o1", "user_id": "member" })])
+}
+
+/// Supabase Auth: `member-token` and `outsider-token` are valid sessions.
+async fn stand_in_auth(headers: HeaderMap) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let user = match headers.get("authorization").and_then(|v| v.to_str().ok()) {
+        Some("Bearer member-token") => "member",
+        Some("Bearer outsider-token") => "outsider",
+        _ => return (axum::http::StatusCode::UNAUTHORIZED, axum::Json(json!({ "msg": "invalid JWT" }))).into_response(),
+    };
+    axum::Json(json!({ "id": user, "email": format!("{}@acme.test", user) })).into_response()
+}
+
+#[tokio::test]
+async fn test_private_page_routes() {
+    let password_hash = access::hash_password("correct horse").unwrap();
+    let backend = stand_in_postgrest(&password_hash).router().route("/auth/v1/user", get(stand_in_auth));
+    let config = Config {
+        public_url: "https://api.statusforge.test".to_string(),
+        trust_forwarded_for: true,
+        ..Default::default()
+    };
+    let state = app_state(backend, config).await;
+    let app_url = serve(router(state)).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let page_url = format!("{}/status/acme", app_url);
+
+    // Password-protected: JSON clients get 401, browsers the password form.
+    assert_eq!(client.get(&page_url).send().await.unwrap().status(), 401);
+    assert_eq!(client.get(format!("{}/feed.rss", page_url)).send().await.unwrap().status(), 401);
+    assert_eq!(client.get(format!("{}/badge.svg", page_url)).send().await.unwrap().status(), 401);
+    let form = client.get(&page_url).header("accept", "text/html").send().await.unwrap();
+    assert_eq!(form.status(), 401);
+    assert!(form.text().await.unwrap().contains("name=\"password\""));
+
+    let wrong = client
+        .post(&page_url)
+        .header("accept", "text/html")
+        .form(&[("password", "wrong horse")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status(), 401);
+    assert!(wrong.text().await.unwrap().contains("Wrong password"));
+
+    let login = client.post(&page_url).form(&[("password", "correct horse")]).send().await.unwrap();
+    assert_eq!(login.status(), 303);
+    assert_eq!(login.headers()["location"], "https://api.statusforge.test/status/acme");
+    let cookie = login.headers()["set-cookie"].to_str().unwrap().to_string();
+    assert!(cookie.starts_with("statusforge_page_sp1="));
+    let session = cookie.split(';').next().unwrap().to_string();
+
+    let status = client.get(&page_url).header("cookie", &session).send().await.unwrap();
+    assert_eq!(status.status(), 200);
+    assert_eq!(status.headers()["cache-control"], "private, max-age=60");
+    assert_eq!(status.headers()["vary"], "Accept, Cookie, Authorization");
+    assert_eq!(status.json::<Value>().await.unwrap()["title"], "Acme");
+    let feed = client.get(format!("{}/feed.atom", page_url)).header("cookie", &session).send().await.unwrap();
+    assert_eq!(feed.status(), 200);
+
+    // Monitor badges follow the page that shows the monitor.
+    let badge_url = format!("{}/monitors/m1/badge.svg", app_url);
+    assert_eq!(client.get(&badge_url).send().await.unwrap().status(), 401);
+    let badge = client.get(&badge_url).header("cookie", &session).send().await.unwrap();
+    assert_eq!(badge.status(), 200);
+    assert_eq!(badge.headers()["cache-control"], "private, max-age=60");
+    assert!(badge.text().await.unwrap().contains("<title>status: up</title>"));
+
+    // Members only, or from an allowed IP.
+    let internal = format!("{}/status/internal", app_url);
+    let get = |ip: &str, token: Option<&str>| {
+        let mut request = client.get(&internal).header("x-forwarded-for", format!("10.0.0.1, {}", ip));
+        if let Some(token) = token {
+            request = request.bearer_auth(token);
+        }
+        request.send()
+    };
+    assert_eq!(get("203.0.113.9", None).await.unwrap().status(), 200);
+    assert_eq!(get("198.51.100.1", None).await.unwrap().status(), 401);
+    assert_eq!(get("198.51.100.1", Some("member-token")).await.unwrap().status(), 200);
+    assert_eq!(get("198.51.100.1", Some("outsider-token")).await.unwrap().status(), 403);
+    assert_eq!(get("198.51.100.1", Some("expired-token")).await.unwrap().status(), 401);
+    let badge_url = format!("{}/monitors/m2/badge.svg", app_url);
+    assert_eq!(client.get(&badge_url).send().await.unwrap().status(), 401);
+    assert_eq!(client.get(&badge_url).bearer_auth("outsider-token").send().await.unwrap().status(), 403);
+    assert_eq!(client.get(&badge_url).bearer_auth("member-token").send().await.unwrap().status(), 200);
+    // Only password pages have a password form.
+    let html = client.get(&internal).header("accept", "text/html").send().await.unwrap();
+    assert_eq!(html.status(), 401);
+    assert_eq!(html.headers()["content-type"], "application/json");
+    assert_eq!(client.post(&internal).form(&[("password", "correct horse")]).send().await.unwrap().status(), 404);
+}
@@ -0,0 +1,150 @@
+use axum::extract::RawQuery;
+use axum::http::Uri;
+use axum::routing::get;
+use serde_json::{json, Value};
+use statusforge_backend::features::status_pages::domains;
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::router;
+
+use crate::support::{serve, PostgRest};
+
+fn app_hosts() -> Vec<String> {
+    vec!["api.statusforge.test".to_string()]
+}
+
+#[test]
+fn test_custom_host() {
+    let hosts = app_hosts();
+    assert_eq!(domains::custom_host(Some("Status.Example.com:443"), &hosts), Some("status.example.com".to_string()));
+    assert_eq!(domains::custom_host(Some("api.statusforge.test:3001"), &hosts), None);
+    assert_eq!(domains::custom_host(Some("localhost:3001"), &hosts), None);
+    assert_eq!(domains::custom_host(Some("127.0.0.1:3001"), &hosts), None);
+    assert_eq!(domains::custom_host(Some("[::1]:3001"), &hosts), None);
+    assert_eq!(domains::custom_host(None, &hosts), None);
+    assert_eq!(domains::custom_host(Some("status.example.com"), &[]), None);
+}
+
+#[test]
+fn test_status_page_uri() {
+    let rewrite = |uri: &str| domains::status_page_uri(&uri.parse::<Uri>().unwrap(), "acme").unwrap().to_string();
+    assert_eq!(rewrite("/"), "/status/acme");
+    assert_eq!(rewrite("/feed.rss"), "/status/acme/feed.rss");
+    assert_eq!(rewrite("/?lang=pl"), "/status/acme?lang=pl");
+}
+
+#[test]
+fn test_verification_records() {
+    assert_eq!(domains::txt_record_name("status.example.com"), "_statusforge.status.example.com");
+    assert_eq!(domains::txt_record_value("abc"), "statusforge-verification=abc");
+    assert_eq!(
+        domains::well_known_url("status.example.com"),
+        "http://status.example.com/.well-known/statusforge-verification.txt"
+    );
+    assert_ne!(domains::new_token(), domains::new_token());
+
+    let answer = json!({
+        "Status": 0,
+        "Answer": [
+            { "name": "_statusforge.status.example.com.", "type": 5, "data": "other.example.com." },
+            { "name": "_statusforge.status.example.com.", "type": 16, "data": "\"statusforge-\" \"verification=abc\"" },
+            { "name": "_statusforge.status.example.com.", "type": 16, "data": "\"v=spf1 -all\"" }
+        ]
+    });
+    assert_eq!(domains::parse_txt_answers(&answer), vec!["statusforge-verification=abc", "v=spf1 -all"]);
+    assert!(domains::parse_txt_answers(&json!({ "Status": 3 })).is_empty());
+}
+
+#[tokio::test]
+async fn test_checks_against_stand_in_servers() {
+    let base_url = serve(
+        axum::Router::new()
+            .route(
+                "/resolve",
+                get(|RawQuery(query): RawQuery| async move {
+                    let answer = if query.unwrap_or_default().contains("name=_statusforge.good.example.com") {
+                        json!([{ "type": 16, "data": "\"statusforge-verification=abc\"" }])
+                    } else {
+                        json!([])
+                    };
+                    axum::Json(json!({ "Status": 0, "Answer": answer }))
+                }),
+            )
+            .route("/.well-known/statusforge-verification.txt", get(|| async { "abc\n" })),
+    )
+    .await;
+
+    let doh_url = format!("{}/resolve", base_url);
+    assert_eq!(domains::check_dns(&doh_url, "good.example.com", "abc").await, Ok(true));
+    assert_eq!(domains::check_dns(&doh_url, "good.example.com", "other").await, Ok(false));
+    assert_eq!(domains::check_dns(&doh_url, "bad.example.com", "abc").await, Ok(false));
+
+    let file_url = format!("{}/.well-known/statusforge-verification.txt", base_url);
+    assert_eq!(domains::check_http(&file_url, "abc").await, Ok(true));
+    assert_eq!(domains::check_http(&file_url, "other").await, Ok(false));
+    assert_eq!(domains::check_http(&format!("{}/missing", base_url), "abc").await, Ok(false));
+}
+
+fn status_page(id: &str, project_id: &str, domain: &str, verified: bool) -> Value {
+    json!({
+        "id": id,
+        "project_id": project_id,
+        "title": "Acme status",
+        "description": null,
+        "custom_domain": domain,
+        "domain_token": "abc",
+        "domain_verified_at": if verified { json!("2026-04-01T00:00:00Z") } else { Value::Null },
+        "enabled": true,
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+fn project() -> Value {
+    json!({
+        "id": "p1",
+        "organization_id": "o1",
+        "name": "Acme",
+        "slug": "acme",
+        "description": null,
+        "created_by": "u1",
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    })
+}
+
+#[tokio::test]
+async fn test_router_resolves_custom_domains() {
+    let postgrest = PostgRest::new()
+        .with("projects", vec![project()])
+        .with(
+            "status_pages",
+            vec![
+                status_page("sp1", "p1", "status.acme.test", true),
+                status_page("sp2", "p2", "pending.acme.test", false),
+            ],
+        );
+    let state = postgrest.state(Config { app_hosts: app_hosts(), ..Default::default() }).await;
+    let app_url = serve(router(state)).await;
+
+    let client = reqwest::Client::new();
+    let get = |host: &'static str, path: &'static str| {
+        client.get(format!("{}{}", app_url, path)).header("host", host).send()
+    };
+
+    let page = get("status.acme.test", "/").await.unwrap();
+    assert_eq!(page.status(), 200);
+    let body: Value = page.json().await.unwrap();
+    assert_eq!(body["title"], "Acme status");
+
+    let pending = get("pending.acme.test", "/").await.unwrap();
+    assert_eq!(pending.status(), 404);
+    let body: Value = pending.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("pending.acme.test"));
+
+    assert_eq!(get("unknown.example.com", "/").await.unwrap().status(), 404);
+    assert_eq!(get("status.acme.test", "/projects/p1/status-page").await.unwrap().status(), 404);
+
+    let api = get("api.statusforge.test", "/status/acme").await.unwrap();
+    assert_eq!(api.status(), 200);
+    assert_eq!(get("api.statusforge.test", "/").await.unwrap().status(), 404);
+}
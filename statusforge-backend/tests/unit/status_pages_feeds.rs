@@ -0,0 +1,260 @@
+use serde_json::{json, Value};
+use statusforge_backend::features::status_pages::feeds::{self, Feed};
+use statusforge_backend::features::status_pages::{badge, render, BadgeQuery, EmbedStatus, PublicPost, PublicPostUpdate};
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::router;
+
+use crate::support::{serve, PostgRest};
+
+fn post() -> PublicPost {
+    PublicPost {
+        id: "2f1c".to_string(),
+        kind: "incident".to_string(),
+        title: "Errors & timeouts".to_string(),
+        impact: "major".to_string(),
+        state: "identified".to_string(),
+        components: vec!["API".to_string()],
+        scheduled_starts_at: None,
+        scheduled_ends_at: None,
+        resolved_at: None,
+        created_at: "2026-04-08T10:00:00Z".to_string(),
+        updates: vec![
+            PublicPostUpdate {
+                state: "identified".to_string(),
+                body: "Rolling back <deploy>".to_string(),
+                created_at: "2026-04-08T10:30:00Z".to_string(),
+            },
+            PublicPostUpdate {
+                state: "investigating".to_string(),
+                body: "Looking into it".to_string(),
+                created_at: "2026-04-08T10:00:00Z".to_string(),
+            },
+        ],
+    }
+}
+
+fn feed() -> Feed {
+    Feed {
+        title: "Acme".to_string(),
+        page_url: "https://status.acme.test".to_string(),
+        updated_at: "2026-04-08T10:30:00Z".to_string(),
+        posts: vec![post()],
+    }
+}
+
+#[test]
+fn test_rss_feed() {
+    let rss = feeds::rss(&feed());
+    assert!(rss.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\""));
+    assert!(rss.contains("<title>Acme status</title>"));
+    assert!(rss.contains("<atom:link href=\"https://status.acme.test/feed.rss\" rel=\"self\""));
+    assert!(rss.contains("<lastBuildDate>Wed, 8 Apr 2026 10:30:00 +0000</lastBuildDate>"));
+    assert!(rss.contains("<title>Errors &amp; timeouts (Identified)</title>"));
+    assert!(rss.contains("<link>https://status.acme.test#post-2f1c</link>"));
+    assert!(rss.contains("<guid isPermaLink=\"false\">2f1c</guid>"));
+    assert!(rss.contains("<pubDate>Wed, 8 Apr 2026 10:00:00 +0000</pubDate>"));
+    // Entry HTML is escaped once for HTML and once more for XML.
+    assert!(rss.contains("&lt;strong&gt;Identified&lt;/strong&gt; – Rolling back &amp;lt;deploy&amp;gt;"));
+    assert!(rss.contains("Impact: Major · Affects: API"));
+}
+
+#[test]
+fn test_atom_feed() {
+    let atom = feeds::atom(&feed());
+    assert!(atom.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(atom.contains("<link rel=\"self\" href=\"https://status.acme.test/feed.atom\"/>"));
+    assert!(atom.contains("<updated>2026-04-08T10:30:00+00:00</updated>"));
+    assert!(atom.contains("<id>urn:uuid:2f1c</id>"));
+    assert!(atom.contains("<published>2026-04-08T10:00:00+00:00</published>"));
+    assert_eq!(atom.matches("<entry>").count(), 1);
+
+    let empty = feeds::atom(&Feed { posts: Vec::new(), ..feed() });
+    assert!(!empty.contains("<entry>"));
+    assert_eq!(feeds::post_updated_at(&post()), "2026-04-08T10:30:00Z");
+}
+
+#[test]
+fn test_badge_spec() {
+    let query = |show: Option<&str>, window: Option<&str>, label: Option<&str>| BadgeQuery {
+        show: show.map(str::to_string),
+        window: window.map(str::to_string),
+        label: label.map(str::to_string),
+    };
+
+    let spec = badge::badge_spec(&BadgeQuery::default()).unwrap();
+    assert_eq!((spec.kind.as_str(), spec.window.as_str(), spec.label.as_str()), ("status", "30d", "status"));
+    let spec = badge::badge_spec(&query(Some("uptime"), Some("7d"), Some(" API "))).unwrap();
+    assert_eq!((spec.kind.as_str(), spec.window.as_str(), spec.label.as_str()), ("uptime", "7d", "API"));
+    assert_eq!(badge::badge_spec(&query(Some("uptime"), None, Some(""))).unwrap().label, "uptime");
+
+    assert!(badge::badge_spec(&query(Some("latency"), None, None)).is_err());
+    assert!(badge::badge_spec(&query(None, Some("1y"), None)).is_err());
+    assert!(badge::badge_spec(&query(None, None, Some(&"x".repeat(badge::MAX_LABEL_LEN + 1)))).is_err());
+}
+
+#[test]
+fn test_badges() {
+    let svg = badge::status_badge("status", "partial_outage");
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains("<title>status: partial outage</title>"));
+    assert!(svg.contains("fill=\"#dc2626\""));
+    assert!(!svg.contains("{{"));
+
+    assert!(badge::status_badge("api", "up").contains("fill=\"#16a34a\""));
+    assert!(badge::status_badge("api", "paused").contains("fill=\"#9ca3af\""));
+    assert!(badge::status_badge("<b>", "up").contains("<title>&lt;b&gt;: up</title>"));
+
+    let uptime = badge::uptime_badge("uptime", Some(99.956));
+    assert!(uptime.contains(">99.95%<"));
+    assert!(uptime.contains("fill=\"#16a34a\""));
+    assert!(badge::uptime_badge("uptime", Some(97.0)).contains("fill=\"#f59e0b\""));
+    assert!(badge::uptime_badge("uptime", None).contains(">no data<"));
+
+    // Longer text makes a wider badge.
+    let width = |svg: &str| svg.split("width=\"").nth(1).unwrap().split('"').next().unwrap().parse::<u32>().unwrap();
+    assert!(width(&badge::status_badge("status", "major_outage")) > width(&badge::status_badge("status", "up")));
+}
+
+#[test]
+fn test_embed_script() {
+    let script = render::embed_script(&EmbedStatus {
+        status: "operational".to_string(),
+        label: "All systems \"operational\"".to_string(),
+        url: "https://status.acme.test".to_string(),
+    });
+    assert!(script.contains("link.href = \"https://status.acme.test\";"));
+    assert!(script.contains("\"statusforge-status statusforge-status-\" + \"operational\""));
+    assert!(script.contains("link.textContent = \"All systems \\\"operational\\\"\";"));
+}
+
+/// The rows read while serving the feeds and embeds of `acme`, and a
+/// monitor that is not on its page.
+fn stand_in_postgrest() -> PostgRest {
+    PostgRest::new()
+        .with(
+            "projects",
+            vec![json!({
+                "id": "p1",
+                "organization_id": "o1",
+                "name": "Acme",
+                "slug": "acme",
+                "description": null,
+                "created_by": "u1",
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z"
+            })],
+        )
+        .with(
+            "status_pages",
+            vec![json!({
+                "id": "sp1",
+                "project_id": "p1",
+                "title": "Acme",
+                "description": null,
+                "custom_domain": null,
+                "domain_token": null,
+                "domain_verified_at": null,
+                "enabled": true,
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z"
+            })],
+        )
+        .with(
+            "monitors",
+            vec![json!({
+                "id": "m1",
+                "project_id": "p1",
+                "name": "API",
+                "kind": "http",
+                "url": "https://api.acme.test/health",
+                "keyword": null,
+                "interval_seconds": 60,
+                "enabled": true,
+                "state": "up",
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z"
+            })],
+        )
+        .with(
+            "status_page_posts",
+            vec![json!({
+                "id": "2f1c",
+                "status_page_id": "sp1",
+                "kind": "incident",
+                "title": "Errors & timeouts",
+                "impact": "major",
+                "state": "identified",
+                "component_ids": [],
+                "scheduled_starts_at": null,
+                "scheduled_ends_at": null,
+                "resolved_at": null,
+                "created_at": "2026-04-08T10:00:00Z",
+                "updated_at": "2026-04-08T10:30:00Z"
+            })],
+        )
+        .with(
+            "status_page_post_updates",
+            vec![json!({
+                "id": "u1",
+                "post_id": "2f1c",
+                "state": "identified",
+                "body": "Rolling back",
+                "created_at": "2026-04-08T10:30:00Z"
+            })],
+        )
+}
+
+#[tokio::test]
+async fn test_feed_badge_and_embed_routes() {
+    let config = Config { public_url: "https://api.statusforge.test".to_string(), ..Default::default() };
+    let state = stand_in_postgrest().state(config).await;
+    let app_url = serve(router(state)).await;
+    let client = reqwest::Client::new();
+
+    let atom = client.get(format!("{}/status/acme/feed.atom", app_url)).send().await.unwrap();
+    assert_eq!(atom.status(), 200);
+    assert_eq!(atom.headers()["content-type"], "application/atom+xml; charset=utf-8");
+    assert_eq!(atom.headers()["cache-control"], "public, max-age=60");
+    let etag = atom.headers()["etag"].to_str().unwrap().to_string();
+    let body = atom.text().await.unwrap();
+    assert!(body.contains("<link href=\"https://api.statusforge.test/status/acme#post-2f1c\"/>"));
+    assert!(body.contains("<updated>2026-04-08T10:30:00+00:00</updated>"));
+
+    let again = client
+        .get(format!("{}/status/acme/feed.atom", app_url))
+        .header("if-none-match", &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(again.status(), 304);
+
+    let rss = client.get(format!("{}/status/acme/feed.rss", app_url)).send().await.unwrap();
+    assert_eq!(rss.headers()["content-type"], "application/rss+xml; charset=utf-8");
+
+    // No components, but an open major incident.
+    let embed = client.get(format!("{}/status/acme/embed.json", app_url)).send().await.unwrap();
+    assert_eq!(embed.headers()["access-control-allow-origin"], "*");
+    let body: Value = embed.json().await.unwrap();
+    assert_eq!(body, json!({
+        "status": "partial_outage",
+        "label": "Partial outage",
+        "url": "https://api.statusforge.test/status/acme"
+    }));
+
+    let script = client.get(format!("{}/status/acme/embed.js", app_url)).send().await.unwrap();
+    assert_eq!(script.headers()["content-type"], "text/javascript; charset=utf-8");
+    assert!(script.text().await.unwrap().contains("\"Partial outage\""));
+
+    let svg = client.get(format!("{}/status/acme/badge.svg", app_url)).send().await.unwrap();
+    assert_eq!(svg.headers()["content-type"], "image/svg+xml");
+    assert!(svg.text().await.unwrap().contains("<title>status: partial outage</title>"));
+
+    let uptime = client.get(format!("{}/status/acme/badge.svg?show=uptime", app_url)).send().await.unwrap();
+    assert!(uptime.text().await.unwrap().contains("<title>uptime: no data</title>"));
+
+    let invalid = client.get(format!("{}/status/acme/badge.svg?show=latency", app_url)).send().await.unwrap();
+    assert_eq!(invalid.status(), 400);
+    // m1 is not a component of the page, so it has no public badge.
+    assert_eq!(client.get(format!("{}/monitors/m1/badge.svg", app_url)).send().await.unwrap().status(), 404);
+    assert_eq!(client.get(format!("{}/status/other/feed.rss", app_url)).send().await.unwrap().status(), 404);
+}
@@ -0,0 +1,214 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde_json::json;
+use statusforge_backend::features::incidents::Incident;
+use statusforge_backend::features::maintenance::MaintenanceWindow;
+use statusforge_backend::features::monitors::ResultRollup;
+use statusforge_backend::features::status_pages::{
+    self, render, status, theme, ComponentView, PageTheme, PublicComponent, StatusPageView,
+};
+
+fn rollup(region: &str, day: &str, up: i64, degraded: i64, down: i64) -> ResultRollup {
+    serde_json::from_value(json!({
+        "monitor_id": "m1",
+        "region": region,
+        "resolution": "day",
+        "bucket_start": format!("{}T00:00:00Z", day),
+        "total": up + degraded + down,
+        "up": up,
+        "degraded": degraded,
+        "down": down,
+        "latency_samples": 0,
+        "min_ms": null, "avg_ms": null, "max_ms": null,
+        "p50_ms": null, "p90_ms": null, "p99_ms": null
+    }))
+    .unwrap()
+}
+
+fn incident(monitor_id: &str, resolved_at: Option<&str>) -> Incident {
+    serde_json::from_value(json!({
+        "id": format!("i-{}", monitor_id),
+        "monitor_id": monitor_id,
+        "project_id": "p1",
+        "status": if resolved_at.is_some() { "resolved" } else { "open" },
+        "started_at": "2026-03-20T10:00:00Z",
+        "resolved_at": resolved_at,
+        "duration_seconds": null,
+        "first_error": null,
+        "created_at": "2026-03-20T10:00:00Z",
+        "updated_at": "2026-03-20T10:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn window(title: &str, monitor_ids: &[&str], starts_at: &str, ends_at: &str) -> MaintenanceWindow {
+    serde_json::from_value(json!({
+        "id": title,
+        "project_id": "p1",
+        "title": title,
+        "description": null,
+        "monitor_ids": monitor_ids,
+        "starts_at": starts_at,
+        "ends_at": ends_at,
+        "schedule": "once",
+        "rule": null,
+        "until": null,
+        "timezone": "UTC",
+        "created_at": "2026-03-01T00:00:00Z",
+        "updated_at": "2026-03-01T00:00:00Z"
+    }))
+    .unwrap()
+}
+
+fn view(name: &str, theme: PageTheme) -> StatusPageView {
+    StatusPageView {
+        title: "Acme <Status>".to_string(),
+        description: Some("Live status".to_string()),
+        status: "partial_outage".to_string(),
+        theme,
+        components: vec![ComponentView {
+            component: PublicComponent {
+                id: "c1".to_string(),
+                name: name.to_string(),
+                group: Some("API".to_string()),
+                status: "outage".to_string(),
+                checked_at: None,
+            },
+            uptime_percent: Some(99.5),
+            days: status::daily_uptime(&[rollup("EU", "2026-03-30", 9, 0, 1)], NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(), 3),
+        }],
+        incidents: Vec::new(),
+        maintenance: Vec::new(),
+        posts: Vec::new(),
+        updated_at: "2026-03-31T12:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn test_daily_uptime_merges_regions() {
+    let today = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+    let rollups = vec![
+        rollup("EU", "2026-03-31", 10, 0, 0),
+        rollup("US", "2026-03-31", 8, 1, 1),
+        rollup("EU", "2026-03-29", 0, 0, 4),
+    ];
+
+    let days = status::daily_uptime(&rollups, today, 3);
+    let dates: Vec<&str> = days.iter().map(|d| d.date.as_str()).collect();
+    assert_eq!(dates, vec!["2026-03-29", "2026-03-30", "2026-03-31"]);
+    assert_eq!(days[0].uptime_percent, Some(0.0));
+    assert_eq!(days[1].uptime_percent, None);
+    assert_eq!(days[2].uptime_percent, Some(95.0));
+
+    assert_eq!(status::uptime_level(None), "nodata");
+    assert_eq!(status::uptime_level(Some(100.0)), "up");
+    assert_eq!(status::uptime_level(Some(97.0)), "degraded");
+    assert_eq!(status::uptime_level(Some(50.0)), "down");
+}
+
+#[test]
+fn test_recent_incidents() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 31, 12, 0, 0).unwrap();
+    let incidents = vec![
+        incident("m1", None),
+        incident("m2", Some("2026-03-20T11:00:00Z")),
+        incident("m1", Some("2026-03-30T11:00:00Z")),
+        incident("hidden", None),
+    ];
+
+    let recent = status_pages::recent_incidents(&incidents, &[("m1", "API"), ("m2", "Web")], now);
+    assert_eq!(recent.len(), 2);
+    assert!(recent.iter().all(|i| i.component == "API"));
+    assert_eq!(recent[0].status, "open");
+}
+
+#[test]
+fn test_upcoming_maintenance() {
+    let now = Utc.with_ymd_and_hms(2026, 3, 31, 12, 0, 0).unwrap();
+    let windows = vec![
+        window("Database upgrade", &[], "2026-04-02T22:00:00Z", "2026-04-02T23:00:00Z"),
+        window("Running now", &["m1"], "2026-03-31T11:00:00Z", "2026-03-31T13:00:00Z"),
+        window("Other monitor", &["m9"], "2026-04-01T22:00:00Z", "2026-04-01T23:00:00Z"),
+        window("Far away", &[], "2026-06-01T22:00:00Z", "2026-06-01T23:00:00Z"),
+        window("Finished", &[], "2026-03-30T22:00:00Z", "2026-03-30T23:00:00Z"),
+    ];
+
+    let upcoming = status_pages::upcoming_maintenance(&windows, &["m1"], now);
+    let titles: Vec<&str> = upcoming.iter().map(|m| m.title.as_str()).collect();
+    assert_eq!(titles, vec!["Running now", "Database upgrade"]);
+}
+
+#[test]
+fn test_validate_theme() {
+    let valid = PageTheme {
+        logo_url: Some("https://example.com/logo.png".to_string()),
+        accent_color: Some("#ff6600".to_string()),
+        background_color: Some("#fff".to_string()),
+        text_color: None,
+        custom_css: Some(".banner { border-radius: 0; }".to_string()),
+    };
+    assert!(theme::validate_theme(&valid).is_ok());
+    assert!(theme::validate_theme(&PageTheme::default()).is_ok());
+
+    let invalid = |change: fn(&mut PageTheme)| {
+        let mut theme = valid.clone();
+        change(&mut theme);
+        theme::validate_theme(&theme).is_err()
+    };
+    assert!(invalid(|t| t.accent_color = Some("red".to_string())));
+    assert!(invalid(|t| t.text_color = Some("#12345".to_string())));
+    assert!(invalid(|t| t.logo_url = Some("javascript:alert(1)".to_string())));
+    assert!(invalid(|t| t.custom_css = Some("</style><script>alert(1)</script>".to_string())));
+    assert!(invalid(|t| t.custom_css = Some("a".repeat(theme::MAX_CUSTOM_CSS_LEN + 1))));
+
+    assert_eq!(theme::css_variables(&valid), ":root { --accent: #ff6600; --background: #fff; }");
+    assert_eq!(theme::css_variables(&PageTheme::default()), "");
+}
+
+#[test]
+fn test_fill_is_single_pass() {
+    assert_eq!(render::escape(r#"<a href="x">&'"#), "&lt;a href=&quot;x&quot;&gt;&amp;&#39;");
+    assert_eq!(
+        render::fill("{{a}} {{b}} {{missing}}", &[("a", "{{b}}"), ("b", "B")]),
+        "{{b}} B {{missing}}"
+    );
+    assert_eq!(render::fill("open {{a", &[("a", "A")]), "open {{a");
+}
+
+#[test]
+fn test_render_page() {
+    let theme = PageTheme {
+        logo_url: Some("https://example.com/logo.png".to_string()),
+        accent_color: Some("#ff6600".to_string()),
+        custom_css: Some("h1 { letter-spacing: 1px; }".to_string()),
+        ..Default::default()
+    };
+    let html = render::render_page(&view("<b>Checkout</b>", theme));
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Acme &lt;Status&gt;</title>"));
+    assert!(html.contains("Partial outage"));
+    assert!(html.contains("&lt;b&gt;Checkout&lt;/b&gt;"));
+    assert!(html.contains("<h2>API</h2>"));
+    assert!(html.contains(r#"<img src="https://example.com/logo.png" alt="">"#));
+    assert!(html.contains("--accent: #ff6600;"));
+    assert!(html.contains("h1 { letter-spacing: 1px; }"));
+    assert!(html.contains(r#"class="bar bar-down" title="2026-03-30: 90.00%""#));
+    assert_eq!(html.matches(r#"class="bar "#).count(), 3);
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("{{"));
+}
+
+#[test]
+fn test_caching_helpers() {
+    assert!(render::wants_html(Some("text/html,application/xhtml+xml;q=0.9")));
+    assert!(!render::wants_html(Some("application/json")));
+    assert!(!render::wants_html(None));
+
+    let etag = render::etag("<html></html>");
+    assert_eq!(etag, render::etag("<html></html>"));
+    assert_ne!(etag, render::etag("<html> </html>"));
+    assert!(render::etag_matches(&format!("\"other\", {}", etag), &etag));
+    assert!(render::etag_matches(&format!("W/{}", etag), &etag));
+    assert!(render::etag_matches("*", &etag));
+    assert!(!render::etag_matches("\"other\"", &etag));
+}
@@ -0,0 +1,160 @@
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+use statusforge_backend::features::status_pages::{
+    self, posts, render, CreatePost, PageTheme, PublicComponent, StatusPageComponent, StatusPagePost,
+    StatusPagePostUpdate, StatusPageView,
+};
+
+fn component(id: &str) -> StatusPageComponent {
+    StatusPageComponent {
+        id: id.to_string(),
+        status_page_id: "sp1".to_string(),
+        monitor_id: format!("m-{}", id),
+        display_name: None,
+        group_name: None,
+        position: 0,
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+    }
+}
+
+fn new_post(kind: Option<&str>) -> CreatePost {
+    CreatePost {
+        kind: kind.map(str::to_string),
+        title: "Elevated error rates".to_string(),
+        impact: Some("major".to_string()),
+        component_ids: Some(vec!["c1".to_string()]),
+        state: None,
+        body: "We are looking into it.".to_string(),
+        scheduled_starts_at: None,
+        scheduled_ends_at: None,
+    }
+}
+
+fn post(kind: &str, state: &str, resolved_at: Option<&str>, ends_at: Option<&str>) -> StatusPagePost {
+    serde_json::from_value(json!({
+        "id": "post1",
+        "status_page_id": "sp1",
+        "kind": kind,
+        "title": "Database upgrade",
+        "impact": "minor",
+        "state": state,
+        "component_ids": ["c1", "gone"],
+        "scheduled_starts_at": ends_at.map(|_| "2026-04-05T22:00:00Z"),
+        "scheduled_ends_at": ends_at,
+        "resolved_at": resolved_at,
+        "created_at": "2026-04-01T10:00:00Z",
+        "updated_at": "2026-04-01T10:00:00Z"
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_post_states() {
+    assert_eq!(posts::initial_state("incident"), "investigating");
+    assert_eq!(posts::initial_state("maintenance"), "scheduled");
+    assert!(posts::is_closed("incident", "resolved"));
+    assert!(posts::is_closed("maintenance", "completed"));
+    assert!(!posts::is_closed("incident", "monitoring"));
+
+    assert!(posts::validate_state("incident", "identified").is_ok());
+    assert!(posts::validate_state("incident", "in_progress").is_err());
+    assert!(posts::validate_state("maintenance", "in_progress").is_ok());
+    assert!(posts::validate_impact("catastrophic").is_err());
+    assert!(posts::validate_kind("announcement").is_err());
+}
+
+#[test]
+fn test_validate_new_post() {
+    let components = vec![component("c1")];
+    assert!(posts::validate_new_post(&new_post(None), &components).is_ok());
+
+    let mut unknown = new_post(None);
+    unknown.component_ids = Some(vec!["c2".to_string()]);
+    assert!(posts::validate_new_post(&unknown, &components).is_err());
+
+    let mut blank = new_post(None);
+    blank.body = " ".to_string();
+    assert!(posts::validate_new_post(&blank, &components).is_err());
+
+    let mut long = new_post(None);
+    long.title = "x".repeat(posts::MAX_TITLE_LEN + 1);
+    assert!(posts::validate_new_post(&long, &components).is_err());
+
+    let mut unscheduled = new_post(Some("maintenance"));
+    assert!(posts::validate_new_post(&unscheduled, &components).is_err());
+    unscheduled.scheduled_starts_at = Some("2026-04-05T22:00:00Z".to_string());
+    unscheduled.scheduled_ends_at = Some("2026-04-05T21:00:00Z".to_string());
+    assert!(posts::validate_new_post(&unscheduled, &components).is_err());
+    unscheduled.scheduled_ends_at = Some("2026-04-05T23:00:00Z".to_string());
+    assert!(posts::validate_new_post(&unscheduled, &components).is_ok());
+
+    let mut scheduled_incident = new_post(None);
+    scheduled_incident.scheduled_starts_at = Some("2026-04-05T22:00:00Z".to_string());
+    assert!(posts::validate_new_post(&scheduled_incident, &components).is_err());
+}
+
+#[test]
+fn test_post_visibility() {
+    let now = Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap();
+    let since = Utc.with_ymd_and_hms(2026, 4, 3, 12, 0, 0).unwrap();
+
+    assert!(posts::is_visible(&post("incident", "monitoring", None, None), since, now));
+    assert!(posts::is_visible(&post("incident", "resolved", Some("2026-04-09T10:00:00Z"), None), since, now));
+    assert!(!posts::is_visible(&post("incident", "resolved", Some("2026-04-01T10:00:00Z"), None), since, now));
+
+    assert!(posts::is_visible(&post("maintenance", "scheduled", None, Some("2026-04-12T23:00:00Z")), since, now));
+    assert!(!posts::is_visible(&post("maintenance", "scheduled", None, Some("2026-04-05T23:00:00Z")), since, now));
+}
+
+#[test]
+fn test_escalate_page_status() {
+    assert_eq!(posts::escalate("operational", &[]), "operational");
+    assert_eq!(posts::escalate("operational", &["none"]), "operational");
+    assert_eq!(posts::escalate("operational", &["minor"]), "degraded");
+    assert_eq!(posts::escalate("degraded", &["minor", "critical"]), "major_outage");
+    assert_eq!(posts::escalate("major_outage", &["major"]), "major_outage");
+    assert_eq!(posts::escalate("maintenance", &["major"]), "partial_outage");
+}
+
+#[test]
+fn test_public_post_and_rendering() {
+    let components = vec![PublicComponent {
+        id: "c1".to_string(),
+        name: "API".to_string(),
+        group: None,
+        status: "operational".to_string(),
+        checked_at: None,
+    }];
+    let updates = vec![StatusPagePostUpdate {
+        id: "u1".to_string(),
+        post_id: "post1".to_string(),
+        state: "in_progress".to_string(),
+        body: "Upgrading <primary>".to_string(),
+        created_at: "2026-04-05T22:00:00Z".to_string(),
+    }];
+    let public = status_pages::public_post(
+        &post("maintenance", "in_progress", None, Some("2026-04-05T23:00:00Z")),
+        &updates,
+        &components,
+    );
+    assert_eq!(public.components, vec!["API"]);
+    assert_eq!(public.updates.len(), 1);
+
+    let view = StatusPageView {
+        title: "Acme".to_string(),
+        description: None,
+        status: "maintenance".to_string(),
+        theme: PageTheme::default(),
+        components: Vec::new(),
+        incidents: Vec::new(),
+        maintenance: Vec::new(),
+        posts: vec![public],
+        updated_at: "2026-04-05T22:30:00Z".to_string(),
+    };
+    let html = render::render_page(&view);
+    assert!(html.contains("<h2>Scheduled maintenance</h2>"));
+    assert!(html.contains(r#"id="post-post1""#));
+    assert!(html.contains("Impact: Minor · Affects: API · Scheduled 2026-04-05 22:00 UTC – 2026-04-05 23:00 UTC"));
+    assert!(html.contains("<strong>In progress</strong> – Upgrading &lt;primary&gt;"));
+    assert!(!html.contains("<h2>Incidents</h2>"));
+}
@@ -0,0 +1,400 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Json;
+use serde_json::{json, Value};
+use statusforge_backend::features::status_pages::subscriptions::{self, Notification, PageLink};
+use statusforge_backend::features::status_pages::{
+    status, PublicComponent, PublicPost, PublicPostUpdate, StatusPage, StatusPageComponent, StatusPageSubscriber,
+    Subscribe,
+};
+use statusforge_backend::features::status_pages::{access, service};
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::shared::mailer::{Email, Mailer};
+use statusforge_backend::shared::net;
+
+use crate::support::{serve, PostgRest};
+
+const SECRET: &str = "test-secret";
+
+fn subscriber(id: &str, kind: &str, target: &str, component_ids: &[&str], confirmed: bool) -> StatusPageSubscriber {
+    StatusPageSubscriber {
+        id: id.to_string(),
+        status_page_id: "sp1".to_string(),
+        kind: kind.to_string(),
+        target: target.to_string(),
+        component_ids: component_ids.iter().map(|id| id.to_string()).collect(),
+        confirmed_at: confirmed.then(|| "2026-04-01T00:00:00Z".to_string()),
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+    }
+}
+
+fn page_link() -> PageLink {
+    PageLink {
+        title: "Acme".to_string(),
+        url: "https://status.acme.test".to_string(),
+    }
+}
+
+fn post_notification(component_ids: &[&str]) -> Notification {
+    Notification::Post {
+        post: PublicPost {
+            id: "post1".to_string(),
+            kind: "incident".to_string(),
+            title: "Elevated error rates".to_string(),
+            impact: "major".to_string(),
+            state: "identified".to_string(),
+            components: vec!["API".to_string()],
+            scheduled_starts_at: None,
+            scheduled_ends_at: None,
+            resolved_at: None,
+            created_at: "2026-04-06T10:00:00Z".to_string(),
+            updates: vec![
+                PublicPostUpdate {
+                    state: "identified".to_string(),
+                    body: "A bad deploy; rolling back.".to_string(),
+                    created_at: "2026-04-06T10:20:00Z".to_string(),
+                },
+                PublicPostUpdate {
+                    state: "investigating".to_string(),
+                    body: "Looking into it.".to_string(),
+                    created_at: "2026-04-06T10:00:00Z".to_string(),
+                },
+            ],
+        },
+        component_ids: component_ids.iter().map(|id| id.to_string()).collect(),
+        update: true,
+    }
+}
+
+fn component_notification(id: &str) -> Notification {
+    Notification::Component {
+        component: PublicComponent {
+            id: id.to_string(),
+            name: "API".to_string(),
+            group: None,
+            status: "outage".to_string(),
+            checked_at: Some("2026-04-06T10:00:00Z".to_string()),
+        },
+        previous_status: "operational".to_string(),
+    }
+}
+
+#[test]
+fn test_signed_tokens() {
+    let token = subscriptions::sign_token(SECRET, subscriptions::UNSUBSCRIBE, "sub-1");
+    assert!(token.starts_with("sub-1."));
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::UNSUBSCRIBE, &token), Some("sub-1".to_string()));
+
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::CONFIRM, &token), None);
+    assert_eq!(subscriptions::verify_token("other-secret", subscriptions::UNSUBSCRIBE, &token), None);
+    let forged = token.replacen("sub-1", "sub-2", 1);
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::UNSUBSCRIBE, &forged), None);
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::UNSUBSCRIBE, "sub-1"), None);
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::UNSUBSCRIBE, "sub-1.zz"), None);
+}
+
+#[test]
+fn test_validate_subscribe() {
+    let components = vec![StatusPageComponent {
+        id: "c1".to_string(),
+        status_page_id: "sp1".to_string(),
+        monitor_id: "m1".to_string(),
+        display_name: None,
+        group_name: None,
+        position: 0,
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+    }];
+    let request = |email: Option<&str>, webhook_url: Option<&str>, component_ids: &[&str]| Subscribe {
+        email: email.map(str::to_string),
+        webhook_url: webhook_url.map(str::to_string),
+        component_ids: Some(component_ids.iter().map(|id| id.to_string()).collect()),
+    };
+
+    assert_eq!(
+        subscriptions::validate_subscribe(&request(Some(" Ops@Example.COM "), None, &["c1"]), &components),
+        Ok(("email", "ops@example.com".to_string()))
+    );
+    assert_eq!(
+        subscriptions::validate_subscribe(&request(None, Some("https://hooks.example.com/status"), &[]), &components),
+        Ok(("webhook", "https://hooks.example.com/status".to_string()))
+    );
+
+    for invalid in [
+        request(None, None, &[]),
+        request(Some("ops@example.com"), Some("https://hooks.example.com"), &[]),
+        request(Some("ops@example.com"), None, &["c2"]),
+        request(Some("not-an-email"), None, &[]),
+        request(Some("ops@@example.com"), None, &[]),
+        request(Some("ops@localhost"), None, &[]),
+        request(Some("ops @example.com"), None, &[]),
+        request(None, Some("ftp://hooks.example.com"), &[]),
+        request(None, Some("hooks.example.com"), &[]),
+    ] {
+        assert!(subscriptions::validate_subscribe(&invalid, &components).is_err(), "{:?}", invalid);
+    }
+}
+
+#[test]
+fn test_page_url_prefers_verified_domain() {
+    let mut page: StatusPage = serde_json::from_value(json!({
+        "id": "sp1",
+        "project_id": "p1",
+        "title": "Acme",
+        "description": null,
+        "custom_domain": "status.acme.test",
+        "domain_token": "abc",
+        "domain_verified_at": null,
+        "enabled": true,
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z"
+    }))
+    .unwrap();
+    assert_eq!(subscriptions::page_url("https://api.statusforge.test", "acme", &page), "https://api.statusforge.test/status/acme");
+
+    page.domain_verified_at = Some("2026-04-02T00:00:00Z".to_string());
+    assert_eq!(subscriptions::page_url("https://api.statusforge.test", "acme", &page), "https://status.acme.test");
+    assert!(
+        subscriptions::unsubscribe_url("https://status.acme.test", SECRET, "sub-1")
+            .starts_with("https://status.acme.test/subscriptions/unsubscribe?token=sub-1.")
+    );
+}
+
+#[test]
+fn test_subscriber_filters() {
+    let everything = subscriber("s1", "email", "a@example.com", &[], true);
+    let api_only = subscriber("s2", "email", "b@example.com", &["c1"], true);
+    let unconfirmed = subscriber("s3", "email", "c@example.com", &[], false);
+
+    assert!(subscriptions::wants(&everything, &component_notification("c2")));
+    assert!(!subscriptions::wants(&unconfirmed, &component_notification("c1")));
+    assert!(subscriptions::wants(&api_only, &component_notification("c1")));
+    assert!(!subscriptions::wants(&api_only, &component_notification("c2")));
+    assert!(subscriptions::wants(&api_only, &post_notification(&["c2", "c1"])));
+    assert!(!subscriptions::wants(&api_only, &post_notification(&["c2"])));
+    // Posts that name no components concern everyone.
+    assert!(subscriptions::wants(&api_only, &post_notification(&[])));
+}
+
+#[test]
+fn test_emails() {
+    let link = page_link();
+    let confirmation = subscriptions::confirmation_email(&link, "ops@example.com", "https://status.acme.test/subscriptions/confirm?token=t");
+    assert_eq!(confirmation.subject, "Confirm your subscription to Acme");
+    assert!(confirmation.body.contains("https://status.acme.test/subscriptions/confirm?token=t"));
+    assert_eq!(confirmation.unsubscribe_url, None);
+
+    let email = subscriptions::notification_email(&link, "ops@example.com", &post_notification(&["c1"]), "https://u");
+    assert_eq!(email.subject, "[Acme] Elevated error rates – Identified");
+    assert!(email.body.starts_with("Elevated error rates\nIdentified · Impact: Major · Affects: API\n\nA bad deploy; rolling back.\n"));
+    assert!(!email.body.contains("Looking into it."));
+    assert!(email.body.contains("https://status.acme.test"));
+    assert!(email.body.ends_with("Unsubscribe: https://u\n"));
+    assert_eq!(email.unsubscribe_url.as_deref(), Some("https://u"));
+
+    let email = subscriptions::notification_email(&link, "ops@example.com", &component_notification("c1"), "https://u");
+    assert_eq!(email.subject, "[Acme] API: Outage");
+    assert!(email.body.starts_with("API is now Outage (was Operational).\n"));
+}
+
+#[test]
+fn test_state_component_status() {
+    assert_eq!(status::state_component_status("up", false), Some("operational"));
+    assert_eq!(status::state_component_status("degraded", false), Some("degraded"));
+    assert_eq!(status::state_component_status("down", false), Some("outage"));
+    assert_eq!(status::state_component_status("down", true), Some("maintenance"));
+    assert_eq!(status::state_component_status("paused", false), None);
+    assert_eq!(status::state_component_status("unknown", false), None);
+}
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        if email.to.starts_with("bounce") {
+            return Err("mailbox unavailable".to_string());
+        }
+        self.sent.lock().unwrap().push(email.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_fan_out_to_email_and_webhooks() {
+    let received: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let hooks = serve(
+        axum::Router::new()
+            .route(
+                "/ok",
+                post(|State(received): State<Arc<Mutex<Vec<Value>>>>, Json(payload): Json<Value>| async move {
+                    received.lock().unwrap().push(payload);
+                    StatusCode::NO_CONTENT
+                }),
+            )
+            .route("/fail", post(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .with_state(received.clone()),
+    )
+    .await;
+
+    let subscribers = vec![
+        subscriber("s1", "email", "ops@example.com", &[], true),
+        subscriber("s2", "email", "late@example.com", &[], false),
+        subscriber("s3", "email", "bounce@example.com", &[], true),
+        subscriber("s4", "email", "web@example.com", &["c2"], true),
+        subscriber("s5", "webhook", &format!("{}/ok", hooks), &["c1"], true),
+        subscriber("s6", "webhook", &format!("{}/fail", hooks), &[], true),
+    ];
+    let mailer = RecordingMailer::default();
+    // The stand-in hooks listen on loopback, which only the development switch allows.
+    let delivered =
+        subscriptions::fan_out(&mailer, SECRET, &page_link(), &subscribers, &component_notification("c1"), true).await;
+
+    // s1 and s5 succeed; s3 bounces and s6 fails; s2 is unconfirmed and s4 follows another component.
+    assert_eq!(delivered, 2);
+    let sent = mailer.sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "ops@example.com");
+    let unsubscribe = sent[0].unsubscribe_url.as_deref().unwrap();
+    let token = unsubscribe.split("token=").nth(1).unwrap();
+    assert_eq!(subscriptions::verify_token(SECRET, subscriptions::UNSUBSCRIBE, token), Some("s1".to_string()));
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0]["event"], "component.updated");
+    assert_eq!(received[0]["page"], json!({ "title": "Acme", "url": "https://status.acme.test" }));
+    assert_eq!(received[0]["component"]["status"], "outage");
+    assert_eq!(received[0]["component"]["previous_status"], "operational");
+    assert!(received[0]["unsubscribe_url"].as_str().unwrap().contains("token=s5."));
+}
+
+#[test]
+fn test_internal_addresses() {
+    let internal = [
+        "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "0.0.0.0", "100.64.0.1",
+        "::1", "::", "fd00::1", "fe80::1", "::ffff:127.0.0.1",
+    ];
+    for internal in internal {
+        assert!(net::is_internal(internal.parse().unwrap()), "{}", internal);
+    }
+    for public in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111", "::ffff:93.184.216.34"] {
+        assert!(!net::is_internal(public.parse().unwrap()), "{}", public);
+    }
+}
+
+#[tokio::test]
+async fn test_webhooks_to_internal_addresses_are_refused() {
+    let hits: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let hooks = serve(
+        axum::Router::new()
+            .route(
+                "/hook",
+                post(|State(hits): State<Arc<Mutex<Vec<Value>>>>, Json(payload): Json<Value>| async move {
+                    hits.lock().unwrap().push(payload);
+                    StatusCode::NO_CONTENT
+                }),
+            )
+            .with_state(hits.clone()),
+    )
+    .await;
+    let localhost = hooks.replace("127.0.0.1", "localhost");
+
+    let subscribers = vec![
+        subscriber("s1", "webhook", &format!("{}/hook", hooks), &[], true),
+        subscriber("s2", "webhook", &format!("{}/hook", localhost), &[], true),
+    ];
+    let mailer = RecordingMailer::default();
+    let delivered =
+        subscriptions::fan_out(&mailer, SECRET, &page_link(), &subscribers, &component_notification("c1"), false).await;
+    assert_eq!(delivered, 0);
+    assert!(hits.lock().unwrap().is_empty());
+
+    let error = subscriptions::post_webhook(&format!("{}/hook", localhost), &json!({}), false).await.unwrap_err();
+    assert!(error.contains("private or local address"), "{}", error);
+}
+
+/// A public page `acme` without components.
+fn stand_in_postgrest() -> PostgRest {
+    PostgRest::new()
+        .with(
+            "projects",
+            vec![json!({
+                "id": "p1",
+                "organization_id": "o1",
+                "name": "Acme",
+                "slug": "acme",
+                "description": null,
+                "created_by": "u1",
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z"
+            })],
+        )
+        .with(
+            "status_pages",
+            vec![json!({
+                "id": "sp1",
+                "project_id": "p1",
+                "title": "Acme",
+                "description": null,
+                "custom_domain": null,
+                "domain_token": null,
+                "domain_verified_at": null,
+                "enabled": true,
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z"
+            })],
+        )
+}
+
+#[tokio::test]
+async fn test_webhook_subscription_handshake() {
+    let received: Arc<Mutex<Vec<Value>>> = Arc::default();
+    let hooks = serve(
+        axum::Router::new()
+            .route(
+                "/hook",
+                post(|State(received): State<Arc<Mutex<Vec<Value>>>>, Json(payload): Json<Value>| async move {
+                    received.lock().unwrap().push(payload);
+                    StatusCode::NO_CONTENT
+                }),
+            )
+            .with_state(received.clone()),
+    )
+    .await;
+    let hook = format!("{}/hook", hooks);
+    let request = || Subscribe {
+        email: None,
+        webhook_url: Some(hook.clone()),
+        component_ids: None,
+    };
+    let postgrest = stand_in_postgrest();
+
+    // Loopback is refused unless the development switch is on.
+    let state = postgrest.state(Config::default()).await;
+    let result = service::subscribe(&state, "acme", &access::Viewer::default(), request()).await;
+    assert!(matches!(result, Err(statusforge_backend::ApiError::BadRequest(_))));
+    assert!(received.lock().unwrap().is_empty());
+
+    let config = Config {
+        allow_private_webhooks: true,
+        ..Default::default()
+    };
+    let state = postgrest.state(config).await;
+    service::subscribe(&state, "acme", &access::Viewer::default(), request()).await.unwrap();
+    let subscriber = postgrest.rows("status_page_subscribers").pop().unwrap();
+    assert!(subscriber["confirmed_at"].is_null());
+
+    let confirmation = received.lock().unwrap().pop().unwrap();
+    assert_eq!(confirmation["event"], "subscription.confirm");
+    let confirm_url = confirmation["confirm_url"].as_str().unwrap();
+    let token = confirm_url.split("token=").nth(1).unwrap();
+    service::confirm_subscription(&state, "acme", token).await.unwrap();
+    let subscriber = postgrest.rows("status_page_subscribers").pop().unwrap();
+    assert!(subscriber["confirmed_at"].is_string());
+}
@@ -0,0 +1,280 @@
+//! Helpers shared by the unit tests, including stand-in servers for the
+//! tests that drive HTTP clients and the router end to end.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use chrono::Utc;
+use serde_json::{json, Value};
+use statusforge_backend::features::incidents::Incident;
+use statusforge_backend::features::ingest::Log;
+use statusforge_backend::features::monitors::Monitor;
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::AppState;
+
+/// State with a Supabase client that points nowhere, for code paths that
+/// fail validation before they reach the database.
+pub fn offline_state() -> AppState {
+    let config = Config {
+        supabase_url: "http://localhost".to_string(),
+        supabase_publishable_key: "test".to_string(),
+        ..Default::default()
+    };
+    AppState {
+        supabase: statusforge_backend::shared::supabase::create_client(&config).unwrap(),
+        config,
+    }
+}
+
+/// Serves `app` on an ephemeral local port and returns its base URL.
+pub async fn serve(app: axum::Router) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", address)
+}
+
+/// Serves `backend` as the Supabase API and builds an `AppState` against it.
+pub async fn app_state(backend: axum::Router, config: Config) -> AppState {
+    let config = Config {
+        supabase_url: serve(backend).await,
+        supabase_publishable_key: "test".to_string(),
+        ..config
+    };
+    AppState {
+        supabase: statusforge_backend::shared::supabase::create_client(&config).unwrap(),
+        config,
+    }
+}
+
+/// Monitor `m1` of project `p1`.
+pub fn monitor() -> Monitor {
+    serde_json::from_value(json!({
+        "id": "m1",
+        "project_id": "p1",
+        "name": "API",
+        "kind": "http",
+        "url": "https://api.acme.test/health",
+        "keyword": null,
+        "interval_seconds": 60,
+        "enabled": true,
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Incident `i1` of `monitor()`; resolved when it has a duration.
+pub fn incident(duration_seconds: Option<i64>) -> Incident {
+    serde_json::from_value(json!({
+        "id": "i1",
+        "monitor_id": "m1",
+        "project_id": "p1",
+        "status": if duration_seconds.is_some() { "resolved" } else { "open" },
+        "started_at": "2026-04-10T12:00:00Z",
+        "resolved_at": null,
+        "duration_seconds": duration_seconds,
+        "affected_regions": ["eu", "us"],
+        "first_error": "connection refused",
+        "created_at": "2026-04-10T12:00:00Z",
+        "updated_at": "2026-04-10T12:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// A fatal log of project `p1`.
+pub fn error_log() -> Log {
+    Log {
+        id: "l1".to_string(),
+        project_id: "p1".to_string(),
+        level: "fatal".to_string(),
+        message: "payment failed".to_string(),
+        context: None,
+        trace_id: None,
+        source: Some("checkout".to_string()),
+        environment: Some("production".to_string()),
+        created_at: "2026-04-10T12:00:00Z".to_string(),
+    }
+}
+
+type Tables = Arc<Mutex<HashMap<String, Vec<Value>>>>;
+
+/// In-memory PostgREST: enough of the `eq`, `neq`, `gt(e)`, `lt(e)`, `like`,
+/// `is` and `in` filters, ordering, paging, inserts, updates and deletes for
+/// the queries the repositories make. Rows are plain JSON objects; inserts
+/// get an `id`, `created_at` and `updated_at` unless they bring their own.
+#[derive(Clone, Default)]
+pub struct PostgRest {
+    tables: Tables,
+}
+
+impl PostgRest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds `table` with `rows`.
+    pub fn with(self, table: &str, rows: Vec<Value>) -> Self {
+        self.tables.lock().unwrap().entry(table.to_string()).or_default().extend(rows);
+        self
+    }
+
+    pub fn rows(&self, table: &str) -> Vec<Value> {
+        self.tables.lock().unwrap().get(table).cloned().unwrap_or_default()
+    }
+
+    /// Applies `change` to every row of `table`.
+    pub fn update_rows(&self, table: &str, mut change: impl FnMut(&mut Value)) {
+        let mut tables = self.tables.lock().unwrap();
+        tables.entry(table.to_string()).or_default().iter_mut().for_each(&mut change);
+    }
+
+    /// The `/rest/v1/{table}` routes, ready to be served or merged with
+    /// other stand-ins.
+    pub fn router(&self) -> axum::Router {
+        axum::Router::new()
+            .route("/rest/v1/{table}", get(select).post(insert).patch(update).delete(delete))
+            .with_state(self.tables.clone())
+    }
+
+    /// Serves the stand-in and builds an `AppState` against it.
+    pub async fn state(&self, config: Config) -> AppState {
+        app_state(self.router(), config).await
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Resolves `column` or a `column->>key` JSON path.
+fn field<'a>(row: &'a Value, column: &str) -> &'a Value {
+    match column.split_once("->>") {
+        Some((column, key)) => &row[column][key],
+        None => &row[column],
+    }
+}
+
+fn compare(value: &Value, bound: &str) -> Option<Ordering> {
+    match (value.as_f64(), bound.parse::<f64>()) {
+        (Some(value), Ok(bound)) => value.partial_cmp(&bound),
+        _ if value.is_null() => None,
+        _ => Some(text(value).as_str().cmp(bound)),
+    }
+}
+
+fn matches(row: &Value, filters: &[(String, String)]) -> bool {
+    filters.iter().all(|(column, filter)| {
+        if matches!(column.as_str(), "select" | "order" | "limit" | "offset" | "columns" | "on_conflict") {
+            return true;
+        }
+        let value = field(row, column);
+        match filter.split_once('.') {
+            Some(("eq", expected)) => !value.is_null() && text(value) == expected,
+            Some(("neq", expected)) => !value.is_null() && text(value) != expected,
+            Some(("gt", bound)) => compare(value, bound) == Some(Ordering::Greater),
+            Some(("gte", bound)) => matches!(compare(value, bound), Some(Ordering::Greater | Ordering::Equal)),
+            Some(("lt", bound)) => compare(value, bound) == Some(Ordering::Less),
+            Some(("lte", bound)) => matches!(compare(value, bound), Some(Ordering::Less | Ordering::Equal)),
+            Some(("like", "*")) => !value.is_null(),
+            Some(("is", "null")) => value.is_null(),
+            Some(("is", expected)) => text(value) == expected,
+            Some(("in", list)) => list.trim_matches(|c| c == '(' || c == ')').split(',').any(|v| v == text(value)),
+            _ => panic!("Unsupported filter {}={}", column, filter),
+        }
+    })
+}
+
+fn param<'a>(filters: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    filters.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+async fn select(
+    State(tables): State<Tables>,
+    Path(table): Path<String>,
+    Query(filters): Query<Vec<(String, String)>>,
+) -> axum::Json<Value> {
+    let mut rows: Vec<Value> = {
+        let tables = tables.lock().unwrap();
+        tables
+            .get(&table)
+            .map(|rows| rows.iter().filter(|row| matches(row, &filters)).cloned().collect())
+            .unwrap_or_default()
+    };
+    if let Some(order) = param(&filters, "order") {
+        for key in order.split(',').rev() {
+            let (column, direction) = key.split_once('.').unwrap_or((key, "asc"));
+            rows.sort_by(|a, b| {
+                let ordering = text(&a[column]).cmp(&text(&b[column]));
+                if direction.starts_with("desc") { ordering.reverse() } else { ordering }
+            });
+        }
+    }
+    let offset = param(&filters, "offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit = param(&filters, "limit").and_then(|v| v.parse().ok()).unwrap_or(usize::MAX);
+    axum::Json(json!(rows.into_iter().skip(offset).take(limit).collect::<Vec<_>>()))
+}
+
+async fn insert(
+    State(tables): State<Tables>,
+    Path(table): Path<String>,
+    axum::Json(body): axum::Json<Value>,
+) -> (StatusCode, axum::Json<Value>) {
+    let rows = match body {
+        Value::Array(rows) => rows,
+        row => vec![row],
+    };
+    let mut tables = tables.lock().unwrap();
+    let stored = tables.entry(table.clone()).or_default();
+    let mut inserted = Vec::new();
+    for mut row in rows {
+        if row["id"].is_null() {
+            row["id"] = json!(format!("{}-{}", table, stored.len() + 1));
+        }
+        if row["created_at"].is_null() {
+            row["created_at"] = json!(Utc::now().to_rfc3339());
+        }
+        if row["updated_at"].is_null() {
+            row["updated_at"] = row["created_at"].clone();
+        }
+        stored.push(row.clone());
+        inserted.push(row);
+    }
+    (StatusCode::CREATED, axum::Json(json!(inserted)))
+}
+
+async fn update(
+    State(tables): State<Tables>,
+    Path(table): Path<String>,
+    Query(filters): Query<Vec<(String, String)>>,
+    axum::Json(changes): axum::Json<Value>,
+) -> axum::Json<Value> {
+    let mut tables = tables.lock().unwrap();
+    let mut updated = Vec::new();
+    for row in tables.entry(table).or_default().iter_mut().filter(|row| matches(row, &filters)) {
+        for (key, value) in changes.as_object().unwrap() {
+            row[key] = value.clone();
+        }
+        updated.push(row.clone());
+    }
+    axum::Json(json!(updated))
+}
+
+async fn delete(
+    State(tables): State<Tables>,
+    Path(table): Path<String>,
+    Query(filters): Query<Vec<(String, String)>>,
+) -> axum::Json<Value> {
+    let mut tables = tables.lock().unwrap();
+    let rows = tables.entry(table).or_default();
+    let (deleted, kept): (Vec<Value>, Vec<Value>) = rows.drain(..).partition(|row| matches(row, &filters));
+    *rows = kept;
+    axum::Json(json!(deleted))
+}
@@ -0,0 +1,209 @@
+use std::sync::{Arc, Mutex};
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::{json, Value};
+use statusforge_backend::features::webhooks::delivery::{self, Attempt};
+use statusforge_backend::features::webhooks::{events, service, Webhook, WebhookEvent};
+use statusforge_backend::shared::config::Config;
+
+use crate::support::{error_log, incident, monitor, serve, PostgRest};
+
+const SECRET: &str = "whsec_test_secret_value";
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn webhook(id: &str, url: &str, events: &[&str], secret: Option<&str>) -> Webhook {
+    Webhook {
+        id: id.to_string(),
+        project_id: "p1".to_string(),
+        url: url.to_string(),
+        events: strings(events),
+        secret: secret.map(str::to_string),
+        enabled: true,
+        consecutive_failures: 0,
+        disabled_at: None,
+        disabled_reason: None,
+        created_at: "2026-04-01T00:00:00Z".to_string(),
+        updated_at: "2026-04-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn test_validate_events() {
+    assert!(events::validate_events(&strings(&["downtime"])).is_ok());
+    assert!(events::validate_events(&strings(&["downtime", "recovery", "error_spike"])).is_ok());
+    assert!(events::validate_events(&[]).is_err());
+    assert!(events::validate_events(&strings(&["deploy"])).is_err());
+    assert!(events::validate_events(&strings(&["downtime", "downtime"])).is_err());
+}
+
+#[test]
+fn test_error_spike_fires_once() {
+    assert!(events::is_error_level("error"));
+    assert!(events::is_error_level("fatal"));
+    assert!(!events::is_error_level("warn"));
+    assert!(!events::is_error_spike(events::ERROR_SPIKE_THRESHOLD - 1));
+    assert!(events::is_error_spike(events::ERROR_SPIKE_THRESHOLD));
+    assert!(!events::is_error_spike(events::ERROR_SPIKE_THRESHOLD + 1));
+}
+
+#[test]
+fn test_event_payloads() {
+    let now = Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap();
+
+    let downtime = events::downtime(&monitor(), &incident(None), now);
+    let payload = delivery::payload(&downtime);
+    assert_eq!(payload["version"], delivery::PAYLOAD_VERSION);
+    assert_eq!(payload["id"], downtime.id);
+    assert_eq!(payload["event"], "downtime");
+    assert_eq!(payload["project_id"], "p1");
+    assert_eq!(payload["created_at"], now.to_rfc3339());
+    assert_eq!(payload["data"]["monitor"]["name"], "API");
+    assert_eq!(payload["data"]["incident"]["id"], "i1");
+
+    let recovery = events::recovery(&monitor(), &incident(None), now);
+    assert_eq!(recovery.event, "recovery");
+    assert_ne!(recovery.id, downtime.id);
+
+    let spike = events::error_spike(&error_log(), 10, now);
+    assert_eq!(spike.event, "error_spike");
+    assert_eq!(spike.data["errors"], 10);
+    assert_eq!(spike.data["window_minutes"], events::ERROR_SPIKE_MINUTES);
+    assert_eq!(spike.data["latest"]["message"], "payment failed");
+}
+
+#[test]
+fn test_signature() {
+    let now = Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap();
+    let body = r#"{"event":"downtime"}"#;
+    let header = delivery::signature_header(SECRET, now.timestamp(), body);
+    assert!(header.starts_with(&format!("t={},v1=", now.timestamp())));
+
+    assert!(delivery::verify_signature(SECRET, &header, body, now));
+    assert!(delivery::verify_signature(SECRET, &header, body, now + Duration::seconds(delivery::SIGNATURE_TOLERANCE_SECONDS)));
+    assert!(!delivery::verify_signature(SECRET, &header, body, now + Duration::seconds(delivery::SIGNATURE_TOLERANCE_SECONDS + 1)));
+    assert!(!delivery::verify_signature(SECRET, &header, r#"{"event":"recovery"}"#, now));
+    assert!(!delivery::verify_signature("another-secret-value", &header, body, now));
+
+    // A forged timestamp invalidates the signature.
+    let replayed = header.replacen(&now.timestamp().to_string(), &(now.timestamp() + 600).to_string(), 1);
+    assert!(!delivery::verify_signature(SECRET, &replayed, body, now + Duration::seconds(600)));
+    assert!(!delivery::verify_signature(SECRET, "v1=abc", body, now));
+}
+
+#[test]
+fn test_attempt_succeeded() {
+    let attempt = |status_code| Attempt { status_code, response_body: None, error_message: None, duration_ms: 5 };
+    assert!(attempt(Some(200)).succeeded());
+    assert!(attempt(Some(204)).succeeded());
+    assert!(!attempt(Some(301)).succeeded());
+    assert!(!attempt(Some(500)).succeeded());
+    assert!(!attempt(None).succeeded());
+}
+
+type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+async fn receive(State(received): State<Received>, headers: HeaderMap, body: String) -> (StatusCode, &'static str) {
+    received.lock().unwrap().push((headers, body));
+    (StatusCode::OK, "thanks")
+}
+
+fn receiver(received: Received) -> axum::Router {
+    axum::Router::new()
+        .route("/ok", post(receive))
+        .route("/fail", post(|| async { (StatusCode::INTERNAL_SERVER_ERROR, "x".repeat(5000)) }))
+        .route("/moved", post(|| async { (StatusCode::FOUND, [("location", "/ok")]) }))
+        .with_state(received)
+}
+
+#[tokio::test]
+async fn test_send() {
+    let received: Received = Arc::default();
+    let hooks = serve(receiver(received.clone())).await;
+    let client = delivery::client().unwrap();
+    let now = Utc::now();
+    let event = events::downtime(&monitor(), &incident(None), now);
+    let body = delivery::payload(&event).to_string();
+
+    let attempt = delivery::send(&client, &webhook("w1", &format!("{}/ok", hooks), &["downtime"], Some(SECRET)), &event, &body, now).await;
+    assert!(attempt.succeeded());
+    assert_eq!(attempt.status_code, Some(200));
+    assert_eq!(attempt.response_body.as_deref(), Some("thanks"));
+    assert_eq!(attempt.error_message, None);
+    {
+        let received = received.lock().unwrap();
+        let (headers, sent) = &received[0];
+        assert_eq!(sent, &body);
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(headers[delivery::EVENT_HEADER], "downtime");
+        assert_eq!(headers[delivery::DELIVERY_HEADER], event.id.as_str());
+        let signature = headers[delivery::SIGNATURE_HEADER].to_str().unwrap();
+        assert!(delivery::verify_signature(SECRET, signature, sent, now));
+    }
+
+    let unsigned = delivery::send(&client, &webhook("w2", &format!("{}/ok", hooks), &["downtime"], None), &event, &body, now).await;
+    assert!(unsigned.succeeded());
+    assert!(!received.lock().unwrap()[1].0.contains_key(delivery::SIGNATURE_HEADER));
+
+    let failed = delivery::send(&client, &webhook("w3", &format!("{}/fail", hooks), &["downtime"], None), &event, &body, now).await;
+    assert!(!failed.succeeded());
+    assert_eq!(failed.status_code, Some(500));
+    assert_eq!(failed.response_body.unwrap().len(), delivery::MAX_RESPONSE_BODY);
+    assert!(failed.error_message.unwrap().contains("500"));
+
+    // Redirects are not followed.
+    let moved = delivery::send(&client, &webhook("w4", &format!("{}/moved", hooks), &["downtime"], None), &event, &body, now).await;
+    assert_eq!(moved.status_code, Some(302));
+    assert!(!moved.succeeded());
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let closed = format!("http://{}/ok", listener.local_addr().unwrap());
+    drop(listener);
+    let refused = delivery::send(&client, &webhook("w5", &closed, &["downtime"], None), &event, &body, now).await;
+    assert_eq!(refused.status_code, None);
+    assert!(refused.error_message.unwrap().contains("failed"));
+}
+
+#[tokio::test]
+async fn test_deliver_event() {
+    let received: Received = Arc::default();
+    let hooks = serve(receiver(received.clone())).await;
+    let webhooks = [
+        webhook("w1", &format!("{}/ok", hooks), &["downtime", "recovery"], Some(SECRET)),
+        webhook("w2", &format!("{}/fail", hooks), &["downtime"], None),
+        webhook("w3", &format!("{}/ok", hooks), &["error_spike"], None),
+    ];
+    let postgrest = PostgRest::new().with("webhooks", webhooks.iter().map(|w| json!(w)).collect());
+    let state = postgrest.state(Config::default()).await;
+
+    let event: WebhookEvent = events::downtime(&monitor(), &incident(None), Utc::now());
+    // w1 succeeds, w2 fails and w3 does not follow downtime.
+    assert_eq!(service::deliver_event(&state, &event).await.unwrap(), 1);
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    let mut logs = postgrest.rows("webhook_logs");
+    logs.sort_by_key(|log| log["webhook_id"].as_str().unwrap().to_string());
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0]["webhook_id"], "w1");
+    assert_eq!(logs[0]["event_type"], "downtime");
+    assert_eq!(logs[0]["event_id"], event.id);
+    assert_eq!(logs[0]["attempt"], 1);
+    assert_eq!(logs[0]["status_code"], 200);
+    assert_eq!(logs[0]["payload"], delivery::payload(&event));
+    assert_eq!(logs[0]["error_message"], Value::Null);
+    assert_eq!(logs[1]["webhook_id"], "w2");
+    assert_eq!(logs[1]["status_code"], 500);
+    assert!(logs[1]["error_message"].as_str().unwrap().contains("500"));
+
+    let recovery = events::recovery(&monitor(), &incident(None), Utc::now());
+    assert_eq!(service::deliver_event(&state, &recovery).await.unwrap(), 1);
+    let spike = events::error_spike(&error_log(), 10, Utc::now());
+    assert_eq!(service::deliver_event(&state, &spike).await.unwrap(), 1);
+    assert_eq!(postgrest.rows("webhook_logs").len(), 4);
+}
@@ -0,0 +1,456 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use chrono::{Duration, TimeZone, Utc};
+use serde_json::{json, Value};
+use statusforge_backend::features::projects::Project;
+use statusforge_backend::features::webhooks::{
+    delivery, events, repository, retry, service, UpdateWebhook, Webhook, WebhookEvent,
+};
+use statusforge_backend::shared::config::Config;
+use statusforge_backend::shared::mailer::{Email, Mailer};
+use statusforge_backend::{router, AppState};
+
+use crate::support::{app_state, incident, monitor, serve, PostgRest};
+
+/// Supabase Auth admin API with an owner and an admin of `o1`.
+async fn admin_user(Path(id): Path<String>, headers: HeaderMap) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    if headers.get("authorization").and_then(|v| v.to_str().ok()) != Some("Bearer secret-key") {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    match id.as_str() {
+        "u-owner" | "u-admin" | "u-member" => {
+            let name = id.trim_start_matches("u-");
+            axum::Json(json!({ "id": id, "email": format!("{}@acme.test", name) })).into_response()
+        }
+        _ => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// A webhook endpoint that fails with 503 while `failing` is set.
+#[derive(Clone, Default)]
+struct Endpoint {
+    failing: Arc<AtomicBool>,
+    received: Arc<Mutex<Vec<Value>>>,
+}
+
+impl Endpoint {
+    fn fail(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    fn received(&self) -> Vec<Value> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+async fn receive(State(endpoint): State<Endpoint>, axum::Json(payload): axum::Json<Value>) -> StatusCode {
+    endpoint.received.lock().unwrap().push(payload);
+    if endpoint.failing.load(Ordering::SeqCst) {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    }
+}
+
+struct Setup {
+    state: AppState,
+    postgrest: PostgRest,
+    endpoint: Endpoint,
+    app_url: String,
+}
+
+impl Setup {
+    fn rows(&self, table: &str) -> Vec<Value> {
+        self.postgrest.rows(table)
+    }
+
+    fn webhook(&self) -> Value {
+        self.rows("webhooks")[0].clone()
+    }
+
+    /// Makes every scheduled retry due now.
+    fn expire_retries(&self) {
+        self.postgrest.update_rows("webhook_logs", |log| {
+            if !log["next_attempt_at"].is_null() {
+                log["next_attempt_at"] = json!("2000-01-01T00:00:00+00:00");
+            }
+        });
+    }
+}
+
+async fn setup(consecutive_failures: i32) -> Setup {
+    let endpoint = Endpoint::default();
+    let hook_url = serve(axum::Router::new().route("/hook", post(receive)).with_state(endpoint.clone())).await;
+
+    let postgrest = PostgRest::new()
+        .with(
+            "webhooks",
+            vec![json!({
+                "id": "w1",
+                "project_id": "p1",
+                "url": format!("{}/hook", hook_url),
+                "events": ["downtime", "recovery"],
+                "secret": "whsec_test_secret_value",
+                "enabled": true,
+                "consecutive_failures": consecutive_failures,
+                "disabled_at": null,
+                "disabled_reason": null,
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z",
+            })],
+        )
+        .with(
+            "projects",
+            vec![json!({
+                "id": "p1",
+                "organization_id": "o1",
+                "name": "Acme",
+                "slug": "acme",
+                "description": null,
+                "created_by": "u-owner",
+                "created_at": "2026-04-01T00:00:00Z",
+                "updated_at": "2026-04-01T00:00:00Z",
+            })],
+        )
+        .with(
+            "organization_members",
+            vec![
+                json!({ "organization_id": "o1", "user_id": "u-owner", "role": "owner" }),
+                json!({ "organization_id": "o1", "user_id": "u-admin", "role": "admin" }),
+                json!({ "organization_id": "o1", "user_id": "u-member", "role": "member" }),
+                json!({ "organization_id": "o2", "user_id": "u-other", "role": "owner" }),
+            ],
+        );
+
+    let backend = postgrest.router().route("/auth/v1/admin/users/{id}", get(admin_user));
+    let config = Config {
+        supabase_secret_key: Some("secret-key".to_string()),
+        public_url: "https://api.statusforge.test".to_string(),
+        ..Default::default()
+    };
+    let state = app_state(backend, config).await;
+    let app_url = serve(router(state.clone())).await;
+    Setup { state, postgrest, endpoint, app_url }
+}
+
+fn downtime() -> WebhookEvent {
+    events::downtime(&monitor(), &incident(None), Utc::now())
+}
+
+#[test]
+fn test_backoff() {
+    assert_eq!(retry::backoff(1), Duration::seconds(retry::RETRY_BASE_SECONDS));
+    assert_eq!(retry::backoff(2), Duration::seconds(retry::RETRY_BASE_SECONDS * 2));
+    assert_eq!(retry::backoff(3), Duration::seconds(retry::RETRY_BASE_SECONDS * 4));
+    assert_eq!(retry::backoff(40), Duration::seconds(retry::RETRY_MAX_SECONDS));
+    assert!((1..40).all(|attempt| retry::backoff(attempt) <= retry::backoff(attempt + 1)));
+
+    let now = Utc.with_ymd_and_hms(2026, 4, 10, 12, 0, 0).unwrap();
+    assert_eq!(retry::next_attempt_at(1, now), Some(now + Duration::seconds(30)));
+    assert!(retry::next_attempt_at(retry::MAX_ATTEMPTS - 1, now).is_some());
+    assert_eq!(retry::next_attempt_at(retry::MAX_ATTEMPTS, now), None);
+
+    assert!(!retry::should_disable(retry::AUTO_DISABLE_AFTER - 1));
+    assert!(retry::should_disable(retry::AUTO_DISABLE_AFTER));
+}
+
+#[test]
+fn test_event_from_payload() {
+    let event = downtime();
+    assert_eq!(delivery::event_from_payload(&delivery::payload(&event)), Some(event));
+    assert_eq!(delivery::event_from_payload(&json!({ "event": "downtime" })), None);
+}
+
+#[test]
+fn test_disabled_email() {
+    let project: Project = serde_json::from_value(json!({
+        "id": "p1",
+        "organization_id": "o1",
+        "name": "Acme",
+        "slug": "acme",
+        "description": null,
+        "created_by": "u-owner",
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z",
+    }))
+    .unwrap();
+    let webhook: Webhook = serde_json::from_value(json!({
+        "id": "w1",
+        "project_id": "p1",
+        "url": "https://hooks.acme.test/statusforge",
+        "events": ["downtime"],
+        "secret": null,
+        "enabled": false,
+        "disabled_reason": "Disabled after 15 consecutive failed deliveries",
+        "created_at": "2026-04-01T00:00:00Z",
+        "updated_at": "2026-04-01T00:00:00Z",
+    }))
+    .unwrap();
+
+    let email = service::disabled_email("https://api.statusforge.test/", &project, &webhook, "owner@acme.test");
+    assert_eq!(email.to, "owner@acme.test");
+    assert_eq!(email.subject, "[Acme] Webhook disabled after failed deliveries");
+    assert!(email.body.contains("https://hooks.acme.test/statusforge"));
+    assert!(email.body.contains("Disabled after 15 consecutive failed deliveries"));
+    assert!(email.body.contains("https://api.statusforge.test/webhooks/w1/dead-letters"));
+    assert_eq!(email.unsubscribe_url, None);
+}
+
+#[tokio::test]
+async fn test_retries_until_delivered() {
+    let setup = setup(0).await;
+    setup.endpoint.fail(true);
+    let event = downtime();
+
+    assert_eq!(service::deliver_event(&setup.state, &event).await.unwrap(), 0);
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["attempt"], 1);
+    assert_eq!(logs[0]["status_code"], 503);
+    let scheduled = statusforge_backend::shared::utils::parse_timestamp(logs[0]["next_attempt_at"].as_str().unwrap()).unwrap();
+    assert!(scheduled > Utc::now() + Duration::seconds(retry::RETRY_BASE_SECONDS - 5));
+    assert_eq!(setup.webhook()["consecutive_failures"], 1);
+
+    // Not due yet: nothing is sent.
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.endpoint.received().len(), 1);
+
+    // The retry fails again and schedules the next one.
+    setup.expire_retries();
+    retry::run(setup.state.clone()).await.unwrap();
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0]["next_attempt_at"], Value::Null);
+    assert_eq!(logs[1]["attempt"], 2);
+    assert_eq!(logs[1]["event_id"], event.id);
+    assert!(!logs[1]["next_attempt_at"].is_null());
+    assert_eq!(setup.webhook()["consecutive_failures"], 2);
+
+    setup.endpoint.fail(false);
+    setup.expire_retries();
+    retry::run(setup.state.clone()).await.unwrap();
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[2]["attempt"], 3);
+    assert_eq!(logs[2]["status_code"], 200);
+    assert_eq!(logs[2]["next_attempt_at"], Value::Null);
+    assert!(logs.iter().all(|log| log["next_attempt_at"].is_null() && log["dead_letter"] != true));
+    assert_eq!(setup.webhook()["consecutive_failures"], 0);
+
+    // Every attempt carried the same payload.
+    let received = setup.endpoint.received();
+    assert_eq!(received.len(), 3);
+    assert!(received.iter().all(|payload| payload == &delivery::payload(&event)));
+
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.rows("webhook_logs").len(), 3);
+}
+
+#[tokio::test]
+async fn test_pending_delivery_is_sent_by_retries() {
+    let setup = setup(0).await;
+    let event = downtime();
+    let webhook: Webhook = serde_json::from_value(setup.webhook()).unwrap();
+
+    // A delivery logged as pending whose sender stopped before sending it.
+    let entry = service::pending_entry(&webhook, &event, &delivery::payload(&event), Utc::now());
+    let pending = repository::create_log(&setup.state, entry).await.unwrap();
+    assert!(pending.is_pending());
+    assert!(setup.endpoint.received().is_empty());
+
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.endpoint.received(), vec![delivery::payload(&event)]);
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["id"], pending.id.as_str());
+    assert_eq!(logs[0]["attempt"], 1);
+    assert_eq!(logs[0]["status_code"], 200);
+    assert_eq!(logs[0]["next_attempt_at"], Value::Null);
+
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.endpoint.received().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_is_claimed_once() {
+    let setup = setup(0).await;
+    setup.endpoint.fail(true);
+    service::deliver_event(&setup.state, &downtime()).await.unwrap();
+    setup.expire_retries();
+
+    let log = &setup.rows("webhook_logs")[0];
+    let id = log["id"].as_str().unwrap();
+    let scheduled = log["next_attempt_at"].as_str().unwrap();
+    let lease = (Utc::now() + Duration::seconds(retry::CLAIM_SECONDS)).to_rfc3339();
+    assert!(repository::claim_retry(&setup.state, id, scheduled, &lease).await.unwrap());
+    assert!(!repository::claim_retry(&setup.state, id, scheduled, &lease).await.unwrap());
+
+    // The claimed retry is not due until the claim runs out.
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.rows("webhook_logs").len(), 1);
+}
+
+#[tokio::test]
+async fn test_dead_letter_and_redelivery() {
+    let setup = setup(0).await;
+    setup.endpoint.fail(true);
+    let event = downtime();
+
+    service::deliver_event(&setup.state, &event).await.unwrap();
+    for _ in 1..retry::MAX_ATTEMPTS {
+        setup.expire_retries();
+        retry::run(setup.state.clone()).await.unwrap();
+    }
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), retry::MAX_ATTEMPTS as usize);
+    let last = logs.last().unwrap();
+    assert_eq!(last["attempt"], retry::MAX_ATTEMPTS);
+    assert_eq!(last["dead_letter"], true);
+    assert_eq!(last["next_attempt_at"], Value::Null);
+
+    // Out of retries: nothing more is sent.
+    setup.expire_retries();
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.endpoint.received().len(), retry::MAX_ATTEMPTS as usize);
+
+    let client = reqwest::Client::new();
+    let dead: Vec<Value> = client
+        .get(format!("{}/webhooks/w1/dead-letters", setup.app_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dead.len(), 1);
+    let dead_id = dead[0]["id"].as_str().unwrap().to_string();
+
+    setup.endpoint.fail(false);
+    let response = client
+        .post(format!("{}/webhooks/w1/logs/{}/redeliver", setup.app_url, dead_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let redelivery: Value = response.json().await.unwrap();
+    assert_eq!(redelivery["status_code"], 200);
+    assert_eq!(redelivery["redelivery_of"], dead_id.as_str());
+    assert_eq!(redelivery["event_id"], event.id);
+    assert_eq!(setup.endpoint.received().last().unwrap(), &delivery::payload(&event));
+
+    let dead: Vec<Value> = client
+        .get(format!("{}/webhooks/w1/dead-letters", setup.app_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(dead.is_empty());
+
+    // Logs of another webhook cannot be redelivered through this one.
+    let response = client
+        .post(format!("{}/webhooks/w2/logs/{}/redeliver", setup.app_url, dead_id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[derive(Default)]
+struct RecordingMailer {
+    sent: Mutex<Vec<Email>>,
+}
+
+#[async_trait]
+impl Mailer for RecordingMailer {
+    async fn send(&self, email: &Email) -> Result<(), String> {
+        self.sent.lock().unwrap().push(email.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_auto_disable() {
+    let setup = setup(retry::AUTO_DISABLE_AFTER - 1).await;
+    setup.endpoint.fail(true);
+
+    service::deliver_event(&setup.state, &downtime()).await.unwrap();
+    let webhook = setup.webhook();
+    assert_eq!(webhook["enabled"], false);
+    assert_eq!(webhook["consecutive_failures"], retry::AUTO_DISABLE_AFTER);
+    assert!(!webhook["disabled_at"].is_null());
+    assert!(webhook["disabled_reason"].as_str().unwrap().contains("consecutive failed deliveries"));
+
+    // The pending retry of a disabled webhook is dead-lettered, not sent.
+    setup.expire_retries();
+    retry::run(setup.state.clone()).await.unwrap();
+    assert_eq!(setup.endpoint.received().len(), 1);
+    let logs = setup.rows("webhook_logs");
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0]["dead_letter"], true);
+    assert_eq!(logs[0]["next_attempt_at"], Value::Null);
+
+    let mailer = RecordingMailer::default();
+    let webhook: Webhook = serde_json::from_value(webhook).unwrap();
+    assert_eq!(service::notify_disabled(&setup.state, &mailer, &webhook).await.unwrap(), 2);
+    let mut recipients: Vec<String> = mailer.sent.lock().unwrap().iter().map(|email| email.to.clone()).collect();
+    recipients.sort();
+    assert_eq!(recipients, vec!["admin@acme.test", "owner@acme.test"]);
+
+    // Enabling it again starts over.
+    let update = UpdateWebhook { enabled: Some(true), ..Default::default() };
+    let webhook = service::update_webhook(&setup.state, "w1", update).await.unwrap();
+    assert!(webhook.enabled);
+    assert_eq!(webhook.consecutive_failures, 0);
+    assert_eq!(webhook.disabled_at, None);
+    assert_eq!(webhook.disabled_reason, None);
+}
+
+#[tokio::test]
+async fn test_send_test_event() {
+    let setup = setup(3).await;
+    setup.endpoint.fail(true);
+    let client = reqwest::Client::new();
+
+    let log: Value = client
+        .post(format!("{}/webhooks/w1/test", setup.app_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(log["event_type"], events::TEST_EVENT);
+    assert_eq!(log["status_code"], 503);
+    // Test events are neither retried nor counted against the webhook.
+    assert_eq!(log["next_attempt_at"], Value::Null);
+    assert_eq!(log["dead_letter"], false);
+    assert_eq!(setup.webhook()["consecutive_failures"], 3);
+
+    setup.endpoint.fail(false);
+    let log: Value = client
+        .post(format!("{}/webhooks/w1/test", setup.app_url))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(log["status_code"], 200);
+    let received = setup.endpoint.received();
+    assert_eq!(received.len(), 2);
+    assert_eq!(received[1]["event"], events::TEST_EVENT);
+    assert_eq!(received[1]["project_id"], "p1");
+    assert_eq!(received[1]["data"]["webhook_id"], "w1");
+
+    let response = client.post(format!("{}/webhooks/missing/test", setup.app_url)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+}
@@ -1,12 +1,56 @@
 import { serve } from "https://deno.land/std@0.168.0/http/server.ts"
 
+type StatusSpec = number | string
+
+type HttpAssertion =
+  | { type: "json_path_equals"; path: string; value: unknown }
+  | { type: "json_path_exists"; path: string }
+  | { type: "header_matches"; name: string; pattern: string }
+  | { type: "body_regex"; pattern: string }
+  | { type: "response_time"; max_ms: number }
+
+interface HttpConfig {
+  method?: string
+  headers?: Record<string, string>
+  body?: string
+  auth?: { type: "basic"; username: string; password: string } | { type: "bearer"; token: string }
+  timeout_ms?: number
+  follow_redirects?: boolean
+  expected_status?: StatusSpec[]
+  assertions?: HttpAssertion[]
+}
+
+type ExtractSource =
+  | { from: "json_path"; path: string }
+  | { from: "header"; header: string }
+  | { from: "cookie"; cookie: string }
+
+interface MultistepStep extends HttpConfig {
+  name: string
+  url: string
+  extract?: ({ name: string } & ExtractSource)[]
+}
+
+interface MultistepConfig {
+  steps: MultistepStep[]
+}
+
+interface StepResult {
+  name: string
+  status: "up" | "down" | "degraded"
+  response_time_ms?: number
+  http_status?: number
+  error_message?: string
+}
+
 interface MonitorCheckRequest {
   monitor_id: string
   project_id: string
   name: string
-  kind: "http" | "https" | "ssl" | "keyword"
+  kind: "http" | "https" | "keyword" | "multistep"
   url: string
   keyword?: string | null
+  config?: HttpConfig | MultistepConfig
   region: "EU" | "US" | "ASIA"
 }
 
@@ -18,113 +62,309 @@ interface MonitorCheckResponse {
   ssl_valid?: boolean
   ssl_expires_at?: string
   error_message?: string
+  failed_step?: string
+  steps?: StepResult[]
+}
+
+// Ten sam podzbiór JSONPath co `features::monitors::jsonpath` w backendzie:
+// `$`, `.key`, `['key']`, `["key"]`, `[index]`
+function parseJsonPath(path: string): (string | number)[] {
+  const segments: (string | number)[] = []
+  const re = /\.([\w-]+)|\[(?:'([^']*)'|"([^"]*)"|(\d+))\]/gy
+  if (!path.startsWith("$")) throw new Error(`JSONPath must start with '$': ${path}`)
+  re.lastIndex = 1
+  while (re.lastIndex < path.length) {
+    const match = re.exec(path)
+    if (!match) throw new Error(`Invalid JSONPath: ${path}`)
+    segments.push(match[4] !== undefined ? Number(match[4]) : (match[1] ?? match[2] ?? match[3]))
+  }
+  return segments
+}
+
+function selectJsonPath(value: unknown, path: string): { found: boolean; value?: unknown } {
+  let current: unknown = value
+  for (const segment of parseJsonPath(path)) {
+    if (current === null || typeof current !== "object" || !(segment in (current as object))) {
+      return { found: false }
+    }
+    current = (current as Record<string | number, unknown>)[segment]
+  }
+  return { found: true, value: current }
+}
+
+function statusMatches(specs: StatusSpec[], code: number): boolean {
+  return specs.some((spec) => {
+    if (typeof spec === "number") return spec === code
+    const pattern = spec.trim()
+    const cls = pattern.match(/^(\d)xx$/i)
+    if (cls) return Math.floor(code / 100) === Number(cls[1])
+    const range = pattern.match(/^(\d+)\s*-\s*(\d+)$/)
+    if (range) return code >= Number(range[1]) && code <= Number(range[2])
+    return Number(pattern) === code
+  })
+}
+
+function buildRequestInit(config: HttpConfig): RequestInit {
+  const headers: Record<string, string> = {
+    "User-Agent": "StatusForge-Monitor/1.0",
+    ...(config.headers ?? {}),
+  }
+  if (config.auth?.type === "basic") {
+    headers["Authorization"] = "Basic " + btoa(`${config.auth.username}:${config.auth.password}`)
+  } else if (config.auth?.type === "bearer") {
+    headers["Authorization"] = `Bearer ${config.auth.token}`
+  }
+  return {
+    method: config.method ?? "GET",
+    headers,
+    body: config.body,
+    redirect: config.follow_redirects === false ? "manual" : "follow",
+    signal: AbortSignal.timeout(config.timeout_ms ?? 10000),
+  }
+}
+
+// Zwraca listę niespełnionych asercji; `slow` oznacza przekroczenie progu czasu
+function evaluateAssertions(
+  assertions: HttpAssertion[],
+  response: Response,
+  body: string,
+  responseTime: number,
+): { failures: string[]; slow: boolean } {
+  const failures: string[] = []
+  let slow = false
+  let json: unknown = undefined
+  let jsonParsed = false
+
+  const parsedJson = () => {
+    if (!jsonParsed) {
+      jsonParsed = true
+      try {
+        json = JSON.parse(body)
+      } catch {
+        json = undefined
+      }
+    }
+    return json
+  }
+
+  for (const assertion of assertions) {
+    switch (assertion.type) {
+      case "json_path_equals": {
+        const selected = selectJsonPath(parsedJson(), assertion.path)
+        if (!selected.found || JSON.stringify(selected.value) !== JSON.stringify(assertion.value)) {
+          failures.push(`${assertion.path} expected ${JSON.stringify(assertion.value)}, got ${JSON.stringify(selected.value)}`)
+        }
+        break
+      }
+      case "json_path_exists":
+        if (!selectJsonPath(parsedJson(), assertion.path).found) {
+          failures.push(`${assertion.path} not found in response`)
+        }
+        break
+      case "header_matches": {
+        const header = response.headers.get(assertion.name)
+        if (header === null || !new RegExp(assertion.pattern).test(header)) {
+          failures.push(`Header ${assertion.name} does not match /${assertion.pattern}/`)
+        }
+        break
+      }
+      case "body_regex":
+        if (!new RegExp(assertion.pattern).test(body)) {
+          failures.push(`Body does not match /${assertion.pattern}/`)
+        }
+        break
+      case "response_time":
+        if (responseTime > assertion.max_ms) slow = true
+        break
+    }
+  }
+
+  return { failures, slow }
+}
+
+interface HttpOutcome {
+  result: MonitorCheckResponse
+  response?: Response
+  body?: string
 }
 
-async function checkHttp(url: string, region: string): Promise<MonitorCheckResponse> {
+async function runHttpRequest(
+  url: string,
+  region: string,
+  config: HttpConfig,
+  keyword?: string,
+  forceBody = false,
+): Promise<HttpOutcome> {
   const startTime = Date.now()
   try {
-    const response = await fetch(url, {
-      method: "GET",
-      headers: {
-        "User-Agent": "StatusForge-Monitor/1.0",
-      },
-      signal: AbortSignal.timeout(10000),
-    })
+    const response = await fetch(url, buildRequestInit(config))
     const responseTime = Date.now() - startTime
+    const assertions = config.assertions ?? []
+    const needsBody =
+      forceBody || keyword !== undefined || assertions.some((a) => a.type !== "response_time" && a.type !== "header_matches")
+    const body = needsBody ? await response.text() : ""
+    const expected = config.expected_status ?? []
+
+    // Bez `expected_status` zostaje domyślna klasyfikacja: 5xx = down, 4xx = degraded
+    const failures: string[] = []
+    if (expected.length > 0 ? !statusMatches(expected, response.status) : response.status >= 500) {
+      failures.push(`Unexpected status ${response.status}`)
+    }
+    if (keyword !== undefined && !body.includes(keyword)) {
+      failures.push(`Keyword "${keyword}" not found in response`)
+    }
+    const evaluated = evaluateAssertions(assertions, response, body, responseTime)
+    failures.push(...evaluated.failures)
 
     let status: "up" | "down" | "degraded" = "up"
-    if (response.status >= 500) {
+    if (failures.length > 0) {
       status = "down"
-    } else if (response.status >= 400 || responseTime > 5000) {
+    } else if (
+      evaluated.slow ||
+      (expected.length === 0 && response.status >= 400) ||
+      (!assertions.some((a) => a.type === "response_time") && responseTime > 5000)
+    ) {
       status = "degraded"
     }
 
     return {
-      region: region as "EU" | "US" | "ASIA",
-      status,
-      response_time_ms: responseTime,
-      http_status: response.status,
+      result: {
+        region: region as "EU" | "US" | "ASIA",
+        status,
+        response_time_ms: responseTime,
+        http_status: response.status,
+        error_message: failures.length > 0 ? failures.join("; ") : undefined,
+      },
+      response,
+      body,
     }
   } catch (error) {
     return {
-      region: region as "EU" | "US" | "ASIA",
-      status: "down",
-      error_message: error instanceof Error ? error.message : String(error),
+      result: {
+        region: region as "EU" | "US" | "ASIA",
+        status: "down",
+        error_message: error instanceof Error ? error.message : String(error),
+      },
     }
   }
 }
 
-async function checkSsl(url: string, region: string): Promise<MonitorCheckResponse> {
-  try {
-    const urlObj = new URL(url)
-    const hostname = urlObj.hostname
-    const port = urlObj.port || (urlObj.protocol === "https:" ? 443 : 80)
+async function checkHttp(
+  url: string,
+  region: string,
+  config: HttpConfig = {},
+  keyword?: string,
+): Promise<MonitorCheckResponse> {
+  return (await runHttpRequest(url, region, config, keyword)).result
+}
 
-    const response = await fetch(`https://api.ssllabs.com/api/v3/analyze?host=${hostname}&publish=off&fromCache=on&maxAge=1`)
-    const data = await response.json()
+function applyTemplate(template: string, variables: Record<string, string>): string {
+  return template.replace(/\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}/g, (_, name) => variables[name] ?? "")
+}
 
-    if (data.status === "READY" && data.endpoints && data.endpoints.length > 0) {
-      const endpoint = data.endpoints[0]
-      const sslValid = endpoint.grade && endpoint.grade !== "F"
-      const sslExpiresAt = endpoint.details?.cert?.notAfter
+function templateConfig(step: MultistepStep, variables: Record<string, string>): HttpConfig {
+  const headers: Record<string, string> = {}
+  for (const [name, value] of Object.entries(step.headers ?? {})) {
+    headers[name] = applyTemplate(value, variables)
+  }
+  let auth = step.auth
+  if (auth?.type === "basic") {
+    auth = { ...auth, username: applyTemplate(auth.username, variables), password: applyTemplate(auth.password, variables) }
+  } else if (auth?.type === "bearer") {
+    auth = { ...auth, token: applyTemplate(auth.token, variables) }
+  }
+  return {
+    ...step,
+    headers,
+    auth,
+    body: step.body !== undefined ? applyTemplate(step.body, variables) : undefined,
+  }
+}
 
-      return {
-        region: region as "EU" | "US" | "ASIA",
-        status: sslValid ? "up" : "down",
-        ssl_valid: sslValid,
-        ssl_expires_at: sslExpiresAt,
-        error_message: sslValid ? undefined : "SSL certificate is invalid or expired",
+function extractVariable(source: ExtractSource, response: Response, body: string): string | undefined {
+  switch (source.from) {
+    case "json_path": {
+      let json: unknown
+      try {
+        json = JSON.parse(body)
+      } catch {
+        return undefined
       }
+      const selected = selectJsonPath(json, source.path)
+      if (!selected.found) return undefined
+      return typeof selected.value === "string" ? selected.value : JSON.stringify(selected.value)
     }
-
-    return {
-      region: region as "EU" | "US" | "ASIA",
-      status: "degraded",
-      error_message: "SSL Labs API did not return valid data",
-    }
-  } catch (error) {
-    return {
-      region: region as "EU" | "US" | "ASIA",
-      status: "down",
-      error_message: error instanceof Error ? error.message : String(error),
+    case "header":
+      return response.headers.get(source.header) ?? undefined
+    case "cookie": {
+      for (const cookie of response.headers.getSetCookie()) {
+        const [pair] = cookie.split(";")
+        const index = pair.indexOf("=")
+        if (index > 0 && pair.slice(0, index).trim() === source.cookie) {
+          return pair.slice(index + 1).trim()
+        }
+      }
+      return undefined
     }
   }
 }
 
-async function checkKeyword(url: string, keyword: string, region: string): Promise<MonitorCheckResponse> {
-  const startTime = Date.now()
-  try {
-    const response = await fetch(url, {
-      method: "GET",
-      headers: {
-        "User-Agent": "StatusForge-Monitor/1.0",
-      },
-      signal: AbortSignal.timeout(10000),
-    })
-    const responseTime = Date.now() - startTime
-    const body = await response.text()
-    const keywordFound = body.includes(keyword)
+async function checkMultistep(config: MultistepConfig, region: string): Promise<MonitorCheckResponse> {
+  const variables: Record<string, string> = {}
+  const steps: StepResult[] = []
+  let totalTime = 0
+  let degraded = false
 
-    let status: "up" | "down" | "degraded" = keywordFound ? "up" : "down"
-    if (response.status >= 500) {
-      status = "down"
-    } else if (response.status >= 400 || responseTime > 5000) {
-      status = "degraded"
+  for (const step of config.steps) {
+    const outcome = await runHttpRequest(
+      applyTemplate(step.url, variables),
+      region,
+      templateConfig(step, variables),
+      undefined,
+      (step.extract ?? []).some((e) => e.from === "json_path"),
+    )
+    const stepResult: StepResult = {
+      name: step.name,
+      status: outcome.result.status,
+      response_time_ms: outcome.result.response_time_ms,
+      http_status: outcome.result.http_status,
+      error_message: outcome.result.error_message,
     }
 
-    return {
-      region: region as "EU" | "US" | "ASIA",
-      status,
-      response_time_ms: responseTime,
-      http_status: response.status,
-      error_message: keywordFound ? undefined : `Keyword "${keyword}" not found in response`,
+    if (outcome.response && stepResult.status !== "down") {
+      for (const extraction of step.extract ?? []) {
+        const value = extractVariable(extraction, outcome.response, outcome.body ?? "")
+        if (value === undefined) {
+          stepResult.status = "down"
+          stepResult.error_message = `Could not extract variable ${extraction.name}`
+          break
+        }
+        variables[extraction.name] = value
+      }
     }
-  } catch (error) {
-    return {
-      region: region as "EU" | "US" | "ASIA",
-      status: "down",
-      error_message: error instanceof Error ? error.message : String(error),
+
+    steps.push(stepResult)
+    totalTime += stepResult.response_time_ms ?? 0
+
+    if (stepResult.status === "down") {
+      return {
+        region: region as "EU" | "US" | "ASIA",
+        status: "down",
+        response_time_ms: totalTime,
+        http_status: stepResult.http_status,
+        error_message: `Step "${step.name}" failed: ${stepResult.error_message ?? "unknown error"}`,
+        failed_step: step.name,
+        steps,
+      }
     }
+    degraded = degraded || stepResult.status === "degraded"
+  }
+
+  return {
+    region: region as "EU" | "US" | "ASIA",
+    status: degraded ? "degraded" : "up",
+    response_time_ms: totalTime,
+    steps,
   }
 }
 
@@ -154,10 +394,7 @@ serve(async (req) => {
     switch (body.kind) {
       case "http":
       case "https":
-        result = await checkHttp(body.url, body.region)
-        break
-      case "ssl":
-        result = await checkSsl(body.url, body.region)
+        result = await checkHttp(body.url, body.region, body.config as HttpConfig)
         break
       case "keyword":
         if (!body.keyword) {
@@ -169,7 +406,19 @@ serve(async (req) => {
             }
           )
         }
-        result = await checkKeyword(body.url, body.keyword, body.region)
+        result = await checkHttp(body.url, body.region, body.config as HttpConfig, body.keyword)
+        break
+      case "multistep":
+        if (!body.config || !("steps" in body.config) || body.config.steps.length === 0) {
+          return new Response(
+            JSON.stringify({ error: "Steps are required for multi-step monitoring" }),
+            {
+              status: 400,
+              headers: { "Content-Type": "application/json" },
+            }
+          )
+        }
+        result = await checkMultistep(body.config, body.region)
         break
       default:
         return new Response(
@@ -0,0 +1,16 @@
+-- Monitory typu ping (ICMP / TCP connect) oraz statystyki pakietów w wynikach
+
+ALTER TABLE monitors DROP CONSTRAINT IF EXISTS monitors_kind_check;
+ALTER TABLE monitors ADD CONSTRAINT monitors_kind_check
+    CHECK (kind IN ('http', 'https', 'ssl', 'keyword', 'ping'));
+
+-- Ustawienia specyficzne dla typu monitora (np. liczba sond dla ping)
+ALTER TABLE monitors ADD COLUMN config JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE monitor_results ADD COLUMN packet_loss REAL CHECK (packet_loss BETWEEN 0 AND 100);
+ALTER TABLE monitor_results ADD COLUMN jitter_ms REAL;
+ALTER TABLE monitor_results ADD COLUMN min_response_time_ms INTEGER;
+ALTER TABLE monitor_results ADD COLUMN max_response_time_ms INTEGER;
+
+COMMENT ON COLUMN monitor_results.packet_loss IS 'Procent utraconych sond (tylko monitory ping)';
+COMMENT ON COLUMN monitor_results.jitter_ms IS 'Średnia różnica czasu pomiędzy kolejnymi sondami (tylko monitory ping)';
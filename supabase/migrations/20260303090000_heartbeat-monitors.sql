@@ -0,0 +1,15 @@
+-- Monitory heartbeat (push / dead-man's switch): zadania same wysyłają ping
+-- na unikalny URL, backend zapisuje `down` gdy ping nie dotrze na czas
+
+ALTER TABLE monitors DROP CONSTRAINT IF EXISTS monitors_kind_check;
+ALTER TABLE monitors ADD CONSTRAINT monitors_kind_check
+    CHECK (kind IN ('http', 'https', 'ssl', 'keyword', 'ping', 'heartbeat'));
+
+ALTER TABLE monitors ADD COLUMN heartbeat_token TEXT UNIQUE;
+ALTER TABLE monitors ADD COLUMN last_heartbeat_at TIMESTAMPTZ;
+ALTER TABLE monitors ADD COLUMN heartbeat_started_at TIMESTAMPTZ;
+
+CREATE INDEX idx_monitors_heartbeat ON monitors(kind) WHERE kind = 'heartbeat' AND enabled = true;
+
+COMMENT ON COLUMN monitors.heartbeat_token IS 'Sekretny token w URL POST /heartbeats/{token}';
+COMMENT ON COLUMN monitors.heartbeat_started_at IS 'Czas pingu /start bieżącego uruchomienia zadania';
@@ -0,0 +1,12 @@
+-- Monitory wieloetapowe (transakcje API): kolejne żądania HTTP ze zmiennymi
+-- wyciąganymi z wcześniejszych odpowiedzi
+
+ALTER TABLE monitors DROP CONSTRAINT IF EXISTS monitors_kind_check;
+ALTER TABLE monitors ADD CONSTRAINT monitors_kind_check
+    CHECK (kind IN ('http', 'https', 'ssl', 'keyword', 'ping', 'heartbeat', 'multistep'));
+
+ALTER TABLE monitor_results ADD COLUMN failed_step TEXT;
+ALTER TABLE monitor_results ADD COLUMN steps JSONB;
+
+COMMENT ON COLUMN monitor_results.failed_step IS 'Nazwa pierwszego nieudanego kroku (tylko monitory multistep)';
+COMMENT ON COLUMN monitor_results.steps IS 'Wyniki i czasy poszczególnych kroków (tylko monitory multistep)';
@@ -0,0 +1,10 @@
+-- Diagnostyka certyfikatów SSL wykonywana przez backend (zamiast SSL Labs)
+
+ALTER TABLE monitor_results ADD COLUMN ssl_days_remaining INTEGER;
+ALTER TABLE monitor_results ADD COLUMN ssl_issuer TEXT;
+ALTER TABLE monitor_results ADD COLUMN ssl_details JSONB;
+
+CREATE INDEX idx_monitor_results_ssl_expires ON monitor_results(monitor_id, created_at DESC)
+    WHERE ssl_expires_at IS NOT NULL;
+
+COMMENT ON COLUMN monitor_results.ssl_details IS 'SAN, zgodność hosta, kompletność łańcucha, algorytm podpisu, rozmiar klucza, protokół';
@@ -0,0 +1,12 @@
+-- Monitory rejestracji domen (RDAP): data wygaśnięcia, rejestrator, statusy
+
+ALTER TABLE monitors DROP CONSTRAINT IF EXISTS monitors_kind_check;
+ALTER TABLE monitors ADD CONSTRAINT monitors_kind_check
+    CHECK (kind IN ('http', 'https', 'ssl', 'keyword', 'ping', 'heartbeat', 'multistep', 'domain'));
+
+ALTER TABLE monitor_results ADD COLUMN domain_expires_at TIMESTAMPTZ;
+ALTER TABLE monitor_results ADD COLUMN domain_days_remaining INTEGER;
+ALTER TABLE monitor_results ADD COLUMN domain_registrar TEXT;
+ALTER TABLE monitor_results ADD COLUMN domain_statuses JSONB;
+
+COMMENT ON COLUMN monitor_results.domain_statuses IS 'Statusy EPP/RDAP domeny, np. ["client transfer prohibited"]';
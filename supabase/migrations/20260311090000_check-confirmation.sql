@@ -0,0 +1,43 @@
+-- Potwierdzanie awarii: ponowienia w regionie i kworum regionów, zanim
+-- monitor zostanie uznany za `down`
+
+ALTER TABLE monitors ADD COLUMN confirmation_retries INTEGER NOT NULL DEFAULT 0
+    CHECK (confirmation_retries BETWEEN 0 AND 5);
+ALTER TABLE monitors ADD COLUMN regions JSONB NOT NULL DEFAULT '["EU"]'::jsonb;
+ALTER TABLE monitors ADD COLUMN down_quorum INTEGER NOT NULL DEFAULT 1
+    CHECK (down_quorum >= 1);
+
+-- Zagregowany wynik jednego uruchomienia checku (wszystkie regiony)
+CREATE TABLE monitor_checks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('up', 'down', 'degraded')),
+    regions_total INTEGER NOT NULL,
+    regions_failed INTEGER NOT NULL,
+    attempts INTEGER NOT NULL,
+    result_ids JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_monitor_checks_monitor_created ON monitor_checks(monitor_id, created_at DESC);
+
+ALTER TABLE monitor_checks ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view monitor checks"
+    ON monitor_checks FOR SELECT
+    USING (
+        monitor_id IN (
+            SELECT m.id FROM monitors m
+            JOIN projects p ON m.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+CREATE POLICY "Service role can insert monitor checks"
+    ON monitor_checks FOR INSERT
+    WITH CHECK (true);
+
+COMMENT ON COLUMN monitors.confirmation_retries IS 'Ile razy ponowić check w regionie, który zwrócił `down`';
+COMMENT ON COLUMN monitors.down_quorum IS 'Ile regionów musi zwrócić `down`, aby cały check był `down`';
+COMMENT ON COLUMN monitor_checks.result_ids IS 'Identyfikatory surowych wyników per region w monitor_results';
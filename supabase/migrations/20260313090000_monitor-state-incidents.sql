@@ -0,0 +1,54 @@
+-- Stan monitora (up/down/degraded/paused/unknown) i automatyczne incydenty
+-- otwierane przy przejściu w `down` i zamykane po powrocie
+
+ALTER TABLE monitors ADD COLUMN state TEXT NOT NULL DEFAULT 'unknown'
+    CHECK (state IN ('up', 'down', 'degraded', 'paused', 'unknown'));
+ALTER TABLE monitors ADD COLUMN state_since TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE monitors SET state = 'paused' WHERE enabled = false;
+
+CREATE TABLE incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'resolved')),
+    started_at TIMESTAMPTZ NOT NULL,
+    resolved_at TIMESTAMPTZ,
+    duration_seconds BIGINT,
+    affected_regions JSONB NOT NULL DEFAULT '[]'::jsonb,
+    first_error TEXT,
+    result_ids JSONB NOT NULL DEFAULT '[]'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_incidents_project_started ON incidents(project_id, started_at DESC);
+-- Najwyżej jeden otwarty incydent na monitor
+CREATE UNIQUE INDEX idx_incidents_open_monitor ON incidents(monitor_id) WHERE status = 'open';
+
+CREATE TRIGGER update_incidents_updated_at BEFORE UPDATE ON incidents
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE incidents ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view incidents from their projects"
+    ON incidents FOR SELECT
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+CREATE POLICY "Users can manage incidents in their projects"
+    ON incidents FOR ALL
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+COMMENT ON COLUMN incidents.result_ids IS 'Wyniki `down` z monitor_results, które złożyły się na incydent';
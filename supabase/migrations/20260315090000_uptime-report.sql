@@ -0,0 +1,140 @@
+-- Raport uptime / SLA monitora liczony w bazie (bez stronicowania wyników):
+-- procent uptime, incydenty, MTTR, MTBF, percentyle opóźnień i podział dzienny
+
+CREATE INDEX IF NOT EXISTS idx_monitor_results_monitor_created
+    ON monitor_results(monitor_id, created_at);
+
+-- Próbki dostępności: jedna na uruchomienie checku (status po kworum regionów
+-- z monitor_checks), a wyniki spoza checków (heartbeaty, wyniki przesłane
+-- przez API) liczą się pojedynczo. Jeden niestabilny region nie obniża więc
+-- uptime, gdy kworum uznało monitor za działający. p_monitor_id = NULL
+-- zwraca próbki wszystkich monitorów
+CREATE OR REPLACE FUNCTION monitor_check_samples(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ
+)
+RETURNS TABLE (monitor_id UUID, sampled_at TIMESTAMPTZ, status TEXT)
+LANGUAGE sql
+STABLE
+AS $$
+WITH results AS (
+    SELECT r.id, r.monitor_id, r.status, r.created_at
+    FROM monitor_results r
+    WHERE (p_monitor_id IS NULL OR r.monitor_id = p_monitor_id)
+      AND r.created_at >= p_from
+      AND r.created_at < p_to
+),
+checked AS (
+    SELECT c.id AS check_id, c.monitor_id, c.status, r.id AS result_id, r.created_at
+    FROM results r
+    JOIN monitor_checks c
+      ON c.monitor_id = r.monitor_id
+     AND c.created_at >= r.created_at
+     AND c.created_at < r.created_at + interval '1 hour'
+     AND c.result_ids ? r.id::text
+)
+SELECT ch.monitor_id, min(ch.created_at), ch.status
+FROM checked ch
+GROUP BY ch.check_id, ch.monitor_id, ch.status
+UNION ALL
+SELECT r.monitor_id, r.created_at, r.status
+FROM results r
+WHERE NOT EXISTS (SELECT 1 FROM checked ch WHERE ch.result_id = r.id);
+$$;
+
+CREATE OR REPLACE FUNCTION monitor_uptime_report(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ,
+    p_degraded_as_up BOOLEAN DEFAULT true
+)
+RETURNS JSONB
+LANGUAGE sql
+STABLE
+AS $$
+WITH samples AS (
+    SELECT sampled_at, status
+    FROM monitor_check_samples(p_monitor_id, p_from, p_to)
+),
+totals AS (
+    SELECT
+        count(*) AS total,
+        count(*) FILTER (WHERE status = 'up') AS up,
+        count(*) FILTER (WHERE status = 'degraded') AS degraded,
+        count(*) FILTER (WHERE status = 'down') AS down
+    FROM samples
+),
+-- Opóźnienia liczone z surowych wyników wszystkich regionów
+latency AS (
+    SELECT
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY response_time_ms) AS p50,
+        percentile_cont(0.9) WITHIN GROUP (ORDER BY response_time_ms) AS p90,
+        percentile_cont(0.99) WITHIN GROUP (ORDER BY response_time_ms) AS p99
+    FROM monitor_results
+    WHERE monitor_id = p_monitor_id
+      AND created_at >= p_from
+      AND created_at < p_to
+),
+days AS (
+    SELECT
+        d.day,
+        count(s.status) AS total,
+        count(s.status) FILTER (WHERE s.status = 'up') AS up,
+        count(s.status) FILTER (WHERE s.status = 'degraded') AS degraded,
+        count(s.status) FILTER (WHERE s.status = 'down') AS down
+    FROM generate_series(
+        date_trunc('day', p_from AT TIME ZONE 'UTC'),
+        date_trunc('day', (p_to - interval '1 microsecond') AT TIME ZONE 'UTC'),
+        interval '1 day'
+    ) AS d(day)
+    LEFT JOIN samples s ON date_trunc('day', s.sampled_at AT TIME ZONE 'UTC') = d.day
+    GROUP BY d.day
+),
+incident_stats AS (
+    SELECT
+        count(*) AS incidents,
+        avg(duration_seconds) FILTER (WHERE status = 'resolved') AS mttr,
+        coalesce(sum(extract(epoch FROM
+            least(coalesce(resolved_at, p_to), p_to) - greatest(started_at, p_from)
+        )), 0) AS downtime
+    FROM incidents
+    WHERE monitor_id = p_monitor_id
+      AND started_at < p_to
+      AND coalesce(resolved_at, p_to) > p_from
+)
+SELECT jsonb_build_object(
+    'monitor_id', p_monitor_id,
+    'from', p_from,
+    'to', p_to,
+    'uptime_percent', CASE WHEN t.total = 0 THEN NULL ELSE round(
+        100.0 * (t.up + CASE WHEN p_degraded_as_up THEN t.degraded ELSE 0 END) / t.total, 3
+    ) END,
+    'total_checks', t.total,
+    'up_checks', t.up,
+    'degraded_checks', t.degraded,
+    'down_checks', t.down,
+    'incidents', i.incidents,
+    'downtime_seconds', i.downtime,
+    'mttr_seconds', i.mttr,
+    'mtbf_seconds', CASE WHEN i.incidents = 0 THEN NULL
+        ELSE (extract(epoch FROM p_to - p_from) - i.downtime) / i.incidents END,
+    'latency', (SELECT jsonb_build_object('p50', l.p50, 'p90', l.p90, 'p99', l.p99) FROM latency l),
+    'days', coalesce((
+        SELECT jsonb_agg(jsonb_build_object(
+            'date', to_char(d.day, 'YYYY-MM-DD'),
+            'total_checks', d.total,
+            'up_checks', d.up,
+            'degraded_checks', d.degraded,
+            'down_checks', d.down,
+            'uptime_percent', CASE WHEN d.total = 0 THEN NULL ELSE round(
+                100.0 * (d.up + CASE WHEN p_degraded_as_up THEN d.degraded ELSE 0 END) / d.total, 3
+            ) END
+        ) ORDER BY d.day)
+        FROM days d
+    ), '[]'::jsonb)
+)
+FROM totals t, incident_stats i;
+$$;
+
+COMMENT ON FUNCTION monitor_uptime_report(UUID, TIMESTAMPTZ, TIMESTAMPTZ, BOOLEAN) IS 'Raport uptime/SLA dla GET /monitors/:id/uptime';
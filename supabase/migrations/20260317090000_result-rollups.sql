@@ -0,0 +1,293 @@
+-- Rollupy godzinowe i dzienne wyników monitorów oraz retencja surowych wierszy.
+-- Job backendu (`monitors::rollups`) odświeża ostatnie kubełki i usuwa surowe
+-- wyniki starsze niż RESULT_RETENTION_DAYS
+
+CREATE TABLE monitor_result_rollups (
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    region TEXT NOT NULL,
+    resolution TEXT NOT NULL CHECK (resolution IN ('hour', 'day')),
+    bucket_start TIMESTAMPTZ NOT NULL,
+    total BIGINT NOT NULL,
+    up BIGINT NOT NULL,
+    degraded BIGINT NOT NULL,
+    down BIGINT NOT NULL,
+    latency_samples BIGINT NOT NULL,
+    min_ms DOUBLE PRECISION,
+    avg_ms DOUBLE PRECISION,
+    max_ms DOUBLE PRECISION,
+    p50_ms DOUBLE PRECISION,
+    p90_ms DOUBLE PRECISION,
+    p99_ms DOUBLE PRECISION,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (monitor_id, region, resolution, bucket_start)
+);
+
+CREATE INDEX idx_monitor_result_rollups_bucket ON monitor_result_rollups(monitor_id, resolution, bucket_start DESC);
+
+ALTER TABLE monitor_result_rollups ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view monitor result rollups"
+    ON monitor_result_rollups FOR SELECT
+    USING (
+        monitor_id IN (
+            SELECT m.id FROM monitors m
+            JOIN projects p ON m.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+-- Próbki uptime (status po kworum regionów, patrz monitor_check_samples)
+-- w tych samych kubełkach; z nich raport uptime liczy długie okna
+CREATE TABLE monitor_check_rollups (
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    resolution TEXT NOT NULL CHECK (resolution IN ('hour', 'day')),
+    bucket_start TIMESTAMPTZ NOT NULL,
+    total BIGINT NOT NULL,
+    up BIGINT NOT NULL,
+    degraded BIGINT NOT NULL,
+    down BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (monitor_id, resolution, bucket_start)
+);
+
+ALTER TABLE monitor_check_rollups ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view monitor check rollups"
+    ON monitor_check_rollups FOR SELECT
+    USING (
+        monitor_id IN (
+            SELECT m.id FROM monitors m
+            JOIN projects p ON m.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+-- Przelicza kubełki z surowych wyników w [p_from, p_to); kubełki są liczone
+-- w całości, więc p_from jest wyrównywane w dół do początku godziny / dnia
+CREATE OR REPLACE FUNCTION rollup_monitor_results(
+    p_resolution TEXT,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ
+)
+RETURNS INTEGER
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    affected INTEGER;
+    bucket_from TIMESTAMPTZ;
+BEGIN
+    IF p_resolution NOT IN ('hour', 'day') THEN
+        RAISE EXCEPTION 'Invalid resolution: %', p_resolution;
+    END IF;
+
+    bucket_from := date_trunc(p_resolution, p_from AT TIME ZONE 'UTC') AT TIME ZONE 'UTC';
+
+    INSERT INTO monitor_result_rollups (
+        monitor_id, region, resolution, bucket_start,
+        total, up, degraded, down, latency_samples,
+        min_ms, avg_ms, max_ms, p50_ms, p90_ms, p99_ms, updated_at
+    )
+    SELECT
+        monitor_id,
+        region,
+        p_resolution,
+        date_trunc(p_resolution, created_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC',
+        count(*),
+        count(*) FILTER (WHERE status = 'up'),
+        count(*) FILTER (WHERE status = 'degraded'),
+        count(*) FILTER (WHERE status = 'down'),
+        count(response_time_ms),
+        min(response_time_ms),
+        avg(response_time_ms),
+        max(response_time_ms),
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY response_time_ms),
+        percentile_cont(0.9) WITHIN GROUP (ORDER BY response_time_ms),
+        percentile_cont(0.99) WITHIN GROUP (ORDER BY response_time_ms),
+        NOW()
+    FROM monitor_results
+    WHERE created_at >= bucket_from
+      AND created_at < p_to
+    GROUP BY 1, 2, 3, 4
+    ON CONFLICT (monitor_id, region, resolution, bucket_start) DO UPDATE SET
+        total = EXCLUDED.total,
+        up = EXCLUDED.up,
+        degraded = EXCLUDED.degraded,
+        down = EXCLUDED.down,
+        latency_samples = EXCLUDED.latency_samples,
+        min_ms = EXCLUDED.min_ms,
+        avg_ms = EXCLUDED.avg_ms,
+        max_ms = EXCLUDED.max_ms,
+        p50_ms = EXCLUDED.p50_ms,
+        p90_ms = EXCLUDED.p90_ms,
+        p99_ms = EXCLUDED.p99_ms,
+        updated_at = EXCLUDED.updated_at;
+
+    GET DIAGNOSTICS affected = ROW_COUNT;
+
+    INSERT INTO monitor_check_rollups (
+        monitor_id, resolution, bucket_start, total, up, degraded, down, updated_at
+    )
+    SELECT
+        monitor_id,
+        p_resolution,
+        date_trunc(p_resolution, sampled_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC',
+        count(*),
+        count(*) FILTER (WHERE status = 'up'),
+        count(*) FILTER (WHERE status = 'degraded'),
+        count(*) FILTER (WHERE status = 'down'),
+        NOW()
+    FROM monitor_check_samples(NULL, bucket_from, p_to)
+    GROUP BY 1, 2, 3
+    ON CONFLICT (monitor_id, resolution, bucket_start) DO UPDATE SET
+        total = EXCLUDED.total,
+        up = EXCLUDED.up,
+        degraded = EXCLUDED.degraded,
+        down = EXCLUDED.down,
+        updated_at = EXCLUDED.updated_at;
+
+    RETURN affected;
+END;
+$$;
+
+-- Usuwa surowe wyniki starsze niż p_before (dane zostają w rollupach)
+CREATE OR REPLACE FUNCTION prune_monitor_results(p_before TIMESTAMPTZ)
+RETURNS BIGINT
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    deleted BIGINT;
+BEGIN
+    DELETE FROM monitor_results WHERE created_at < p_before;
+    GET DIAGNOSTICS deleted = ROW_COUNT;
+    RETURN deleted;
+END;
+$$;
+
+-- Raport uptime może teraz korzystać z rollupów (p_resolution = 'hour' / 'day').
+-- Percentyle z rollupów są średnią percentyli kubełków ważoną liczbą próbek
+DROP FUNCTION IF EXISTS monitor_uptime_report(UUID, TIMESTAMPTZ, TIMESTAMPTZ, BOOLEAN);
+
+CREATE OR REPLACE FUNCTION monitor_uptime_report(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ,
+    p_degraded_as_up BOOLEAN DEFAULT true,
+    p_resolution TEXT DEFAULT 'raw'
+)
+RETURNS JSONB
+LANGUAGE sql
+STABLE
+AS $$
+WITH samples AS (
+    SELECT sampled_at AS at, 1::bigint AS total,
+        (status = 'up')::int::bigint AS up,
+        (status = 'degraded')::int::bigint AS degraded,
+        (status = 'down')::int::bigint AS down
+    FROM monitor_check_samples(p_monitor_id, p_from, p_to)
+    WHERE p_resolution = 'raw'
+    UNION ALL
+    SELECT bucket_start, total, up, degraded, down
+    FROM monitor_check_rollups
+    WHERE resolution = p_resolution
+      AND monitor_id = p_monitor_id
+      AND bucket_start >= p_from
+      AND bucket_start < p_to
+),
+latency AS (
+    SELECT
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY response_time_ms) AS p50,
+        percentile_cont(0.9) WITHIN GROUP (ORDER BY response_time_ms) AS p90,
+        percentile_cont(0.99) WITHIN GROUP (ORDER BY response_time_ms) AS p99
+    FROM monitor_results
+    WHERE p_resolution = 'raw'
+      AND monitor_id = p_monitor_id
+      AND created_at >= p_from
+      AND created_at < p_to
+),
+rollup_latency AS (
+    SELECT
+        sum(p50_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p50_ms IS NOT NULL), 0) AS p50,
+        sum(p90_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p90_ms IS NOT NULL), 0) AS p90,
+        sum(p99_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p99_ms IS NOT NULL), 0) AS p99
+    FROM monitor_result_rollups
+    WHERE resolution = p_resolution
+      AND monitor_id = p_monitor_id
+      AND bucket_start >= p_from
+      AND bucket_start < p_to
+),
+totals AS (
+    SELECT
+        coalesce(sum(total), 0) AS total,
+        coalesce(sum(up), 0) AS up,
+        coalesce(sum(degraded), 0) AS degraded,
+        coalesce(sum(down), 0) AS down
+    FROM samples
+),
+days AS (
+    SELECT
+        d.day,
+        coalesce(sum(s.total), 0) AS total,
+        coalesce(sum(s.up), 0) AS up,
+        coalesce(sum(s.degraded), 0) AS degraded,
+        coalesce(sum(s.down), 0) AS down
+    FROM generate_series(
+        date_trunc('day', p_from AT TIME ZONE 'UTC'),
+        date_trunc('day', (p_to - interval '1 microsecond') AT TIME ZONE 'UTC'),
+        interval '1 day'
+    ) AS d(day)
+    LEFT JOIN samples s ON date_trunc('day', s.at AT TIME ZONE 'UTC') = d.day
+    GROUP BY d.day
+),
+incident_stats AS (
+    SELECT
+        count(*) AS incidents,
+        avg(duration_seconds) FILTER (WHERE status = 'resolved') AS mttr,
+        coalesce(sum(extract(epoch FROM
+            least(coalesce(resolved_at, p_to), p_to) - greatest(started_at, p_from)
+        )), 0) AS downtime
+    FROM incidents
+    WHERE monitor_id = p_monitor_id
+      AND started_at < p_to
+      AND coalesce(resolved_at, p_to) > p_from
+)
+SELECT jsonb_build_object(
+    'monitor_id', p_monitor_id,
+    'from', p_from,
+    'to', p_to,
+    'uptime_percent', CASE WHEN t.total = 0 THEN NULL ELSE round(
+        100.0 * (t.up + CASE WHEN p_degraded_as_up THEN t.degraded ELSE 0 END) / t.total, 3
+    ) END,
+    'total_checks', t.total,
+    'up_checks', t.up,
+    'degraded_checks', t.degraded,
+    'down_checks', t.down,
+    'incidents', i.incidents,
+    'downtime_seconds', i.downtime,
+    'mttr_seconds', i.mttr,
+    'mtbf_seconds', CASE WHEN i.incidents = 0 THEN NULL
+        ELSE (extract(epoch FROM p_to - p_from) - i.downtime) / i.incidents END,
+    'latency', CASE WHEN p_resolution = 'raw'
+        THEN (SELECT jsonb_build_object('p50', l.p50, 'p90', l.p90, 'p99', l.p99) FROM latency l)
+        ELSE (SELECT jsonb_build_object('p50', r.p50, 'p90', r.p90, 'p99', r.p99) FROM rollup_latency r)
+    END,
+    'days', coalesce((
+        SELECT jsonb_agg(jsonb_build_object(
+            'date', to_char(d.day, 'YYYY-MM-DD'),
+            'total_checks', d.total,
+            'up_checks', d.up,
+            'degraded_checks', d.degraded,
+            'down_checks', d.down,
+            'uptime_percent', CASE WHEN d.total = 0 THEN NULL ELSE round(
+                100.0 * (d.up + CASE WHEN p_degraded_as_up THEN d.degraded ELSE 0 END) / d.total, 3
+            ) END
+        ) ORDER BY d.day)
+        FROM days d
+    ), '[]'::jsonb)
+)
+FROM totals t, incident_stats i;
+$$;
+
+COMMENT ON TABLE monitor_result_rollups IS 'Zagregowane wyniki monitorów per region (godzina / dzień)';
+COMMENT ON TABLE monitor_check_rollups IS 'Zagregowane próbki uptime monitorów po kworum regionów (godzina / dzień)';
@@ -0,0 +1,297 @@
+-- Okna serwisowe (jednorazowe, RRULE lub cron, w strefie czasowej okna)
+-- dla całego projektu lub wybranych monitorów. W trakcie okna checki dalej
+-- działają, ale wyniki są oznaczane `in_maintenance`, incydenty nie są
+-- otwierane, a raporty uptime i rollupy pomijają takie wyniki
+
+CREATE TABLE maintenance_windows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT,
+    monitor_ids JSONB NOT NULL DEFAULT '[]'::jsonb,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    schedule TEXT NOT NULL DEFAULT 'once' CHECK (schedule IN ('once', 'rrule', 'cron')),
+    rule TEXT,
+    until TIMESTAMPTZ,
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (ends_at > starts_at),
+    CHECK (schedule = 'once' OR rule IS NOT NULL)
+);
+
+CREATE INDEX idx_maintenance_windows_project ON maintenance_windows(project_id);
+
+CREATE TRIGGER update_maintenance_windows_updated_at BEFORE UPDATE ON maintenance_windows
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE maintenance_windows ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view maintenance windows from their projects"
+    ON maintenance_windows FOR SELECT
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+CREATE POLICY "Users can manage maintenance windows in their projects"
+    ON maintenance_windows FOR ALL
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+ALTER TABLE monitor_results ADD COLUMN in_maintenance BOOLEAN NOT NULL DEFAULT false;
+
+-- Rollupy i raport uptime pomijają wyniki z okien serwisowych
+CREATE OR REPLACE FUNCTION rollup_monitor_results(
+    p_resolution TEXT,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ
+)
+RETURNS INTEGER
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    affected INTEGER;
+    bucket_from TIMESTAMPTZ;
+BEGIN
+    IF p_resolution NOT IN ('hour', 'day') THEN
+        RAISE EXCEPTION 'Invalid resolution: %', p_resolution;
+    END IF;
+
+    bucket_from := date_trunc(p_resolution, p_from AT TIME ZONE 'UTC') AT TIME ZONE 'UTC';
+
+    INSERT INTO monitor_result_rollups (
+        monitor_id, region, resolution, bucket_start,
+        total, up, degraded, down, latency_samples,
+        min_ms, avg_ms, max_ms, p50_ms, p90_ms, p99_ms, updated_at
+    )
+    SELECT
+        monitor_id,
+        region,
+        p_resolution,
+        date_trunc(p_resolution, created_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC',
+        count(*),
+        count(*) FILTER (WHERE status = 'up'),
+        count(*) FILTER (WHERE status = 'degraded'),
+        count(*) FILTER (WHERE status = 'down'),
+        count(response_time_ms),
+        min(response_time_ms),
+        avg(response_time_ms),
+        max(response_time_ms),
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY response_time_ms),
+        percentile_cont(0.9) WITHIN GROUP (ORDER BY response_time_ms),
+        percentile_cont(0.99) WITHIN GROUP (ORDER BY response_time_ms),
+        NOW()
+    FROM monitor_results
+    WHERE created_at >= bucket_from
+      AND NOT in_maintenance
+      AND created_at < p_to
+    GROUP BY 1, 2, 3, 4
+    ON CONFLICT (monitor_id, region, resolution, bucket_start) DO UPDATE SET
+        total = EXCLUDED.total,
+        up = EXCLUDED.up,
+        degraded = EXCLUDED.degraded,
+        down = EXCLUDED.down,
+        latency_samples = EXCLUDED.latency_samples,
+        min_ms = EXCLUDED.min_ms,
+        avg_ms = EXCLUDED.avg_ms,
+        max_ms = EXCLUDED.max_ms,
+        p50_ms = EXCLUDED.p50_ms,
+        p90_ms = EXCLUDED.p90_ms,
+        p99_ms = EXCLUDED.p99_ms,
+        updated_at = EXCLUDED.updated_at;
+
+    GET DIAGNOSTICS affected = ROW_COUNT;
+
+    INSERT INTO monitor_check_rollups (
+        monitor_id, resolution, bucket_start, total, up, degraded, down, updated_at
+    )
+    SELECT
+        monitor_id,
+        p_resolution,
+        date_trunc(p_resolution, sampled_at AT TIME ZONE 'UTC') AT TIME ZONE 'UTC',
+        count(*),
+        count(*) FILTER (WHERE status = 'up'),
+        count(*) FILTER (WHERE status = 'degraded'),
+        count(*) FILTER (WHERE status = 'down'),
+        NOW()
+    FROM monitor_check_samples(NULL, bucket_from, p_to)
+    GROUP BY 1, 2, 3
+    ON CONFLICT (monitor_id, resolution, bucket_start) DO UPDATE SET
+        total = EXCLUDED.total,
+        up = EXCLUDED.up,
+        degraded = EXCLUDED.degraded,
+        down = EXCLUDED.down,
+        updated_at = EXCLUDED.updated_at;
+
+    RETURN affected;
+END;
+$$;
+
+-- Próbki uptime bez wyników z okien serwisowych
+CREATE OR REPLACE FUNCTION monitor_check_samples(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ
+)
+RETURNS TABLE (monitor_id UUID, sampled_at TIMESTAMPTZ, status TEXT)
+LANGUAGE sql
+STABLE
+AS $$
+WITH results AS (
+    SELECT r.id, r.monitor_id, r.status, r.created_at
+    FROM monitor_results r
+    WHERE (p_monitor_id IS NULL OR r.monitor_id = p_monitor_id)
+      AND NOT r.in_maintenance
+      AND r.created_at >= p_from
+      AND r.created_at < p_to
+),
+checked AS (
+    SELECT c.id AS check_id, c.monitor_id, c.status, r.id AS result_id, r.created_at
+    FROM results r
+    JOIN monitor_checks c
+      ON c.monitor_id = r.monitor_id
+     AND c.created_at >= r.created_at
+     AND c.created_at < r.created_at + interval '1 hour'
+     AND c.result_ids ? r.id::text
+)
+SELECT ch.monitor_id, min(ch.created_at), ch.status
+FROM checked ch
+GROUP BY ch.check_id, ch.monitor_id, ch.status
+UNION ALL
+SELECT r.monitor_id, r.created_at, r.status
+FROM results r
+WHERE NOT EXISTS (SELECT 1 FROM checked ch WHERE ch.result_id = r.id);
+$$;
+
+CREATE OR REPLACE FUNCTION monitor_uptime_report(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ,
+    p_degraded_as_up BOOLEAN DEFAULT true,
+    p_resolution TEXT DEFAULT 'raw'
+)
+RETURNS JSONB
+LANGUAGE sql
+STABLE
+AS $$
+WITH samples AS (
+    SELECT sampled_at AS at, 1::bigint AS total,
+        (status = 'up')::int::bigint AS up,
+        (status = 'degraded')::int::bigint AS degraded,
+        (status = 'down')::int::bigint AS down
+    FROM monitor_check_samples(p_monitor_id, p_from, p_to)
+    WHERE p_resolution = 'raw'
+    UNION ALL
+    SELECT bucket_start, total, up, degraded, down
+    FROM monitor_check_rollups
+    WHERE resolution = p_resolution
+      AND monitor_id = p_monitor_id
+      AND bucket_start >= p_from
+      AND bucket_start < p_to
+),
+latency AS (
+    SELECT
+        percentile_cont(0.5) WITHIN GROUP (ORDER BY response_time_ms) AS p50,
+        percentile_cont(0.9) WITHIN GROUP (ORDER BY response_time_ms) AS p90,
+        percentile_cont(0.99) WITHIN GROUP (ORDER BY response_time_ms) AS p99
+    FROM monitor_results
+    WHERE p_resolution = 'raw'
+      AND monitor_id = p_monitor_id
+      AND NOT in_maintenance
+      AND created_at >= p_from
+      AND created_at < p_to
+),
+rollup_latency AS (
+    SELECT
+        sum(p50_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p50_ms IS NOT NULL), 0) AS p50,
+        sum(p90_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p90_ms IS NOT NULL), 0) AS p90,
+        sum(p99_ms * latency_samples) / nullif(sum(latency_samples) FILTER (WHERE p99_ms IS NOT NULL), 0) AS p99
+    FROM monitor_result_rollups
+    WHERE resolution = p_resolution
+      AND monitor_id = p_monitor_id
+      AND bucket_start >= p_from
+      AND bucket_start < p_to
+),
+totals AS (
+    SELECT
+        coalesce(sum(total), 0) AS total,
+        coalesce(sum(up), 0) AS up,
+        coalesce(sum(degraded), 0) AS degraded,
+        coalesce(sum(down), 0) AS down
+    FROM samples
+),
+days AS (
+    SELECT
+        d.day,
+        coalesce(sum(s.total), 0) AS total,
+        coalesce(sum(s.up), 0) AS up,
+        coalesce(sum(s.degraded), 0) AS degraded,
+        coalesce(sum(s.down), 0) AS down
+    FROM generate_series(
+        date_trunc('day', p_from AT TIME ZONE 'UTC'),
+        date_trunc('day', (p_to - interval '1 microsecond') AT TIME ZONE 'UTC'),
+        interval '1 day'
+    ) AS d(day)
+    LEFT JOIN samples s ON date_trunc('day', s.at AT TIME ZONE 'UTC') = d.day
+    GROUP BY d.day
+),
+incident_stats AS (
+    SELECT
+        count(*) AS incidents,
+        avg(duration_seconds) FILTER (WHERE status = 'resolved') AS mttr,
+        coalesce(sum(extract(epoch FROM
+            least(coalesce(resolved_at, p_to), p_to) - greatest(started_at, p_from)
+        )), 0) AS downtime
+    FROM incidents
+    WHERE monitor_id = p_monitor_id
+      AND started_at < p_to
+      AND coalesce(resolved_at, p_to) > p_from
+)
+SELECT jsonb_build_object(
+    'monitor_id', p_monitor_id,
+    'from', p_from,
+    'to', p_to,
+    'uptime_percent', CASE WHEN t.total = 0 THEN NULL ELSE round(
+        100.0 * (t.up + CASE WHEN p_degraded_as_up THEN t.degraded ELSE 0 END) / t.total, 3
+    ) END,
+    'total_checks', t.total,
+    'up_checks', t.up,
+    'degraded_checks', t.degraded,
+    'down_checks', t.down,
+    'incidents', i.incidents,
+    'downtime_seconds', i.downtime,
+    'mttr_seconds', i.mttr,
+    'mtbf_seconds', CASE WHEN i.incidents = 0 THEN NULL
+        ELSE (extract(epoch FROM p_to - p_from) - i.downtime) / i.incidents END,
+    'latency', CASE WHEN p_resolution = 'raw'
+        THEN (SELECT jsonb_build_object('p50', l.p50, 'p90', l.p90, 'p99', l.p99) FROM latency l)
+        ELSE (SELECT jsonb_build_object('p50', r.p50, 'p90', r.p90, 'p99', r.p99) FROM rollup_latency r)
+    END,
+    'days', coalesce((
+        SELECT jsonb_agg(jsonb_build_object(
+            'date', to_char(d.day, 'YYYY-MM-DD'),
+            'total_checks', d.total,
+            'up_checks', d.up,
+            'degraded_checks', d.degraded,
+            'down_checks', d.down,
+            'uptime_percent', CASE WHEN d.total = 0 THEN NULL ELSE round(
+                100.0 * (d.up + CASE WHEN p_degraded_as_up THEN d.degraded ELSE 0 END) / d.total, 3
+            ) END
+        ) ORDER BY d.day)
+        FROM days d
+    ), '[]'::jsonb)
+)
+FROM totals t, incident_stats i;
+$$;
+
+COMMENT ON TABLE maintenance_windows IS 'Planowane okna serwisowe projektu lub wybranych monitorów';
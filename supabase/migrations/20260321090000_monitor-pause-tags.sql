@@ -0,0 +1,16 @@
+-- Pauza monitorów z powodem, autorem i opcjonalnym terminem automatycznego
+-- wznowienia (job backendu `pause-expiry`) oraz tagi klucz/wartość używane
+-- przez operacje zbiorcze
+
+ALTER TABLE monitors ADD COLUMN tags JSONB NOT NULL DEFAULT '{}'::jsonb
+    CHECK (jsonb_typeof(tags) = 'object');
+ALTER TABLE monitors ADD COLUMN paused_at TIMESTAMPTZ;
+ALTER TABLE monitors ADD COLUMN paused_until TIMESTAMPTZ;
+ALTER TABLE monitors ADD COLUMN pause_reason TEXT;
+ALTER TABLE monitors ADD COLUMN paused_by TEXT;
+
+CREATE INDEX idx_monitors_tags ON monitors USING GIN (tags);
+CREATE INDEX idx_monitors_paused_until ON monitors(paused_until) WHERE paused_until IS NOT NULL;
+
+COMMENT ON COLUMN monitors.paused_until IS 'Po tym czasie monitor jest automatycznie wznawiany';
+COMMENT ON COLUMN monitors.paused_by IS 'Kto wstrzymał monitor (użytkownik, pipeline deployu itp.)';
@@ -0,0 +1,46 @@
+-- Nazwane grupy monitorów w projekcie oraz indeksy pod filtrowanie listy
+-- monitorów (tag, grupa, typ, enabled, stan)
+
+CREATE TABLE monitor_groups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (project_id, name)
+);
+
+CREATE TRIGGER update_monitor_groups_updated_at BEFORE UPDATE ON monitor_groups
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE monitor_groups ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can view monitor groups from their projects"
+    ON monitor_groups FOR SELECT
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+CREATE POLICY "Users can manage monitor groups in their projects"
+    ON monitor_groups FOR ALL
+    USING (
+        project_id IN (
+            SELECT p.id FROM projects p
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+-- Usunięcie grupy zostawia monitory bez grupy
+ALTER TABLE monitors ADD COLUMN group_id UUID REFERENCES monitor_groups(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_monitors_group ON monitors(group_id) WHERE group_id IS NOT NULL;
+CREATE INDEX idx_monitors_project_kind ON monitors(project_id, kind);
+CREATE INDEX idx_monitors_project_state ON monitors(project_id, state);
+
+COMMENT ON TABLE monitor_groups IS 'Grupy monitorów (np. API, Płatności) w obrębie projektu';
@@ -0,0 +1,7 @@
+-- Stabilny klucz monitora dla monitors-as-code (eksport / apply dokumentu
+-- YAML/JSON). Monitory bez klucza dostają go przy pierwszym apply (z nazwy)
+
+ALTER TABLE monitors ADD COLUMN key TEXT
+    CHECK (key ~ '^[a-z0-9_-]{1,64}$');
+
+CREATE UNIQUE INDEX idx_monitors_project_key ON monitors(project_id, key) WHERE key IS NOT NULL;
@@ -0,0 +1,13 @@
+-- Wykrywanie anomalii czasu odpowiedzi: ustawienia per monitor (EWMA + MAD),
+-- oznaczenie wyników i incydenty typu `latency` po N anomaliach z rzędu
+
+ALTER TABLE monitors ADD COLUMN anomaly JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE monitor_results ADD COLUMN anomalous BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE monitor_results ADD COLUMN anomaly_score DOUBLE PRECISION;
+ALTER TABLE monitor_results ADD COLUMN baseline_ms DOUBLE PRECISION;
+
+ALTER TABLE incidents ADD COLUMN cause TEXT NOT NULL DEFAULT 'down'
+    CHECK (cause IN ('down', 'latency'));
+
+COMMENT ON COLUMN monitor_results.baseline_ms IS 'EWMA czasów odpowiedzi regionu w chwili zapisu wyniku';
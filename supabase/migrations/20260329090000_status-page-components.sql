@@ -0,0 +1,39 @@
+-- Komponenty status page: monitory pokazywane na publicznej stronie statusu
+-- (nazwa wyświetlana, grupa i kolejność)
+
+CREATE TABLE status_page_components (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    status_page_id UUID NOT NULL REFERENCES status_pages(id) ON DELETE CASCADE,
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    display_name TEXT,
+    group_name TEXT,
+    position INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (status_page_id, monitor_id)
+);
+
+CREATE INDEX idx_status_page_components_page ON status_page_components(status_page_id, position);
+
+-- Domena własna może należeć tylko do jednej strony
+CREATE UNIQUE INDEX idx_status_pages_custom_domain ON status_pages(custom_domain) WHERE custom_domain IS NOT NULL;
+
+ALTER TABLE status_page_components ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Public can view components of enabled status pages"
+    ON status_page_components FOR SELECT
+    USING (
+        status_page_id IN (SELECT id FROM status_pages WHERE enabled = true)
+    );
+
+CREATE POLICY "Users can manage status page components in their projects"
+    ON status_page_components FOR ALL
+    USING (
+        status_page_id IN (
+            SELECT sp.id FROM status_pages sp
+            JOIN projects p ON sp.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+COMMENT ON TABLE status_page_components IS 'Monitory widoczne na status page jako komponenty';
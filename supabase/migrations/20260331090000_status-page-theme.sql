@@ -0,0 +1,5 @@
+-- Wygląd strony statusu renderowanej przez backend: logo, kolory i własny CSS
+
+ALTER TABLE status_pages ADD COLUMN theme JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+COMMENT ON COLUMN status_pages.theme IS 'logo_url, accent_color, background_color, text_color, custom_css';
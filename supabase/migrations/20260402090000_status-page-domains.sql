@@ -0,0 +1,7 @@
+-- Weryfikacja domen własnych status page (rekord TXT lub plik .well-known);
+-- strona jest serwowana na domenie dopiero po weryfikacji
+
+ALTER TABLE status_pages ADD COLUMN domain_token TEXT;
+ALTER TABLE status_pages ADD COLUMN domain_verified_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN status_pages.domain_token IS 'Token do rekordu TXT _statusforge.<domena> lub pliku /.well-known/statusforge-verification.txt';
@@ -0,0 +1,77 @@
+-- Ręczne komunikaty na status page: incydenty z osią czasu aktualizacji
+-- oraz zapowiedzi planowanych prac serwisowych
+
+CREATE TABLE status_page_posts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    status_page_id UUID NOT NULL REFERENCES status_pages(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL DEFAULT 'incident' CHECK (kind IN ('incident', 'maintenance')),
+    title TEXT NOT NULL,
+    impact TEXT NOT NULL DEFAULT 'none' CHECK (impact IN ('none', 'minor', 'major', 'critical')),
+    state TEXT NOT NULL CHECK (state IN (
+        'investigating', 'identified', 'monitoring', 'resolved',
+        'scheduled', 'in_progress', 'completed'
+    )),
+    -- Id komponentów strony (status_page_components)
+    component_ids UUID[] NOT NULL DEFAULT '{}',
+    scheduled_starts_at TIMESTAMPTZ,
+    scheduled_ends_at TIMESTAMPTZ,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (kind <> 'maintenance' OR (scheduled_starts_at IS NOT NULL AND scheduled_ends_at > scheduled_starts_at))
+);
+
+CREATE INDEX idx_status_page_posts_page ON status_page_posts(status_page_id, created_at DESC);
+
+CREATE TRIGGER update_status_page_posts_updated_at BEFORE UPDATE ON status_page_posts
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE status_page_post_updates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    post_id UUID NOT NULL REFERENCES status_page_posts(id) ON DELETE CASCADE,
+    state TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_status_page_post_updates_post ON status_page_post_updates(post_id, created_at DESC);
+
+ALTER TABLE status_page_posts ENABLE ROW LEVEL SECURITY;
+ALTER TABLE status_page_post_updates ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Public can view posts of enabled status pages"
+    ON status_page_posts FOR SELECT
+    USING (
+        status_page_id IN (SELECT id FROM status_pages WHERE enabled = true)
+    );
+
+CREATE POLICY "Users can manage posts in their projects"
+    ON status_page_posts FOR ALL
+    USING (
+        status_page_id IN (
+            SELECT sp.id FROM status_pages sp
+            JOIN projects p ON sp.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+CREATE POLICY "Public can view updates of visible posts"
+    ON status_page_post_updates FOR SELECT
+    USING (
+        post_id IN (SELECT id FROM status_page_posts)
+    );
+
+CREATE POLICY "Users can manage post updates in their projects"
+    ON status_page_post_updates FOR ALL
+    USING (
+        post_id IN (
+            SELECT sp_post.id FROM status_page_posts sp_post
+            JOIN status_pages sp ON sp_post.status_page_id = sp.id
+            JOIN projects p ON sp.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+COMMENT ON TABLE status_page_posts IS 'Komunikaty o incydentach i planowanych pracach na status page';
@@ -0,0 +1,34 @@
+-- Subskrypcje status page: powiadomienia e-mail (z potwierdzeniem adresu)
+-- lub webhook o komunikatach i zmianach statusu komponentów
+
+CREATE TABLE status_page_subscribers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    status_page_id UUID NOT NULL REFERENCES status_pages(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('email', 'webhook')),
+    -- Adres e-mail lub URL webhooka
+    target TEXT NOT NULL,
+    -- Obserwowane komponenty (status_page_components); pusta lista = wszystkie
+    component_ids UUID[] NOT NULL DEFAULT '{}',
+    -- NULL dopóki adres e-mail nie zostanie potwierdzony
+    confirmed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (status_page_id, kind, target)
+);
+
+CREATE INDEX idx_status_page_subscribers_page ON status_page_subscribers(status_page_id);
+
+ALTER TABLE status_page_subscribers ENABLE ROW LEVEL SECURITY;
+
+-- Zapisy, potwierdzenia i wypisania z publicznej strony obsługuje backend
+CREATE POLICY "Users can manage subscribers of their status pages"
+    ON status_page_subscribers FOR ALL
+    USING (
+        status_page_id IN (
+            SELECT sp.id FROM status_pages sp
+            JOIN projects p ON sp.project_id = p.id
+            JOIN organization_members om ON p.organization_id = om.organization_id
+            WHERE om.user_id = auth.uid()
+        )
+    );
+
+COMMENT ON TABLE status_page_subscribers IS 'Subskrybenci powiadomień status page (e-mail lub webhook)';
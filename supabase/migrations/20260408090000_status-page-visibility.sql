@@ -0,0 +1,35 @@
+-- Prywatne status page: dostęp publiczny, chroniony hasłem albo tylko dla
+-- członków organizacji i adresów IP z listy dozwolonych.
+-- Dostęp do stron niepublicznych sprawdza backend; bezpośredni odczyt przez
+-- API Supabase obejmuje już tylko strony publiczne.
+
+ALTER TABLE status_pages ADD COLUMN visibility TEXT NOT NULL DEFAULT 'public'
+    CHECK (visibility IN ('public', 'password', 'restricted'));
+-- Hash Argon2 (format PHC); samo hasło nie jest przechowywane
+ALTER TABLE status_pages ADD COLUMN password_hash TEXT;
+-- Adresy IP i zakresy CIDR wpuszczane na stronę 'restricted' bez logowania
+ALTER TABLE status_pages ADD COLUMN allowed_ips TEXT[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE status_pages ADD CONSTRAINT status_pages_password_required
+    CHECK (visibility <> 'password' OR password_hash IS NOT NULL);
+
+DROP POLICY "Public can view enabled status pages" ON status_pages;
+CREATE POLICY "Public can view enabled status pages"
+    ON status_pages FOR SELECT
+    USING (enabled = true AND visibility = 'public');
+
+DROP POLICY "Public can view components of enabled status pages" ON status_page_components;
+CREATE POLICY "Public can view components of enabled status pages"
+    ON status_page_components FOR SELECT
+    USING (
+        status_page_id IN (SELECT id FROM status_pages WHERE enabled = true AND visibility = 'public')
+    );
+
+DROP POLICY "Public can view posts of enabled status pages" ON status_page_posts;
+CREATE POLICY "Public can view posts of enabled status pages"
+    ON status_page_posts FOR SELECT
+    USING (
+        status_page_id IN (SELECT id FROM status_pages WHERE enabled = true AND visibility = 'public')
+    );
+
+COMMENT ON COLUMN status_pages.visibility IS 'public, password (hasło) lub restricted (członkowie organizacji, allowed_ips)';
@@ -0,0 +1,18 @@
+-- Dostarczanie webhooków: każda próba wysłania zdarzenia trafia do
+-- webhook_logs razem z wysłanym payloadem (wersjonowany JSON podpisany
+-- HMAC-SHA256 sekretem webhooka)
+
+ALTER TABLE webhooks ADD CONSTRAINT webhooks_events_valid
+    CHECK (events <@ ARRAY['downtime', 'recovery', 'error_spike'] AND cardinality(events) > 0);
+
+-- Identyfikator zdarzenia – wspólny dla wszystkich prób (nagłówek X-StatusForge-Delivery)
+ALTER TABLE webhook_logs ADD COLUMN event_id UUID;
+ALTER TABLE webhook_logs ADD COLUMN payload JSONB;
+ALTER TABLE webhook_logs ADD COLUMN attempt INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE webhook_logs ADD COLUMN duration_ms INTEGER;
+
+CREATE INDEX idx_webhook_logs_event ON webhook_logs(event_id) WHERE event_id IS NOT NULL;
+-- Zliczanie błędów w logach projektu (zdarzenie error_spike)
+CREATE INDEX idx_logs_project_errors ON logs(project_id, created_at DESC) WHERE level IN ('error', 'fatal');
+
+COMMENT ON COLUMN webhook_logs.payload IS 'Payload wysłany do webhooka (wersja w polu version)';
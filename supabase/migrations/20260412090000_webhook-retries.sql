@@ -0,0 +1,17 @@
+-- Ponawianie dostarczeń webhooków: nieudana próba dostaje next_attempt_at,
+-- a job webhook-retries wysyła ją ponownie z wykładniczym opóźnieniem
+-- (przetrwa restart serwera). Po ostatniej próbie zdarzenie trafia na listę
+-- dead-letter; webhook wyłącza się po serii nieudanych prób.
+
+ALTER TABLE webhooks ADD COLUMN consecutive_failures INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE webhooks ADD COLUMN disabled_at TIMESTAMPTZ;
+ALTER TABLE webhooks ADD COLUMN disabled_reason TEXT;
+
+ALTER TABLE webhook_logs ADD COLUMN next_attempt_at TIMESTAMPTZ;
+ALTER TABLE webhook_logs ADD COLUMN dead_letter BOOLEAN NOT NULL DEFAULT false;
+-- Ręczne ponowienie wskazuje log, z którego payload został wysłany
+ALTER TABLE webhook_logs ADD COLUMN redelivery_of UUID REFERENCES webhook_logs(id) ON DELETE SET NULL;
+
+-- Job szuka zaległych ponowień
+CREATE INDEX idx_webhook_logs_next_attempt ON webhook_logs(next_attempt_at) WHERE next_attempt_at IS NOT NULL;
+CREATE INDEX idx_webhook_logs_dead_letter ON webhook_logs(webhook_id, created_at DESC) WHERE dead_letter = true;
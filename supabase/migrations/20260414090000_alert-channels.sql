@@ -0,0 +1,13 @@
+-- Kanały alertów: email, Slack, Discord, SMS. Konfiguracja kanału w JSONB
+-- (walidowana przez backend):
+--   email:   {"to": ["ops@example.com"]}
+--   slack:   {"webhook_url": "https://hooks.slack.com/services/..."}
+--   discord: {"webhook_url": "https://discord.com/api/webhooks/..."}
+--   sms:     {"to": ["+48123456789"]}
+
+ALTER TABLE alerts ADD CONSTRAINT alerts_config_object CHECK (jsonb_typeof(config) = 'object');
+
+-- Wysyłka przy zdarzeniu pobiera tylko włączone alerty projektu
+CREATE INDEX idx_alerts_project_enabled ON alerts(project_id) WHERE enabled = true;
+
+COMMENT ON TABLE alerts IS 'Alerty projektu wysyłane przy downtime, recovery i error spike';
@@ -0,0 +1,6 @@
+-- Zawężanie alertów selektorem tagów monitorów (np. `env=prod,team`): alert
+-- z selektorem dostaje tylko downtime / recovery pasujących monitorów
+
+ALTER TABLE alerts ADD COLUMN tag_selector TEXT;
+
+COMMENT ON COLUMN alerts.tag_selector IS 'Selektor tagów monitorów; NULL = wszystkie zdarzenia projektu';
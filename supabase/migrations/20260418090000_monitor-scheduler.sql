@@ -0,0 +1,20 @@
+-- Sprawdzenia monitorów planuje backend (job `monitor-checks`): każdy
+-- aktywny monitor jest sprawdzany przez `run_monitor_check` co
+-- `interval_seconds`, we wszystkich swoich regionach, z ponowieniami,
+-- kworum, incydentami i alertami. Cron wysyłający monitory prosto do Edge
+-- Function z regionem EU zostaje usunięty
+
+ALTER TABLE monitors ADD COLUMN last_checked_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN monitors.last_checked_at IS 'Początek ostatniego sprawdzenia; kolejne planowane po interval_seconds';
+
+-- Bez pg_cron (np. lokalnie) nie ma czego wyłączać
+DO $$
+BEGIN
+  IF to_regclass('cron.job') IS NOT NULL THEN
+    EXECUTE 'SELECT cron.unschedule(jobid) FROM cron.job WHERE jobname = ''monitor-checks-every-5min''';
+  END IF;
+END;
+$$;
+
+DROP FUNCTION IF EXISTS run_monitor_checks_via_edge_function();
@@ -0,0 +1,22 @@
+-- Najnowszy wynik każdego monitora SSL projektu jednym zapytaniem, dla
+-- GET /projects/:id/certificates (zamiast osobnego zapytania per monitor)
+
+CREATE OR REPLACE FUNCTION latest_ssl_results(p_project_id UUID)
+RETURNS SETOF monitor_results
+LANGUAGE sql
+STABLE
+AS $$
+SELECT r.*
+FROM monitors m
+CROSS JOIN LATERAL (
+    SELECT *
+    FROM monitor_results
+    WHERE monitor_id = m.id
+    ORDER BY created_at DESC
+    LIMIT 1
+) r
+WHERE m.project_id = p_project_id
+  AND m.kind = 'ssl';
+$$;
+
+COMMENT ON FUNCTION latest_ssl_results(UUID) IS 'Najnowsze wyniki monitorów SSL projektu dla GET /projects/:id/certificates';
@@ -0,0 +1,45 @@
+-- Wynik zna check, do którego należy (run_monitor_check zapisuje check_id),
+-- więc próbki uptime łączą wyniki z checkami po kluczu zamiast dopasowywać
+-- je po czasie i result_ids
+
+ALTER TABLE monitor_results ADD COLUMN check_id UUID REFERENCES monitor_checks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_monitor_results_check ON monitor_results(check_id) WHERE check_id IS NOT NULL;
+
+COMMENT ON COLUMN monitor_results.check_id IS 'Check (po kworum regionów), w którym powstał wynik; NULL dla wyników spoza run_monitor_check';
+
+-- Istniejące wyniki dostają check_id z monitor_checks.result_ids
+UPDATE monitor_results r
+SET check_id = c.id
+FROM monitor_checks c
+WHERE c.monitor_id = r.monitor_id
+  AND c.result_ids ? r.id::text;
+
+-- Próbki uptime bez wyników z okien serwisowych: jedna na check, a wyniki
+-- bez checku (heartbeat, POST /monitors/:id/results) liczą się osobno
+CREATE OR REPLACE FUNCTION monitor_check_samples(
+    p_monitor_id UUID,
+    p_from TIMESTAMPTZ,
+    p_to TIMESTAMPTZ
+)
+RETURNS TABLE (monitor_id UUID, sampled_at TIMESTAMPTZ, status TEXT)
+LANGUAGE sql
+STABLE
+AS $$
+WITH results AS (
+    SELECT r.monitor_id, r.check_id, r.status, r.created_at
+    FROM monitor_results r
+    WHERE (p_monitor_id IS NULL OR r.monitor_id = p_monitor_id)
+      AND NOT r.in_maintenance
+      AND r.created_at >= p_from
+      AND r.created_at < p_to
+)
+SELECT r.monitor_id, min(r.created_at), c.status
+FROM results r
+JOIN monitor_checks c ON c.id = r.check_id
+GROUP BY c.id, r.monitor_id, c.status
+UNION ALL
+SELECT r.monitor_id, r.created_at, r.status
+FROM results r
+WHERE r.check_id IS NULL;
+$$;